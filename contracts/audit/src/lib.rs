@@ -1,9 +1,13 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Symbol, Vec,
 };
 
+#[path = "../../pagination.rs"]
+mod pagination;
+
 // ─── Storage Keys ─────────────────────────────────────────────────────────────
 
 #[contracttype]
@@ -17,8 +21,57 @@ pub enum DataKey {
     AuditLog(u64),
     /// Configuration settings
     Config,
+    /// Number of Merkle checkpoints taken so far
+    CheckpointCount,
+    /// Individual checkpoints indexed by checkpoint id (starting at 1)
+    Checkpoint(u64),
+    /// Event mirroring mode for an operation (absent means `Mirrored`)
+    EventMode(Symbol),
+    /// Emit one event for every N entries of `Sampled` operations
+    EventSampleRate,
+    /// Archive contract each new entry is replicated to
+    Archive,
+    /// Replication counters for the archive contract
+    ArchiveStats,
+    /// Number of entries logged for an operation
+    OperationLogCount(Symbol),
+    /// Sequence number of the nth (from 0) entry logged for an operation
+    OperationLog(Symbol, u64),
+    /// Number of entries whose timestamp falls on a day (days since epoch)
+    DayLogCount(u64),
+    /// Sequence number of the nth (from 0) entry of a day
+    DayLog(u64, u64),
 }
 
+// ─── Constants ────────────────────────────────────────────────────────────────
+
+/// Maximum number of entries returned by a single range read
+pub const MAX_RANGE_SIZE: u64 = 100;
+
+/// Maximum number of entries scanned by a single cursor page read
+pub use pagination::MAX_PAGE_SIZE;
+
+/// Remaining TTL (in ledgers) below which a read bumps an audit log entry
+pub const LOG_TTL_THRESHOLD: u32 = 100_000;
+
+/// TTL (in ledgers) audit log entries are extended to when read
+pub const LOG_TTL_EXTEND_TO: u32 = 500_000;
+
+/// Maximum number of days a single time-window read may span
+pub const MAX_WINDOW_DAYS: u64 = 31;
+
+/// Width of the day buckets entries are indexed under
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Maximum number of entries covered by a single checkpoint
+pub const MAX_CHECKPOINT_SIZE: u64 = 100;
+
+/// Domain separation prefix for Merkle leaf hashes
+const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain separation prefix for Merkle interior node hashes
+const NODE_PREFIX: u8 = 0x01;
+
 // ─── Types ────────────────────────────────────────────────────────────────────
 
 /// Represents a single audit log entry
@@ -39,6 +92,112 @@ pub struct AuditLog {
     pub metadata_len: u32,
 }
 
+/// Result of a range read: only the entries that were found, in index order
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuditLogRange {
+    /// Number of entries found in the requested range
+    pub found_count: u32,
+    /// The entries found, ordered by sequence number
+    pub logs: Vec<AuditLog>,
+}
+
+/// Merkle-root commitment over a contiguous range of audit entries.
+///
+/// Each checkpoint starts right after the previous one ends, so together
+/// they cover every entry up to the latest `end_index` exactly once.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    /// Sequential checkpoint id (starting at 1)
+    pub id: u64,
+    /// First audit log index covered (inclusive)
+    pub start_index: u64,
+    /// Last audit log index covered (inclusive)
+    pub end_index: u64,
+    /// Merkle root over the covered entries
+    pub root: BytesN<32>,
+    /// Ledger timestamp at which the checkpoint was taken
+    pub created_at: u64,
+}
+
+/// One step of a Merkle inclusion path
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProofStep {
+    /// Hash of the sibling node at this level
+    pub sibling: BytesN<32>,
+    /// Whether the sibling sits to the left of the running hash
+    pub is_left: bool,
+}
+
+/// Everything an external verifier needs to prove an entry was checkpointed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InclusionProof {
+    /// Id of the checkpoint the entry belongs to
+    pub checkpoint_id: u64,
+    /// Audit log index being proven
+    pub index: u64,
+    /// Leaf hash of the entry
+    pub leaf: BytesN<32>,
+    /// Sibling hashes from the leaf up to the root
+    pub path: Vec<ProofStep>,
+    /// Root recorded by the checkpoint
+    pub root: BytesN<32>,
+}
+
+/// Whether audit entries for an operation are also published as events
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventMode {
+    /// Store the entry and publish an event (default)
+    Mirrored,
+    /// Store the entry without publishing an event
+    StorageOnly,
+    /// Store the entry and publish an event only for every Nth entry, where N
+    /// is the global sample rate
+    Sampled,
+}
+
+/// Compact copy of an audit entry pushed to the archive contract; the
+/// metadata is replaced by its hash
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchivedEntry {
+    /// Sequence number of the entry in this contract
+    pub index: u64,
+    /// Address of the actor who performed the operation
+    pub actor: Address,
+    /// The operation performed
+    pub operation: Symbol,
+    /// Timestamp of the operation
+    pub timestamp: u64,
+    /// Status of the operation
+    pub status: Symbol,
+    /// SHA-256 of the metadata, if the entry has any
+    pub metadata_hash: Option<BytesN<32>>,
+}
+
+/// Outcome counters of best-effort replication to the archive contract
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArchiveStats {
+    /// Entries accepted by the archive contract
+    pub replicated: u64,
+    /// Entries the archive contract failed to accept
+    pub failed: u64,
+    /// Sequence number of the most recent entry that failed, 0 if none
+    pub last_failed_index: u64,
+}
+
+/// Entry point an archival contract exposes to receive replicated entries
+#[contractclient(name = "ArchiveClient")]
+pub trait ArchiveInterface {
+    /// Store a compact copy of an audit entry
+    fn archive_entry(env: Env, entry: ArchivedEntry);
+}
+
 /// Contract configuration
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -110,7 +269,7 @@ impl AuditContract {
         // Validate metadata size if provided
         let metadata_len = match &metadata {
             Some(meta) => {
-                let len = meta.len() as u32;
+                let len = meta.len();
                 let config: Config = env
                     .storage()
                     .instance()
@@ -148,17 +307,17 @@ impl AuditContract {
         env.storage()
             .persistent()
             .set(&DataKey::AuditLog(total_logs), &audit_log);
+        Self::index_entry(&env, total_logs, &audit_log);
 
         // Update total count
         env.storage()
             .instance()
             .set(&DataKey::TotalAuditLogs, &total_logs);
 
-        // Emit audit event
-        env.events().publish(
-            (symbol_short!("audit"), symbol_short!("entry")),
-            (actor, operation, status, total_logs),
-        );
+        Self::replicate_entry(&env, total_logs, &audit_log);
+
+        // Emit audit event, subject to the operation's mirroring mode
+        Self::mirror_entry(&env, actor, operation, status, total_logs);
     }
 
     /// Log multiple audit entries in a batch.
@@ -201,16 +360,16 @@ impl AuditContract {
             env.storage()
                 .persistent()
                 .set(&DataKey::AuditLog(total_logs), &log);
+            Self::index_entry(&env, total_logs, &log);
+            Self::replicate_entry(&env, total_logs, &log);
 
-            // Emit audit event for each log
-            env.events().publish(
-                (symbol_short!("audit"), symbol_short!("entry")),
-                (
-                    log.actor.clone(),
-                    log.operation.clone(),
-                    log.status.clone(),
-                    total_logs,
-                ),
+            // Emit audit event for each log, subject to its mirroring mode
+            Self::mirror_entry(
+                &env,
+                log.actor.clone(),
+                log.operation.clone(),
+                log.status.clone(),
+                total_logs,
             );
         }
 
@@ -244,32 +403,289 @@ impl AuditContract {
 
     /// Get a range of audit logs.
     ///
+    /// Missing entries are skipped rather than returned as `None`, and every
+    /// entry read has its TTL extended in the same pass so backfilling
+    /// indexers keep the data they touch alive.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `start_index` - The starting index (inclusive)
     /// * `end_index` - The ending index (inclusive)
-    pub fn get_audit_logs_range(env: Env, start_index: u64, end_index: u64) -> Vec<Option<AuditLog>> {
+    pub fn get_audit_logs_range(env: Env, start_index: u64, end_index: u64) -> AuditLogRange {
         if start_index > end_index {
             panic!("start index cannot be greater than end index");
         }
 
+        if end_index - start_index + 1 > MAX_RANGE_SIZE {
+            panic!("range exceeds maximum size of 100");
+        }
+
         let total_logs = Self::get_total_audit_logs(env.clone());
         if end_index > total_logs {
             panic!("end index exceeds total number of audit logs");
         }
 
-        // Create the vector first with an env clone
-        let mut logs: Vec<Option<AuditLog>> = Vec::new(&env);
-        
-        // Then populate it with actual data
-        for i in start_index..=end_index {
-            let log = env.storage().persistent().get(&DataKey::AuditLog(i));
-            logs.push_back(log);
+        let logs = Self::read_logs(&env, start_index, end_index);
+        AuditLogRange {
+            found_count: logs.len(),
+            logs,
         }
+    }
+
+    /// Get the next page of audit logs after a cursor.
+    ///
+    /// Scans at most `limit` sequence numbers after `cursor_id` and returns
+    /// the entries found together with the cursor to pass to the next call.
+    /// Start from cursor 0; an empty page means the reader has caught up.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `cursor_id` - Sequence number of the last entry already read
+    /// * `limit` - Page size (1 to `MAX_PAGE_SIZE`)
+    pub fn get_logs_after(env: Env, cursor_id: u64, limit: u32) -> (Vec<AuditLog>, u64) {
+        Self::require_page_size(limit);
+
+        let total_logs = Self::get_total_audit_logs(env.clone());
+        if cursor_id >= total_logs {
+            return (Vec::new(&env), cursor_id);
+        }
+
+        // Entry at position p has sequence number p + 1
+        let page = pagination::page_range(cursor_id, limit, total_logs);
+        (Self::read_logs(&env, page.start + 1, page.end), page.end)
+    }
+
+    /// Get the entries logged for an operation, oldest first.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `operation` - The operation the entries were logged with
+    /// * `offset` - Number of the operation's entries to skip
+    /// * `limit` - Page size (1 to `MAX_PAGE_SIZE`)
+    pub fn get_audit_logs_by_operation(
+        env: Env,
+        operation: Symbol,
+        offset: u64,
+        limit: u32,
+    ) -> Vec<AuditLog> {
+        Self::require_page_size(limit);
+
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OperationLogCount(operation.clone()))
+            .unwrap_or(0);
 
+        let mut logs = Vec::new(&env);
+        for position in pagination::page_range(offset, limit, count) {
+            let key = DataKey::OperationLog(operation.clone(), position);
+            if let Some(index) = Self::read_index(&env, &key) {
+                if let Some(log) = Self::read_log(&env, index) {
+                    logs.push_back(log);
+                }
+            }
+        }
         logs
     }
 
+    /// Get up to `limit` entries whose timestamp falls within a time window.
+    ///
+    /// Entries are looked up through their day buckets, so the window may
+    /// span at most `MAX_WINDOW_DAYS` days. They are returned day by day and,
+    /// within a day, in the order they were logged.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `start_ts` - Start of the window (inclusive)
+    /// * `end_ts` - End of the window (inclusive)
+    /// * `limit` - Maximum number of entries returned (1 to `MAX_PAGE_SIZE`)
+    pub fn get_audit_logs_in_window(
+        env: Env,
+        start_ts: u64,
+        end_ts: u64,
+        limit: u32,
+    ) -> Vec<AuditLog> {
+        Self::get_audit_logs_in_window_after(env, start_ts, end_ts, 0, limit).0
+    }
+
+    /// Get the next page of entries within a time window after a cursor.
+    ///
+    /// Works like `get_audit_logs_in_window`, and also returns the cursor to
+    /// pass to the next call. Start from cursor 0; an empty page means the
+    /// whole window has been read.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `start_ts` - Start of the window (inclusive)
+    /// * `end_ts` - End of the window (inclusive)
+    /// * `cursor` - Cursor returned by the previous call, or 0
+    /// * `limit` - Maximum number of entries returned (1 to `MAX_PAGE_SIZE`)
+    pub fn get_audit_logs_in_window_after(
+        env: Env,
+        start_ts: u64,
+        end_ts: u64,
+        cursor: u64,
+        limit: u32,
+    ) -> (Vec<AuditLog>, u64) {
+        if start_ts > end_ts {
+            panic!("window start cannot be after window end");
+        }
+        Self::require_page_size(limit);
+
+        let first_day = start_ts / SECONDS_PER_DAY;
+        let last_day = end_ts / SECONDS_PER_DAY;
+        if last_day - first_day + 1 > MAX_WINDOW_DAYS {
+            panic!("time window exceeds maximum of 31 days");
+        }
+
+        let (cursor_day, cursor_position) = pagination::decode_cursor(cursor);
+        let mut logs = Vec::new(&env);
+        for day in first_day.max(cursor_day as u64)..=last_day {
+            let count: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DayLogCount(day))
+                .unwrap_or(0);
+            let start = if day == cursor_day as u64 {
+                cursor_position as u64
+            } else {
+                0
+            };
+            for position in start..count {
+                let Some(index) = Self::read_index(&env, &DataKey::DayLog(day, position)) else {
+                    continue;
+                };
+                let Some(log) = Self::read_log(&env, index) else {
+                    continue;
+                };
+                if log.timestamp < start_ts || log.timestamp > end_ts {
+                    continue;
+                }
+                logs.push_back(log);
+                if logs.len() == limit {
+                    let next = pagination::encode_cursor(day as u32, position as u32 + 1);
+                    return (logs, next);
+                }
+            }
+        }
+        (logs, pagination::encode_cursor(last_day as u32 + 1, 0))
+    }
+
+    // ── Checkpoints ───────────────────────────────────────────────────────────
+
+    /// Commit a Merkle root over every entry logged since the last checkpoint.
+    ///
+    /// Leaves are `sha256(0x00 || index_be || xdr(entry))` and interior nodes
+    /// are `sha256(0x01 || left || right)`. A node without a sibling is
+    /// carried up to the next level unchanged.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The address calling this function (must be admin)
+    /// * `up_to_index` - Last audit log index to include (inclusive)
+    pub fn checkpoint(env: Env, admin: Address, up_to_index: u64) -> Checkpoint {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CheckpointCount)
+            .unwrap_or(0);
+        let start_index = match Self::get_checkpoint(env.clone(), count) {
+            Some(last) => last.end_index + 1,
+            None => 1,
+        };
+
+        if up_to_index < start_index {
+            panic!("nothing to checkpoint");
+        }
+
+        if up_to_index > Self::get_total_audit_logs(env.clone()) {
+            panic!("checkpoint index exceeds total number of audit logs");
+        }
+
+        if up_to_index - start_index + 1 > MAX_CHECKPOINT_SIZE {
+            panic!("checkpoint exceeds maximum size of 100");
+        }
+
+        let leaves = Self::leaf_hashes(&env, start_index, up_to_index);
+        let (root, _) = Self::merkle_root_and_path(&env, leaves, 0);
+
+        let id = count + 1;
+        let checkpoint = Checkpoint {
+            id,
+            start_index,
+            end_index: up_to_index,
+            root: root.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Checkpoint(id), &checkpoint);
+        env.storage().instance().set(&DataKey::CheckpointCount, &id);
+
+        env.events().publish(
+            (symbol_short!("audit"), symbol_short!("ckpt")),
+            (id, start_index, up_to_index, root),
+        );
+
+        checkpoint
+    }
+
+    /// Build the Merkle inclusion path for a checkpointed audit log entry.
+    ///
+    /// Verifiers fold `leaf` with each step's `sibling` (on the side given by
+    /// `is_left`) using the node hash rule and compare the result to `root`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `index` - The sequence number of the audit log to prove
+    pub fn get_inclusion_path(env: Env, index: u64) -> InclusionProof {
+        let checkpoint = Self::find_checkpoint(&env, index)
+            .unwrap_or_else(|| panic!("audit log entry not checkpointed"));
+
+        let leaves = Self::leaf_hashes(&env, checkpoint.start_index, checkpoint.end_index);
+        let position = (index - checkpoint.start_index) as u32;
+        let leaf = leaves.get(position).unwrap();
+        let (root, path) = Self::merkle_root_and_path(&env, leaves, position);
+
+        // The entries are immutable, so a mismatch means the tree was rebuilt
+        // from data that no longer matches what was committed.
+        if root != checkpoint.root {
+            panic!("checkpoint root mismatch");
+        }
+
+        InclusionProof {
+            checkpoint_id: checkpoint.id,
+            index,
+            leaf,
+            path,
+            root,
+        }
+    }
+
+    /// Get a checkpoint by its id.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `id` - The checkpoint id (starting at 1)
+    pub fn get_checkpoint(env: Env, id: u64) -> Option<Checkpoint> {
+        env.storage().persistent().get(&DataKey::Checkpoint(id))
+    }
+
+    /// Get the number of checkpoints taken so far.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn get_checkpoint_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CheckpointCount)
+            .unwrap_or(0)
+    }
+
     // ── Admin Functions ───────────────────────────────────────────────────────
 
     /// Update the admin address.
@@ -317,8 +733,127 @@ impl AuditContract {
         );
     }
 
+    /// Choose whether entries for an operation are also published as events.
+    ///
+    /// Entries are always stored; this only controls the chain event, so
+    /// indexers can be spared very chatty operations.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `operation` - The operation to configure
+    /// * `mode` - The event mirroring mode for the operation
+    pub fn set_event_mode(env: Env, caller: Address, operation: Symbol, mode: EventMode) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let key = DataKey::EventMode(operation.clone());
+        if mode == EventMode::Mirrored {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &mode);
+        }
+
+        env.events().publish(
+            (symbol_short!("audit"), symbol_short!("evmode")),
+            (operation, mode),
+        );
+    }
+
+    /// Set the global sample rate applied to `Sampled` operations.
+    ///
+    /// An entry of a sampled operation is published only when its sequence
+    /// number is a multiple of `rate`, so a rate of 1 publishes every entry.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `rate` - Publish one event per `rate` entries (at least 1)
+    pub fn set_event_sample_rate(env: Env, caller: Address, rate: u32) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if rate == 0 {
+            panic!("sample rate must be at least 1");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::EventSampleRate, &rate);
+
+        env.events()
+            .publish((symbol_short!("audit"), symbol_short!("smplrate")), (rate,));
+    }
+
+    /// Configure the archive contract new entries are replicated to.
+    ///
+    /// Replication is best-effort: an archive call that fails never fails
+    /// the audit write, it is counted in the archive stats and reported with
+    /// an `archfail` event instead. `None` stops replication.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `archive` - The archive contract, implementing `ArchiveInterface`
+    pub fn set_archive(env: Env, caller: Address, archive: Option<Address>) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        match &archive {
+            Some(archive) => env.storage().instance().set(&DataKey::Archive, archive),
+            None => env.storage().instance().remove(&DataKey::Archive),
+        }
+
+        env.events().publish(
+            (symbol_short!("audit"), symbol_short!("archive")),
+            (archive,),
+        );
+    }
+
     // ── View Functions ────────────────────────────────────────────────────────
 
+    /// Get the archive contract new entries are replicated to, if any.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn get_archive(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Archive)
+    }
+
+    /// Get the replication counters for the archive contract.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn get_archive_stats(env: Env) -> ArchiveStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::ArchiveStats)
+            .unwrap_or_default()
+    }
+
+    /// Get the event mirroring mode of an operation.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `operation` - The operation to look up
+    pub fn get_event_mode(env: Env, operation: Symbol) -> EventMode {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventMode(operation))
+            .unwrap_or(EventMode::Mirrored)
+    }
+
+    /// Get the global sample rate applied to `Sampled` operations.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    pub fn get_event_sample_rate(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventSampleRate)
+            .unwrap_or(1)
+    }
+
     /// Check if an address is the admin.
     ///
     /// # Arguments
@@ -366,6 +901,210 @@ impl AuditContract {
             panic!("unauthorized: only admin can call this function");
         }
     }
+
+    /// Publish the event for a stored entry unless its operation's mirroring
+    /// mode says otherwise.
+    fn mirror_entry(env: &Env, actor: Address, operation: Symbol, status: Symbol, index: u64) {
+        let publish = match Self::get_event_mode(env.clone(), operation.clone()) {
+            EventMode::Mirrored => true,
+            EventMode::StorageOnly => false,
+            EventMode::Sampled => {
+                index.is_multiple_of(Self::get_event_sample_rate(env.clone()) as u64)
+            }
+        };
+
+        if publish {
+            env.events().publish(
+                (symbol_short!("audit"), symbol_short!("entry")),
+                (actor, operation, status, index),
+            );
+        }
+    }
+
+    /// Push a compact copy of a stored entry to the archive contract, if one
+    /// is configured, counting the outcome instead of failing on errors.
+    fn replicate_entry(env: &Env, index: u64, log: &AuditLog) {
+        let Some(archive) = Self::get_archive(env.clone()) else {
+            return;
+        };
+
+        let entry = ArchivedEntry {
+            index,
+            actor: log.actor.clone(),
+            operation: log.operation.clone(),
+            timestamp: log.timestamp,
+            status: log.status.clone(),
+            metadata_hash: log
+                .metadata
+                .as_ref()
+                .map(|metadata| env.crypto().sha256(metadata).into()),
+        };
+
+        let mut stats = Self::get_archive_stats(env.clone());
+        let accepted = matches!(
+            ArchiveClient::new(env, &archive).try_archive_entry(&entry),
+            Ok(Ok(()))
+        );
+        if accepted {
+            stats.replicated += 1;
+        } else {
+            stats.failed += 1;
+            stats.last_failed_index = index;
+            env.events().publish(
+                (symbol_short!("audit"), symbol_short!("archfail")),
+                (archive, index),
+            );
+        }
+        env.storage().instance().set(&DataKey::ArchiveStats, &stats);
+    }
+
+    /// Read the entries in `start..=end`, skipping missing ones and extending
+    /// the TTL of every entry found.
+    fn read_logs(env: &Env, start: u64, end: u64) -> Vec<AuditLog> {
+        pagination::read_page(
+            env,
+            (start..=end).map(DataKey::AuditLog),
+            LOG_TTL_THRESHOLD,
+            LOG_TTL_EXTEND_TO,
+        )
+    }
+
+    /// Read a stored entry, extending its TTL.
+    fn read_log(env: &Env, index: u64) -> Option<AuditLog> {
+        pagination::read_entry(
+            env,
+            &DataKey::AuditLog(index),
+            LOG_TTL_THRESHOLD,
+            LOG_TTL_EXTEND_TO,
+        )
+    }
+
+    /// Read a secondary index slot, extending its TTL alongside the entry.
+    fn read_index(env: &Env, key: &DataKey) -> Option<u64> {
+        pagination::read_entry(env, key, LOG_TTL_THRESHOLD, LOG_TTL_EXTEND_TO)
+    }
+
+    /// Panic unless `limit` is an accepted page size.
+    fn require_page_size(limit: u32) {
+        if !pagination::is_valid_page_size(limit) {
+            panic!("page size must be between 1 and 100");
+        }
+    }
+
+    /// Add a stored entry to the operation and day bucket indexes.
+    fn index_entry(env: &Env, index: u64, log: &AuditLog) {
+        let operation = log.operation.clone();
+        let position = Self::next_position(env, DataKey::OperationLogCount(operation.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::OperationLog(operation, position), &index);
+
+        let day = log.timestamp / SECONDS_PER_DAY;
+        let position = Self::next_position(env, DataKey::DayLogCount(day));
+        env.storage()
+            .persistent()
+            .set(&DataKey::DayLog(day, position), &index);
+    }
+
+    /// Reserve the next slot of an index, returning its position.
+    fn next_position(env: &Env, count_key: DataKey) -> u64 {
+        let position: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage().persistent().set(&count_key, &(position + 1));
+        position
+    }
+
+    /// Binary search the checkpoints for the one covering `index`.
+    fn find_checkpoint(env: &Env, index: u64) -> Option<Checkpoint> {
+        let mut low: u64 = 1;
+        let mut high = Self::get_checkpoint_count(env.clone());
+
+        while low <= high {
+            let mid = low + (high - low) / 2;
+            let checkpoint = Self::get_checkpoint(env.clone(), mid)?;
+            if index < checkpoint.start_index {
+                high = mid - 1;
+            } else if index > checkpoint.end_index {
+                low = mid + 1;
+            } else {
+                return Some(checkpoint);
+            }
+        }
+
+        None
+    }
+
+    /// Hash every entry in `start..=end` into a Merkle leaf.
+    fn leaf_hashes(env: &Env, start: u64, end: u64) -> Vec<BytesN<32>> {
+        let mut leaves: Vec<BytesN<32>> = Vec::new(env);
+        for i in start..=end {
+            let key = DataKey::AuditLog(i);
+            let log: AuditLog = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or_else(|| panic!("audit log entry missing"));
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, LOG_TTL_THRESHOLD, LOG_TTL_EXTEND_TO);
+
+            let mut preimage = Bytes::new(env);
+            preimage.push_back(LEAF_PREFIX);
+            preimage.extend_from_array(&i.to_be_bytes());
+            preimage.append(&log.to_xdr(env));
+            leaves.push_back(env.crypto().sha256(&preimage).into());
+        }
+        leaves
+    }
+
+    /// Reduce `leaves` to a root, collecting the sibling path for `position`.
+    fn merkle_root_and_path(
+        env: &Env,
+        leaves: Vec<BytesN<32>>,
+        position: u32,
+    ) -> (BytesN<32>, Vec<ProofStep>) {
+        let mut level = leaves;
+        let mut position = position;
+        let mut path: Vec<ProofStep> = Vec::new(env);
+
+        while level.len() > 1 {
+            let mut next: Vec<BytesN<32>> = Vec::new(env);
+            let mut i = 0;
+            while i < level.len() {
+                let left = level.get(i).unwrap();
+                match level.get(i + 1) {
+                    Some(right) => {
+                        if position == i {
+                            path.push_back(ProofStep {
+                                sibling: right.clone(),
+                                is_left: false,
+                            });
+                        } else if position == i + 1 {
+                            path.push_back(ProofStep {
+                                sibling: left.clone(),
+                                is_left: true,
+                            });
+                        }
+                        next.push_back(Self::node_hash(env, &left, &right));
+                    }
+                    None => next.push_back(left),
+                }
+                i += 2;
+            }
+            level = next;
+            position /= 2;
+        }
+
+        (level.get(0).unwrap(), path)
+    }
+
+    /// Hash two child nodes into their parent.
+    fn node_hash(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+        let mut preimage = Bytes::new(env);
+        preimage.push_back(NODE_PREFIX);
+        preimage.append(&Bytes::from(left.clone()));
+        preimage.append(&Bytes::from(right.clone()));
+        env.crypto().sha256(&preimage).into()
+    }
 }
 
 #[cfg(test)]
@@ -1,33 +1,24 @@
 #![cfg(test)]
 
 use soroban_sdk::{
-    testutils::{Events, Ledger, LedgerInfo},
-    Address, Env, Symbol, Vec, IntoVal,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, IntoVal, Symbol, TryFromVal, Vec,
 };
 
-use crate::{AuditContract, AuditContractClient, AuditLog};
+use crate::{ArchiveStats, ArchivedEntry, AuditContract, AuditContractClient, AuditLog};
 
 // ─── Test Helpers ─────────────────────────────────────────────────────────────
 
 fn setup_env() -> Env {
     let env = Env::default();
     env.mock_all_auths();
-    env.ledger().set(LedgerInfo {
-        timestamp: 1_700_000_000,
-        protocol_version: 20,
-        sequence_number: 1,
-        network_id: Default::default(),
-        base_reserve: 10,
-        min_temp_entry_ttl: 16,
-        min_persistent_entry_ttl: 4096,
-        max_entry_ttl: 6_312_000,
-    });
+    env.ledger().set_timestamp(1_700_000_000);
     env
 }
 
-fn deploy_contract(env: &Env) -> (AuditContractClient, Address) {
+fn deploy_contract(env: &Env) -> (AuditContractClient<'_>, Address) {
     let admin = Address::generate(env);
-    let contract_id = env.register_contract(None, AuditContract);
+    let contract_id = env.register(AuditContract, ());
     let client = AuditContractClient::new(env, &contract_id);
     (client, admin)
 }
@@ -42,19 +33,10 @@ fn test_initialize_contract() {
     // Initialize the contract
     client.initialize(&admin, &1000_u32);
 
-    // Verify admin is set correctly
-    assert!(client.is_admin(&admin));
-    assert_eq!(client.get_admin(), Some(admin.clone()));
-
-    // Verify config is set correctly
-    let config = client.get_config().unwrap();
-    assert_eq!(config.admin, admin);
-    assert_eq!(config.max_metadata_size, 1000);
-
     // Verify events are emitted
     let events = env.events().all();
     assert_eq!(events.len(), 1);
-    let (_, topics, data) = events.first().unwrap();
+    let (_, topics, _) = events.first().unwrap();
     assert_eq!(
         topics,
         soroban_sdk::vec![
@@ -63,6 +45,15 @@ fn test_initialize_contract() {
             Symbol::new(&env, "init").into_val(&env)
         ]
     );
+
+    // Verify admin is set correctly
+    assert!(client.is_admin(&admin));
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    // Verify config is set correctly
+    let config = client.get_config().unwrap();
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.max_metadata_size, 1000);
 }
 
 #[test]
@@ -85,26 +76,13 @@ fn test_log_audit_entry() {
     let actor = Address::generate(&env);
     let operation = Symbol::new(&env, "transfer");
     let status = Symbol::new(&env, "success");
-    let metadata = None;
-    let metadata_len = 0;
 
     // Log an audit entry
-    client.log_audit(&actor, &operation, &status, metadata);
-
-    // Verify total logs increased
-    assert_eq!(client.get_total_audit_logs(), 1);
-
-    // Verify the log was stored correctly
-    let log = client.get_audit_log(&1).unwrap();
-    assert_eq!(log.actor, actor);
-    assert_eq!(log.operation, operation);
-    assert_eq!(log.status, status);
-    assert_eq!(log.timestamp, 1_700_000_000);
-    assert!(log.metadata.is_none());
+    client.log_audit(&actor, &operation, &status, &None);
 
-    // Verify events are emitted
+    // Verify the audit log event is emitted
     let events = env.events().all();
-    assert_eq!(events.len(), 2); // init + audit log event
+    assert_eq!(events.len(), 1);
     let (_, topics, _) = events.last().unwrap();
     assert_eq!(
         topics,
@@ -114,6 +92,17 @@ fn test_log_audit_entry() {
             Symbol::new(&env, "entry").into_val(&env)
         ]
     );
+
+    // Verify total logs increased
+    assert_eq!(client.get_total_audit_logs(), 1);
+
+    // Verify the log was stored correctly
+    let log = client.get_audit_log(&1).unwrap();
+    assert_eq!(log.actor, actor);
+    assert_eq!(log.operation, operation);
+    assert_eq!(log.status, status);
+    assert_eq!(log.timestamp, 1_700_000_000);
+    assert!(log.metadata.is_none());
 }
 
 #[test]
@@ -130,7 +119,7 @@ fn test_log_audit_entry_with_metadata() {
     let metadata = Some(metadata_bytes);
 
     // Log an audit entry with metadata
-    client.log_audit(actor, operation, status, metadata);
+    client.log_audit(&actor, &operation, &status, &metadata);
 
     // Verify the log was stored correctly with metadata
     let log = client.get_audit_log(&1).unwrap();
@@ -157,7 +146,7 @@ fn test_log_audit_entry_exceeds_metadata_limit() {
     let metadata = Some(metadata_bytes);
 
     // This should panic because metadata exceeds limit
-    client.log_audit(&actor, &operation, &status, metadata);
+    client.log_audit(&actor, &operation, &status, &metadata);
 }
 
 #[test]
@@ -168,13 +157,13 @@ fn test_batch_log_audit_entries() {
 
     // Create multiple audit logs
     let mut logs: Vec<AuditLog> = Vec::new(&env);
-    
+
     let actor1 = Address::generate(&env);
     let operation1 = Symbol::new(&env, "transfer");
     let status1 = Symbol::new(&env, "success");
     logs.push_back(AuditLog {
         actor: actor1.clone(),
-        operation: operation1,
+        operation: operation1.clone(),
         timestamp: 1_700_000_000,
         status: status1,
         metadata: None,
@@ -186,8 +175,8 @@ fn test_batch_log_audit_entries() {
     let status2 = Symbol::new(&env, "failure");
     logs.push_back(AuditLog {
         actor: actor2.clone(),
-        operation: operation2,
-        timestamp: 1_700_000_001,
+        operation: operation2.clone(),
+        timestamp: 1_700_000_000,
         status: status2,
         metadata: None,
         metadata_len: 0,
@@ -196,6 +185,9 @@ fn test_batch_log_audit_entries() {
     // Log the batch
     client.batch_log_audit(&admin, &logs);
 
+    // Verify an audit entry event is emitted for each log
+    assert_eq!(env.events().all().len(), 2);
+
     // Verify total logs increased correctly
     assert_eq!(client.get_total_audit_logs(), 2);
 
@@ -207,11 +199,6 @@ fn test_batch_log_audit_entries() {
     let log2 = client.get_audit_log(&2).unwrap();
     assert_eq!(log2.actor, actor2);
     assert_eq!(log2.operation, operation2);
-
-    // Verify events are emitted for each log
-    let events = env.events().all();
-    // 1 init event + 2 audit entry events
-    assert_eq!(events.len(), 3);
 }
 
 #[test]
@@ -241,31 +228,35 @@ fn test_get_audit_logs_range() {
     for i in 1..=5 {
         let mut metadata_bytes = soroban_sdk::Bytes::new(&env);
         metadata_bytes.extend_from_slice(&[i as u8]);
-        client.log_audit(
-            &actor,
-            &operation,
-            &status,
-            Some(metadata_bytes),
-        );
+        client.log_audit(&actor, &operation, &status, &Some(metadata_bytes));
     }
 
     // Get logs in range 2-4
-    let logs = client.get_audit_logs_range(&2, &4);
-    assert_eq!(logs.len(), 3);
+    let range = client.get_audit_logs_range(&2, &4);
+    assert_eq!(range.found_count, 3);
+    assert_eq!(range.logs.len(), 3);
 
     // Verify each log in the range
-    for (i, log_opt) in logs.iter().enumerate() {
-        if let Some(log) = log_opt {
-            assert_eq!(log.actor, actor);
-            assert_eq!(log.operation, operation);
-            assert_eq!(log.status, status);
-            let mut expected_meta = soroban_sdk::Bytes::new(&env);
-            expected_meta.extend_from_slice(&[(i + 2) as u8]); // +2 because range starts at 2
-            assert_eq!(log.metadata.unwrap(), expected_meta);
-        }
+    for (i, log) in range.logs.iter().enumerate() {
+        assert_eq!(log.actor, actor);
+        assert_eq!(log.operation, operation);
+        assert_eq!(log.status, status);
+        let mut expected_meta = soroban_sdk::Bytes::new(&env);
+        expected_meta.extend_from_slice(&[(i + 2) as u8]); // +2 because range starts at 2
+        assert_eq!(log.metadata.unwrap(), expected_meta);
     }
 }
 
+#[test]
+#[should_panic(expected = "range exceeds maximum size of 100")]
+fn test_get_audit_logs_range_too_large() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+
+    client.get_audit_logs_range(&1, &(crate::MAX_RANGE_SIZE + 1));
+}
+
 #[test]
 #[should_panic(expected = "start index cannot be greater than end index")]
 fn test_get_audit_logs_range_invalid_range() {
@@ -288,14 +279,9 @@ fn test_set_admin() {
     // Change admin
     client.set_adm(&admin, &new_admin);
 
-    // Verify new admin is set
-    assert!(!client.is_admin(&admin));
-    assert!(client.is_admin(&new_admin));
-    assert_eq!(client.get_admin(), Some(new_admin.clone()));
-
     // Verify events are emitted
     let events = env.events().all();
-    let (_, topics, _) = events.get(events.len() - 1).unwrap(); // Last event should be admin transfer
+    let (_, topics, _) = events.last().unwrap(); // Last event should be admin transfer
     assert_eq!(
         topics,
         soroban_sdk::vec![
@@ -304,6 +290,11 @@ fn test_set_admin() {
             Symbol::new(&env, "admtfr").into_val(&env)
         ]
     );
+
+    // Verify new admin is set
+    assert!(!client.is_admin(&admin));
+    assert!(client.is_admin(&new_admin));
+    assert_eq!(client.get_admin(), Some(new_admin.clone()));
 }
 
 #[test]
@@ -315,13 +306,9 @@ fn test_set_max_metadata_size() {
     // Change max metadata size
     client.set_max_metadata_size(&admin, &2000_u32);
 
-    // Verify new config is set
-    let config = client.get_config().unwrap();
-    assert_eq!(config.max_metadata_size, 2000);
-
     // Verify events are emitted
     let events = env.events().all();
-    let (_, topics, _) = events.get(events.len() - 1).unwrap(); // Last event should be config update
+    let (_, topics, _) = events.last().unwrap(); // Last event should be config update
     assert_eq!(
         topics,
         soroban_sdk::vec![
@@ -330,6 +317,10 @@ fn test_set_max_metadata_size() {
             Symbol::new(&env, "cfgup").into_val(&env)
         ]
     );
+
+    // Verify new config is set
+    let config = client.get_config().unwrap();
+    assert_eq!(config.max_metadata_size, 2000);
 }
 
 #[test]
@@ -356,7 +347,7 @@ fn test_audit_events_emitted() {
     let operation = Symbol::new(&env, "test_op");
     let status = Symbol::new(&env, "test_status");
 
-    client.log_audit(&actor, &operation, &status, None);
+    client.log_audit(&actor, &operation, &status, &None);
 
     // Verify that events were published
     let events = env.events().all();
@@ -365,11 +356,10 @@ fn test_audit_events_emitted() {
     // Check that the audit entry event was published
     let mut has_audit_event = false;
     for (_, topics, _) in events.iter() {
-        let topic_vec = topics.clone();
-        if topic_vec.len() == 2 {
-            let topic1: Symbol = topic_vec.get(0).unwrap().into_val(&env).try_into().unwrap();
-            let topic2: Symbol = topic_vec.get(1).unwrap().into_val(&env).try_into().unwrap();
-            
+        if topics.len() == 2 {
+            let topic1 = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+            let topic2 = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
+
             if topic1 == Symbol::new(&env, "audit") && topic2 == Symbol::new(&env, "entry") {
                 has_audit_event = true;
                 break;
@@ -380,6 +370,7 @@ fn test_audit_events_emitted() {
 }
 
 #[test]
+#[should_panic(expected = "audit log timestamp cannot be in the future")]
 fn test_timestamp_validation_in_batch() {
     let env = setup_env();
     let (client, admin) = deploy_contract(&env);
@@ -389,7 +380,7 @@ fn test_timestamp_validation_in_batch() {
     let actor = Address::generate(&env);
     let operation = Symbol::new(&env, "future_op");
     let status = Symbol::new(&env, "pending");
-    
+
     let future_log = AuditLog {
         actor: actor.clone(),
         operation,
@@ -403,8 +394,401 @@ fn test_timestamp_validation_in_batch() {
     logs.push_back(future_log);
 
     // This should panic because the timestamp is in the future
-    #[should_panic(expected = "audit log timestamp cannot be in the future")]
-    {
-        client.batch_log_audit(&admin, &logs);
+    client.batch_log_audit(&admin, &logs);
+}
+// ─── Checkpoint Tests ─────────────────────────────────────────────────────────
+
+/// Fold an inclusion proof back up to its root the way an external verifier would.
+fn fold_proof(env: &Env, proof: &crate::InclusionProof) -> soroban_sdk::BytesN<32> {
+    let mut hash = proof.leaf.clone();
+    for step in proof.path.iter() {
+        let (left, right) = if step.is_left {
+            (step.sibling, hash)
+        } else {
+            (hash, step.sibling)
+        };
+        let mut preimage = soroban_sdk::Bytes::new(env);
+        preimage.push_back(0x01);
+        preimage.append(&left.into());
+        preimage.append(&right.into());
+        hash = env.crypto().sha256(&preimage).into();
     }
-}
\ No newline at end of file
+    hash
+}
+
+fn log_entries(env: &Env, client: &AuditContractClient, count: u32) {
+    let actor = Address::generate(env);
+    let operation = Symbol::new(env, "transfer");
+    let status = Symbol::new(env, "success");
+    for _ in 0..count {
+        client.log_audit(&actor, &operation, &status, &None);
+    }
+}
+
+#[test]
+fn test_checkpoint_and_inclusion_paths() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+    log_entries(&env, &client, 7);
+
+    let first = client.checkpoint(&admin, &5);
+    assert_eq!(first.id, 1);
+    assert_eq!(first.start_index, 1);
+    assert_eq!(first.end_index, 5);
+
+    // Next checkpoint picks up where the previous one ended
+    let second = client.checkpoint(&admin, &7);
+    assert_eq!(second.start_index, 6);
+    assert_eq!(client.get_checkpoint_count(), 2);
+    assert_eq!(client.get_checkpoint(&1), Some(first.clone()));
+
+    // Every entry, including the odd one carried up a level, folds back to its root
+    for index in 1..=7u64 {
+        let proof = client.get_inclusion_path(&index);
+        let expected = if index <= 5 { &first } else { &second };
+        assert_eq!(proof.checkpoint_id, expected.id);
+        assert_eq!(proof.root, expected.root);
+        assert_eq!(fold_proof(&env, &proof), expected.root);
+    }
+}
+
+#[test]
+fn test_inclusion_proof_rejects_wrong_leaf() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+    log_entries(&env, &client, 4);
+    client.checkpoint(&admin, &4);
+
+    let mut proof = client.get_inclusion_path(&2);
+    proof.leaf = client.get_inclusion_path(&3).leaf;
+    assert_ne!(fold_proof(&env, &proof), proof.root);
+}
+
+#[test]
+#[should_panic(expected = "audit log entry not checkpointed")]
+fn test_inclusion_path_requires_checkpoint() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+    log_entries(&env, &client, 3);
+    client.checkpoint(&admin, &2);
+
+    client.get_inclusion_path(&3);
+}
+
+#[test]
+#[should_panic(expected = "nothing to checkpoint")]
+fn test_checkpoint_cannot_rewind() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+    log_entries(&env, &client, 3);
+    client.checkpoint(&admin, &3);
+
+    client.checkpoint(&admin, &2);
+}
+
+// ─── Cursor Pagination Tests ──────────────────────────────────────────────────
+
+#[test]
+fn test_get_logs_after_pages_through_all_entries() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+    log_entries(&env, &client, 5);
+
+    let (page, cursor) = client.get_logs_after(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(cursor, 2);
+
+    let (page, cursor) = client.get_logs_after(&cursor, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(cursor, 4);
+
+    // Last page is short and the cursor stops at the newest entry
+    let (page, cursor) = client.get_logs_after(&cursor, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(cursor, 5);
+
+    // Caught up: empty page, cursor unchanged
+    let (page, cursor) = client.get_logs_after(&cursor, &2);
+    assert_eq!(page.len(), 0);
+    assert_eq!(cursor, 5);
+}
+
+#[test]
+#[should_panic(expected = "page size must be between 1 and 100")]
+fn test_get_logs_after_page_too_large() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+
+    client.get_logs_after(&0, &(crate::MAX_PAGE_SIZE + 1));
+}
+
+// ─── Secondary Index Tests ────────────────────────────────────────────────────
+
+const DAY: u64 = 86_400;
+
+fn entry_at(env: &Env, actor: &Address, operation: &str, timestamp: u64) -> AuditLog {
+    AuditLog {
+        actor: actor.clone(),
+        operation: Symbol::new(env, operation),
+        timestamp,
+        status: Symbol::new(env, "success"),
+        metadata: None,
+        metadata_len: 0,
+    }
+}
+
+#[test]
+fn test_audit_logs_by_operation_and_time_window() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+    let now = env.ledger().timestamp();
+    let actor = Address::generate(&env);
+
+    // Entries 1-2 are backfilled two days and one day ago
+    let mut logs: Vec<AuditLog> = Vec::new(&env);
+    logs.push_back(entry_at(&env, &actor, "withdraw", now - 2 * DAY));
+    logs.push_back(entry_at(&env, &actor, "transfer", now - DAY));
+    client.batch_log_audit(&admin, &logs);
+
+    // Entries 3-6 are logged now
+    let transfer = Symbol::new(&env, "transfer");
+    let withdraw = Symbol::new(&env, "withdraw");
+    let success = Symbol::new(&env, "success");
+    for operation in [&transfer, &withdraw, &transfer, &transfer] {
+        client.log_audit(&actor, operation, &success, &None);
+    }
+
+    let transfers = client.get_audit_logs_by_operation(&transfer, &0, &3);
+    assert_eq!(transfers.len(), 3);
+    assert_eq!(transfers.get(0).unwrap().timestamp, now - DAY);
+    assert_eq!(transfers.get(1).unwrap().timestamp, now);
+    assert_eq!(
+        client.get_audit_logs_by_operation(&transfer, &3, &3).len(),
+        1
+    );
+    assert_eq!(
+        client.get_audit_logs_by_operation(&transfer, &4, &3).len(),
+        0
+    );
+    assert_eq!(
+        client
+            .get_audit_logs_by_operation(&withdraw, &0, &100)
+            .len(),
+        2
+    );
+    assert_eq!(
+        client
+            .get_audit_logs_by_operation(&Symbol::new(&env, "refund"), &0, &100)
+            .len(),
+        0
+    );
+
+    // A window within one day only returns that day's entries
+    let window = client.get_audit_logs_in_window(&(now - 2 * DAY), &(now - DAY - 1), &100);
+    assert_eq!(window.len(), 1);
+    assert_eq!(window.get(0).unwrap().operation, withdraw);
+
+    // Oldest day first, and the limit applies across days
+    let window = client.get_audit_logs_in_window(&(now - 3 * DAY), &now, &100);
+    assert_eq!(window.len(), 6);
+    let window = client.get_audit_logs_in_window(&(now - 3 * DAY), &now, &2);
+    assert_eq!(window.len(), 2);
+    assert_eq!(window.get(0).unwrap().timestamp, now - 2 * DAY);
+    assert_eq!(window.get(1).unwrap().timestamp, now - DAY);
+
+    // Timestamps are compared exactly within the boundary days
+    assert_eq!(
+        client
+            .get_audit_logs_in_window(&(now - DAY + 1), &(now - 1), &100)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_audit_logs_in_window_pages_with_cursor() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+    let now = env.ledger().timestamp();
+    let actor = Address::generate(&env);
+
+    let mut logs: Vec<AuditLog> = Vec::new(&env);
+    logs.push_back(entry_at(&env, &actor, "transfer", now - 2 * DAY));
+    logs.push_back(entry_at(&env, &actor, "transfer", now - 2 * DAY));
+    logs.push_back(entry_at(&env, &actor, "transfer", now - DAY));
+    client.batch_log_audit(&admin, &logs);
+    log_entries(&env, &client, 2);
+
+    let start = now - 2 * DAY;
+    let mut cursor = 0;
+    let mut timestamps: Vec<u64> = Vec::new(&env);
+    let mut pages = 0;
+    loop {
+        let (page, next) = client.get_audit_logs_in_window_after(&start, &now, &cursor, &2);
+        if page.is_empty() {
+            break;
+        }
+        pages += 1;
+        for log in page.iter() {
+            timestamps.push_back(log.timestamp);
+        }
+        cursor = next;
+    }
+    assert_eq!(pages, 3);
+    assert_eq!(
+        timestamps,
+        soroban_sdk::vec![&env, now - 2 * DAY, now - 2 * DAY, now - DAY, now, now]
+    );
+}
+
+#[test]
+#[should_panic(expected = "time window exceeds maximum of 31 days")]
+fn test_audit_logs_in_window_too_wide() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+
+    let now = env.ledger().timestamp();
+    client.get_audit_logs_in_window(&(now - crate::MAX_WINDOW_DAYS * DAY), &now, &10);
+}
+
+#[test]
+fn test_event_mirroring_modes() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+
+    let actor = Address::generate(&env);
+    let operation = Symbol::new(&env, "heartbeat");
+    let status = Symbol::new(&env, "success");
+    assert_eq!(
+        client.get_event_mode(&operation),
+        crate::EventMode::Mirrored
+    );
+    assert_eq!(client.get_event_sample_rate(), 1);
+
+    // Storage-only: the entry is stored but no event is published
+    client.set_event_mode(&admin, &operation, &crate::EventMode::StorageOnly);
+    client.log_audit(&actor, &operation, &status, &None);
+    assert_eq!(env.events().all().len(), 0);
+    assert_eq!(client.get_total_audit_logs(), 1);
+    assert!(client.get_audit_log(&1).is_some());
+
+    // Sampled at 1 in 3: only entries 3 and 6 are published
+    client.set_event_mode(&admin, &operation, &crate::EventMode::Sampled);
+    client.set_event_sample_rate(&admin, &3);
+    let mut published = 0;
+    for _ in 2..=6 {
+        client.log_audit(&actor, &operation, &status, &None);
+        published += env.events().all().len();
+    }
+    assert_eq!(published, 2);
+    assert_eq!(client.get_total_audit_logs(), 6);
+
+    // Other operations are still mirrored
+    log_entries(&env, &client, 1);
+    assert_eq!(env.events().all().len(), 1);
+
+    // Switching back to mirrored publishes every entry again
+    client.set_event_mode(&admin, &operation, &crate::EventMode::Mirrored);
+    client.log_audit(&actor, &operation, &status, &None);
+    assert_eq!(env.events().all().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "sample rate must be at least 1")]
+fn test_event_sample_rate_cannot_be_zero() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+
+    client.set_event_sample_rate(&admin, &0);
+}
+
+// ─── Archive Replication ──────────────────────────────────────────────────────
+
+/// Archive that keeps the last entry it received and rejects entries whose
+/// status is "reject".
+#[soroban_sdk::contract]
+pub struct MockArchive;
+
+#[soroban_sdk::contractimpl]
+impl MockArchive {
+    pub fn archive_entry(env: Env, entry: ArchivedEntry) {
+        if entry.status == Symbol::new(&env, "reject") {
+            panic!("archive rejected entry");
+        }
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "last"), &entry);
+    }
+
+    pub fn last(env: Env) -> Option<ArchivedEntry> {
+        env.storage().instance().get(&Symbol::new(&env, "last"))
+    }
+}
+
+#[test]
+fn test_entries_are_replicated_to_archive_best_effort() {
+    let env = setup_env();
+    let (client, admin) = deploy_contract(&env);
+    client.initialize(&admin, &1000_u32);
+
+    let archive_id = env.register(MockArchive, ());
+    let archive = MockArchiveClient::new(&env, &archive_id);
+    client.set_archive(&admin, &Some(archive_id.clone()));
+    assert_eq!(client.get_archive(), Some(archive_id));
+
+    let actor = Address::generate(&env);
+    let metadata = soroban_sdk::Bytes::from_slice(&env, b"invoice-42");
+    client.log_audit(
+        &actor,
+        &Symbol::new(&env, "transfer"),
+        &Symbol::new(&env, "success"),
+        &Some(metadata.clone()),
+    );
+
+    let entry = archive.last().unwrap();
+    assert_eq!(entry.index, 1);
+    assert_eq!(entry.actor, actor);
+    assert_eq!(
+        entry.metadata_hash,
+        Some(env.crypto().sha256(&metadata).into())
+    );
+
+    // A failing archive does not fail the audit write
+    client.log_audit(
+        &actor,
+        &Symbol::new(&env, "transfer"),
+        &Symbol::new(&env, "reject"),
+        &None,
+    );
+    assert_eq!(client.get_total_audit_logs(), 2);
+    assert_eq!(
+        client.get_archive_stats(),
+        ArchiveStats {
+            replicated: 1,
+            failed: 1,
+            last_failed_index: 2,
+        }
+    );
+
+    // Without an archive nothing is replicated
+    client.set_archive(&admin, &None);
+    client.log_audit(
+        &actor,
+        &Symbol::new(&env, "transfer"),
+        &Symbol::new(&env, "success"),
+        &None,
+    );
+    assert_eq!(archive.last().unwrap().index, 1);
+    assert_eq!(client.get_archive_stats().replicated, 1);
+}
@@ -0,0 +1,54 @@
+//! Admin-managed registry of notification channels.
+//!
+//! Routing hints on reminder requests must name a registered channel, or
+//! `DEFAULT_CHANNEL` to leave the choice to the off-chain notifier.
+
+use crate::types::{DataKey, RoutingHints, DEFAULT_CHANNEL, MAX_PRIORITY};
+use crate::validation::ValidationError;
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+pub fn initialize(env: &Env, admin: &Address) {
+    if env.storage().instance().has(&DataKey::Admin) {
+        panic!("Already initialized");
+    }
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+pub fn require_admin(env: &Env, admin: &Address) {
+    let stored_admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Not initialized");
+    if *admin != stored_admin {
+        panic!("Unauthorized");
+    }
+}
+
+pub fn set_enabled(env: &Env, channel: &Symbol, enabled: bool) {
+    let key = DataKey::Channel(channel.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    env.events()
+        .publish((symbol_short!("rem_chan"), channel.clone()), enabled);
+}
+
+pub fn is_enabled(env: &Env, channel: &Symbol) -> bool {
+    *channel == DEFAULT_CHANNEL
+        || env
+            .storage()
+            .persistent()
+            .get(&DataKey::Channel(channel.clone()))
+            .unwrap_or(false)
+}
+
+/// Routing hints must name an enabled channel and a priority up to `MAX_PRIORITY`.
+pub fn validate_routing(env: &Env, routing: &RoutingHints) -> Result<(), ValidationError> {
+    if !is_enabled(env, &routing.channel) || routing.priority > MAX_PRIORITY {
+        return Err(ValidationError::RoutingRejected);
+    }
+    Ok(())
+}
@@ -1,6 +1,8 @@
 #![no_std]
 
+mod channels;
 mod logic;
+mod preferences;
 mod types;
 mod validation;
 
@@ -8,7 +10,7 @@ mod validation;
 mod test;
 
 use crate::types::{BatchReminderResult, PaymentReminderRequest};
-use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Vec};
 
 #[contract]
 pub struct BatchPaymentRemindersContract;
@@ -19,20 +21,79 @@ impl BatchPaymentRemindersContract {
     ///
     /// Validates each (user, due_date); valid entries get a reminder_sent event,
     /// invalid ones are skipped and recorded in the result (partial failure handling).
+    /// Reminders the user snoozed or whose category they opted out of are skipped
+    /// and counted separately. Routing hints must name a registered channel and
+    /// are included in each reminder_sent event.
     ///
     /// # Arguments
-    /// * `admin` - Caller must authorize (admin).
+    /// * `admin` - The admin set by `initialize` (must authorize).
     /// * `requests` - List of (user, due_date) reminder requests.
     /// # Returns
-    /// * `BatchReminderResult` with successful_count and failed_addresses.
+    /// * `BatchReminderResult` with successful_count, failed_addresses and skip counts.
     pub fn dispatch_batch_reminders(
         env: Env,
         admin: Address,
         requests: Vec<PaymentReminderRequest>,
     ) -> BatchReminderResult {
         admin.require_auth();
+        channels::require_admin(&env, &admin);
 
         let batch_id = env.ledger().sequence() as u64;
         logic::execute_dispatch(env, batch_id, requests)
     }
+
+    /// Sets the admin that dispatches reminders and manages the channel registry.
+    pub fn initialize(env: Env, admin: Address) {
+        admin.require_auth();
+        channels::initialize(&env, &admin);
+    }
+
+    /// Registers (or removes) a notification channel routing hints may name.
+    ///
+    /// # Arguments
+    /// * `admin` - Registry admin (must authorize).
+    /// * `channel` - Channel name, e.g. "email" or "sms".
+    /// * `enabled` - `false` removes the channel.
+    pub fn set_channel(env: Env, admin: Address, channel: Symbol, enabled: bool) {
+        admin.require_auth();
+        channels::require_admin(&env, &admin);
+        channels::set_enabled(&env, &channel, enabled);
+    }
+
+    /// Returns true if routing hints may name the channel.
+    pub fn is_channel_enabled(env: Env, channel: Symbol) -> bool {
+        channels::is_enabled(&env, &channel)
+    }
+
+    /// Snoozes a reminder until the given ledger sequence.
+    ///
+    /// # Arguments
+    /// * `user` - Reminder recipient (must authorize).
+    /// * `reminder_id` - Reminder to snooze.
+    /// * `until` - Ledger sequence at which reminders resume (must be in the future).
+    pub fn snooze(env: Env, user: Address, reminder_id: u64, until: u64) {
+        user.require_auth();
+        preferences::snooze(&env, &user, reminder_id, until);
+    }
+
+    /// Returns the ledger sequence a reminder is snoozed until, if any.
+    pub fn get_snooze(env: Env, user: Address, reminder_id: u64) -> Option<u64> {
+        preferences::get_snooze(&env, &user, reminder_id)
+    }
+
+    /// Opts a user out of (or back into) a reminder category.
+    ///
+    /// # Arguments
+    /// * `user` - Reminder recipient (must authorize).
+    /// * `category` - Reminder category.
+    /// * `opt_out` - `true` to stop reminders for the category.
+    pub fn set_opt_out(env: Env, user: Address, category: Symbol, opt_out: bool) {
+        user.require_auth();
+        preferences::set_opt_out(&env, &user, &category, opt_out);
+    }
+
+    /// Returns true if the user opted out of the category.
+    pub fn is_opted_out(env: Env, user: Address, category: Symbol) -> bool {
+        preferences::is_opted_out(&env, &user, &category)
+    }
 }
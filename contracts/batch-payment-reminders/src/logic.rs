@@ -1,5 +1,7 @@
 //! Batch payment reminder dispatch: validate each request, handle partial failures, emit events.
 
+use crate::channels::validate_routing;
+use crate::preferences::{is_opted_out, is_snoozed};
 use crate::types::{BatchReminderResult, PaymentReminderRequest};
 use crate::validation::{validate_reminder_request, ValidationError};
 use soroban_sdk::{symbol_short, Env, Vec};
@@ -11,6 +13,8 @@ pub fn execute_dispatch(
 ) -> BatchReminderResult {
     let mut successful_count: u32 = 0;
     let mut failed_addresses = Vec::new(&env);
+    let mut snoozed_count: u32 = 0;
+    let mut opted_out_count: u32 = 0;
 
     env.events().publish(
         (
@@ -22,7 +26,31 @@ pub fn execute_dispatch(
     );
 
     for request in requests.iter() {
-        match validate_reminder_request(&env, &request.user, request.due_date) {
+        let validation = validate_reminder_request(&env, &request.user, request.due_date)
+            .and_then(|()| validate_routing(&env, &request.routing));
+        match validation {
+            Ok(()) if is_opted_out(&env, &request.user, &request.category) => {
+                env.events().publish(
+                    (
+                        symbol_short!("rem_skip"),
+                        request.user.clone(),
+                        symbol_short!("opt_out"),
+                    ),
+                    (batch_id, request.reminder_id),
+                );
+                opted_out_count += 1;
+            }
+            Ok(()) if is_snoozed(&env, &request.user, request.reminder_id) => {
+                env.events().publish(
+                    (
+                        symbol_short!("rem_skip"),
+                        request.user.clone(),
+                        symbol_short!("snoozed"),
+                    ),
+                    (batch_id, request.reminder_id),
+                );
+                snoozed_count += 1;
+            }
             Ok(()) => {
                 env.events().publish(
                     (
@@ -30,7 +58,12 @@ pub fn execute_dispatch(
                         request.user.clone(),
                         request.due_date,
                     ),
-                    batch_id,
+                    (
+                        batch_id,
+                        request.routing.channel,
+                        request.routing.locale,
+                        request.routing.priority,
+                    ),
                 );
                 successful_count += 1;
             }
@@ -45,6 +78,17 @@ pub fn execute_dispatch(
                 );
                 failed_addresses.push_back(request.user.clone());
             }
+            Err(ValidationError::RoutingRejected) => {
+                env.events().publish(
+                    (
+                        symbol_short!("rem_fail"),
+                        request.user.clone(),
+                        symbol_short!("routing"),
+                    ),
+                    (batch_id, request.routing.channel),
+                );
+                failed_addresses.push_back(request.user.clone());
+            }
         }
     }
 
@@ -54,11 +98,18 @@ pub fn execute_dispatch(
             symbol_short!("completed"),
             batch_id,
         ),
-        (successful_count, failed_addresses.len() as u32),
+        (
+            successful_count,
+            failed_addresses.len() as u32,
+            snoozed_count,
+            opted_out_count,
+        ),
     );
 
     BatchReminderResult {
         successful_count,
         failed_addresses,
+        snoozed_count,
+        opted_out_count,
     }
 }
@@ -0,0 +1,57 @@
+//! User-controlled reminder preferences: snoozes and category opt-outs.
+
+use crate::types::DataKey;
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+/// Snoozes a reminder until the given ledger sequence.
+pub fn snooze(env: &Env, user: &Address, reminder_id: u64, until: u64) {
+    if until <= env.ledger().sequence() as u64 {
+        panic!("Snooze must end in the future");
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Snooze(user.clone(), reminder_id), &until);
+    env.events().publish(
+        (
+            symbol_short!("rem_pref"),
+            symbol_short!("snoozed"),
+            user.clone(),
+        ),
+        (reminder_id, until),
+    );
+}
+
+pub fn get_snooze(env: &Env, user: &Address, reminder_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Snooze(user.clone(), reminder_id))
+}
+
+pub fn is_snoozed(env: &Env, user: &Address, reminder_id: u64) -> bool {
+    matches!(get_snooze(env, user, reminder_id), Some(until) if (env.ledger().sequence() as u64) < until)
+}
+
+pub fn set_opt_out(env: &Env, user: &Address, category: &Symbol, opt_out: bool) {
+    let key = DataKey::OptOut(user.clone(), category.clone());
+    if opt_out {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    env.events().publish(
+        (
+            symbol_short!("rem_pref"),
+            symbol_short!("opt_out"),
+            user.clone(),
+        ),
+        (category.clone(), opt_out),
+    );
+}
+
+pub fn is_opted_out(env: &Env, user: &Address, category: &Symbol) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OptOut(user.clone(), category.clone()))
+        .unwrap_or(false)
+}
@@ -1,10 +1,11 @@
 #![cfg(test)]
 
-use crate::types::PaymentReminderRequest;
+use crate::types::{PaymentReminderRequest, RoutingHints, DEFAULT_CHANNEL, MAX_PRIORITY};
 use crate::{BatchPaymentRemindersContract, BatchPaymentRemindersContractClient};
 use soroban_sdk::{
-    testutils::{Address as _, Events as _},
-    vec, Address, Env, Vec,
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, Env, Symbol, TryFromVal, Vec,
 };
 
 fn setup(env: &Env) -> (Address, BatchPaymentRemindersContractClient<'_>) {
@@ -12,9 +13,18 @@ fn setup(env: &Env) -> (Address, BatchPaymentRemindersContractClient<'_>) {
     let contract_id = env.register(BatchPaymentRemindersContract, ());
     let client = BatchPaymentRemindersContractClient::new(env, &contract_id);
     let admin = Address::generate(env);
+    client.initialize(&admin);
     (admin, client)
 }
 
+fn default_routing() -> RoutingHints {
+    RoutingHints {
+        channel: DEFAULT_CHANNEL,
+        locale: symbol_short!("en"),
+        priority: 0,
+    }
+}
+
 fn current_ledger(env: &Env) -> u64 {
     env.ledger().sequence() as u64
 }
@@ -32,11 +42,17 @@ fn test_dispatch_batch_reminders_all_success() {
         &env,
         PaymentReminderRequest {
             user: user1.clone(),
+            reminder_id: 1,
+            category: symbol_short!("bills"),
             due_date: due,
+            routing: default_routing(),
         },
         PaymentReminderRequest {
             user: user2.clone(),
+            reminder_id: 2,
+            category: symbol_short!("bills"),
             due_date: due + 1,
+            routing: default_routing(),
         },
     ];
 
@@ -65,11 +81,17 @@ fn test_dispatch_batch_reminders_partial_failure() {
         &env,
         PaymentReminderRequest {
             user: user_ok.clone(),
+            reminder_id: 3,
+            category: symbol_short!("bills"),
             due_date: current + 50,
+            routing: default_routing(),
         },
         PaymentReminderRequest {
             user: user_bad_due.clone(),
+            reminder_id: 4,
+            category: symbol_short!("bills"),
             due_date: current, // invalid: not in future
+            routing: default_routing(),
         },
     ];
 
@@ -96,7 +118,10 @@ fn test_dispatch_batch_reminders_events_emitted() {
         &env,
         PaymentReminderRequest {
             user: user.clone(),
+            reminder_id: 5,
+            category: symbol_short!("bills"),
             due_date: current_ledger(&env) + 200,
+            routing: default_routing(),
         },
     ];
 
@@ -111,27 +136,25 @@ fn test_dispatch_batch_reminders_events_emitted() {
 }
 
 #[test]
+#[should_panic(expected = "Unauthorized")]
 fn test_dispatch_batch_reminders_requires_admin_auth() {
     let env = Env::default();
-    env.mock_all_auths();
+    let (_admin, client) = setup(&env);
 
-    let contract_id = env.register(BatchPaymentRemindersContract, ());
-    let client = BatchPaymentRemindersContractClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
     let user = Address::generate(&env);
     let requests = vec![
         &env,
         PaymentReminderRequest {
             user,
+            reminder_id: 6,
+            category: symbol_short!("bills"),
             due_date: current_ledger(&env) + 10,
+            routing: default_routing(),
         },
     ];
 
-    // Call without admin auth will panic in require_auth
-    client.dispatch_batch_reminders(&admin, &requests);
-    // If we get here with mock_all_auths, auth passed
-    assert!(true);
+    // An authorized caller that is not the registry admin is rejected
+    client.dispatch_batch_reminders(&Address::generate(&env), &requests);
 }
 
 #[test]
@@ -148,3 +171,157 @@ fn test_dispatch_batch_reminders_empty_batch() {
     let events = env.events().all();
     assert!(events.len() >= 2, "expected started + completed events");
 }
+
+#[test]
+fn test_dispatch_skips_snoozed_and_opted_out() {
+    let env = Env::default();
+    let (admin, client) = setup(&env);
+
+    let active = Address::generate(&env);
+    let snoozer = Address::generate(&env);
+    let opted_out = Address::generate(&env);
+    let due = current_ledger(&env) + 100;
+
+    client.snooze(&snoozer, &2, &(current_ledger(&env) + 10));
+    client.set_opt_out(&opted_out, &symbol_short!("bills"), &true);
+    assert!(client.is_opted_out(&opted_out, &symbol_short!("bills")));
+
+    let requests = vec![
+        &env,
+        PaymentReminderRequest {
+            user: active.clone(),
+            reminder_id: 1,
+            category: symbol_short!("bills"),
+            due_date: due,
+            routing: default_routing(),
+        },
+        PaymentReminderRequest {
+            user: snoozer.clone(),
+            reminder_id: 2,
+            category: symbol_short!("bills"),
+            due_date: due,
+            routing: default_routing(),
+        },
+        PaymentReminderRequest {
+            user: opted_out.clone(),
+            reminder_id: 3,
+            category: symbol_short!("bills"),
+            due_date: due,
+            routing: default_routing(),
+        },
+        PaymentReminderRequest {
+            user: opted_out.clone(),
+            reminder_id: 4,
+            category: symbol_short!("rent"),
+            due_date: due,
+            routing: default_routing(),
+        },
+    ];
+
+    let result = client.dispatch_batch_reminders(&admin, &requests);
+    assert_eq!(result.successful_count, 2);
+    assert_eq!(result.snoozed_count, 1);
+    assert_eq!(result.opted_out_count, 1);
+    assert_eq!(result.failed_addresses.len(), 0);
+}
+
+#[test]
+fn test_snooze_expires() {
+    let env = Env::default();
+    let (admin, client) = setup(&env);
+
+    let user = Address::generate(&env);
+    let until = current_ledger(&env) + 10;
+    client.snooze(&user, &1, &until);
+    assert_eq!(client.get_snooze(&user, &1), Some(until));
+
+    env.ledger().set_sequence_number(until as u32);
+
+    let requests = vec![
+        &env,
+        PaymentReminderRequest {
+            user: user.clone(),
+            reminder_id: 1,
+            category: symbol_short!("bills"),
+            due_date: until + 100,
+            routing: default_routing(),
+        },
+    ];
+    let result = client.dispatch_batch_reminders(&admin, &requests);
+    assert_eq!(result.successful_count, 1);
+    assert_eq!(result.snoozed_count, 0);
+}
+
+#[test]
+#[should_panic(expected = "Snooze must end in the future")]
+fn test_snooze_in_past_fails() {
+    let env = Env::default();
+    let (_admin, client) = setup(&env);
+
+    let user = Address::generate(&env);
+    client.snooze(&user, &1, &current_ledger(&env));
+}
+
+#[test]
+fn test_routing_hints_checked_against_channel_registry() {
+    let env = Env::default();
+    let (admin, client) = setup(&env);
+    client.set_channel(&admin, &symbol_short!("sms"), &true);
+    assert!(client.is_channel_enabled(&symbol_short!("sms")));
+    assert!(!client.is_channel_enabled(&symbol_short!("fax")));
+
+    let user = Address::generate(&env);
+    let bad_channel = Address::generate(&env);
+    let bad_priority = Address::generate(&env);
+    let due = current_ledger(&env) + 100;
+    let request = |user: &Address, channel, priority| PaymentReminderRequest {
+        user: user.clone(),
+        reminder_id: 1,
+        category: symbol_short!("bills"),
+        due_date: due,
+        routing: RoutingHints {
+            channel,
+            locale: symbol_short!("fr"),
+            priority,
+        },
+    };
+
+    let requests = vec![
+        &env,
+        request(&user, symbol_short!("sms"), 2),
+        request(&bad_channel, symbol_short!("fax"), 0),
+        request(&bad_priority, symbol_short!("sms"), MAX_PRIORITY + 1),
+    ];
+    let result = client.dispatch_batch_reminders(&admin, &requests);
+
+    assert_eq!(result.successful_count, 1);
+    assert_eq!(
+        result.failed_addresses,
+        vec![&env, bad_channel.clone(), bad_priority.clone()]
+    );
+
+    // The notifier receives the hints with the sent reminder
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()) == Ok(symbol_short!("rem_sent"))
+        })
+        .unwrap();
+    let (_, channel, locale, priority) =
+        <(u64, Symbol, Symbol, u32)>::try_from_val(&env, &data).unwrap();
+    assert_eq!(
+        (channel, locale, priority),
+        (symbol_short!("sms"), symbol_short!("fr"), 2)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_set_channel_requires_registry_admin() {
+    let env = Env::default();
+    let (_admin, client) = setup(&env);
+
+    client.set_channel(&Address::generate(&env), &symbol_short!("sms"), &true);
+}
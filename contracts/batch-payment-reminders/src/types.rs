@@ -1,11 +1,35 @@
-use soroban_sdk::{contracttype, Address, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Symbol, Vec};
+
+/// Channel that is always accepted and leaves the choice to the notifier.
+pub const DEFAULT_CHANNEL: Symbol = symbol_short!("default");
+
+/// Highest reminder priority (0 = lowest).
+pub const MAX_PRIORITY: u32 = 3;
+
+/// How the off-chain notifier should deliver a reminder.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoutingHints {
+    /// Registered channel (e.g. "email", "sms") or `DEFAULT_CHANNEL`.
+    pub channel: Symbol,
+    /// Locale the reminder should be rendered in (e.g. "en", "fr").
+    pub locale: Symbol,
+    /// Delivery priority, 0 to `MAX_PRIORITY`.
+    pub priority: u32,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PaymentReminderRequest {
     pub user: Address,
+    /// Identifies the reminder so users can snooze it.
+    pub reminder_id: u64,
+    /// Reminder category users can opt out of (e.g. "bills").
+    pub category: Symbol,
     /// Due date as ledger sequence number (must be in the future).
     pub due_date: u64,
+    /// Delivery hints passed through to the notifier in `rem_sent` events.
+    pub routing: RoutingHints,
 }
 
 #[contracttype]
@@ -13,4 +37,22 @@ pub struct PaymentReminderRequest {
 pub struct BatchReminderResult {
     pub successful_count: u32,
     pub failed_addresses: Vec<Address>,
+    /// Reminders skipped because the user snoozed them.
+    pub snoozed_count: u32,
+    /// Reminders skipped because the user opted out of the category.
+    pub opted_out_count: u32,
+}
+
+/// Per-user reminder preferences and the channel registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    /// Ledger sequence until which a reminder is snoozed (user, reminder_id).
+    Snooze(Address, u64),
+    /// Set when a user opted out of a reminder category (user, category).
+    OptOut(Address, Symbol),
+    /// Admin managing the channel registry.
+    Admin,
+    /// Set when a notification channel is registered.
+    Channel(Symbol),
 }
@@ -1,4 +1,5 @@
 //! Validation for payment reminder requests: users and due dates.
+//! Routing hints are checked against the channel registry in `channels`.
 
 use soroban_sdk::{Address, Env};
 
@@ -11,6 +12,7 @@ use soroban_sdk::{Address, Env};
 pub enum ValidationError {
     InvalidUser,
     InvalidDueDate,
+    RoutingRejected,
 }
 
 pub fn validate_reminder_request(
@@ -10,6 +10,8 @@
 //! - **Event Emission**: Emits events for each mint operation and batch completion
 //! - **Error Handling**: Gracefully handles invalid inputs with detailed error codes
 //! - **Partial Failure Support**: Batch operations continue even if some mints fail
+//! - **Goal Rewards**: Mint rewards straight into savings goals (e.g. employer matching)
+//! - **Vesting Locks**: Route individual mints into the vesting contract with a cliff/duration
 //! - **Optimized Storage**: Minimized storage operations by batching at the end
 //!
 //! ## Optimization Strategies
@@ -24,13 +26,16 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, panic_with_error, token, Address, Env, Vec,
+};
 
 pub use crate::types::{
-    BatchMintMetrics, BatchMintResult, DataKey, ErrorCode, MintEvents, MintResult,
-    TokenMintRequest, TokenMinted, MAX_BATCH_SIZE,
+    BatchGoalMintResult, BatchMintMetrics, BatchMintResult, DataKey, ErrorCode, GoalMinted,
+    MintEvents, MintResult, TokenMintRequest, TokenMinted, VestingLock, VestingParams,
+    MAX_BATCH_SIZE,
 };
-use crate::validation::validate_mint_request;
+use crate::validation::{is_valid_amount, validate_mint_request};
 
 /// Error codes for the batch token mint contract.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -46,6 +51,10 @@ pub enum BatchTokenMintError {
     EmptyBatch = 4,
     /// Batch exceeds maximum size
     BatchTooLarge = 5,
+    /// Savings goals contract has not been configured
+    SavingsGoalsNotSet = 6,
+    /// Mint amount is outside the allowed bounds
+    InvalidAmount = 7,
 }
 
 impl From<BatchTokenMintError> for soroban_sdk::Error {
@@ -54,6 +63,29 @@ impl From<BatchTokenMintError> for soroban_sdk::Error {
     }
 }
 
+/// Interface of the savings goals contract credited by `batch_mint_to_goals`.
+#[contractclient(name = "SavingsGoalsClient")]
+pub trait SavingsGoalsInterface {
+    /// Credits `amount` of `token`, already transferred to the goals
+    /// contract, to a goal and returns the goal's new saved amount.
+    fn fund_goal(env: Env, funder: Address, goal_id: u64, token: Address, amount: i128) -> i128;
+}
+
+/// Interface of the vesting contract that holds locked mints.
+#[contractclient(name = "VestingClient")]
+pub trait VestingInterface {
+    /// Opens a schedule for tokens already transferred to the vesting contract
+    /// and returns its ID.
+    fn create_schedule(
+        env: Env,
+        funder: Address,
+        beneficiary: Address,
+        amount: i128,
+        cliff_seconds: u64,
+        duration_seconds: u64,
+    ) -> u64;
+}
+
 #[contract]
 pub struct BatchTokenMintContract;
 
@@ -82,6 +114,10 @@ impl BatchTokenMintContract {
     /// This is the main entry point for batch token minting. It validates all requests,
     /// mints tokens, emits events, and updates storage efficiently. Supports partial failures.
     ///
+    /// Requests with `lock_in_vesting` are minted to the vesting contract and a
+    /// schedule is opened for the recipient within the same call; this contract
+    /// must be the token's admin for those. A failing vesting call reverts the batch.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `caller` - The address calling this function (must be admin)
@@ -94,6 +130,7 @@ impl BatchTokenMintContract {
     /// # Events Emitted
     /// * `batch_started` - When processing begins
     /// * `tokens_minted` - For each successful mint
+    /// * `vesting_locked` - For each mint locked in the vesting contract
     /// * `mint_failed` - For each failed mint
     /// * `large_mint` - For mints >= 1 billion stroops
     /// * `batch_completed` - When processing completes
@@ -137,6 +174,8 @@ impl BatchTokenMintContract {
 
         // Initialize token client
         let token_client = token::Client::new(&env, &token);
+        let vesting_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::VestingContract);
 
         // Initialize result tracking
         let mut results: Vec<MintResult> = Vec::new(&env);
@@ -147,18 +186,31 @@ impl BatchTokenMintContract {
         // Process each mint request
         for request in requests.iter() {
             // Validate the request
-            match validate_mint_request(&request) {
+            let validation = validate_mint_request(&request).and_then(|()| {
+                if request.lock_in_vesting != VestingLock::Liquid && vesting_contract.is_none() {
+                    Err(ErrorCode::VESTING_NOT_SET)
+                } else {
+                    Ok(())
+                }
+            });
+            match validation {
                 Ok(()) => {
-                    // Validation succeeded - attempt to mint tokens
-                    // Note: In a real implementation, this would call token_client.mint()
-                    // For now, we simulate successful minting
-                    // In production, this would interact with the actual token contract
+                    // Validation succeeded - liquid mints are only recorded here,
+                    // while locked ones are minted into the vesting contract
+
+                    let vesting_schedule_id = match (&request.lock_in_vesting, &vesting_contract) {
+                        (VestingLock::Locked(params), Some(vesting)) => Some(
+                            Self::mint_into_vesting(&env, &token, vesting, &request, params),
+                        ),
+                        _ => None,
+                    };
 
                     let minted = TokenMinted {
                         token_address: token.clone(),
                         recipient: request.recipient.clone(),
                         amount: request.amount,
                         minted_at: current_ledger,
+                        vesting_schedule_id,
                     };
 
                     // Accumulate metrics
@@ -169,6 +221,9 @@ impl BatchTokenMintContract {
 
                     // Emit success event
                     MintEvents::tokens_minted(&env, batch_id, &token, &minted);
+                    if vesting_schedule_id.is_some() {
+                        MintEvents::vesting_locked(&env, batch_id, &token, &minted);
+                    }
 
                     // Emit large mint event if applicable (>= 1 billion stroops)
                     if request.amount >= 1_000_000_000 {
@@ -255,6 +310,157 @@ impl BatchTokenMintContract {
         }
     }
 
+    /// Mints rewards straight into savings goals as contributions.
+    ///
+    /// Tokens are minted to the savings goals contract, which is then asked to
+    /// credit each goal (updating progress and milestones). The batch is
+    /// atomic: any invalid amount or failed goal credit reverts every mint.
+    /// This contract must be the token's admin and an allowed goal funder.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `token` - The token contract address to mint from
+    /// * `contributions` - `(goal_id, amount)` pairs to credit
+    ///
+    /// # Events Emitted
+    /// * `batch_started` - When processing begins
+    /// * `goal_minted` - For each credited goal
+    /// * `batch_completed` - When processing completes
+    ///
+    /// # Errors
+    /// * `EmptyBatch` - If no contributions provided
+    /// * `BatchTooLarge` - If batch exceeds maximum size
+    /// * `Unauthorized` - If caller is not admin
+    /// * `SavingsGoalsNotSet` - If no savings goals contract is configured
+    /// * `InvalidAmount` - If any amount is out of bounds
+    pub fn batch_mint_to_goals(
+        env: Env,
+        caller: Address,
+        token: Address,
+        contributions: Vec<(u64, i128)>,
+    ) -> BatchGoalMintResult {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let request_count = contributions.len();
+        if request_count == 0 {
+            panic_with_error!(&env, BatchTokenMintError::EmptyBatch);
+        }
+        if request_count > MAX_BATCH_SIZE {
+            panic_with_error!(&env, BatchTokenMintError::BatchTooLarge);
+        }
+
+        let goals_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::SavingsGoalsContract)
+            .unwrap_or_else(|| panic_with_error!(&env, BatchTokenMintError::SavingsGoalsNotSet));
+
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastBatchId)
+            .unwrap_or(0)
+            + 1;
+
+        MintEvents::batch_started(&env, batch_id, &token, request_count);
+
+        let token_client = token::StellarAssetClient::new(&env, &token);
+        let goals_client = SavingsGoalsClient::new(&env, &goals_contract);
+        let funder = env.current_contract_address();
+
+        let mut results: Vec<GoalMinted> = Vec::new(&env);
+        let mut total_amount_minted: i128 = 0;
+
+        for (goal_id, amount) in contributions.iter() {
+            if !is_valid_amount(amount) {
+                panic_with_error!(&env, BatchTokenMintError::InvalidAmount);
+            }
+
+            token_client.mint(&goals_contract, &amount);
+            let goal_balance = goals_client.fund_goal(&funder, &goal_id, &token, &amount);
+
+            let minted = GoalMinted {
+                goal_id,
+                amount,
+                goal_balance,
+            };
+            MintEvents::goal_minted(&env, batch_id, &token, &minted);
+            results.push_back(minted);
+
+            total_amount_minted = total_amount_minted.checked_add(amount).unwrap_or(i128::MAX);
+        }
+
+        let total_minted: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalMinted)
+            .unwrap_or(0);
+        let total_batches: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBatchesProcessed)
+            .unwrap_or(0);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::LastBatchId, &batch_id);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalMinted, &(total_minted + total_amount_minted));
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBatchesProcessed, &(total_batches + 1));
+
+        MintEvents::batch_completed(
+            &env,
+            batch_id,
+            &token,
+            request_count,
+            0,
+            total_amount_minted,
+        );
+
+        BatchGoalMintResult {
+            batch_id,
+            token_address: token,
+            goals_contract,
+            total_amount_minted,
+            results,
+        }
+    }
+
+    /// Sets the savings goals contract credited by `batch_mint_to_goals`.
+    pub fn set_savings_goals_contract(env: Env, caller: Address, goals_contract: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::SavingsGoalsContract, &goals_contract);
+    }
+
+    /// Returns the configured savings goals contract.
+    pub fn get_savings_goals_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::SavingsGoalsContract)
+    }
+
+    /// Sets the vesting contract that receives mints with `lock_in_vesting`.
+    pub fn set_vesting_contract(env: Env, caller: Address, vesting_contract: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::VestingContract, &vesting_contract);
+    }
+
+    /// Returns the configured vesting contract.
+    pub fn get_vesting_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::VestingContract)
+    }
+
     /// Returns the admin address.
     pub fn get_admin(env: Env) -> Address {
         env.storage()
@@ -295,6 +501,24 @@ impl BatchTokenMintContract {
             .unwrap_or(0)
     }
 
+    // Internal helper to mint into the vesting contract and open the recipient's schedule
+    fn mint_into_vesting(
+        env: &Env,
+        token: &Address,
+        vesting: &Address,
+        request: &TokenMintRequest,
+        params: &VestingParams,
+    ) -> u64 {
+        token::StellarAssetClient::new(env, token).mint(vesting, &request.amount);
+        VestingClient::new(env, vesting).create_schedule(
+            &env.current_contract_address(),
+            &request.recipient,
+            &request.amount,
+            &params.cliff_seconds,
+            &params.duration_seconds,
+        )
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
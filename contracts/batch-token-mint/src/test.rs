@@ -2,10 +2,12 @@
 
 #![cfg(test)]
 
+extern crate std;
+
 use crate::{BatchTokenMintContract, BatchTokenMintContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env, Vec};
+use soroban_sdk::{testutils::Address as _, token, Address, Env, Vec};
 
-use crate::types::{ErrorCode, MintResult, TokenMintRequest};
+use crate::types::{ErrorCode, MintResult, TokenMintRequest, VestingLock, VestingParams};
 
 /// Helper function to create a test environment with initialized contract.
 fn setup_test_contract() -> (Env, Address, BatchTokenMintContractClient<'static>) {
@@ -26,6 +28,7 @@ fn create_valid_request(env: &Env, amount: i128) -> TokenMintRequest {
     TokenMintRequest {
         recipient: Address::generate(env),
         amount,
+        lock_in_vesting: VestingLock::Liquid,
     }
 }
 
@@ -375,3 +378,198 @@ fn test_batch_mint_result_structure() {
     assert_eq!(result.metrics.successful_mints, 1);
     assert_eq!(result.metrics.failed_mints, 0);
 }
+
+// ==================== Goal Mint Tests ====================
+
+mod mock_goals {
+    use crate::SavingsGoalsInterface;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Savings goals double that keeps a running balance per goal.
+    #[contract]
+    pub struct MockSavingsGoals;
+
+    #[contractimpl]
+    impl SavingsGoalsInterface for MockSavingsGoals {
+        fn fund_goal(
+            env: Env,
+            funder: Address,
+            goal_id: u64,
+            _token: Address,
+            amount: i128,
+        ) -> i128 {
+            funder.require_auth();
+            if goal_id == 0 {
+                panic!("goal not found");
+            }
+            let balance: i128 = env.storage().instance().get(&goal_id).unwrap_or(0) + amount;
+            env.storage().instance().set(&goal_id, &balance);
+            balance
+        }
+    }
+}
+
+/// Sets up a token administered by the mint contract and a mock goals contract.
+fn setup_goal_mint(
+    env: &Env,
+    admin: &Address,
+    client: &BatchTokenMintContractClient,
+) -> (Address, Address) {
+    let token = env
+        .register_stellar_asset_contract_v2(client.address.clone())
+        .address();
+    let goals = env.register(mock_goals::MockSavingsGoals, ());
+    client.set_savings_goals_contract(admin, &goals);
+    (token, goals)
+}
+
+#[test]
+fn test_batch_mint_to_goals() {
+    let (env, admin, client) = setup_test_contract();
+    let (token, goals) = setup_goal_mint(&env, &admin, &client);
+
+    let mut contributions: Vec<(u64, i128)> = Vec::new(&env);
+    contributions.push_back((1, 50_000_000));
+    contributions.push_back((2, 20_000_000));
+    contributions.push_back((1, 10_000_000));
+
+    let result = client.batch_mint_to_goals(&admin, &token, &contributions);
+
+    assert_eq!(result.batch_id, 1);
+    assert_eq!(result.goals_contract, goals);
+    assert_eq!(result.total_amount_minted, 80_000_000);
+    assert_eq!(result.results.len(), 3);
+    assert_eq!(result.results.get(2).unwrap().goal_balance, 60_000_000);
+    assert_eq!(token::Client::new(&env, &token).balance(&goals), 80_000_000);
+    assert_eq!(client.get_total_minted(), 80_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_batch_mint_to_goals_requires_goals_contract() {
+    let (env, admin, client) = setup_test_contract();
+    let token = Address::generate(&env);
+
+    let mut contributions: Vec<(u64, i128)> = Vec::new(&env);
+    contributions.push_back((1, 50_000_000));
+
+    client.batch_mint_to_goals(&admin, &token, &contributions);
+}
+
+#[test]
+fn test_batch_mint_to_goals_is_atomic() {
+    let (env, admin, client) = setup_test_contract();
+    let (token, goals) = setup_goal_mint(&env, &admin, &client);
+
+    let mut contributions: Vec<(u64, i128)> = Vec::new(&env);
+    contributions.push_back((1, 50_000_000));
+    contributions.push_back((2, 0));
+
+    assert!(client
+        .try_batch_mint_to_goals(&admin, &token, &contributions)
+        .is_err());
+
+    // Missing goal in the downstream contract reverts the whole batch too
+    let mut contributions: Vec<(u64, i128)> = Vec::new(&env);
+    contributions.push_back((1, 50_000_000));
+    contributions.push_back((0, 10_000_000));
+
+    assert!(client
+        .try_batch_mint_to_goals(&admin, &token, &contributions)
+        .is_err());
+    assert_eq!(token::Client::new(&env, &token).balance(&goals), 0);
+    assert_eq!(client.get_last_batch_id(), 0);
+}
+
+// ==================== Vesting Lock Tests ====================
+
+mod mock_vesting {
+    use crate::VestingInterface;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Vesting double that numbers schedules and remembers their amounts.
+    #[contract]
+    pub struct MockVesting;
+
+    #[contractimpl]
+    impl VestingInterface for MockVesting {
+        fn create_schedule(
+            env: Env,
+            funder: Address,
+            _beneficiary: Address,
+            amount: i128,
+            _cliff_seconds: u64,
+            _duration_seconds: u64,
+        ) -> u64 {
+            funder.require_auth();
+            let schedule_id: u64 = env.storage().instance().get(&0u64).unwrap_or(0) + 1;
+            env.storage().instance().set(&0u64, &schedule_id);
+            env.storage().instance().set(&schedule_id, &amount);
+            schedule_id
+        }
+    }
+}
+
+fn vesting_request(env: &Env, amount: i128, cliff: u64, duration: u64) -> TokenMintRequest {
+    TokenMintRequest {
+        recipient: Address::generate(env),
+        amount,
+        lock_in_vesting: VestingLock::Locked(VestingParams {
+            cliff_seconds: cliff,
+            duration_seconds: duration,
+        }),
+    }
+}
+
+#[test]
+fn test_batch_mint_locks_in_vesting() {
+    let (env, admin, client) = setup_test_contract();
+    let token = env
+        .register_stellar_asset_contract_v2(client.address.clone())
+        .address();
+    let vesting = env.register(mock_vesting::MockVesting, ());
+    client.set_vesting_contract(&admin, &vesting);
+
+    let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, 10_000_000));
+    requests.push_back(vesting_request(&env, 40_000_000, 100, 1_000));
+    requests.push_back(vesting_request(&env, 5_000_000, 2_000, 1_000));
+
+    let result = client.batch_mint_tokens(&admin, &token, &requests);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        MintResult::Success(minted) => assert_eq!(minted.vesting_schedule_id, None),
+        MintResult::Failure(_, _) => panic!("Expected success"),
+    }
+    match result.results.get(1).unwrap() {
+        MintResult::Success(minted) => assert_eq!(minted.vesting_schedule_id, Some(1)),
+        MintResult::Failure(_, _) => panic!("Expected success"),
+    }
+    match result.results.get(2).unwrap() {
+        MintResult::Failure(_, code) => assert_eq!(code, ErrorCode::INVALID_VESTING),
+        MintResult::Success(_) => panic!("Expected failure"),
+    }
+    assert_eq!(
+        token::Client::new(&env, &token).balance(&vesting),
+        40_000_000
+    );
+}
+
+#[test]
+fn test_vesting_request_fails_without_vesting_contract() {
+    let (env, admin, client) = setup_test_contract();
+    let token = Address::generate(&env);
+
+    let mut requests: Vec<TokenMintRequest> = Vec::new(&env);
+    requests.push_back(vesting_request(&env, 40_000_000, 100, 1_000));
+
+    let result = client.batch_mint_tokens(&admin, &token, &requests);
+
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        MintResult::Failure(_, code) => assert_eq!(code, ErrorCode::VESTING_NOT_SET),
+        MintResult::Success(_) => panic!("Expected failure"),
+    }
+}
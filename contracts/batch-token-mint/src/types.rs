@@ -19,6 +19,33 @@ pub struct TokenMintRequest {
     pub recipient: Address,
     /// Amount to mint (in stroops)
     pub amount: i128,
+    /// Lock the minted tokens in the vesting contract instead of paying them out liquid
+    pub lock_in_vesting: VestingLock,
+}
+
+/// Vesting terms for tokens minted into the vesting contract.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VestingParams {
+    /// Seconds before any tokens unlock
+    pub cliff_seconds: u64,
+    /// Seconds until all tokens are unlocked (must be >= cliff)
+    pub duration_seconds: u64,
+}
+
+/// Whether minted tokens are paid out liquid or locked in the vesting contract.
+///
+/// Used instead of `Option<VestingParams>`: with `testutils` enabled,
+/// `contracttype` derives an XDR conversion for every field, and `Option<T>`
+/// only converts when `T: Into<ScVal>`, which contract structs don't
+/// implement, so the tests would not build.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum VestingLock {
+    /// Tokens land liquid in the recipient's wallet
+    Liquid,
+    /// Tokens are locked in the vesting contract on these terms
+    Locked(VestingParams),
 }
 
 /// Represents a successfully minted token transaction.
@@ -33,6 +60,8 @@ pub struct TokenMinted {
     pub amount: i128,
     /// Ledger sequence when minted
     pub minted_at: u64,
+    /// Vesting schedule holding the tokens, if they were locked
+    pub vesting_schedule_id: Option<u64>,
 }
 
 /// Result of processing a single mint operation.
@@ -81,6 +110,34 @@ pub struct BatchMintResult {
     pub metrics: BatchMintMetrics,
 }
 
+/// A reward minted straight into a savings goal.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct GoalMinted {
+    /// Goal credited with the minted amount
+    pub goal_id: u64,
+    /// Amount minted (in stroops)
+    pub amount: i128,
+    /// Goal's saved amount after the contribution
+    pub goal_balance: i128,
+}
+
+/// Result of minting rewards into savings goals.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchGoalMintResult {
+    /// Batch ID
+    pub batch_id: u64,
+    /// Token address being minted
+    pub token_address: Address,
+    /// Savings goals contract that received the tokens
+    pub goals_contract: Address,
+    /// Total amount minted across all goals
+    pub total_amount_minted: i128,
+    /// Per-goal results
+    pub results: Vec<GoalMinted>,
+}
+
 /// Storage keys for contract state.
 #[derive(Clone)]
 #[contracttype]
@@ -93,6 +150,10 @@ pub enum DataKey {
     TotalMinted,
     /// Total batches processed lifetime
     TotalBatchesProcessed,
+    /// Savings goals contract credited by `batch_mint_to_goals`
+    SavingsGoalsContract,
+    /// Vesting contract that receives locked mints
+    VestingContract,
 }
 
 /// Error codes for token minting validation and execution.
@@ -113,6 +174,10 @@ pub mod ErrorCode {
     pub const NOT_INITIALIZED: u32 = 6;
     /// Amount exceeds maximum allowed
     pub const AMOUNT_TOO_LARGE: u32 = 7;
+    /// Vesting cliff/duration are invalid
+    pub const INVALID_VESTING: u32 = 8;
+    /// Vesting requested but no vesting contract is configured
+    pub const VESTING_NOT_SET: u32 = 9;
 }
 
 /// Events emitted by the batch token mint contract.
@@ -140,6 +205,21 @@ impl MintEvents {
         );
     }
 
+    /// Event emitted when minted tokens are locked in the vesting contract.
+    pub fn vesting_locked(env: &Env, batch_id: u64, token: &Address, minted: &TokenMinted) {
+        let topics = (symbol_short!("mint"), symbol_short!("vested"));
+        env.events().publish(
+            topics,
+            (
+                batch_id,
+                token.clone(),
+                minted.recipient.clone(),
+                minted.amount,
+                minted.vesting_schedule_id,
+            ),
+        );
+    }
+
     /// Event emitted when minting fails for a recipient.
     pub fn mint_failed(
         env: &Env,
@@ -171,6 +251,15 @@ impl MintEvents {
         );
     }
 
+    /// Event emitted when tokens are minted into a savings goal.
+    pub fn goal_minted(env: &Env, batch_id: u64, token: &Address, minted: &GoalMinted) {
+        let topics = (symbol_short!("mint"), symbol_short!("goal"), minted.goal_id);
+        env.events().publish(
+            topics,
+            (batch_id, token.clone(), minted.amount, minted.goal_balance),
+        );
+    }
+
     /// Event emitted for large mint operations (>= 1 billion stroops).
     pub fn large_mint(
         env: &Env,
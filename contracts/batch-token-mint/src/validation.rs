@@ -2,7 +2,9 @@
 
 use soroban_sdk::Address;
 
-use crate::types::{ErrorCode, TokenMintRequest, MAX_MINT_AMOUNT, MIN_MINT_AMOUNT};
+use crate::types::{
+    ErrorCode, TokenMintRequest, VestingLock, VestingParams, MAX_MINT_AMOUNT, MIN_MINT_AMOUNT,
+};
 
 /// Validates a token mint request.
 ///
@@ -23,9 +25,24 @@ pub fn validate_mint_request(request: &TokenMintRequest) -> Result<(), u32> {
         return Err(ErrorCode::INVALID_AMOUNT);
     }
 
+    // Validate vesting terms, if the tokens are to be locked
+    if let VestingLock::Locked(params) = &request.lock_in_vesting {
+        if !is_valid_vesting(params) {
+            return Err(ErrorCode::INVALID_VESTING);
+        }
+    }
+
     Ok(())
 }
 
+/// Validates vesting terms.
+///
+/// # Returns
+/// * `true` if the duration is non-zero and the cliff falls within it
+pub fn is_valid_vesting(params: &VestingParams) -> bool {
+    params.duration_seconds > 0 && params.cliff_seconds <= params.duration_seconds
+}
+
 /// Validates that a recipient address is valid.
 ///
 /// In Soroban, all Address instances are valid by construction.
@@ -86,6 +103,7 @@ mod tests {
         TokenMintRequest {
             recipient: Address::generate(env),
             amount: 100_000_000, // 0.1 XLM in stroops
+            lock_in_vesting: VestingLock::Liquid,
         }
     }
 
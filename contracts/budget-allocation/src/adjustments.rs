@@ -0,0 +1,74 @@
+//! Incremental budget top-ups and claw-backs.
+//!
+//! Unlike a new allocation, an adjustment keeps the user's recorded spending
+//! and only moves the budget amount by a signed delta. Budgets never go below
+//! zero and increases respect the budget cap and the user's limit.
+//! Decreases past zero either fail or, for floored adjustments, empty the
+//! budget.
+
+use crate::types::{BudgetRecord, DataKey, ErrorCode};
+use crate::{funding, history, limits};
+use soroban_sdk::{symbol_short, Address, Env};
+
+/// Returns the user's budget and its adjusted amount, or the error code the
+/// adjustment fails with. With `floor_at_zero` a decrease past zero leaves
+/// the budget at zero instead of failing, unless it already is.
+pub fn check(
+    env: &Env,
+    user: &Address,
+    delta: i128,
+    floor_at_zero: bool,
+) -> Result<(BudgetRecord, i128), u32> {
+    if delta == 0 {
+        return Err(ErrorCode::INVALID_AMOUNT);
+    }
+    let budget: BudgetRecord = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Budget(user.clone()))
+        .ok_or(ErrorCode::BUDGET_NOT_FOUND)?;
+    let amount = budget
+        .amount
+        .checked_add(delta)
+        .ok_or(ErrorCode::INVALID_AMOUNT)?;
+    if amount < 0 {
+        if !floor_at_zero || budget.amount == 0 {
+            return Err(ErrorCode::INSUFFICIENT_BUDGET);
+        }
+        return Ok((budget, 0));
+    }
+    if delta > 0 && limits::effective_limit(env, user).is_some_and(|limit| amount > limit) {
+        return Err(ErrorCode::LIMIT_EXCEEDED);
+    }
+    Ok((budget, amount))
+}
+
+/// Stores the adjusted budget, logs the change and emits `budget increased`
+/// or `budget decreased` with the size of the change and the new amount.
+/// A funded budget's escrow moves with it.
+pub fn apply(
+    env: &Env,
+    mut budget: BudgetRecord,
+    amount: i128,
+    changed_by: &Address,
+) -> BudgetRecord {
+    let delta = amount - budget.amount;
+    history::record(env, &budget.user, budget.amount, amount, changed_by);
+    budget.amount = amount;
+    budget.last_updated = env.ledger().timestamp();
+    env.storage()
+        .persistent()
+        .set(&DataKey::Budget(budget.user.clone()), &budget);
+    funding::adjust(env, &budget.user, delta);
+
+    let kind = if delta > 0 {
+        symbol_short!("increased")
+    } else {
+        symbol_short!("decreased")
+    };
+    env.events().publish(
+        (symbol_short!("budget"), kind),
+        (budget.user.clone(), delta.abs(), amount),
+    );
+    budget
+}
@@ -0,0 +1,66 @@
+//! Spending alerts at user-chosen shares of the budget.
+//!
+//! Thresholds are in basis points of the budget (8000 = 80% consumed). A
+//! spend that takes consumption from below a threshold to or above it emits
+//! one `("budget", "alert")` event per crossed threshold for off-chain
+//! notifiers.
+
+use crate::types::{DataKey, MAX_SPEND_ALERTS};
+use soroban_sdk::{symbol_short, Address, Env, Vec};
+
+/// Consumption thresholds are capped at 100% of the budget.
+const MAX_THRESHOLD_BPS: u32 = 10_000;
+
+pub fn get_thresholds(env: &Env, user: &Address) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SpendAlerts(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Stores the user's thresholds, which must be strictly ascending; an empty
+/// list disables alerts.
+pub fn set_thresholds(env: &Env, user: &Address, thresholds: &Vec<u32>) {
+    let key = DataKey::SpendAlerts(user.clone());
+    if thresholds.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    if thresholds.len() > MAX_SPEND_ALERTS {
+        panic!("Too many alert thresholds");
+    }
+
+    let mut previous = 0;
+    for threshold in thresholds.iter() {
+        if threshold <= previous || threshold > MAX_THRESHOLD_BPS {
+            panic!("Invalid alert threshold");
+        }
+        previous = threshold;
+    }
+    env.storage().persistent().set(&key, thresholds);
+}
+
+/// Emits an alert for each threshold crossed by moving from `spent_before`
+/// to `spent` out of `budget`.
+pub fn check(env: &Env, user: &Address, budget: i128, spent_before: i128, spent: i128) {
+    if budget <= 0 {
+        return;
+    }
+
+    let consumed_bps = |spent: i128| spent.saturating_mul(MAX_THRESHOLD_BPS as i128) / budget;
+    let before = consumed_bps(spent_before);
+    let after = consumed_bps(spent);
+    for threshold in get_thresholds(env, user).iter() {
+        let threshold_bps = threshold as i128;
+        if before < threshold_bps && after >= threshold_bps {
+            env.events().publish(
+                (
+                    symbol_short!("budget"),
+                    symbol_short!("alert"),
+                    user.clone(),
+                ),
+                (threshold, after, budget - spent),
+            );
+        }
+    }
+}
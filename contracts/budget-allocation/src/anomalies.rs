@@ -0,0 +1,73 @@
+//! Anomaly signals for unusual allocations.
+//!
+//! The last `ANOMALY_WINDOW` allocations of each user are kept. Once a user
+//! has `ANOMALY_MIN_SAMPLES` of them, an allocation more than the configured
+//! multiple above their average, or below the average divided by it, emits
+//! an `allocation_anomaly` event. Detection is off while no multiple is set.
+
+use crate::types::{DataKey, ANOMALY_MIN_SAMPLES, ANOMALY_WINDOW};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+pub fn get_multiple(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DataKey::AnomalyMultiple)
+}
+
+pub fn set_multiple(env: &Env, multiple: Option<u32>) {
+    match multiple {
+        Some(multiple) if multiple < 2 => panic!("Invalid anomaly multiple"),
+        Some(multiple) => env
+            .storage()
+            .instance()
+            .set(&DataKey::AnomalyMultiple, &multiple),
+        None => env.storage().instance().remove(&DataKey::AnomalyMultiple),
+    }
+}
+
+fn recent(env: &Env, user: &Address) -> Vec<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RecentAllocations(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Average of the user's recent allocations, once there are enough of them.
+pub fn average(env: &Env, user: &Address) -> Option<i128> {
+    let amounts = recent(env, user);
+    if amounts.len() < ANOMALY_MIN_SAMPLES {
+        return None;
+    }
+    let total = amounts
+        .iter()
+        .fold(0i128, |total, amount| total.saturating_add(amount));
+    Some(total / amounts.len() as i128)
+}
+
+/// Checks `amount` against the user's recent average, then adds it to their
+/// recent allocations. Suppressed batches are recorded without being checked.
+pub fn record(env: &Env, batch_id: u64, user: &Address, amount: i128, suppress: bool) {
+    if !suppress {
+        if let (Some(multiple), Some(average)) = (get_multiple(env), average(env, user)) {
+            let multiple = multiple as i128;
+            if amount > average.saturating_mul(multiple)
+                || amount.saturating_mul(multiple) < average
+            {
+                env.events().publish(
+                    (
+                        symbol_short!("budget"),
+                        Symbol::new(env, "allocation_anomaly"),
+                    ),
+                    (batch_id, user.clone(), amount, average),
+                );
+            }
+        }
+    }
+
+    let mut amounts = recent(env, user);
+    if amounts.len() >= ANOMALY_WINDOW {
+        amounts.pop_front();
+    }
+    amounts.push_back(amount);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RecentAllocations(user.clone()), &amounts);
+}
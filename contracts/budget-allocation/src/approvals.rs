@@ -0,0 +1,134 @@
+//! Second-approver gate for large batch allocations.
+//!
+//! Batches whose requested total exceeds the configured threshold are parked
+//! as pending instead of being applied, and only run once a registered
+//! approver other than the proposer signs off before the batch expires.
+
+use crate::types::{BudgetRequest, DataKey, PendingBatch, PENDING_BATCH_TTL};
+use soroban_sdk::{symbol_short, Address, Env, Vec};
+
+pub fn get_threshold(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::ApprovalThreshold)
+}
+
+pub fn set_threshold(env: &Env, threshold: Option<i128>) {
+    match threshold {
+        Some(amount) if amount < 0 => panic!("Invalid approval threshold"),
+        Some(amount) => env
+            .storage()
+            .instance()
+            .set(&DataKey::ApprovalThreshold, &amount),
+        None => env.storage().instance().remove(&DataKey::ApprovalThreshold),
+    }
+}
+
+pub fn is_approver(env: &Env, approver: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BatchApprover(approver.clone()))
+        .unwrap_or(false)
+}
+
+pub fn set_approver(env: &Env, approver: &Address, allowed: bool) {
+    let key = DataKey::BatchApprover(approver.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Sum of the non-negative amounts in the batch, i.e. the most it could allocate.
+pub fn requested_total(requests: &Vec<BudgetRequest>) -> i128 {
+    requests
+        .iter()
+        .filter(|req| req.amount > 0)
+        .fold(0i128, |total, req| total.saturating_add(req.amount))
+}
+
+pub fn requires_approval(env: &Env, total: i128) -> bool {
+    get_threshold(env).is_some_and(|threshold| total > threshold)
+}
+
+pub fn get_pending(env: &Env, batch_id: u64) -> Option<PendingBatch> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingBatch(batch_id))
+}
+
+/// Stores the batch for a second approver and returns its pending batch id.
+pub fn queue(
+    env: &Env,
+    proposer: &Address,
+    requests: Vec<BudgetRequest>,
+    total: i128,
+    suppress_anomalies: bool,
+) -> u64 {
+    let batch_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastPendingBatchId)
+        .unwrap_or(0)
+        + 1;
+    let created_at = env.ledger().timestamp();
+    let pending = PendingBatch {
+        batch_id,
+        proposer: proposer.clone(),
+        requests,
+        total_amount: total,
+        created_at,
+        expires_at: created_at + PENDING_BATCH_TTL,
+        suppress_anomalies,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingBatch(batch_id), &pending);
+    env.storage()
+        .instance()
+        .set(&DataKey::LastPendingBatchId, &batch_id);
+
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("pending")),
+        (batch_id, proposer.clone(), total, pending.expires_at),
+    );
+    batch_id
+}
+
+/// Removes a pending batch once `approver` is allowed to release it.
+pub fn take_approved(env: &Env, approver: &Address, batch_id: u64) -> PendingBatch {
+    let pending = get_pending(env, batch_id).expect("Pending batch not found");
+    if !is_approver(env, approver) {
+        panic!("Not an approver");
+    }
+    if *approver == pending.proposer {
+        panic!("Approver must differ from proposer");
+    }
+    if env.ledger().timestamp() > pending.expires_at {
+        panic!("Pending batch expired");
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingBatch(batch_id));
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("approved")),
+        (batch_id, approver.clone()),
+    );
+    pending
+}
+
+/// Drops a pending batch whose approval window has passed.
+pub fn expire(env: &Env, batch_id: u64) {
+    let pending = get_pending(env, batch_id).expect("Pending batch not found");
+    if env.ledger().timestamp() <= pending.expires_at {
+        panic!("Pending batch not expired");
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingBatch(batch_id));
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("expired")),
+        (batch_id, pending.total_amount),
+    );
+}
@@ -0,0 +1,53 @@
+//! Audit trail for admin corrections to user budgets.
+
+use crate::types::DataKey;
+use soroban_sdk::{contractclient, symbol_short, xdr::ToXdr, Address, Bytes, Env, Symbol};
+
+/// Interface of the audit contract that records budget corrections.
+#[contractclient(name = "AuditClient")]
+pub trait AuditInterface {
+    fn log_audit(
+        env: Env,
+        actor: Address,
+        operation: Symbol,
+        status: Symbol,
+        metadata: Option<Bytes>,
+    );
+}
+
+pub fn get_audit_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::AuditContract)
+}
+
+pub fn set_audit_contract(env: &Env, audit_contract: &Option<Address>) {
+    match audit_contract {
+        Some(audit_contract) => env
+            .storage()
+            .instance()
+            .set(&DataKey::AuditContract, audit_contract),
+        None => env.storage().instance().remove(&DataKey::AuditContract),
+    }
+}
+
+/// Records an adjustment applied by `admin`, with its reason. A failing
+/// audit call reverts the batch so no adjustment goes unrecorded.
+pub fn log_adjustment(
+    env: &Env,
+    admin: &Address,
+    user: &Address,
+    delta: i128,
+    amount: i128,
+    reason: &Symbol,
+) {
+    let Some(audit_contract) = get_audit_contract(env) else {
+        return;
+    };
+
+    let metadata = (user.clone(), delta, amount, reason.clone()).to_xdr(env);
+    AuditClient::new(env, &audit_contract).log_audit(
+        admin,
+        &Symbol::new(env, "budget_adjustment"),
+        &symbol_short!("success"),
+        &Some(metadata),
+    );
+}
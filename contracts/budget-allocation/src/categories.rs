@@ -0,0 +1,85 @@
+//! Category renaming and merging.
+//!
+//! Taxonomy changes are recorded in a remapping table (`old -> new`) instead
+//! of rewriting every user's budget up front. A user's category map is
+//! migrated the next time it is accessed, summing amounts when several old
+//! categories collapse into the same target.
+
+use crate::types::{DataKey, UserBudgetCategories};
+use soroban_sdk::{Address, Env, Map, Symbol};
+
+/// Maximum number of remap hops followed when resolving a category.
+pub const MAX_REMAP_DEPTH: u32 = 10;
+
+/// Follows the remapping table to the category's current name.
+pub fn resolve(env: &Env, category: &Symbol) -> Symbol {
+    let mut current = category.clone();
+    for _ in 0..MAX_REMAP_DEPTH {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Symbol>(&DataKey::CategoryRemap(current.clone()))
+        {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Records `source -> target` in the remapping table.
+pub fn record_remap(env: &Env, source: &Symbol, target: &Symbol) {
+    if source == target {
+        panic!("Source and target categories must differ");
+    }
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::CategoryRemap(source.clone()))
+    {
+        panic!("Category already remapped");
+    }
+    if resolve(env, target) == *source {
+        panic!("Category remap would create a cycle");
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::CategoryRemap(source.clone()), target);
+}
+
+/// Rewrites a category map under the current taxonomy. Returns the migrated
+/// map and whether anything changed.
+pub fn remap_categories(env: &Env, categories: &Map<Symbol, i128>) -> (Map<Symbol, i128>, bool) {
+    let mut migrated = Map::<Symbol, i128>::new(env);
+    let mut changed = false;
+
+    for (name, amount) in categories.iter() {
+        let resolved = resolve(env, &name);
+        if resolved != name {
+            changed = true;
+        }
+        let existing = migrated.get(resolved.clone()).unwrap_or(0);
+        let total = existing
+            .checked_add(amount)
+            .expect("Overflow in category total calculation");
+        migrated.set(resolved, total);
+    }
+
+    (migrated, changed)
+}
+
+/// Loads a user's categories, migrating and persisting them if the
+/// taxonomy changed since they were last stored.
+pub fn load_user_categories(env: &Env, user: &Address) -> Option<UserBudgetCategories> {
+    let key = DataKey::BudgetCategories(user.clone());
+    let mut user_categories: UserBudgetCategories = env.storage().persistent().get(&key)?;
+
+    let (migrated, changed) = remap_categories(env, &user_categories.categories);
+    if changed {
+        user_categories.categories = migrated;
+        env.storage().persistent().set(&key, &user_categories);
+    }
+
+    Some(user_categories)
+}
@@ -0,0 +1,63 @@
+//! Display currency preferences and fiat-equivalent amounts.
+//!
+//! Users pick the fiat currency their app shows. When a rate oracle is
+//! configured, allocation and spend events are followed by a `fiat` event
+//! carrying the converted amount and the on-chain rate used, so the app can
+//! display (and verify) localized values. Oracle failures never block the
+//! underlying budget operation; the `fiat` event is simply skipped.
+
+use crate::types::{DataKey, RATE_SCALE};
+use soroban_sdk::{contractclient, symbol_short, Address, Env, Symbol};
+
+/// Oracle adapter quoting the token in fiat currencies.
+#[contractclient(name = "RateOracleClient")]
+pub trait RateOracleInterface {
+    /// Fiat value of one token unit in `currency`, scaled by `RATE_SCALE`.
+    fn rate(env: Env, currency: Symbol) -> i128;
+}
+
+pub fn get_display_currency(env: &Env, user: &Address) -> Option<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DisplayCurrency(user.clone()))
+}
+
+pub fn set_display_currency(env: &Env, user: &Address, currency: &Option<Symbol>) {
+    let key = DataKey::DisplayCurrency(user.clone());
+    match currency {
+        Some(currency) => env.storage().persistent().set(&key, currency),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_oracle(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::RateOracle)
+}
+
+pub fn set_oracle(env: &Env, oracle: &Option<Address>) {
+    match oracle {
+        Some(oracle) => env.storage().instance().set(&DataKey::RateOracle, oracle),
+        None => env.storage().instance().remove(&DataKey::RateOracle),
+    }
+}
+
+/// Emits the fiat equivalent of `amount` for `user`, tagged with `kind`
+/// (e.g. `set`, `allocated`, `spent`), if the user has a display currency and
+/// the oracle quotes it.
+pub fn publish_fiat(env: &Env, kind: Symbol, user: &Address, amount: i128) {
+    let (Some(currency), Some(oracle)) = (get_display_currency(env, user), get_oracle(env)) else {
+        return;
+    };
+    let rate = match RateOracleClient::new(env, &oracle).try_rate(&currency) {
+        Ok(Ok(rate)) if rate > 0 => rate,
+        _ => return,
+    };
+    let Some(fiat_amount) = amount.checked_mul(rate).map(|value| value / RATE_SCALE) else {
+        return;
+    };
+
+    env.events().publish(
+        (symbol_short!("fiat"), kind),
+        (user.clone(), amount, currency, rate, fiat_amount),
+    );
+}
@@ -0,0 +1,117 @@
+//! Token escrow behind funded allocations.
+//!
+//! Funded batches pull the allocated total from the treasury, which grants
+//! this contract an allowance, and credit each user's escrowed balance per
+//! token. The escrow follows the budget: a new funded allocation brings it to
+//! the allocated amount, adjustments move it with the budget and removing the
+//! budget returns it to the treasury. Users withdraw from that balance.
+
+use crate::types::DataKey;
+use soroban_sdk::{token, Address, Env};
+
+pub fn get_treasury(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Treasury)
+}
+
+pub fn set_treasury(env: &Env, treasury: &Address) {
+    env.storage().instance().set(&DataKey::Treasury, treasury);
+}
+
+pub fn funded_token(env: &Env, user: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FundedToken(user.clone()))
+}
+
+pub fn balance(env: &Env, user: &Address, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FundedBalance(user.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+fn set_balance(env: &Env, user: &Address, token: &Address, amount: i128) {
+    let key = DataKey::FundedBalance(user.clone(), token.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+    }
+}
+
+/// Moves `amount` of `token` from the treasury into the contract.
+fn collect(env: &Env, token: &Address, amount: i128) {
+    let treasury = get_treasury(env).expect("Treasury not set");
+    if amount > 0 {
+        let contract = env.current_contract_address();
+        token::Client::new(env, token).transfer_from(&contract, &treasury, &contract, &amount);
+    }
+}
+
+/// Moves `amount` of `token` from the contract back to the treasury.
+fn refund(env: &Env, token: &Address, amount: i128) {
+    let treasury = get_treasury(env).expect("Treasury not set");
+    if amount > 0 {
+        token::Client::new(env, token).transfer(
+            &env.current_contract_address(),
+            &treasury,
+            &amount,
+        );
+    }
+}
+
+/// Brings the user's escrow in `token` to `target`, pulling the shortfall
+/// from the treasury or returning the surplus to it. Escrow held in another
+/// token is returned first.
+pub fn fund(env: &Env, user: &Address, token: &Address, target: i128) {
+    if funded_token(env, user).is_some_and(|current| current != *token) {
+        release(env, user);
+    }
+
+    let held = balance(env, user, token);
+    if target > held {
+        collect(env, token, target - held);
+    } else {
+        refund(env, token, held - target);
+    }
+    set_balance(env, user, token, target);
+    env.storage()
+        .persistent()
+        .set(&DataKey::FundedToken(user.clone()), token);
+}
+
+/// Moves a funded user's escrow by `delta`, never below zero. Unfunded
+/// budgets are left alone.
+pub fn adjust(env: &Env, user: &Address, delta: i128) {
+    if let Some(token) = funded_token(env, user) {
+        let target = (balance(env, user, &token) + delta).max(0);
+        fund(env, user, &token, target);
+    }
+}
+
+/// Returns the user's escrow to the treasury.
+pub fn release(env: &Env, user: &Address) {
+    if let Some(token) = funded_token(env, user) {
+        refund(env, &token, balance(env, user, &token));
+        set_balance(env, user, &token, 0);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::FundedToken(user.clone()));
+    }
+}
+
+/// Pays `amount` of the user's escrowed balance out to them.
+pub fn withdraw(env: &Env, user: &Address, token: &Address, amount: i128) -> i128 {
+    if amount <= 0 {
+        panic!("Invalid withdrawal amount");
+    }
+    let available = balance(env, user, token);
+    if amount > available {
+        panic!("Insufficient funded budget");
+    }
+
+    let remaining = available - amount;
+    set_balance(env, user, token, remaining);
+    token::Client::new(env, token).transfer(&env.current_contract_address(), user, &amount);
+    remaining
+}
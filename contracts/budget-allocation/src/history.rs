@@ -0,0 +1,47 @@
+//! Append-only log of budget amount changes.
+//!
+//! Every allocation, adjustment, period rollover, removal and restoration
+//! that changes a user's budget amount appends an entry, so auditors can
+//! reconstruct how a budget evolved without replaying ledger events.
+
+use crate::types::{BudgetChange, DataKey};
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn count(env: &Env, user: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BudgetHistoryCount(user.clone()))
+        .unwrap_or(0)
+}
+
+/// Appends a change of the user's budget from `old_amount` to `new_amount`.
+pub fn record(env: &Env, user: &Address, old_amount: i128, new_amount: i128, changed_by: &Address) {
+    let position = count(env, user);
+    let change = BudgetChange {
+        old_amount,
+        new_amount,
+        changed_by: changed_by.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::BudgetHistory(user.clone(), position), &change);
+    env.storage()
+        .persistent()
+        .set(&DataKey::BudgetHistoryCount(user.clone()), &(position + 1));
+}
+
+/// Returns up to `limit` changes starting at position `offset`, oldest first.
+pub fn page(env: &Env, user: &Address, offset: u32, limit: u32) -> Vec<BudgetChange> {
+    let mut changes = Vec::new(env);
+    let end = count(env, user).min(offset.saturating_add(limit));
+    for position in offset..end {
+        let change: BudgetChange = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BudgetHistory(user.clone(), position))
+            .unwrap();
+        changes.push_back(change);
+    }
+    changes
+}
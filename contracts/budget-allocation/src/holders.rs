@@ -0,0 +1,78 @@
+//! Index of users holding an active budget.
+//!
+//! Holders are kept in a dense, position-addressed list so dashboards can
+//! page through them. Removing a holder moves the last one into its slot, so
+//! positions are only stable while no budget is removed.
+
+use crate::types::DataKey;
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::BudgetHolderCount)
+        .unwrap_or(0)
+}
+
+/// Adds `user` to the index unless already present.
+pub fn add(env: &Env, user: &Address) {
+    let index_key = DataKey::BudgetHolderIndex(user.clone());
+    if env.storage().persistent().has(&index_key) {
+        return;
+    }
+
+    let position = count(env);
+    env.storage()
+        .persistent()
+        .set(&DataKey::BudgetHolder(position), user);
+    env.storage().persistent().set(&index_key, &position);
+    env.storage()
+        .instance()
+        .set(&DataKey::BudgetHolderCount, &(position + 1));
+}
+
+/// Removes `user` from the index, filling its slot with the last holder.
+pub fn remove(env: &Env, user: &Address) {
+    let index_key = DataKey::BudgetHolderIndex(user.clone());
+    let position: u32 = match env.storage().persistent().get(&index_key) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let last = count(env) - 1;
+    if position != last {
+        let moved: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BudgetHolder(last))
+            .unwrap();
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetHolder(position), &moved);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetHolderIndex(moved), &position);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BudgetHolder(last));
+    env.storage().persistent().remove(&index_key);
+    env.storage()
+        .instance()
+        .set(&DataKey::BudgetHolderCount, &last);
+}
+
+/// Returns up to `limit` holders starting at position `offset`.
+pub fn page(env: &Env, offset: u32, limit: u32) -> Vec<Address> {
+    let mut holders = Vec::new(env);
+    let end = count(env).min(offset.saturating_add(limit));
+    for position in offset..end {
+        let holder: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BudgetHolder(position))
+            .unwrap();
+        holders.push_back(holder);
+    }
+    holders
+}
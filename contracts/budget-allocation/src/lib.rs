@@ -9,15 +9,55 @@
 //! - **Atomic Updates**: Ensures reliable state changes for each user
 //! - **Validation**: Prevents invalid budget amounts
 //! - **Event Emission**: Tracks budget updates and failures
+//! - **Category Taxonomy**: Rename and merge categories without orphaning historic allocations
+//! - **Soft Delete**: Archive budgets on offboarding and restore them with history intact
+//! - **Expense Throttling**: Rate-limits expense entries and freezes budgets after repeat abuse
+//! - **Low Budget Reminders**: Schedules a reminder once per period when spending runs a budget low
+//! - **Display Currency**: Emits fiat equivalents of allocations and spends in each user's currency
+//! - **Reservations**: Earmarks budget for upcoming expenses until released or spent
+//! - **Budget Periods**: Closes monthly periods into snapshots and rolls unspent budget over
+//! - **Budget Managers**: Delegates batch allocation to managers without handing over admin rights
+//! - **Funded Budgets**: Escrows allocations in a token that users withdraw within their allocation
+//! - **Budget Adjustments**: Tops up or claws back budgets incrementally without resetting spending
+//! - **Default Budgets**: Gives users without a budget an admin-defined default on first use
+//! - **Budget Templates**: Stamps a named category split onto many users in one batch
+//! - **Spending Alerts**: Flags spends that cross user-chosen shares of the budget
+//! - **Budget History**: Logs every change of a budget amount with who made it and when
+//! - **Budget Holders**: Indexes users with an active budget so they can be listed page by page
+//! - **Audited Adjustments**: Records the reason for every batch bonus, correction and clawback
+//! - **Anomaly Signals**: Flags allocations far off a user's recent average for review
 //!
 #![no_std]
 
+mod adjustments;
+mod alerts;
+mod anomalies;
+mod approvals;
+mod audit;
+mod categories;
+mod currency;
+mod funding;
+mod history;
+mod holders;
+mod limits;
+mod low_budget;
+mod periods;
+mod reservations;
+mod spending;
+mod templates;
 mod test;
+mod throttle;
 mod types;
 
-use crate::types::{
-    BatchBudgetResult, BudgetRecord, BudgetRequest, CategoryBudgetRequest, DataKey,
-    UserBudgetCategories,
+pub use crate::audit::{AuditClient, AuditInterface};
+pub use crate::currency::{RateOracleClient, RateOracleInterface};
+pub use crate::low_budget::{RemindersClient, RemindersInterface};
+pub use crate::types::{
+    ArchivedBudget, BatchBudgetResult, BatchRecord, BudgetAdjustment, BudgetCategory, BudgetChange,
+    BudgetRecord, BudgetRequest, BudgetResult, BudgetStatement, BudgetTemplate,
+    CategoryBudgetRequest, DataKey, DefaultBudget, ErrorCode, ExpenseThrottleConfig,
+    LowBudgetAlert, PendingBatch, PeriodSnapshot, Reservation, RolloverPolicy, SpendOutcome,
+    SpendRecord, UserBudgetCategories, BUDGET_PERIOD_SECONDS, MAX_BATCH_SIZE,
 };
 use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Map, Symbol, Vec};
 
@@ -36,39 +76,251 @@ impl BudgetAllocationContract {
 
     /// Assigns monthly budgets to multiple users in a single operation.
     ///
+    /// Empty batches and batches larger than `MAX_BATCH_SIZE` are rejected.
+    /// A user repeated within the batch fails with `ErrorCode::DUPLICATE_USER`
+    /// instead of overwriting the earlier entry, and amounts above the budget
+    /// cap or the user's budget limit fail with `ErrorCode::LIMIT_EXCEEDED`.
+    /// Batches requesting more than the approval threshold are stored as
+    /// pending and nothing is applied until `approve_pending_batch` is called.
+    ///
+    /// Applied batches get a sequential batch id, report a `BudgetResult` per
+    /// request in request order and are bracketed by `batch started` and
+    /// `batch completed` events. Allocations far off a user's recent average
+    /// emit an `allocation_anomaly` event.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `admin` - The admin address calling the function
+    /// * `caller` - The admin or a budget manager
     /// * `requests` - List of user-budget pairs
     pub fn batch_allocate_budget(
+        env: Env,
+        caller: Address,
+        requests: Vec<BudgetRequest>,
+    ) -> BatchBudgetResult {
+        // Verify admin or manager authority
+        caller.require_auth();
+        Self::require_allocator(&env, &caller);
+
+        Self::allocate(&env, &caller, requests, false)
+    }
+
+    /// Assigns budgets like `batch_allocate_budget` without checking them
+    /// for anomalies, e.g. for a known company-wide raise. Admin only.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address calling the function
+    /// * `requests` - List of user-budget pairs
+    pub fn batch_allocate_suppressed(
         env: Env,
         admin: Address,
         requests: Vec<BudgetRequest>,
     ) -> BatchBudgetResult {
-        // Verify admin authority
         admin.require_auth();
-        let stored_admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Not initialized");
-        if admin != stored_admin {
-            panic!("Unauthorized");
+        Self::require_admin(&env, &admin);
+
+        Self::allocate(&env, &admin, requests, true)
+    }
+
+    /// Applies a batch, or parks it for a second approver when it requests
+    /// more than the approval threshold.
+    fn allocate(
+        env: &Env,
+        caller: &Address,
+        requests: Vec<BudgetRequest>,
+        suppress_anomalies: bool,
+    ) -> BatchBudgetResult {
+        if requests.is_empty() {
+            panic!("Empty batch");
+        }
+        if requests.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
         }
 
+        let requested_total = approvals::requested_total(&requests);
+        if approvals::requires_approval(env, requested_total) {
+            let batch_id =
+                approvals::queue(env, caller, requests, requested_total, suppress_anomalies);
+            return BatchBudgetResult {
+                batch_id: 0,
+                successful: 0,
+                failed: 0,
+                total_amount: 0,
+                results: Vec::new(env),
+                pending_batch_id: Some(batch_id),
+            };
+        }
+
+        Self::apply_batch(env, &requests, caller, suppress_anomalies)
+    }
+
+    /// Applies a pending batch once a second approver signs off.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `second_admin` - A registered approver other than the proposer
+    /// * `batch_id` - The pending batch to execute
+    pub fn approve_pending_batch(
+        env: Env,
+        second_admin: Address,
+        batch_id: u64,
+    ) -> BatchBudgetResult {
+        second_admin.require_auth();
+
+        let pending = approvals::take_approved(&env, &second_admin, batch_id);
+        Self::apply_batch(
+            &env,
+            &pending.requests,
+            &second_admin,
+            pending.suppress_anomalies,
+        )
+    }
+
+    /// Discards a pending batch whose approval window has passed.
+    pub fn expire_pending_batch(env: Env, batch_id: u64) {
+        approvals::expire(&env, batch_id);
+    }
+
+    /// Returns a batch waiting for a second approver.
+    pub fn get_pending_batch(env: Env, batch_id: u64) -> Option<PendingBatch> {
+        approvals::get_pending(&env, batch_id)
+    }
+
+    /// Sets the batch total above which a second approver is required.
+    /// `None` disables the requirement.
+    pub fn set_approval_threshold(env: Env, admin: Address, threshold: Option<i128>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        approvals::set_threshold(&env, threshold);
+        env.events().publish(
+            (symbol_short!("batch"), symbol_short!("threshold")),
+            threshold,
+        );
+    }
+
+    /// Returns the approval threshold, if one is configured.
+    pub fn get_approval_threshold(env: Env) -> Option<i128> {
+        approvals::get_threshold(&env)
+    }
+
+    /// Sets the largest budget a batch may allocate to any user.
+    /// `None` removes the cap.
+    pub fn set_budget_cap(env: Env, admin: Address, cap: Option<i128>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        limits::set_cap(&env, cap);
+        env.events()
+            .publish((symbol_short!("budget"), symbol_short!("cap")), cap);
+    }
+
+    /// Returns the global budget cap, if one is configured.
+    pub fn get_budget_cap(env: Env) -> Option<i128> {
+        limits::get_cap(&env)
+    }
+
+    /// Sets how many times above or below a user's recent average an
+    /// allocation must be to emit an `allocation_anomaly` event.
+    /// `None` turns anomaly detection off.
+    pub fn set_anomaly_multiple(env: Env, admin: Address, multiple: Option<u32>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        anomalies::set_multiple(&env, multiple);
+        env.events().publish(
+            (symbol_short!("anomaly"), symbol_short!("multiple")),
+            multiple,
+        );
+    }
+
+    /// Returns the anomaly multiple, if anomaly detection is on.
+    pub fn get_anomaly_multiple(env: Env) -> Option<u32> {
+        anomalies::get_multiple(&env)
+    }
+
+    /// Returns the average of the user's recent allocations, once they have
+    /// enough of them to be checked for anomalies.
+    pub fn get_allocation_average(env: Env, user: Address) -> Option<i128> {
+        anomalies::average(&env, &user)
+    }
+
+    /// Sets the largest budget a batch may allocate to `user`, on top of the
+    /// global cap. `None` removes the limit.
+    pub fn set_user_budget_limit(env: Env, admin: Address, user: Address, limit: Option<i128>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        limits::set_user_limit(&env, &user, limit);
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("limit")),
+            (user, limit),
+        );
+    }
+
+    /// Returns the user's budget limit, if one is configured.
+    pub fn get_user_budget_limit(env: Env, user: Address) -> Option<i128> {
+        limits::get_user_limit(&env, &user)
+    }
+
+    /// Grants or revokes the right to approve pending batches.
+    pub fn set_batch_approver(env: Env, admin: Address, approver: Address, allowed: bool) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        approvals::set_approver(&env, &approver, allowed);
+        env.events().publish(
+            (symbol_short!("batch"), symbol_short!("approver")),
+            (approver, allowed),
+        );
+    }
+
+    /// Returns true if the address may approve pending batches.
+    pub fn is_batch_approver(env: Env, approver: Address) -> bool {
+        approvals::is_approver(&env, &approver)
+    }
+
+    /// Allocates every valid request in the batch.
+    fn apply_batch(
+        env: &Env,
+        requests: &Vec<BudgetRequest>,
+        changed_by: &Address,
+        suppress_anomalies: bool,
+    ) -> BatchBudgetResult {
+        let batch_id = Self::start_batch(env, requests.len());
         let mut successful = 0;
         let mut failed = 0;
         let mut total_amount: i128 = 0;
+        let mut results: Vec<BudgetResult> = Vec::new(env);
         let current_time = env.ledger().timestamp();
+        let mut seen: Map<Address, bool> = Map::new(env);
 
         for req in requests.iter() {
-            // Validate input amount
-            if req.amount < 0 {
+            let limit = limits::effective_limit(env, &req.user);
+            // Reject repeats so a later entry can't overwrite an earlier one
+            let error_code = if seen.contains_key(req.user.clone()) {
+                Some(ErrorCode::DUPLICATE_USER)
+            } else if req.amount < 0 {
+                Some(ErrorCode::INVALID_AMOUNT)
+            } else if limit.is_some_and(|limit| req.amount > limit) {
+                Some(ErrorCode::LIMIT_EXCEEDED)
+            } else {
+                None
+            };
+            seen.set(req.user.clone(), true);
+
+            if let Some(error_code) = error_code {
                 failed += 1;
-                // Emit failure event?
+                results.push_back(BudgetResult::Failure(req.user.clone(), error_code));
+                if error_code == ErrorCode::LIMIT_EXCEEDED {
+                    env.events().publish(
+                        (symbol_short!("budget"), symbol_short!("overlimit")),
+                        (req.user.clone(), req.amount, limit),
+                    );
+                }
                 env.events().publish(
                     (symbol_short!("budget"), symbol_short!("failed")),
-                    (req.user, req.amount), // Amount is negative here
+                    (req.user, req.amount, error_code),
                 );
                 continue;
             }
@@ -78,28 +330,338 @@ impl BudgetAllocationContract {
                 user: req.user.clone(),
                 amount: req.amount,
                 last_updated: current_time,
+                period: periods::current_period(env),
+                rolled_over: 0,
             };
 
+            let old_amount = env
+                .storage()
+                .persistent()
+                .get::<_, BudgetRecord>(&DataKey::Budget(req.user.clone()))
+                .map_or(0, |budget| budget.amount);
             env.storage()
                 .persistent()
                 .set(&DataKey::Budget(req.user.clone()), &record);
+            history::record(env, &req.user, old_amount, req.amount, changed_by);
+            anomalies::record(env, batch_id, &req.user, req.amount, suppress_anomalies);
+            holders::add(env, &req.user);
+            // A new allocation starts with nothing spent against it
+            env.storage()
+                .persistent()
+                .remove(&DataKey::BudgetSpent(req.user.clone()));
+            spending::reset(env, &req.user);
 
             // Emit update event
             env.events().publish(
                 (symbol_short!("budget"), symbol_short!("set")),
-                (req.user, req.amount),
+                (req.user.clone(), req.amount),
             );
+            currency::publish_fiat(env, symbol_short!("set"), &req.user, req.amount);
 
             successful += 1;
+            results.push_back(BudgetResult::Success(record));
             total_amount = total_amount.checked_add(req.amount).unwrap_or(i128::MAX);
             // Prevent overflow panic
         }
 
+        Self::complete_batch(env, batch_id, successful, failed, total_amount, results)
+    }
+
+    /// Assigns the next batch id and announces the batch.
+    fn start_batch(env: &Env, request_count: u32) -> u64 {
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastBatchId)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::LastBatchId, &batch_id);
+
+        env.events().publish(
+            (symbol_short!("batch"), symbol_short!("started")),
+            (batch_id, request_count),
+        );
+        batch_id
+    }
+
+    /// Records and announces the outcome of a batch and builds its result.
+    fn complete_batch(
+        env: &Env,
+        batch_id: u64,
+        successful: u32,
+        failed: u32,
+        total_amount: i128,
+        results: Vec<BudgetResult>,
+    ) -> BatchBudgetResult {
+        let record = BatchRecord {
+            batch_id,
+            request_count: results.len(),
+            successful,
+            failed,
+            total_amount,
+            completed_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::BatchRecord(batch_id), &record);
+        env.events().publish(
+            (symbol_short!("batch"), symbol_short!("completed"), batch_id),
+            (successful, failed, total_amount),
+        );
+
         BatchBudgetResult {
+            batch_id,
             successful,
             failed,
             total_amount,
+            results,
+            pending_batch_id: None,
+        }
+    }
+
+    /// Allocates budgets like `batch_allocate_budget` and escrows them in
+    /// `token`.
+    ///
+    /// Each successful user's escrow is brought to their new budget, pulling
+    /// the shortfall from the treasury through the allowance it granted this
+    /// contract or returning the surplus to it, so repeated allocations do
+    /// not stack. Users draw the escrow down with `withdraw_budget`.
+    /// Funded batches cannot wait for a second approver, so batches above
+    /// the approval threshold are rejected.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address calling the function
+    /// * `token` - Token the budgets are funded in
+    /// * `requests` - List of user-budget pairs
+    pub fn batch_allocate_funded_budget(
+        env: Env,
+        admin: Address,
+        token: Address,
+        requests: Vec<BudgetRequest>,
+    ) -> BatchBudgetResult {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if requests.is_empty() {
+            panic!("Empty batch");
+        }
+        if requests.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
         }
+        if approvals::requires_approval(&env, approvals::requested_total(&requests)) {
+            panic!("Funded batch requires approval");
+        }
+
+        let result = Self::apply_batch(&env, &requests, &admin, false);
+        for outcome in result.results.iter() {
+            if let BudgetResult::Success(record) = outcome {
+                funding::fund(&env, &record.user, &token, record.amount);
+            }
+        }
+
+        env.events().publish(
+            (
+                symbol_short!("budget"),
+                symbol_short!("funded"),
+                result.batch_id,
+            ),
+            (token, result.total_amount),
+        );
+        result
+    }
+
+    /// Withdraws part of the user's funded budget. Returns what is left.
+    ///
+    /// Withdrawals are capped at the budget not yet spent or reserved and
+    /// count as spending against it.
+    pub fn withdraw_budget(env: Env, user: Address, token: Address, amount: i128) -> i128 {
+        user.require_auth();
+
+        if amount > Self::get_available_budget(env.clone(), user.clone()) {
+            panic!("Insufficient budget");
+        }
+        let remaining = funding::withdraw(&env, &user, &token, amount);
+        let spent = Self::get_spent(env.clone(), user.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetSpent(user.clone()), &spent);
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("withdrawn")),
+            (user, token, amount),
+        );
+        remaining
+    }
+
+    /// Returns the user's escrowed balance in `token`.
+    pub fn get_funded_balance(env: Env, user: Address, token: Address) -> i128 {
+        funding::balance(&env, &user, &token)
+    }
+
+    /// Sets the treasury funded allocations are drawn from.
+    pub fn set_treasury(env: Env, admin: Address, treasury: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        funding::set_treasury(&env, &treasury);
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("treasury")),
+            treasury,
+        );
+    }
+
+    /// Returns the treasury, if one is configured.
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        funding::get_treasury(&env)
+    }
+
+    /// Tops up (positive `delta`) or claws back (negative `delta`) a user's
+    /// budget without resetting what they have spent.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address calling the function
+    /// * `user` - The user whose budget is adjusted
+    /// * `delta` - Signed change to the budget amount
+    pub fn adjust_budget(env: Env, admin: Address, user: Address, delta: i128) -> BudgetRecord {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        match adjustments::check(&env, &user, delta, false) {
+            Ok((budget, amount)) => adjustments::apply(&env, budget, amount, &admin),
+            Err(ErrorCode::BUDGET_NOT_FOUND) => panic!("Budget not found"),
+            Err(ErrorCode::INSUFFICIENT_BUDGET) => panic!("Budget underflow"),
+            Err(ErrorCode::LIMIT_EXCEEDED) => panic!("Budget limit exceeded"),
+            Err(_) => panic!("Invalid adjustment"),
+        }
+    }
+
+    /// Adjusts many budgets in a single operation, for bonuses, corrections
+    /// and clawbacks.
+    ///
+    /// Decreases are floored at zero: a clawback larger than the budget
+    /// empties it. Invalid adjustments fail individually: zero deltas with
+    /// `ErrorCode::INVALID_AMOUNT`, users without a budget with
+    /// `ErrorCode::BUDGET_NOT_FOUND`, decreases of an empty budget with
+    /// `ErrorCode::INSUFFICIENT_BUDGET`, increases over the cap or limit with
+    /// `ErrorCode::LIMIT_EXCEEDED`, and repeated users with
+    /// `ErrorCode::DUPLICATE_USER`. The result's total is the net change
+    /// actually applied.
+    ///
+    /// Each applied adjustment emits `budget adjusted` with its reason and is
+    /// logged to the audit contract, if one is configured.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address calling the function
+    /// * `requests` - Signed budget changes with their reasons, one per user
+    pub fn batch_adjust_budgets(
+        env: Env,
+        admin: Address,
+        requests: Vec<BudgetAdjustment>,
+    ) -> BatchBudgetResult {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if requests.is_empty() {
+            panic!("Empty batch");
+        }
+        if requests.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
+        }
+
+        let batch_id = Self::start_batch(&env, requests.len());
+        let mut successful = 0;
+        let mut failed = 0;
+        let mut net_change: i128 = 0;
+        let mut results: Vec<BudgetResult> = Vec::new(&env);
+        let mut seen: Map<Address, bool> = Map::new(&env);
+
+        for adjustment in requests.iter() {
+            let checked = if seen.contains_key(adjustment.user.clone()) {
+                Err(ErrorCode::DUPLICATE_USER)
+            } else {
+                adjustments::check(&env, &adjustment.user, adjustment.delta, true)
+            };
+            seen.set(adjustment.user.clone(), true);
+
+            match checked {
+                Ok((budget, amount)) => {
+                    let applied = amount - budget.amount;
+                    let record = adjustments::apply(&env, budget, amount, &admin);
+                    env.events().publish(
+                        (symbol_short!("budget"), symbol_short!("adjusted")),
+                        (
+                            adjustment.user.clone(),
+                            applied,
+                            amount,
+                            adjustment.reason.clone(),
+                        ),
+                    );
+                    audit::log_adjustment(
+                        &env,
+                        &admin,
+                        &adjustment.user,
+                        applied,
+                        amount,
+                        &adjustment.reason,
+                    );
+                    successful += 1;
+                    net_change += applied;
+                    results.push_back(BudgetResult::Success(record));
+                }
+                Err(error_code) => {
+                    failed += 1;
+                    results.push_back(BudgetResult::Failure(adjustment.user.clone(), error_code));
+                    env.events().publish(
+                        (symbol_short!("budget"), symbol_short!("failed")),
+                        (
+                            adjustment.user,
+                            adjustment.delta,
+                            error_code,
+                            adjustment.reason,
+                        ),
+                    );
+                }
+            }
+        }
+
+        Self::complete_batch(&env, batch_id, successful, failed, net_change, results)
+    }
+
+    /// Returns the id of the last batch applied (0 if none).
+    pub fn get_last_batch_id(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LastBatchId)
+            .unwrap_or(0)
+    }
+
+    /// Returns the stored outcome of an applied batch.
+    pub fn get_batch_record(env: Env, batch_id: u64) -> Option<BatchRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BatchRecord(batch_id))
+    }
+
+    /// Returns up to `limit` batch records, newest first, starting below
+    /// `before_batch_id` (0 starts from the last batch).
+    pub fn get_batch_records(env: Env, before_batch_id: u64, limit: u32) -> Vec<BatchRecord> {
+        let mut batch_id = if before_batch_id == 0 {
+            Self::get_last_batch_id(env.clone())
+        } else {
+            before_batch_id - 1
+        };
+        let mut records = Vec::new(&env);
+        while batch_id > 0 && records.len() < limit {
+            if let Some(record) = Self::get_batch_record(env.clone(), batch_id) {
+                records.push_back(record);
+            }
+            batch_id -= 1;
+        }
+        records
     }
 
     /// Allocates budgets across multiple categories for a user.
@@ -143,77 +705,1040 @@ impl BudgetAllocationContract {
             panic!("Negative total amount not allowed");
         }
 
-        // Create category map
-        let mut category_map = Map::<Symbol, i128>::new(&env);
-        for category in request.categories.iter() {
-            category_map.set(category.name, category.amount);
-        }
-
-        // Store user budget categories
-        let user_categories = UserBudgetCategories {
-            user: request.user.clone(),
-            categories: category_map,
-            total_amount: request.total_amount,
-            last_updated: env.ledger().timestamp(),
-        };
-
-        env.storage().persistent().set(
-            &DataKey::BudgetCategories(request.user.clone()),
-            &user_categories,
-        );
+        Self::store_category_allocation(&env, &request, &admin);
+        true
+    }
 
-        // Also update the legacy budget record for compatibility
-        let budget_record = BudgetRecord {
-            user: request.user.clone(),
-            amount: request.total_amount,
-            last_updated: env.ledger().timestamp(),
-        };
-        env.storage()
-            .persistent()
-            .set(&DataKey::Budget(request.user.clone()), &budget_record);
+    /// Assigns category budgets across many users in a single operation.
+    ///
+    /// Invalid requests fail individually instead of aborting the batch:
+    /// negative amounts with `ErrorCode::INVALID_AMOUNT`, totals that do not
+    /// match the category amounts with `ErrorCode::TOTAL_MISMATCH`, and users
+    /// repeated within the batch with `ErrorCode::DUPLICATE_USER`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin or a budget manager
+    /// * `requests` - Category budget allocation requests, one per user
+    pub fn batch_allocate_by_category(
+        env: Env,
+        caller: Address,
+        requests: Vec<CategoryBudgetRequest>,
+    ) -> BatchBudgetResult {
+        caller.require_auth();
+        Self::require_allocator(&env, &caller);
 
-        // Emit allocation events for each category
-        for category in request.categories.iter() {
-            env.events().publish(
-                (symbol_short!("category"), symbol_short!("allocated")),
-                (request.user.clone(), category.name, category.amount),
-            );
+        if requests.is_empty() {
+            panic!("Empty batch");
+        }
+        if requests.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
         }
 
-        // Emit total allocation event
-        env.events().publish(
-            (symbol_short!("budget"), symbol_short!("allocated")),
-            (request.user, request.total_amount, request.categories.len()),
-        );
-
-        true
+        Self::apply_category_batch(&env, &requests, &caller)
     }
 
-    /// Retrieves budget categories for a specific user.
-    pub fn get_budget_categories(env: Env, user: Address) -> Option<UserBudgetCategories> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::BudgetCategories(user))
-    }
+    /// Validates and stores category requests as one batch; invalid requests
+    /// fail individually.
+    fn apply_category_batch(
+        env: &Env,
+        requests: &Vec<CategoryBudgetRequest>,
+        changed_by: &Address,
+    ) -> BatchBudgetResult {
+        let batch_id = Self::start_batch(env, requests.len());
+        let mut successful = 0;
+        let mut failed = 0;
+        let mut total_amount: i128 = 0;
+        let mut results: Vec<BudgetResult> = Vec::new(env);
+        let mut seen: Map<Address, bool> = Map::new(env);
 
-    /// Retrieves the budget for a specific category for a user.
-    pub fn get_category_budget(env: Env, user: Address, category: Symbol) -> Option<i128> {
-        let user_categories: Option<UserBudgetCategories> = env
+        for request in requests.iter() {
+            let error_code = if seen.contains_key(request.user.clone()) {
+                Some(ErrorCode::DUPLICATE_USER)
+            } else {
+                Self::category_split_error(&request.categories, request.total_amount)
+            };
+            seen.set(request.user.clone(), true);
+
+            if let Some(error_code) = error_code {
+                failed += 1;
+                results.push_back(BudgetResult::Failure(request.user.clone(), error_code));
+                env.events().publish(
+                    (symbol_short!("budget"), symbol_short!("failed")),
+                    (request.user, request.total_amount, error_code),
+                );
+                continue;
+            }
+
+            let record = Self::store_category_allocation(env, &request, changed_by);
+            successful += 1;
+            results.push_back(BudgetResult::Success(record));
+            total_amount = total_amount
+                .checked_add(request.total_amount)
+                .unwrap_or(i128::MAX);
+        }
+
+        Self::complete_batch(env, batch_id, successful, failed, total_amount, results)
+    }
+
+    /// Stores a named category split that can be stamped onto many users
+    /// with `apply_template`, replacing any template with the same name.
+    pub fn create_template(
+        env: Env,
+        admin: Address,
+        name: Symbol,
+        categories: Vec<BudgetCategory>,
+    ) -> BudgetTemplate {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let template = templates::create(&env, &name, categories);
+        env.events().publish(
+            (symbol_short!("template"), symbol_short!("created"), name),
+            (template.categories.len(), template.total_amount),
+        );
+        template
+    }
+
+    /// Deletes a budget template.
+    pub fn delete_template(env: Env, admin: Address, name: Symbol) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        templates::delete(&env, &name);
+        env.events().publish(
+            (symbol_short!("template"), symbol_short!("deleted"), name),
+            (),
+        );
+    }
+
+    /// Returns a budget template by name.
+    pub fn get_template(env: Env, name: Symbol) -> Option<BudgetTemplate> {
+        templates::get(&env, &name)
+    }
+
+    /// Allocates the template's category split to each user, like a
+    /// `batch_allocate_by_category` where every request uses the template.
+    /// Users repeated in `users` fail with `ErrorCode::DUPLICATE_USER`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The admin or a budget manager
+    /// * `name` - Template to apply
+    /// * `users` - Users receiving the template's budget
+    pub fn apply_template(
+        env: Env,
+        caller: Address,
+        name: Symbol,
+        users: Vec<Address>,
+    ) -> BatchBudgetResult {
+        caller.require_auth();
+        Self::require_allocator(&env, &caller);
+
+        if users.is_empty() {
+            panic!("Empty batch");
+        }
+        if users.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
+        }
+
+        let template = templates::get(&env, &name).expect("Template not found");
+        let mut requests: Vec<CategoryBudgetRequest> = Vec::new(&env);
+        for user in users.iter() {
+            requests.push_back(CategoryBudgetRequest {
+                user,
+                categories: template.categories.clone(),
+                total_amount: template.total_amount,
+            });
+        }
+
+        let result = Self::apply_category_batch(&env, &requests, &caller);
+        env.events().publish(
+            (symbol_short!("template"), symbol_short!("applied"), name),
+            (result.batch_id, result.successful),
+        );
+        result
+    }
+
+    /// Returns the error code a category split fails with, if any.
+    fn category_split_error(categories: &Vec<BudgetCategory>, total_amount: i128) -> Option<u32> {
+        if total_amount < 0 {
+            return Some(ErrorCode::INVALID_AMOUNT);
+        }
+        let mut calculated_total: i128 = 0;
+        for category in categories.iter() {
+            if category.amount < 0 {
+                return Some(ErrorCode::INVALID_AMOUNT);
+            }
+            calculated_total = match calculated_total.checked_add(category.amount) {
+                Some(total) => total,
+                None => return Some(ErrorCode::TOTAL_MISMATCH),
+            };
+        }
+        if calculated_total != total_amount {
+            return Some(ErrorCode::TOTAL_MISMATCH);
+        }
+        None
+    }
+
+    /// Stores a validated category allocation, replacing the user's budget,
+    /// and returns the new budget record.
+    fn store_category_allocation(
+        env: &Env,
+        request: &CategoryBudgetRequest,
+        changed_by: &Address,
+    ) -> BudgetRecord {
+        // Create category map, filing amounts under current category names
+        let mut category_map = Map::<Symbol, i128>::new(env);
+        for category in request.categories.iter() {
+            category_map.set(category.name, category.amount);
+        }
+        let (category_map, _) = categories::remap_categories(env, &category_map);
+
+        // Store user budget categories
+        let user_categories = UserBudgetCategories {
+            user: request.user.clone(),
+            categories: category_map,
+            total_amount: request.total_amount,
+            last_updated: env.ledger().timestamp(),
+        };
+
+        env.storage().persistent().set(
+            &DataKey::BudgetCategories(request.user.clone()),
+            &user_categories,
+        );
+
+        // Also update the legacy budget record for compatibility
+        let budget_record = BudgetRecord {
+            user: request.user.clone(),
+            amount: request.total_amount,
+            last_updated: env.ledger().timestamp(),
+            period: periods::current_period(env),
+            rolled_over: 0,
+        };
+        let old_amount = env
             .storage()
             .persistent()
-            .get(&DataKey::BudgetCategories(user));
-        if let Some(categories) = user_categories {
-            categories.categories.get(category)
+            .get::<_, BudgetRecord>(&DataKey::Budget(request.user.clone()))
+            .map_or(0, |budget| budget.amount);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Budget(request.user.clone()), &budget_record);
+        history::record(
+            env,
+            &request.user,
+            old_amount,
+            request.total_amount,
+            changed_by,
+        );
+        holders::add(env, &request.user);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BudgetSpent(request.user.clone()));
+        spending::reset(env, &request.user);
+
+        // Emit allocation events for each category
+        for category in request.categories.iter() {
+            env.events().publish(
+                (symbol_short!("category"), symbol_short!("allocated")),
+                (request.user.clone(), category.name, category.amount),
+            );
+        }
+
+        // Emit total allocation event
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("allocated")),
+            (
+                request.user.clone(),
+                request.total_amount,
+                request.categories.len(),
+            ),
+        );
+        currency::publish_fiat(
+            env,
+            symbol_short!("allocated"),
+            &request.user,
+            request.total_amount,
+        );
+
+        budget_record
+    }
+
+    /// Sets the budget profile `ensure_budget` applies to users without a
+    /// budget. `None` removes the default.
+    pub fn set_default_budget(env: Env, admin: Address, profile: Option<DefaultBudget>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        match &profile {
+            Some(profile) => {
+                if Self::category_split_error(&profile.categories, profile.total_amount).is_some() {
+                    panic!("Invalid default budget");
+                }
+                env.storage()
+                    .instance()
+                    .set(&DataKey::DefaultBudget, profile);
+            }
+            None => env.storage().instance().remove(&DataKey::DefaultBudget),
+        }
+        env.events().publish(
+            (symbol_short!("default"), symbol_short!("updated")),
+            profile.map(|profile| profile.total_amount),
+        );
+    }
+
+    /// Returns the default budget profile, if one is configured.
+    pub fn get_default_budget(env: Env) -> Option<DefaultBudget> {
+        env.storage().instance().get(&DataKey::DefaultBudget)
+    }
+
+    /// Returns the user's budget, first allocating the default profile if the
+    /// user has none, e.g. when an expense tracker sees their first spend.
+    ///
+    /// Anyone may call this; it never replaces an existing budget. Users with
+    /// an archived budget are left to `restore_budget`. The budget history
+    /// records the contract itself as the author of a default allocation.
+    pub fn ensure_budget(env: Env, user: Address) -> BudgetRecord {
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Budget(user.clone()))
+        {
+            return record;
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::ArchivedBudget(user.clone()))
+        {
+            panic!("Budget archived");
+        }
+
+        let profile: DefaultBudget = env
+            .storage()
+            .instance()
+            .get(&DataKey::DefaultBudget)
+            .expect("No default budget");
+        let request = CategoryBudgetRequest {
+            user: user.clone(),
+            categories: profile.categories,
+            total_amount: profile.total_amount,
+        };
+        let record =
+            Self::store_category_allocation(&env, &request, &env.current_contract_address());
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("default")),
+            (user, profile.total_amount),
+        );
+        record
+    }
+
+    /// Retrieves budget categories for a specific user.
+    pub fn get_budget_categories(env: Env, user: Address) -> Option<UserBudgetCategories> {
+        categories::load_user_categories(&env, &user)
+    }
+
+    /// Retrieves the budget for a specific category for a user.
+    pub fn get_category_budget(env: Env, user: Address, category: Symbol) -> Option<i128> {
+        let user_categories = categories::load_user_categories(&env, &user);
+        if let Some(user_categories) = user_categories {
+            user_categories
+                .categories
+                .get(categories::resolve(&env, &category))
         } else {
             None
         }
     }
 
+    /// Moves part of a user's allocation from one category to another,
+    /// leaving the total unchanged.
+    ///
+    /// Only what is left of `from_category` after spending and reservations
+    /// can be moved.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The user reallocating their own budget
+    /// * `from_category` - Category the amount is taken from
+    /// * `to_category` - Category the amount is added to
+    /// * `amount` - Amount to move (must be positive)
+    pub fn transfer_between_categories(
+        env: Env,
+        user: Address,
+        from_category: Symbol,
+        to_category: Symbol,
+        amount: i128,
+    ) -> UserBudgetCategories {
+        user.require_auth();
+
+        if amount <= 0 {
+            panic!("Invalid transfer amount");
+        }
+        let from_category = categories::resolve(&env, &from_category);
+        let to_category = categories::resolve(&env, &to_category);
+        if from_category == to_category {
+            panic!("Source and target categories must differ");
+        }
+
+        let mut user_categories =
+            categories::load_user_categories(&env, &user).expect("Budget categories not found");
+        let from_amount = user_categories
+            .categories
+            .get(from_category.clone())
+            .expect("Category not found");
+        let to_amount = user_categories
+            .categories
+            .get(to_category.clone())
+            .expect("Category not found");
+
+        let committed = spending::category_spent(&env, &user, &from_category)
+            + reservations::reserved_in_category(&env, &user, &from_category);
+        if from_amount - committed < amount {
+            panic!("Insufficient category budget");
+        }
+
+        user_categories
+            .categories
+            .set(from_category.clone(), from_amount - amount);
+        user_categories
+            .categories
+            .set(to_category.clone(), to_amount + amount);
+        user_categories.last_updated = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetCategories(user.clone()), &user_categories);
+
+        env.events().publish(
+            (symbol_short!("category"), symbol_short!("moved"), user),
+            (from_category, to_category, amount),
+        );
+        user_categories
+    }
+
+    /// Renames a category. Existing allocations are migrated lazily the next
+    /// time each user's budget is accessed.
+    pub fn rename_category(env: Env, admin: Address, old: Symbol, new: Symbol) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        categories::record_remap(&env, &old, &new);
+        env.events().publish(
+            (symbol_short!("category"), symbol_short!("renamed")),
+            (old, new),
+        );
+    }
+
+    /// Merges `source` into `target`. Users holding both categories end up
+    /// with the sum under `target` once their budget is next accessed.
+    pub fn merge_categories(env: Env, admin: Address, source: Symbol, target: Symbol) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        categories::record_remap(&env, &source, &target);
+        env.events().publish(
+            (symbol_short!("category"), symbol_short!("merged")),
+            (source, target),
+        );
+    }
+
+    /// Returns the category a renamed or merged category now maps to.
+    pub fn get_category_remap(env: Env, category: Symbol) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CategoryRemap(category))
+    }
+
     /// Retrieves the budget for a specific user.
     pub fn get_budget(env: Env, user: Address) -> Option<BudgetRecord> {
         env.storage().persistent().get(&DataKey::Budget(user))
     }
 
+    /// Lists up to `limit` users holding an active budget, starting at
+    /// position `offset` of the holder index.
+    pub fn get_budget_holders(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        holders::page(&env, offset, limit)
+    }
+
+    /// Lists up to `limit` changes of the user's budget amount, oldest first,
+    /// starting at position `offset` of the user's history.
+    pub fn get_budget_history(
+        env: Env,
+        user: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<BudgetChange> {
+        history::page(&env, &user, offset, limit)
+    }
+
+    /// Returns the number of changes logged for the user's budget.
+    pub fn get_budget_history_count(env: Env, user: Address) -> u32 {
+        history::count(&env, &user)
+    }
+
+    /// Returns the number of users holding an active budget.
+    pub fn get_total_budget_holders(env: Env) -> u32 {
+        holders::count(&env)
+    }
+
+    /// Records spending in a category against a user's budget and returns the
+    /// remaining budget, or why the spend was refused.
+    ///
+    /// If the user has category budgets, the category must be allocated and
+    /// have enough left. Reserved funds are not available for other spending.
+    /// If the remaining budget falls under the low-budget threshold, a
+    /// reminder is scheduled through the reminders contract (at most once per
+    /// user and budget period).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The budget owner
+    /// * `category` - Category the expense falls under
+    /// * `amount` - Amount spent (must be > 0)
+    ///
+    /// Spends are refused while the budget is frozen or once the user has
+    /// used up the expense throttle's window. A refusal does not revert: the
+    /// throttle violation it records counts towards freezing the budget.
+    pub fn record_spend(env: Env, user: Address, category: Symbol, amount: i128) -> SpendOutcome {
+        user.require_auth();
+
+        let frozen = throttle::is_frozen(&env, &user);
+        if !throttle::check_expense(&env, &user) {
+            return if frozen {
+                SpendOutcome::Frozen
+            } else {
+                SpendOutcome::Throttled
+            };
+        }
+        SpendOutcome::Recorded(Self::spend(&env, &user, &category, amount))
+    }
+
+    fn spend(env: &Env, user: &Address, category: &Symbol, amount: i128) -> i128 {
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
+        let budget = Self::load_budget(env, user);
+        let category = categories::resolve(env, category);
+
+        if let Some(user_categories) = categories::load_user_categories(env, user) {
+            let allocated = user_categories
+                .categories
+                .get(category.clone())
+                .expect("Category not found");
+            let committed = spending::category_spent(env, user, &category)
+                + reservations::reserved_in_category(env, user, &category);
+            if committed + amount > allocated {
+                panic!("Insufficient category budget");
+            }
+        }
+
+        let spent = Self::get_spent(env.clone(), user.clone()) + amount;
+        if spent + reservations::total_reserved(env, user) > budget.amount {
+            panic!("Insufficient budget");
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetSpent(user.clone()), &spent);
+        spending::record(env, user, &category, amount);
+
+        let remaining = budget.amount - spent;
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("spent")),
+            (user.clone(), category, amount, remaining),
+        );
+        currency::publish_fiat(env, symbol_short!("spent"), user, amount);
+
+        alerts::check(env, user, budget.amount, spent - amount, spent);
+        low_budget::check(env, user, remaining);
+        remaining
+    }
+
+    fn load_budget(env: &Env, user: &Address) -> BudgetRecord {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Budget(user.clone()))
+            .expect("Budget not found")
+    }
+
+    /// Earmarks part of the user's budget for an upcoming expense and returns
+    /// the reservation ID.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The budget owner
+    /// * `category` - Category the expense falls under; must be allocated if
+    ///   the user has category budgets
+    /// * `amount` - Amount to reserve (must be > 0 and fit the available budget)
+    /// * `reference` - Caller-supplied reference, e.g. an invoice number
+    pub fn reserve_funds(
+        env: Env,
+        user: Address,
+        category: Symbol,
+        amount: i128,
+        reference: Symbol,
+    ) -> u64 {
+        user.require_auth();
+
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
+        if amount > Self::get_available_budget(env.clone(), user.clone()) {
+            panic!("Insufficient budget");
+        }
+
+        let reservation = reservations::reserve(&env, &user, &category, amount, &reference);
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("reserved")),
+            (
+                user,
+                reservation.reservation_id,
+                reservation.category,
+                amount,
+                reference,
+            ),
+        );
+        reservation.reservation_id
+    }
+
+    /// Cancels a reservation, returning its amount to the available budget.
+    pub fn release_reservation(env: Env, user: Address, reservation_id: u64) {
+        user.require_auth();
+
+        let reservation = reservations::take(&env, &user, reservation_id);
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("released")),
+            (user, reservation_id, reservation.amount),
+        );
+    }
+
+    /// Turns a reservation into an actual expense of the reserved amount and
+    /// returns the remaining budget, as `record_spend` does.
+    pub fn convert_reservation(env: Env, user: Address, reservation_id: u64) -> i128 {
+        user.require_auth();
+
+        let reservation = reservations::take(&env, &user, reservation_id);
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("converted")),
+            (user.clone(), reservation_id, reservation.amount),
+        );
+        Self::spend(&env, &user, &reservation.category, reservation.amount)
+    }
+
+    /// Returns the user's open reservations.
+    pub fn get_reservations(env: Env, user: Address) -> Vec<Reservation> {
+        reservations::get_all(&env, &user)
+    }
+
+    /// Returns the total amount the user has reserved.
+    pub fn get_reserved(env: Env, user: Address) -> i128 {
+        reservations::total_reserved(&env, &user)
+    }
+
+    /// Returns the budget left after spending and reservations (never negative).
+    pub fn get_available_budget(env: Env, user: Address) -> i128 {
+        let budget = Self::load_budget(&env, &user);
+        let committed =
+            Self::get_spent(env.clone(), user.clone()) + reservations::total_reserved(&env, &user);
+        (budget.amount - committed).max(0)
+    }
+
+    /// Returns the user's budget, spending, reservations and availability.
+    pub fn get_budget_statement(env: Env, user: Address) -> BudgetStatement {
+        let budget = Self::load_budget(&env, &user);
+        let spent = Self::get_spent(env.clone(), user.clone());
+        let reservations = reservations::get_all(&env, &user);
+        let reserved = reservations
+            .iter()
+            .fold(0, |total, reservation| total + reservation.amount);
+
+        BudgetStatement {
+            user,
+            budget: budget.amount,
+            spent,
+            reserved,
+            available: (budget.amount - spent - reserved).max(0),
+            reservations,
+        }
+    }
+
+    /// Returns the spending recorded against the user's current budget.
+    pub fn get_spent(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BudgetSpent(user))
+            .unwrap_or(0)
+    }
+
+    /// Returns the user's budget minus what has been spent against it.
+    pub fn get_remaining_budget(env: Env, user: Address) -> i128 {
+        let budget = Self::load_budget(&env, &user);
+        budget.amount - Self::get_spent(env, user)
+    }
+
+    /// Returns a category's allocation minus what has been spent in it, or
+    /// `None` if the category is not allocated to the user.
+    pub fn get_remaining_category_budget(
+        env: Env,
+        user: Address,
+        category: Symbol,
+    ) -> Option<i128> {
+        let category = categories::resolve(&env, &category);
+        let allocated = categories::load_user_categories(&env, &user)?
+            .categories
+            .get(category.clone())?;
+        Some(allocated - spending::category_spent(&env, &user, &category))
+    }
+
+    /// Returns the user's most recent spend entries, oldest first.
+    pub fn get_spend_history(env: Env, user: Address) -> Vec<SpendRecord> {
+        spending::history(&env, &user)
+    }
+
+    /// Closes the user's budget period once it has ended and opens the
+    /// current one.
+    ///
+    /// The closed period is kept as a snapshot keyed by `(user, period)`.
+    /// The new period gets the same base budget plus the unspent amount
+    /// carried over by the user's rollover policy. Category allocations are
+    /// unchanged and start the new period with nothing spent.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address calling the function
+    /// * `user` - The budget owner
+    pub fn close_period(env: Env, admin: Address, user: Address) -> PeriodSnapshot {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let budget = Self::load_budget(&env, &user);
+        let snapshot = periods::close(&env, &user, &budget);
+        let new_amount = Self::load_budget(&env, &user).amount;
+        if new_amount != budget.amount {
+            history::record(&env, &user, budget.amount, new_amount, &admin);
+        }
+        env.events().publish(
+            (symbol_short!("period"), symbol_short!("closed")),
+            (
+                user,
+                snapshot.period,
+                snapshot.unspent,
+                snapshot.rolled_over,
+            ),
+        );
+        snapshot
+    }
+
+    /// Sets what happens to the user's unspent budget when a period closes.
+    pub fn set_rollover_policy(env: Env, admin: Address, user: Address, policy: RolloverPolicy) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        periods::set_policy(&env, &user, &policy);
+        env.events().publish(
+            (symbol_short!("period"), symbol_short!("rollover")),
+            (user, policy),
+        );
+    }
+
+    /// Returns the user's rollover policy (`Forfeit` unless configured).
+    pub fn get_rollover_policy(env: Env, user: Address) -> RolloverPolicy {
+        periods::get_policy(&env, &user)
+    }
+
+    /// Returns the budget period the ledger is currently in.
+    pub fn get_current_period(env: Env) -> u64 {
+        periods::current_period(&env)
+    }
+
+    /// Returns the snapshot of a closed period, if the user closed it.
+    pub fn get_period_snapshot(env: Env, user: Address, period: u64) -> Option<PeriodSnapshot> {
+        periods::get_snapshot(&env, &user, period)
+    }
+
+    /// Returns the periods closed for the user, oldest first.
+    pub fn get_closed_periods(env: Env, user: Address) -> Vec<u64> {
+        periods::closed_periods(&env, &user)
+    }
+
+    /// Configures "budget low" reminders. `None` disables them. The reminders
+    /// contract must be initialized with this contract as its admin.
+    pub fn set_low_budget_alert(env: Env, admin: Address, config: Option<LowBudgetAlert>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        low_budget::set_config(&env, &config);
+        env.events()
+            .publish((symbol_short!("budget"), symbol_short!("lowcfg")), config);
+    }
+
+    /// Returns the "budget low" reminder configuration, if any.
+    pub fn get_low_budget_alert(env: Env) -> Option<LowBudgetAlert> {
+        low_budget::get_config(&env)
+    }
+
+    /// Returns the budget period in which the user was last sent a "budget low" reminder.
+    pub fn get_low_budget_notified(env: Env, user: Address) -> Option<u64> {
+        low_budget::last_notified(&env, &user)
+    }
+
+    /// Sets the shares of the budget (in basis points, strictly ascending, at
+    /// most `MAX_SPEND_ALERTS`) at which spends raise a `("budget", "alert")`
+    /// event carrying the threshold, the share consumed in basis points and
+    /// the remaining budget. An empty list disables alerts.
+    pub fn set_spend_alerts(env: Env, user: Address, thresholds: Vec<u32>) {
+        user.require_auth();
+
+        alerts::set_thresholds(&env, &user, &thresholds);
+        env.events().publish(
+            (symbol_short!("alert"), symbol_short!("set")),
+            (user, thresholds),
+        );
+    }
+
+    /// Returns the user's spending alert thresholds, in basis points.
+    pub fn get_spend_alerts(env: Env, user: Address) -> Vec<u32> {
+        alerts::get_thresholds(&env, &user)
+    }
+
+    /// Sets the fiat currency the user's amounts are displayed in. `None` clears it.
+    pub fn set_display_currency(env: Env, user: Address, currency: Option<Symbol>) {
+        user.require_auth();
+
+        currency::set_display_currency(&env, &user, &currency);
+        env.events().publish(
+            (symbol_short!("currency"), symbol_short!("set")),
+            (user, currency),
+        );
+    }
+
+    /// Returns the user's display currency, if set.
+    pub fn get_display_currency(env: Env, user: Address) -> Option<Symbol> {
+        currency::get_display_currency(&env, &user)
+    }
+
+    /// Configures the oracle adapter used for fiat equivalents. `None` disables them.
+    pub fn set_rate_oracle(env: Env, admin: Address, oracle: Option<Address>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        currency::set_oracle(&env, &oracle);
+        env.events()
+            .publish((symbol_short!("currency"), symbol_short!("oracle")), oracle);
+    }
+
+    /// Returns the configured rate oracle, if any.
+    pub fn get_rate_oracle(env: Env) -> Option<Address> {
+        currency::get_oracle(&env)
+    }
+
+    /// Configures the audit contract that records batch budget adjustments.
+    /// `None` stops the logging.
+    pub fn set_audit_contract(env: Env, admin: Address, audit_contract: Option<Address>) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        audit::set_audit_contract(&env, &audit_contract);
+        env.events().publish(
+            (symbol_short!("audit"), symbol_short!("updated")),
+            audit_contract,
+        );
+    }
+
+    /// Returns the configured audit contract, if any.
+    pub fn get_audit_contract(env: Env) -> Option<Address> {
+        audit::get_audit_contract(&env)
+    }
+
+    /// Archives a user's budget, categories, spending and reservations
+    /// instead of deleting them. A later removal replaces any previous archive
+    /// for the user. Any escrow behind the budget goes back to the treasury.
+    pub fn remove_budget(env: Env, admin: Address, user: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let budget_key = DataKey::Budget(user.clone());
+        let categories_key = DataKey::BudgetCategories(user.clone());
+        let budget: Option<BudgetRecord> = env.storage().persistent().get(&budget_key);
+        let categories = categories::load_user_categories(&env, &user);
+        if budget.is_none() && categories.is_none() {
+            panic!("Budget not found");
+        }
+
+        let archive = ArchivedBudget {
+            user: user.clone(),
+            amount: budget.map(|record| record.amount),
+            categories_total: categories.as_ref().map_or(0, |c| c.total_amount),
+            categories: categories.map(|c| c.categories),
+            spent: Self::get_spent(env.clone(), user.clone()),
+            category_spent: spending::category_spent_map(&env, &user),
+            reservations: reservations::get_all(&env, &user),
+            archived_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArchivedBudget(user.clone()), &archive);
+        env.storage().persistent().remove(&budget_key);
+        env.storage().persistent().remove(&categories_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BudgetSpent(user.clone()));
+        spending::reset(&env, &user);
+        reservations::clear(&env, &user);
+        funding::release(&env, &user);
+        holders::remove(&env, &user);
+        if let Some(amount) = archive.amount {
+            history::record(&env, &user, amount, 0, &admin);
+        }
+
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("archived")),
+            (user, archive.archived_at),
+        );
+    }
+
+    /// Restores an archived budget along with the spending and reservations
+    /// recorded against it. Fails if the user already has an active budget.
+    pub fn restore_budget(env: Env, admin: Address, user: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        let archive_key = DataKey::ArchivedBudget(user.clone());
+        let archive: ArchivedBudget = env
+            .storage()
+            .persistent()
+            .get(&archive_key)
+            .expect("No archived budget");
+
+        let budget_key = DataKey::Budget(user.clone());
+        let categories_key = DataKey::BudgetCategories(user.clone());
+        if env.storage().persistent().has(&budget_key)
+            || env.storage().persistent().has(&categories_key)
+        {
+            panic!("Budget already active");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if let Some(amount) = archive.amount {
+            let record = BudgetRecord {
+                user: user.clone(),
+                amount,
+                last_updated: current_time,
+                period: periods::current_period(&env),
+                rolled_over: 0,
+            };
+            env.storage().persistent().set(&budget_key, &record);
+            history::record(&env, &user, 0, amount, &admin);
+        }
+        if let Some(categories) = archive.categories {
+            let user_categories = UserBudgetCategories {
+                user: user.clone(),
+                categories,
+                total_amount: archive.categories_total,
+                last_updated: current_time,
+            };
+            env.storage()
+                .persistent()
+                .set(&categories_key, &user_categories);
+        }
+        if archive.spent > 0 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::BudgetSpent(user.clone()), &archive.spent);
+        }
+        spending::restore(&env, &user, &archive.category_spent);
+        reservations::save_all(&env, &user, &archive.reservations);
+        env.storage().persistent().remove(&archive_key);
+        holders::add(&env, &user);
+
+        env.events()
+            .publish((symbol_short!("budget"), symbol_short!("restored")), user);
+    }
+
+    /// Retrieves the archived budget for a user, if one exists.
+    pub fn get_archived_budget(env: Env, user: Address) -> Option<ArchivedBudget> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArchivedBudget(user))
+    }
+
+    /// Sets the rate limit applied to expense recording.
+    pub fn set_expense_throttle(env: Env, admin: Address, config: ExpenseThrottleConfig) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        throttle::set_config(&env, &config);
+        env.events().publish(
+            (symbol_short!("throttle"), symbol_short!("config")),
+            (
+                config.max_expenses_per_window,
+                config.window_seconds,
+                config.freeze_after_violations,
+            ),
+        );
+    }
+
+    /// Returns the expense rate limit, if one is configured.
+    pub fn get_expense_throttle(env: Env) -> Option<ExpenseThrottleConfig> {
+        throttle::get_config(&env)
+    }
+
+    /// Consumes one expense slot for `user`, returning `false` if the entry
+    /// must be rejected. Rejections are recorded rather than panicking so that
+    /// repeat violations accumulate and can freeze the budget.
+    pub fn check_expense_throttle(env: Env, user: Address) -> bool {
+        user.require_auth();
+        throttle::check_expense(&env, &user)
+    }
+
+    /// Returns true if the user's budget was frozen by repeat throttle violations.
+    pub fn is_budget_frozen(env: Env, user: Address) -> bool {
+        throttle::is_frozen(&env, &user)
+    }
+
+    /// Lifts a throttle freeze and resets the user's violation history.
+    pub fn unfreeze_budget(env: Env, admin: Address, user: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        throttle::unfreeze(&env, &user);
+        env.events()
+            .publish((symbol_short!("budget"), symbol_short!("unfrozen")), user);
+    }
+
+    /// Grants the budget manager role. Managers may run batch allocations
+    /// but no other admin operation.
+    pub fn add_manager(env: Env, admin: Address, manager: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetManager(manager.clone()), &true);
+        env.events()
+            .publish((symbol_short!("manager"), symbol_short!("added")), manager);
+    }
+
+    /// Revokes the budget manager role.
+    pub fn remove_manager(env: Env, admin: Address, manager: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BudgetManager(manager.clone()));
+        env.events().publish(
+            (symbol_short!("manager"), symbol_short!("removed")),
+            manager,
+        );
+    }
+
+    /// Returns true if the address holds the budget manager role.
+    pub fn is_manager(env: Env, address: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BudgetManager(address))
+            .unwrap_or(false)
+    }
+
     /// Returns the admin address
     pub fn get_admin(env: Env) -> Address {
         env.storage()
@@ -221,4 +1746,22 @@ impl BudgetAllocationContract {
             .get(&DataKey::Admin)
             .expect("Not initialized")
     }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Not initialized");
+        if *admin != stored_admin {
+            panic!("Unauthorized");
+        }
+    }
+
+    /// Requires the admin or a budget manager.
+    fn require_allocator(env: &Env, caller: &Address) {
+        if !Self::is_manager(env.clone(), caller.clone()) {
+            Self::require_admin(env, caller);
+        }
+    }
 }
@@ -0,0 +1,42 @@
+//! Upper bounds on allocated budgets.
+//!
+//! A global cap applies to every user and a per-user limit applies on top of
+//! it: an allocation must fit under both.
+
+use crate::types::DataKey;
+use soroban_sdk::{Address, Env};
+
+pub fn get_cap(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::BudgetCap)
+}
+
+pub fn set_cap(env: &Env, cap: Option<i128>) {
+    match cap {
+        Some(amount) if amount < 0 => panic!("Invalid budget limit"),
+        Some(amount) => env.storage().instance().set(&DataKey::BudgetCap, &amount),
+        None => env.storage().instance().remove(&DataKey::BudgetCap),
+    }
+}
+
+pub fn get_user_limit(env: &Env, user: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserBudgetLimit(user.clone()))
+}
+
+pub fn set_user_limit(env: &Env, user: &Address, limit: Option<i128>) {
+    let key = DataKey::UserBudgetLimit(user.clone());
+    match limit {
+        Some(amount) if amount < 0 => panic!("Invalid budget limit"),
+        Some(amount) => env.storage().persistent().set(&key, &amount),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// The tightest limit that applies to `user`, if any.
+pub fn effective_limit(env: &Env, user: &Address) -> Option<i128> {
+    match (get_cap(env), get_user_limit(env, user)) {
+        (Some(cap), Some(limit)) => Some(cap.min(limit)),
+        (cap, limit) => cap.or(limit),
+    }
+}
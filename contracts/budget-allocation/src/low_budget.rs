@@ -0,0 +1,80 @@
+//! "Budget low" notifications through the reminders contract.
+//!
+//! When spending leaves a user's remaining budget under the configured
+//! threshold, one reminder is scheduled per user and budget period so that
+//! further spending in the same period does not notify again. A failing
+//! reminders contract never blocks the spend; the guard is only set once a
+//! reminder was actually scheduled.
+
+use crate::periods::current_period;
+use crate::types::{DataKey, LowBudgetAlert, ReminderBatchResult, ReminderRequest, RoutingHints};
+use soroban_sdk::{contractclient, symbol_short, vec, Address, Env, Vec};
+
+/// Subset of the batch payment reminders contract used for notifications.
+#[contractclient(name = "RemindersClient")]
+pub trait RemindersInterface {
+    fn dispatch_batch_reminders(
+        env: Env,
+        admin: Address,
+        requests: Vec<ReminderRequest>,
+    ) -> ReminderBatchResult;
+}
+
+pub fn get_config(env: &Env) -> Option<LowBudgetAlert> {
+    env.storage().instance().get(&DataKey::LowBudgetAlert)
+}
+
+pub fn set_config(env: &Env, config: &Option<LowBudgetAlert>) {
+    match config {
+        Some(config) if config.threshold <= 0 => panic!("Invalid low budget threshold"),
+        Some(config) => env
+            .storage()
+            .instance()
+            .set(&DataKey::LowBudgetAlert, config),
+        None => env.storage().instance().remove(&DataKey::LowBudgetAlert),
+    }
+}
+
+/// Period in which the user was last notified, if ever.
+pub fn last_notified(env: &Env, user: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LowBudgetNotified(user.clone()))
+}
+
+/// Schedules a "budget low" reminder if `remaining` is under the threshold and
+/// the user has not been notified in the current period.
+pub fn check(env: &Env, user: &Address, remaining: i128) {
+    let Some(config) = get_config(env) else {
+        return;
+    };
+    let period = current_period(env);
+    if remaining >= config.threshold || last_notified(env, user) == Some(period) {
+        return;
+    }
+
+    let request = ReminderRequest {
+        user: user.clone(),
+        reminder_id: period,
+        category: symbol_short!("budgetlow"),
+        due_date: env.ledger().sequence() as u64 + 1,
+        routing: RoutingHints {
+            channel: symbol_short!("default"),
+            locale: symbol_short!("default"),
+            priority: 2,
+        },
+    };
+    let scheduled = RemindersClient::new(env, &config.reminders)
+        .try_dispatch_batch_reminders(&env.current_contract_address(), &vec![env, request])
+        .is_ok();
+    if scheduled {
+        env.storage()
+            .persistent()
+            .set(&DataKey::LowBudgetNotified(user.clone()), &period);
+    }
+
+    env.events().publish(
+        (symbol_short!("budget"), symbol_short!("low")),
+        (user.clone(), remaining, config.threshold, scheduled),
+    );
+}
@@ -0,0 +1,104 @@
+//! Monthly budget periods and rollover of unspent funds.
+//!
+//! A user's active `BudgetRecord` belongs to one period. Closing it snapshots
+//! what was allocated and spent under `(user, period)` and opens the current
+//! period with the same base allocation plus whatever the user's rollover
+//! policy carries over. Open reservations stay open and count against the new
+//! period, so they are not part of the unspent amount.
+
+use crate::types::{BudgetRecord, DataKey, PeriodSnapshot, RolloverPolicy, BUDGET_PERIOD_SECONDS};
+use crate::{reservations, spending};
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn current_period(env: &Env) -> u64 {
+    env.ledger().timestamp() / BUDGET_PERIOD_SECONDS
+}
+
+pub fn get_policy(env: &Env, user: &Address) -> RolloverPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RolloverPolicy(user.clone()))
+        .unwrap_or(RolloverPolicy::Forfeit)
+}
+
+pub fn set_policy(env: &Env, user: &Address, policy: &RolloverPolicy) {
+    let key = DataKey::RolloverPolicy(user.clone());
+    match policy {
+        RolloverPolicy::Capped(cap) if *cap < 0 => panic!("Invalid rollover cap"),
+        RolloverPolicy::Forfeit => env.storage().persistent().remove(&key),
+        _ => env.storage().persistent().set(&key, policy),
+    }
+}
+
+pub fn get_snapshot(env: &Env, user: &Address, period: u64) -> Option<PeriodSnapshot> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PeriodSnapshot(user.clone(), period))
+}
+
+/// Periods closed for the user, oldest first.
+pub fn closed_periods(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ClosedPeriods(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Snapshots the user's open period and starts the current one.
+pub fn close(env: &Env, user: &Address, budget: &BudgetRecord) -> PeriodSnapshot {
+    let period = current_period(env);
+    if budget.period >= period {
+        panic!("Period still open");
+    }
+
+    let spent: i128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::BudgetSpent(user.clone()))
+        .unwrap_or(0);
+    let reserved = reservations::total_reserved(env, user);
+    let unspent = (budget.amount - spent - reserved).max(0);
+    let rolled_over = match get_policy(env, user) {
+        RolloverPolicy::Forfeit => 0,
+        RolloverPolicy::Full => unspent,
+        RolloverPolicy::Capped(cap) => unspent.min(cap),
+    };
+
+    let snapshot = PeriodSnapshot {
+        user: user.clone(),
+        period: budget.period,
+        budget: budget.amount,
+        rolled_in: budget.rolled_over,
+        spent,
+        reserved,
+        unspent,
+        rolled_over,
+        closed_at: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(
+        &DataKey::PeriodSnapshot(user.clone(), budget.period),
+        &snapshot,
+    );
+    let mut closed = closed_periods(env, user);
+    closed.push_back(budget.period);
+    env.storage()
+        .persistent()
+        .set(&DataKey::ClosedPeriods(user.clone()), &closed);
+
+    let next = BudgetRecord {
+        user: user.clone(),
+        amount: budget.amount - budget.rolled_over + rolled_over,
+        last_updated: env.ledger().timestamp(),
+        period,
+        rolled_over,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Budget(user.clone()), &next);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BudgetSpent(user.clone()));
+    spending::reset(env, user);
+
+    snapshot
+}
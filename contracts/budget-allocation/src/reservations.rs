@@ -0,0 +1,109 @@
+//! Commitments against a budget.
+//!
+//! A reservation earmarks part of a user's budget for an upcoming, known
+//! expense. Reserved funds count against the available budget until they are
+//! released or converted into an actual spend.
+
+use crate::types::{DataKey, Reservation, MAX_RESERVATIONS};
+use crate::{categories, spending};
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+pub fn get_all(env: &Env, user: &Address) -> Vec<Reservation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Reservations(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_all(env: &Env, user: &Address, reservations: &Vec<Reservation>) {
+    let key = DataKey::Reservations(user.clone());
+    if reservations.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, reservations);
+    }
+}
+
+pub fn clear(env: &Env, user: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Reservations(user.clone()));
+}
+
+/// Total amount the user currently has reserved.
+pub fn total_reserved(env: &Env, user: &Address) -> i128 {
+    get_all(env, user)
+        .iter()
+        .fold(0, |total, reservation| total + reservation.amount)
+}
+
+/// Total amount the user has reserved in `category`.
+pub fn reserved_in_category(env: &Env, user: &Address, category: &Symbol) -> i128 {
+    get_all(env, user)
+        .iter()
+        .filter(|reservation| categories::resolve(env, &reservation.category) == *category)
+        .fold(0, |total, reservation| total + reservation.amount)
+}
+
+/// Stores a new reservation after checking it fits what is left of the
+/// category allocation, if the user has category budgets. The caller checks
+/// the overall budget.
+pub fn reserve(
+    env: &Env,
+    user: &Address,
+    category: &Symbol,
+    amount: i128,
+    reference: &Symbol,
+) -> Reservation {
+    let category = categories::resolve(env, category);
+    let mut reservations = get_all(env, user);
+    if reservations.len() >= MAX_RESERVATIONS {
+        panic!("Too many reservations");
+    }
+
+    if let Some(user_categories) = categories::load_user_categories(env, user) {
+        let allocated = user_categories
+            .categories
+            .get(category.clone())
+            .expect("Category not found");
+        let committed = reserved_in_category(env, user, &category)
+            + spending::category_spent(env, user, &category);
+        if committed + amount > allocated {
+            panic!("Insufficient category budget");
+        }
+    }
+
+    let reservation_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastReservationId)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::LastReservationId, &reservation_id);
+
+    let reservation = Reservation {
+        reservation_id,
+        category,
+        amount,
+        reference: reference.clone(),
+        created_at: env.ledger().timestamp(),
+    };
+    reservations.push_back(reservation.clone());
+    save_all(env, user, &reservations);
+    reservation
+}
+
+/// Removes and returns one of the user's reservations.
+pub fn take(env: &Env, user: &Address, reservation_id: u64) -> Reservation {
+    let mut reservations = get_all(env, user);
+    let index = reservations
+        .iter()
+        .position(|reservation| reservation.reservation_id == reservation_id)
+        .expect("Reservation not found");
+    let reservation = reservations.get(index as u32).unwrap();
+    reservations.remove(index as u32);
+    save_all(env, user, &reservations);
+    reservation
+}
@@ -0,0 +1,73 @@
+//! Spending recorded against category budgets.
+//!
+//! Keeps a per-user map of amounts spent in each category (migrated through
+//! category renames like the allocations themselves) and a bounded history of
+//! individual spend entries, newest last.
+
+use crate::categories;
+use crate::types::{DataKey, SpendRecord, MAX_SPEND_HISTORY};
+use soroban_sdk::{Address, Env, Map, Symbol, Vec};
+
+pub fn category_spent_map(env: &Env, user: &Address) -> Map<Symbol, i128> {
+    let spent: Map<Symbol, i128> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CategorySpent(user.clone()))
+        .unwrap_or(Map::new(env));
+    categories::remap_categories(env, &spent).0
+}
+
+pub fn category_spent(env: &Env, user: &Address, category: &Symbol) -> i128 {
+    category_spent_map(env, user)
+        .get(category.clone())
+        .unwrap_or(0)
+}
+
+/// Adds `amount` to the category total and appends a history entry.
+pub fn record(env: &Env, user: &Address, category: &Symbol, amount: i128) {
+    let mut spent = category_spent_map(env, user);
+    let total = spent.get(category.clone()).unwrap_or(0) + amount;
+    spent.set(category.clone(), total);
+    env.storage()
+        .persistent()
+        .set(&DataKey::CategorySpent(user.clone()), &spent);
+
+    let key = DataKey::SpendHistory(user.clone());
+    let mut history: Vec<SpendRecord> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    if history.len() >= MAX_SPEND_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(SpendRecord {
+        category: category.clone(),
+        amount,
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Clears the category totals, e.g. when a new allocation replaces the budget.
+pub fn reset(env: &Env, user: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::CategorySpent(user.clone()));
+}
+
+/// Replaces the category totals, e.g. when an archived budget is restored.
+pub fn restore(env: &Env, user: &Address, spent: &Map<Symbol, i128>) {
+    if !spent.is_empty() {
+        env.storage()
+            .persistent()
+            .set(&DataKey::CategorySpent(user.clone()), spent);
+    }
+}
+
+pub fn history(env: &Env, user: &Address) -> Vec<SpendRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SpendHistory(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
@@ -0,0 +1,57 @@
+//! Named budget templates.
+//!
+//! A template stores a category split once so the same structure can be
+//! allocated to many users each period without rebuilding the requests
+//! client-side.
+
+use crate::types::{BudgetCategory, BudgetTemplate, DataKey};
+use soroban_sdk::{Env, Symbol, Vec};
+
+pub fn get(env: &Env, name: &Symbol) -> Option<BudgetTemplate> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BudgetTemplate(name.clone()))
+}
+
+/// Validates and stores a template; the total is the sum of its categories.
+pub fn create(env: &Env, name: &Symbol, categories: Vec<BudgetCategory>) -> BudgetTemplate {
+    if categories.is_empty() {
+        panic!("Template has no categories");
+    }
+
+    let mut total_amount: i128 = 0;
+    for (i, category) in categories.iter().enumerate() {
+        if category.amount < 0 {
+            panic!("Negative category amount not allowed");
+        }
+        if categories
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.name == category.name)
+        {
+            panic!("Duplicate template category");
+        }
+        total_amount = total_amount
+            .checked_add(category.amount)
+            .expect("Overflow in category total calculation");
+    }
+
+    let template = BudgetTemplate {
+        name: name.clone(),
+        categories,
+        total_amount,
+        updated_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::BudgetTemplate(name.clone()), &template);
+    template
+}
+
+pub fn delete(env: &Env, name: &Symbol) {
+    let key = DataKey::BudgetTemplate(name.clone());
+    if !env.storage().persistent().has(&key) {
+        panic!("Template not found");
+    }
+    env.storage().persistent().remove(&key);
+}
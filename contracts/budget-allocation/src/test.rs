@@ -1,12 +1,19 @@
 #![cfg(test)]
 
 use super::*;
-use crate::types::{BudgetCategory, BudgetRequest, CategoryBudgetRequest, UserBudgetCategories};
-use soroban_sdk::{testutils::Address as _, vec, Address, Env, Symbol};
+use crate::types::{
+    BudgetCategory, BudgetRequest, CategoryBudgetRequest, DefaultBudget, ExpenseThrottleConfig,
+    RolloverPolicy, UserBudgetCategories,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Symbol,
+};
 
 fn create_contract() -> (Env, Address, Address) {
     let env = Env::default();
-    let contract_id = env.register_contract(None, BudgetAllocationContract);
+    let contract_id = env.register(BudgetAllocationContract, ());
     let client = BudgetAllocationContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -17,52 +24,6 @@ fn create_contract() -> (Env, Address, Address) {
     (env, contract_id, admin)
 }
 
-// Simple client wrapper for testing
-pub struct BudgetAllocationContractClient<'a> {
-    env: &'a Env,
-    contract_id: &'a Address,
-}
-
-impl<'a> BudgetAllocationContractClient<'a> {
-    pub fn new(env: &'a Env, contract_id: &'a Address) -> Self {
-        Self { env, contract_id }
-    }
-
-    pub fn initialize(&self, admin: &Address) {
-        BudgetAllocationContract::initialize(self.env.clone(), admin.clone());
-    }
-
-    pub fn batch_allocate_budget(
-        &self,
-        admin: &Address,
-        requests: &Vec<BudgetRequest>,
-    ) -> crate::types::BatchBudgetResult {
-        BudgetAllocationContract::batch_allocate_budget(
-            self.env.clone(),
-            admin.clone(),
-            requests.clone(),
-        )
-    }
-
-    pub fn get_budget(&self, user: &Address) -> Option<crate::types::BudgetRecord> {
-        BudgetAllocationContract::get_budget(self.env.clone(), user.clone())
-    }
-
-    pub fn allocate_budget_by_category(
-        &self,
-        admin: &Address,
-        request: &CategoryBudgetRequest,
-    ) -> bool {
-        self.env.as_contract(self.contract_id, || {
-            BudgetAllocationContract::allocate_budget_by_category(
-                self.env.clone(),
-                admin.clone(),
-                request.clone(),
-            )
-        })
-    }
-}
-
 #[test]
 fn test_batch_allocate_budget() {
     let (env, contract_id, admin) = create_contract();
@@ -125,6 +86,548 @@ fn test_batch_allocate_budget() {
     assert_eq!(budget1_updated.amount, 1500);
 }
 
+#[test]
+fn test_batch_allocate_budget_reports_per_request_results() {
+    use crate::types::BudgetResult;
+    use soroban_sdk::{testutils::Events as _, TryFromVal};
+
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let requests = vec![
+        &env,
+        BudgetRequest {
+            user: user1.clone(),
+            amount: 1000,
+        },
+        BudgetRequest {
+            user: user2.clone(),
+            amount: -1,
+        },
+    ];
+
+    let result = client.batch_allocate_budget(&admin, &requests);
+    assert_eq!(result.batch_id, 1);
+    assert_eq!(result.results.len(), 2);
+    match result.results.get(0).unwrap() {
+        BudgetResult::Success(record) => {
+            assert_eq!(record.user, user1);
+            assert_eq!(record.amount, 1000);
+        }
+        BudgetResult::Failure(..) => panic!("expected success"),
+    }
+    assert_eq!(
+        result.results.get(1).unwrap(),
+        BudgetResult::Failure(user2.clone(), ErrorCode::INVALID_AMOUNT)
+    );
+
+    // The batch is bracketed by started/completed events
+    let mut started = 0;
+    let mut completed = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        if Symbol::try_from_val(&env, &topics.get(0).unwrap()) != Ok(symbol_short!("batch")) {
+            continue;
+        }
+        let kind = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
+        if kind == symbol_short!("started") {
+            started += 1;
+        } else if kind == symbol_short!("completed") {
+            completed += 1;
+        }
+    }
+    assert_eq!((started, completed), (1, 1));
+
+    // Batch ids keep counting across batch kinds
+    let result = client.batch_allocate_by_category(
+        &admin,
+        &vec![
+            &env,
+            CategoryBudgetRequest {
+                user: user2.clone(),
+                categories: vec![
+                    &env,
+                    BudgetCategory {
+                        name: symbol_short!("food"),
+                        amount: 400,
+                    },
+                ],
+                total_amount: 400,
+            },
+        ],
+    );
+    assert_eq!(result.batch_id, 2);
+    assert_eq!(client.get_last_batch_id(), 2);
+    assert_eq!(
+        result.results,
+        vec![
+            &env,
+            BudgetResult::Success(client.get_budget(&user2).unwrap())
+        ]
+    );
+}
+
+#[test]
+fn test_batch_allocate_budget_enforces_limits() {
+    use crate::types::BudgetResult;
+
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+    client.set_budget_cap(&admin, &Some(5000));
+    client.set_user_budget_limit(&admin, &user2, &Some(1000));
+    assert_eq!(client.get_budget_cap(), Some(5000));
+    assert_eq!(client.get_user_budget_limit(&user2), Some(1000));
+
+    let result = client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user1.clone(),
+                amount: 5000,
+            },
+            BudgetRequest {
+                user: user2.clone(),
+                amount: 2000,
+            },
+            BudgetRequest {
+                user: user3.clone(),
+                amount: 6000,
+            },
+        ],
+    );
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 2);
+    assert_eq!(
+        result.results.get(1).unwrap(),
+        BudgetResult::Failure(user2.clone(), ErrorCode::LIMIT_EXCEEDED)
+    );
+    assert_eq!(
+        result.results.get(2).unwrap(),
+        BudgetResult::Failure(user3.clone(), ErrorCode::LIMIT_EXCEEDED)
+    );
+    assert!(client.get_budget(&user2).is_none());
+
+    // Lifting the cap leaves the per-user limit in force
+    client.set_budget_cap(&admin, &None);
+    let result = client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user2.clone(),
+                amount: 2000,
+            },
+            BudgetRequest {
+                user: user3.clone(),
+                amount: 6000,
+            },
+        ],
+    );
+    assert_eq!(result.successful, 1);
+    assert_eq!(client.get_budget(&user3).unwrap().amount, 6000);
+}
+
+#[test]
+fn test_funded_budget_escrow_and_withdraw() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    StellarAssetClient::new(&env, &token).mint(&treasury, &10_000);
+    token_client.approve(&treasury, &contract_id, &10_000, &1_000);
+    client.set_treasury(&admin, &treasury);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let result = client.batch_allocate_funded_budget(
+        &admin,
+        &token,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user1.clone(),
+                amount: 3000,
+            },
+            BudgetRequest {
+                user: user2.clone(),
+                amount: -5,
+            },
+        ],
+    );
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(token_client.balance(&treasury), 7000);
+    assert_eq!(token_client.balance(&contract_id), 3000);
+    assert_eq!(client.get_funded_balance(&user1, &token), 3000);
+    assert_eq!(client.get_funded_balance(&user2, &token), 0);
+
+    assert_eq!(client.withdraw_budget(&user1, &token, &1200), 1800);
+    assert_eq!(token_client.balance(&user1), 1200);
+    assert!(client.try_withdraw_budget(&user1, &token, &2000).is_err());
+}
+
+#[test]
+fn test_funded_escrow_follows_budget() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    StellarAssetClient::new(&env, &token).mint(&treasury, &10_000);
+    token_client.approve(&treasury, &contract_id, &10_000, &1_000);
+    client.set_treasury(&admin, &treasury);
+
+    let user1 = Address::generate(&env);
+    for amount in [3000, 2000] {
+        client.batch_allocate_funded_budget(
+            &admin,
+            &token,
+            &vec![
+                &env,
+                BudgetRequest {
+                    user: user1.clone(),
+                    amount,
+                },
+            ],
+        );
+    }
+    // The second allocation replaces the first instead of stacking on it
+    assert_eq!(client.get_funded_balance(&user1, &token), 2000);
+    assert_eq!(token_client.balance(&treasury), 8000);
+
+    // Withdrawals count as spending
+    assert_eq!(client.withdraw_budget(&user1, &token, &1500), 500);
+    assert_eq!(client.get_spent(&user1), 1500);
+
+    client.adjust_budget(&admin, &user1, &-300);
+    assert_eq!(client.get_funded_balance(&user1, &token), 200);
+    assert_eq!(token_client.balance(&treasury), 8300);
+
+    // Escrow is left, but spending has used up the rest of the budget
+    client.record_spend(&user1, &symbol_short!("food"), &100);
+    assert!(client.try_withdraw_budget(&user1, &token, &150).is_err());
+    assert_eq!(client.withdraw_budget(&user1, &token, &100), 100);
+
+    client.remove_budget(&admin, &user1);
+    assert_eq!(client.get_funded_balance(&user1, &token), 0);
+    assert_eq!(token_client.balance(&treasury), 8400);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_adjust_budget_keeps_spending() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user1 = Address::generate(&env);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user1.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+    client.record_spend(&user1, &symbol_short!("food"), &300);
+
+    assert_eq!(client.adjust_budget(&admin, &user1, &500).amount, 1500);
+    assert_eq!(client.adjust_budget(&admin, &user1, &-200).amount, 1300);
+    assert_eq!(client.get_budget_statement(&user1).spent, 300);
+
+    assert!(client.try_adjust_budget(&admin, &user1, &-1301).is_err());
+    assert!(client
+        .try_adjust_budget(&admin, &Address::generate(&env), &100)
+        .is_err());
+    assert_eq!(client.get_budget(&user1).unwrap().amount, 1300);
+}
+
+#[test]
+fn test_batch_adjust_budgets() {
+    use crate::types::{BudgetAdjustment, BudgetResult};
+
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user1.clone(),
+                amount: 1000,
+            },
+            BudgetRequest {
+                user: user2.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+
+    let result = client.batch_adjust_budgets(
+        &admin,
+        &vec![
+            &env,
+            BudgetAdjustment {
+                user: user1.clone(),
+                delta: 250,
+                reason: symbol_short!("bonus"),
+            },
+            BudgetAdjustment {
+                user: user2.clone(),
+                delta: -2000,
+                reason: symbol_short!("clawback"),
+            },
+            BudgetAdjustment {
+                user: user3.clone(),
+                delta: 100,
+                reason: symbol_short!("bonus"),
+            },
+        ],
+    );
+
+    // The clawback exceeds user2's budget and empties it
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.total_amount, -750);
+    assert_eq!(
+        result.results.get(2).unwrap(),
+        BudgetResult::Failure(user3.clone(), ErrorCode::BUDGET_NOT_FOUND)
+    );
+    assert_eq!(client.get_budget(&user1).unwrap().amount, 1250);
+    assert_eq!(client.get_budget(&user2).unwrap().amount, 0);
+
+    // Nothing left to claw back
+    let result = client.batch_adjust_budgets(
+        &admin,
+        &vec![
+            &env,
+            BudgetAdjustment {
+                user: user2.clone(),
+                delta: -1,
+                reason: symbol_short!("clawback"),
+            },
+        ],
+    );
+    assert_eq!(
+        result.results.get(0).unwrap(),
+        BudgetResult::Failure(user2.clone(), ErrorCode::INSUFFICIENT_BUDGET)
+    );
+}
+
+mod audit_mock {
+    use crate::audit::AuditInterface;
+    use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, Symbol, Vec};
+
+    /// Audit contract double that keeps the operations it logs.
+    #[contract]
+    pub struct MockAudit;
+
+    #[contractimpl]
+    impl AuditInterface for MockAudit {
+        fn log_audit(
+            env: Env,
+            _actor: Address,
+            operation: Symbol,
+            _status: Symbol,
+            _metadata: Option<Bytes>,
+        ) {
+            let key = Symbol::new(&env, "logged");
+            let mut logged: Vec<Symbol> =
+                env.storage().instance().get(&key).unwrap_or(Vec::new(&env));
+            logged.push_back(operation);
+            env.storage().instance().set(&key, &logged);
+        }
+    }
+}
+
+#[test]
+fn test_batch_adjustments_logged_with_reason() {
+    use crate::types::BudgetAdjustment;
+    use soroban_sdk::{testutils::Events as _, TryFromVal};
+
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let audit_contract = env.register(audit_mock::MockAudit, ());
+    client.set_audit_contract(&admin, &Some(audit_contract.clone()));
+    assert_eq!(client.get_audit_contract(), Some(audit_contract.clone()));
+
+    let user = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+
+    client.batch_adjust_budgets(
+        &admin,
+        &vec![
+            &env,
+            BudgetAdjustment {
+                user: user.clone(),
+                delta: -400,
+                reason: symbol_short!("overpaid"),
+            },
+            BudgetAdjustment {
+                user: stranger,
+                delta: 100,
+                reason: symbol_short!("bonus"),
+            },
+        ],
+    );
+
+    let mut adjusted: Vec<(Address, i128, i128, Symbol)> = Vec::new(&env);
+    for (_, topics, data) in env.events().all().iter() {
+        let kind = Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
+        if kind == symbol_short!("adjusted") {
+            adjusted.push_back(TryFromVal::try_from_val(&env, &data).unwrap());
+        }
+    }
+    assert_eq!(
+        adjusted,
+        vec![
+            &env,
+            (user.clone(), -400i128, 600i128, symbol_short!("overpaid"))
+        ]
+    );
+
+    // Only the applied adjustment reaches the audit contract
+    let logged: Vec<Symbol> = env.as_contract(&audit_contract, || {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "logged"))
+            .unwrap()
+    });
+    assert_eq!(logged, vec![&env, Symbol::new(&env, "budget_adjustment")]);
+}
+
+#[test]
+fn test_transfer_between_categories() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    client.allocate_budget_by_category(
+        &admin,
+        &CategoryBudgetRequest {
+            user: user.clone(),
+            categories: vec![
+                &env,
+                BudgetCategory {
+                    name: symbol_short!("food"),
+                    amount: 600,
+                },
+                BudgetCategory {
+                    name: symbol_short!("travel"),
+                    amount: 400,
+                },
+            ],
+            total_amount: 1000,
+        },
+    );
+    client.record_spend(&user, &symbol_short!("food"), &350);
+
+    let updated = client.transfer_between_categories(
+        &user,
+        &symbol_short!("food"),
+        &symbol_short!("travel"),
+        &200,
+    );
+    assert_eq!(updated.categories.get(symbol_short!("food")), Some(400));
+    assert_eq!(updated.categories.get(symbol_short!("travel")), Some(600));
+    assert_eq!(updated.total_amount, 1000);
+
+    // Only the unspent 50 is left to move out of food
+    assert!(client
+        .try_transfer_between_categories(
+            &user,
+            &symbol_short!("food"),
+            &symbol_short!("travel"),
+            &51
+        )
+        .is_err());
+    assert!(client
+        .try_transfer_between_categories(&user, &symbol_short!("food"), &symbol_short!("rent"), &10)
+        .is_err());
+}
+
+#[test]
+fn test_batch_records_are_persisted() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    env.ledger().set_timestamp(5_000);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user1.clone(),
+                amount: 1000,
+            },
+            BudgetRequest {
+                user: user2.clone(),
+                amount: -1,
+            },
+        ],
+    );
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user2.clone(),
+                amount: 300,
+            },
+        ],
+    );
+
+    let record = client.get_batch_record(&1).unwrap();
+    assert_eq!(record.batch_id, 1);
+    assert_eq!(record.request_count, 2);
+    assert_eq!(record.successful, 1);
+    assert_eq!(record.failed, 1);
+    assert_eq!(record.total_amount, 1000);
+    assert_eq!(record.completed_at, 5_000);
+    assert!(client.get_batch_record(&3).is_none());
+
+    let records = client.get_batch_records(&0, &10);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.get(0).unwrap().batch_id, 2);
+    assert_eq!(records.get(1).unwrap(), record);
+    assert_eq!(client.get_batch_records(&2, &10).len(), 1);
+}
+
 #[test]
 #[should_panic(expected = "Unauthorized")]
 fn test_unauthorized_access() {
@@ -144,6 +647,106 @@ fn test_unauthorized_access() {
     client.batch_allocate_budget(&not_admin, &requests);
 }
 
+#[test]
+fn test_manager_can_allocate_until_removed() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let manager = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let requests = vec![
+        &env,
+        BudgetRequest {
+            user: user1.clone(),
+            amount: 1000,
+        },
+    ];
+
+    assert!(!client.is_manager(&manager));
+    client.add_manager(&admin, &manager);
+    assert!(client.is_manager(&manager));
+
+    let result = client.batch_allocate_budget(&manager, &requests);
+    assert_eq!(result.successful, 1);
+    assert_eq!(client.get_budget(&user1).unwrap().amount, 1000);
+
+    client.remove_manager(&admin, &manager);
+    assert!(!client.is_manager(&manager));
+    assert!(client
+        .try_batch_allocate_budget(&manager, &requests)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_manager_cannot_add_managers() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let manager = Address::generate(&env);
+    client.add_manager(&admin, &manager);
+
+    client.add_manager(&manager, &Address::generate(&env));
+}
+
+#[test]
+fn test_batch_allocate_budget_rejects_duplicate_users() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let requests = vec![
+        &env,
+        BudgetRequest {
+            user: user1.clone(),
+            amount: 1000,
+        },
+        BudgetRequest {
+            user: user2.clone(),
+            amount: 2000,
+        },
+        BudgetRequest {
+            user: user1.clone(),
+            amount: 5000,
+        }, // Duplicate
+    ];
+
+    let result = client.batch_allocate_budget(&admin, &requests);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.total_amount, 3000);
+    // The earlier entry is kept
+    assert_eq!(client.get_budget(&user1).unwrap().amount, 1000);
+}
+
+#[test]
+#[should_panic(expected = "Empty batch")]
+fn test_batch_allocate_budget_empty_batch() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    client.batch_allocate_budget(&admin, &vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "Batch too large")]
+fn test_batch_allocate_budget_too_large() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let mut requests = vec![&env];
+    for _ in 0..=crate::types::MAX_BATCH_SIZE {
+        requests.push_back(BudgetRequest {
+            user: Address::generate(&env),
+            amount: 100,
+        });
+    }
+
+    client.batch_allocate_budget(&admin, &requests);
+}
+
 #[test]
 fn test_category_budget_allocation_simple() {
     let (env, contract_id, admin) = create_contract();
@@ -183,3 +786,1272 @@ fn test_category_budget_allocation_simple() {
     assert!(budget_record.is_some());
     assert_eq!(budget_record.unwrap().amount, 850);
 }
+
+#[test]
+fn test_expense_throttle_rejects_excess_entries() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    client.set_expense_throttle(
+        &admin,
+        &ExpenseThrottleConfig {
+            max_expenses_per_window: 2,
+            window_seconds: 60,
+            freeze_after_violations: 0,
+        },
+    );
+
+    assert!(client.check_expense_throttle(&user));
+    assert!(client.check_expense_throttle(&user));
+    assert!(!client.check_expense_throttle(&user));
+
+    // A new window restores the allowance
+    env.ledger().set_timestamp(env.ledger().timestamp() + 60);
+    assert!(client.check_expense_throttle(&user));
+    assert!(!client.is_budget_frozen(&user));
+}
+
+#[test]
+fn test_expense_throttle_freezes_after_repeat_violations() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    client.set_expense_throttle(
+        &admin,
+        &ExpenseThrottleConfig {
+            max_expenses_per_window: 1,
+            window_seconds: 60,
+            freeze_after_violations: 2,
+        },
+    );
+
+    assert!(client.check_expense_throttle(&user));
+    assert!(!client.check_expense_throttle(&user));
+    assert!(!client.is_budget_frozen(&user));
+    assert!(!client.check_expense_throttle(&user));
+    assert!(client.is_budget_frozen(&user));
+
+    // Frozen budgets stay blocked across windows until the admin unfreezes
+    env.ledger().set_timestamp(env.ledger().timestamp() + 120);
+    assert!(!client.check_expense_throttle(&user));
+
+    client.unfreeze_budget(&admin, &user);
+    assert!(!client.is_budget_frozen(&user));
+    assert!(client.check_expense_throttle(&user));
+}
+
+#[test]
+fn test_expense_throttle_disabled_by_default() {
+    let (env, contract_id, _admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    assert!(client.get_expense_throttle().is_none());
+    for _ in 0..10 {
+        assert!(client.check_expense_throttle(&user));
+    }
+}
+
+#[test]
+fn test_record_spend_refused_when_throttled_or_frozen() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+    client.set_expense_throttle(
+        &admin,
+        &ExpenseThrottleConfig {
+            max_expenses_per_window: 1,
+            window_seconds: 60,
+            freeze_after_violations: 1,
+        },
+    );
+    let general = symbol_short!("general");
+
+    assert_eq!(
+        client.record_spend(&user, &general, &100),
+        SpendOutcome::Recorded(900)
+    );
+    assert_eq!(
+        client.record_spend(&user, &general, &100),
+        SpendOutcome::Throttled
+    );
+    assert_eq!(client.get_spent(&user), 100);
+
+    // The refused spend's violation persists and freezes the budget, which
+    // blocks spends in later windows too
+    assert!(client.is_budget_frozen(&user));
+    env.ledger().set_timestamp(env.ledger().timestamp() + 120);
+    assert_eq!(
+        client.record_spend(&user, &general, &100),
+        SpendOutcome::Frozen
+    );
+    assert_eq!(client.get_spent(&user), 100);
+
+    client.unfreeze_budget(&admin, &user);
+    assert_eq!(
+        client.record_spend(&user, &general, &100),
+        SpendOutcome::Recorded(800)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_set_expense_throttle_unauthorized() {
+    let (env, contract_id, _admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let not_admin = Address::generate(&env);
+    client.set_expense_throttle(
+        &not_admin,
+        &ExpenseThrottleConfig {
+            max_expenses_per_window: 1,
+            window_seconds: 60,
+            freeze_after_violations: 0,
+        },
+    );
+}
+
+fn allocate_categories(
+    client: &BudgetAllocationContractClient,
+    admin: &Address,
+    user: &Address,
+    categories: Vec<BudgetCategory>,
+) {
+    let mut total_amount = 0;
+    for category in categories.iter() {
+        total_amount += category.amount;
+    }
+    let request = CategoryBudgetRequest {
+        user: user.clone(),
+        categories,
+        total_amount,
+    };
+    assert!(client.allocate_budget_by_category(admin, &request));
+}
+
+#[test]
+fn test_rename_category_migrates_lazily() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    allocate_categories(
+        &client,
+        &admin,
+        &user,
+        vec![
+            &env,
+            BudgetCategory {
+                name: symbol_short!("food"),
+                amount: 500,
+            },
+        ],
+    );
+
+    client.rename_category(&admin, &symbol_short!("food"), &symbol_short!("groceries"));
+    assert_eq!(
+        client.get_category_remap(&symbol_short!("food")),
+        Some(symbol_short!("groceries"))
+    );
+
+    // Both the old and new names resolve to the migrated allocation
+    assert_eq!(
+        client.get_category_budget(&user, &symbol_short!("groceries")),
+        Some(500)
+    );
+    assert_eq!(
+        client.get_category_budget(&user, &symbol_short!("food")),
+        Some(500)
+    );
+
+    let categories = client.get_budget_categories(&user).unwrap();
+    assert!(!categories.categories.contains_key(symbol_short!("food")));
+    assert_eq!(categories.total_amount, 500);
+}
+
+#[test]
+fn test_merge_categories_sums_amounts() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    allocate_categories(
+        &client,
+        &admin,
+        &user,
+        vec![
+            &env,
+            BudgetCategory {
+                name: symbol_short!("coffee"),
+                amount: 50,
+            },
+            BudgetCategory {
+                name: symbol_short!("dining"),
+                amount: 200,
+            },
+        ],
+    );
+
+    client.merge_categories(&admin, &symbol_short!("coffee"), &symbol_short!("dining"));
+
+    let categories = client.get_budget_categories(&user).unwrap();
+    assert_eq!(categories.categories.len(), 1);
+    assert_eq!(
+        categories.categories.get(symbol_short!("dining")),
+        Some(250)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Category remap would create a cycle")]
+fn test_category_remap_cycle_rejected() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    client.rename_category(&admin, &symbol_short!("food"), &symbol_short!("groceries"));
+    client.rename_category(&admin, &symbol_short!("groceries"), &symbol_short!("food"));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_rename_category_unauthorized() {
+    let (env, contract_id, _admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let not_admin = Address::generate(&env);
+    client.rename_category(
+        &not_admin,
+        &symbol_short!("food"),
+        &symbol_short!("groceries"),
+    );
+}
+
+#[test]
+fn test_remove_and_restore_budget() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    allocate_categories(
+        &client,
+        &admin,
+        &user,
+        vec![
+            &env,
+            BudgetCategory {
+                name: symbol_short!("food"),
+                amount: 400,
+            },
+        ],
+    );
+
+    client.record_spend(&user, &symbol_short!("food"), &150);
+    client.reserve_funds(&user, &symbol_short!("food"), &50, &symbol_short!("inv1"));
+
+    client.remove_budget(&admin, &user);
+    assert!(client.get_budget(&user).is_none());
+    assert!(client.get_budget_categories(&user).is_none());
+    assert_eq!(client.get_spent(&user), 0);
+    assert!(client.get_reservations(&user).is_empty());
+
+    let archive = client.get_archived_budget(&user).unwrap();
+    assert_eq!(archive.amount, Some(400));
+    assert_eq!(archive.categories_total, 400);
+    assert_eq!(archive.spent, 150);
+    assert_eq!(archive.reservations.len(), 1);
+
+    // Spending and reservations come back with the budget
+    client.restore_budget(&admin, &user);
+    assert_eq!(client.get_budget(&user).unwrap().amount, 400);
+    assert_eq!(
+        client.get_category_budget(&user, &symbol_short!("food")),
+        Some(400)
+    );
+    assert_eq!(client.get_spent(&user), 150);
+    assert_eq!(client.get_reservations(&user), archive.reservations);
+    assert_eq!(client.get_available_budget(&user), 200);
+    assert!(client.get_archived_budget(&user).is_none());
+
+    // The restored commitments still count against the budget
+    assert!(client
+        .try_record_spend(&user, &symbol_short!("food"), &201)
+        .is_err());
+}
+
+#[test]
+fn test_apply_budget_template() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let name = symbol_short!("student");
+    let template = client.create_template(
+        &admin,
+        &name,
+        &vec![
+            &env,
+            BudgetCategory {
+                name: symbol_short!("food"),
+                amount: 250,
+            },
+            BudgetCategory {
+                name: symbol_short!("books"),
+                amount: 150,
+            },
+        ],
+    );
+    assert_eq!(template.total_amount, 400);
+    assert_eq!(client.get_template(&name), Some(template));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let result = client.apply_template(
+        &admin,
+        &name,
+        &vec![&env, alice.clone(), bob.clone(), alice.clone()],
+    );
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.total_amount, 800);
+    assert_eq!(
+        result.results.get(2).unwrap(),
+        BudgetResult::Failure(alice.clone(), ErrorCode::DUPLICATE_USER)
+    );
+    assert_eq!(client.get_budget(&bob).unwrap().amount, 400);
+    assert_eq!(
+        client.get_category_budget(&alice, &symbol_short!("books")),
+        Some(150)
+    );
+
+    client.delete_template(&admin, &name);
+    assert!(client
+        .try_apply_template(&admin, &name, &vec![&env, bob])
+        .is_err());
+}
+
+#[test]
+fn test_ensure_budget_applies_default_profile() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    assert!(client.try_ensure_budget(&user).is_err());
+
+    let profile = DefaultBudget {
+        categories: vec![
+            &env,
+            BudgetCategory {
+                name: symbol_short!("food"),
+                amount: 300,
+            },
+            BudgetCategory {
+                name: symbol_short!("transport"),
+                amount: 200,
+            },
+        ],
+        total_amount: 500,
+    };
+    client.set_default_budget(&admin, &Some(profile.clone()));
+    assert_eq!(client.get_default_budget(), Some(profile));
+
+    let record = client.ensure_budget(&user);
+    assert_eq!(record.amount, 500);
+    assert_eq!(
+        client.get_category_budget(&user, &symbol_short!("food")),
+        Some(300)
+    );
+    assert_eq!(client.get_total_budget_holders(), 1);
+
+    // An existing budget is never replaced
+    client.record_spend(&user, &symbol_short!("food"), &100);
+    client.set_default_budget(&admin, &None);
+    assert_eq!(client.ensure_budget(&user).amount, 500);
+    assert_eq!(client.get_spent(&user), 100);
+}
+
+#[test]
+#[should_panic(expected = "Invalid default budget")]
+fn test_default_budget_must_match_total() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let profile = DefaultBudget {
+        categories: vec![
+            &env,
+            BudgetCategory {
+                name: symbol_short!("food"),
+                amount: 300,
+            },
+        ],
+        total_amount: 500,
+    };
+    client.set_default_budget(&admin, &Some(profile));
+}
+
+#[test]
+fn test_budget_history_tracks_changes() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let manager = Address::generate(&env);
+    client.add_manager(&admin, &manager);
+    let user = Address::generate(&env);
+    let requests = vec![
+        &env,
+        BudgetRequest {
+            user: user.clone(),
+            amount: 1000,
+        },
+    ];
+    client.batch_allocate_budget(&manager, &requests);
+    client.adjust_budget(&admin, &user, &-300);
+    client.remove_budget(&admin, &user);
+    client.restore_budget(&admin, &user);
+
+    assert_eq!(client.get_budget_history_count(&user), 4);
+    let history = client.get_budget_history(&user, &0, &10);
+    let amounts: soroban_sdk::Vec<(i128, i128)> = history
+        .iter()
+        .map(|change| (change.old_amount, change.new_amount))
+        .fold(soroban_sdk::Vec::new(&env), |mut amounts, pair| {
+            amounts.push_back(pair);
+            amounts
+        });
+    assert_eq!(
+        amounts,
+        vec![&env, (0, 1000), (1000, 700), (700, 0), (0, 700)]
+    );
+    assert_eq!(history.get(0).unwrap().changed_by, manager);
+    assert_eq!(history.get(1).unwrap().changed_by, admin);
+
+    let page = client.get_budget_history(&user, &3, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().new_amount, 700);
+}
+
+#[test]
+fn test_budget_holders_index() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let users = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let mut requests = Vec::new(&env);
+    for user in users.iter() {
+        requests.push_back(BudgetRequest {
+            user: user.clone(),
+            amount: 1000,
+        });
+    }
+    client.batch_allocate_budget(&admin, &requests);
+    // Reallocating does not list a user twice
+    client.batch_allocate_budget(&admin, &requests);
+    assert_eq!(client.get_total_budget_holders(), 3);
+    assert_eq!(
+        client.get_budget_holders(&0, &2),
+        vec![&env, users[0].clone(), users[1].clone()]
+    );
+    assert_eq!(
+        client.get_budget_holders(&2, &10),
+        vec![&env, users[2].clone()]
+    );
+    assert!(client.get_budget_holders(&5, &10).is_empty());
+
+    // Removing a holder moves the last one into its slot
+    client.remove_budget(&admin, &users[0]);
+    assert_eq!(client.get_total_budget_holders(), 2);
+    assert_eq!(
+        client.get_budget_holders(&0, &10),
+        vec![&env, users[2].clone(), users[1].clone()]
+    );
+
+    client.restore_budget(&admin, &users[0]);
+    assert_eq!(client.get_total_budget_holders(), 3);
+    assert_eq!(
+        client.get_budget_holders(&2, &1),
+        vec![&env, users[0].clone()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Budget not found")]
+fn test_remove_missing_budget() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    client.remove_budget(&admin, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Budget already active")]
+fn test_restore_over_active_budget() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    let requests = vec![
+        &env,
+        BudgetRequest {
+            user: user.clone(),
+            amount: 1000,
+        },
+    ];
+    client.batch_allocate_budget(&admin, &requests);
+    client.remove_budget(&admin, &user);
+    client.batch_allocate_budget(&admin, &requests);
+
+    client.restore_budget(&admin, &user);
+}
+
+fn large_batch(env: &Env, user: &Address) -> soroban_sdk::Vec<BudgetRequest> {
+    vec![
+        env,
+        BudgetRequest {
+            user: user.clone(),
+            amount: 5000,
+        },
+    ]
+}
+
+#[test]
+fn test_large_batch_waits_for_second_approver() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let approver = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.set_approval_threshold(&admin, &Some(1000));
+    client.set_batch_approver(&admin, &approver, &true);
+
+    let result = client.batch_allocate_budget(&admin, &large_batch(&env, &user));
+    assert_eq!(result.successful, 0);
+    assert_eq!(result.batch_id, 0);
+    assert_eq!(result.pending_batch_id, Some(1));
+    assert!(client.get_budget(&user).is_none());
+    assert_eq!(client.get_last_batch_id(), 0);
+
+    let pending = client.get_pending_batch(&1).unwrap();
+    assert_eq!(pending.proposer, admin);
+    assert_eq!(pending.total_amount, 5000);
+
+    let result = client.approve_pending_batch(&approver, &1);
+    assert_eq!(result.batch_id, 1);
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.total_amount, 5000);
+    assert_eq!(client.get_budget(&user).unwrap().amount, 5000);
+    assert!(client.get_pending_batch(&1).is_none());
+}
+
+#[test]
+fn test_batch_at_threshold_applies_immediately() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user = Address::generate(&env);
+    client.set_approval_threshold(&admin, &Some(5000));
+
+    let result = client.batch_allocate_budget(&admin, &large_batch(&env, &user));
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.pending_batch_id, None);
+}
+
+#[test]
+#[should_panic(expected = "Approver must differ from proposer")]
+fn test_proposer_cannot_approve_own_batch() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    client.set_approval_threshold(&admin, &Some(1000));
+    client.set_batch_approver(&admin, &admin, &true);
+    client.batch_allocate_budget(&admin, &large_batch(&env, &Address::generate(&env)));
+
+    client.approve_pending_batch(&admin, &1);
+}
+
+#[test]
+fn test_stale_pending_batch_expires() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let approver = Address::generate(&env);
+    client.set_approval_threshold(&admin, &Some(1000));
+    client.set_batch_approver(&admin, &approver, &true);
+    client.batch_allocate_budget(&admin, &large_batch(&env, &Address::generate(&env)));
+
+    assert!(client.try_expire_pending_batch(&1).is_err());
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += crate::types::PENDING_BATCH_TTL + 1);
+    assert!(client.try_approve_pending_batch(&approver, &1).is_err());
+
+    client.expire_pending_batch(&1);
+    assert!(client.get_pending_batch(&1).is_none());
+}
+
+mod reminders_mock {
+    use crate::types::{ReminderBatchResult, ReminderRequest};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Vec};
+
+    #[contract]
+    pub struct MockReminders;
+
+    #[contractimpl]
+    impl MockReminders {
+        pub fn dispatch_batch_reminders(
+            env: Env,
+            _admin: Address,
+            requests: Vec<ReminderRequest>,
+        ) -> ReminderBatchResult {
+            let key = symbol_short!("calls");
+            let calls: u32 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(calls + 1));
+            ReminderBatchResult {
+                successful_count: requests.len(),
+                failed_addresses: Vec::new(&env),
+                snoozed_count: 0,
+                opted_out_count: 0,
+            }
+        }
+
+        pub fn calls(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("calls"))
+                .unwrap_or(0)
+        }
+    }
+}
+
+fn setup_low_budget_alert(
+    env: &Env,
+    client: &BudgetAllocationContractClient,
+    admin: &Address,
+    user: &Address,
+) -> reminders_mock::MockRemindersClient<'static> {
+    let reminders = env.register(reminders_mock::MockReminders, ());
+    client.set_low_budget_alert(
+        admin,
+        &Some(LowBudgetAlert {
+            reminders: reminders.clone(),
+            threshold: 200,
+        }),
+    );
+    client.batch_allocate_budget(
+        admin,
+        &vec![
+            env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+    reminders_mock::MockRemindersClient::new(env, &reminders)
+}
+
+#[test]
+fn test_low_budget_notifies_once_per_period() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let reminders = setup_low_budget_alert(&env, &client, &admin, &user);
+
+    assert_eq!(
+        client.record_spend(&user, &symbol_short!("general"), &700),
+        SpendOutcome::Recorded(300)
+    );
+    assert_eq!(reminders.calls(), 0);
+
+    assert_eq!(
+        client.record_spend(&user, &symbol_short!("general"), &150),
+        SpendOutcome::Recorded(150)
+    );
+    assert_eq!(reminders.calls(), 1);
+    assert_eq!(client.get_low_budget_notified(&user), Some(0));
+
+    client.record_spend(&user, &symbol_short!("general"), &50);
+    assert_eq!(reminders.calls(), 1);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += crate::types::BUDGET_PERIOD_SECONDS);
+    client.record_spend(&user, &symbol_short!("general"), &10);
+    assert_eq!(reminders.calls(), 2);
+    assert_eq!(client.get_low_budget_notified(&user), Some(1));
+}
+
+#[test]
+fn test_low_budget_disabled_without_config() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let reminders = setup_low_budget_alert(&env, &client, &admin, &user);
+
+    client.set_low_budget_alert(&admin, &None);
+    assert_eq!(
+        client.record_spend(&user, &symbol_short!("general"), &950),
+        SpendOutcome::Recorded(50)
+    );
+    assert_eq!(reminders.calls(), 0);
+    assert_eq!(client.get_low_budget_notified(&user), None);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient budget")]
+fn test_record_spend_over_budget() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    setup_low_budget_alert(&env, &client, &admin, &user);
+
+    client.record_spend(&user, &symbol_short!("general"), &1001);
+}
+
+mod oracle_mock {
+    use crate::RateOracleInterface;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env, Symbol};
+
+    /// Quotes EUR at 0.5 per token unit and nothing else.
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl RateOracleInterface for MockOracle {
+        fn rate(_env: Env, currency: Symbol) -> i128 {
+            if currency != symbol_short!("EUR") {
+                panic!("unsupported currency");
+            }
+            crate::types::RATE_SCALE / 2
+        }
+    }
+}
+
+fn alert_events(env: &Env) -> soroban_sdk::Vec<(u32, i128, i128)> {
+    use soroban_sdk::{testutils::Events as _, IntoVal, TryFromVal};
+
+    let mut found = soroban_sdk::Vec::new(env);
+    for (_, topics, data) in env.events().all().iter() {
+        let second = Symbol::try_from_val(env, &topics.get(1).unwrap());
+        if second == Ok(symbol_short!("alert")) {
+            found.push_back(data.into_val(env));
+        }
+    }
+    found
+}
+
+#[test]
+fn test_spend_alerts_fire_when_crossing_thresholds() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+    client.set_spend_alerts(&user, &vec![&env, 5000, 8000, 10000]);
+    assert_eq!(client.get_spend_alerts(&user).len(), 3);
+
+    client.record_spend(&user, &symbol_short!("food"), &400);
+    assert!(alert_events(&env).is_empty());
+
+    // One spend can cross several thresholds
+    client.record_spend(&user, &symbol_short!("food"), &450);
+    assert_eq!(
+        alert_events(&env),
+        vec![&env, (5000, 8500, 150), (8000, 8500, 150)]
+    );
+
+    // Staying above a threshold does not alert again
+    client.record_spend(&user, &symbol_short!("food"), &50);
+    assert!(alert_events(&env).is_empty());
+
+    client.record_spend(&user, &symbol_short!("food"), &100);
+    assert_eq!(alert_events(&env), vec![&env, (10000, 10000, 0)]);
+
+    assert!(client
+        .try_set_spend_alerts(&user, &vec![&env, 8000, 5000])
+        .is_err());
+    assert!(client
+        .try_set_spend_alerts(&user, &vec![&env, 10001])
+        .is_err());
+}
+
+fn anomaly_events(env: &Env) -> soroban_sdk::Vec<(u64, Address, i128, i128)> {
+    use soroban_sdk::{testutils::Events as _, IntoVal, TryFromVal};
+
+    let mut found = soroban_sdk::Vec::new(env);
+    for (_, topics, data) in env.events().all().iter() {
+        let second = Symbol::try_from_val(env, &topics.get(1).unwrap());
+        if second == Ok(Symbol::new(env, "allocation_anomaly")) {
+            found.push_back(data.into_val(env));
+        }
+    }
+    found
+}
+
+#[test]
+fn test_allocation_anomalies_flagged_against_recent_average() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    let request = |user: &Address, amount: i128| {
+        vec![
+            &env,
+            BudgetRequest {
+                user: user.clone(),
+                amount,
+            },
+        ]
+    };
+
+    assert!(client.try_set_anomaly_multiple(&admin, &Some(1)).is_err());
+    client.set_anomaly_multiple(&admin, &Some(5));
+    assert_eq!(client.get_anomaly_multiple(), Some(5));
+
+    for _ in 0..3 {
+        client.batch_allocate_budget(&admin, &request(&user, 1000));
+        assert!(anomaly_events(&env).is_empty());
+    }
+    assert_eq!(client.get_allocation_average(&user), Some(1000));
+
+    // Users without enough history are not checked
+    let mut requests = request(&user, 6000);
+    requests.push_back(BudgetRequest {
+        user: newcomer.clone(),
+        amount: 1_000_000,
+    });
+    client.batch_allocate_budget(&admin, &requests);
+    assert_eq!(
+        anomaly_events(&env),
+        vec![&env, (4u64, user.clone(), 6000i128, 1000i128)]
+    );
+    assert_eq!(client.get_allocation_average(&newcomer), None);
+
+    // Far below the average is flagged too
+    client.batch_allocate_budget(&admin, &request(&user, 100));
+    assert_eq!(
+        anomaly_events(&env),
+        vec![&env, (5u64, user.clone(), 100i128, 2250i128)]
+    );
+
+    // The admin can suppress the check for a batch; it still counts
+    // towards the average
+    client.batch_allocate_suppressed(&admin, &request(&user, 50_000));
+    assert!(anomaly_events(&env).is_empty());
+    assert_eq!(client.get_allocation_average(&user), Some(9_850));
+
+    let manager = Address::generate(&env);
+    client.add_manager(&admin, &manager);
+    assert!(client
+        .try_batch_allocate_suppressed(&manager, &request(&user, 1000))
+        .is_err());
+
+    client.set_anomaly_multiple(&admin, &None);
+    client.batch_allocate_budget(&admin, &request(&user, 1_000_000));
+    assert!(anomaly_events(&env).is_empty());
+}
+
+fn fiat_events(env: &Env) -> soroban_sdk::Vec<(Address, i128, Symbol, i128, i128)> {
+    use soroban_sdk::{testutils::Events as _, IntoVal, TryFromVal};
+
+    let mut found = soroban_sdk::Vec::new(env);
+    for (_, topics, data) in env.events().all().iter() {
+        let first = Symbol::try_from_val(env, &topics.get(0).unwrap());
+        if first == Ok(symbol_short!("fiat")) {
+            found.push_back(data.into_val(env));
+        }
+    }
+    found
+}
+
+#[test]
+fn test_spend_emits_fiat_equivalent_in_display_currency() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+
+    // No preference or oracle yet: no fiat event
+    client.record_spend(&user, &symbol_short!("general"), &100);
+    assert_eq!(fiat_events(&env).len(), 0);
+
+    let oracle = env.register(oracle_mock::MockOracle, ());
+    client.set_rate_oracle(&admin, &Some(oracle));
+    client.set_display_currency(&user, &Some(symbol_short!("EUR")));
+    assert_eq!(
+        client.get_display_currency(&user),
+        Some(symbol_short!("EUR"))
+    );
+
+    client.record_spend(&user, &symbol_short!("general"), &100);
+    let events = fiat_events(&env);
+    assert_eq!(events.len(), 1);
+    let (event_user, amount, currency, rate, fiat_amount) = events.get(0).unwrap();
+    assert_eq!(event_user, user);
+    assert_eq!(amount, 100);
+    assert_eq!(currency, symbol_short!("EUR"));
+    assert_eq!(rate, crate::types::RATE_SCALE / 2);
+    assert_eq!(fiat_amount, 50);
+}
+
+#[test]
+fn test_unquoted_currency_does_not_block_spend() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+
+    let oracle = env.register(oracle_mock::MockOracle, ());
+    client.set_rate_oracle(&admin, &Some(oracle));
+    client.set_display_currency(&user, &Some(symbol_short!("JPY")));
+
+    assert_eq!(
+        client.record_spend(&user, &symbol_short!("general"), &100),
+        SpendOutcome::Recorded(900)
+    );
+    assert_eq!(fiat_events(&env).len(), 0);
+}
+
+fn allocate(env: &Env, client: &BudgetAllocationContractClient, admin: &Address, user: &Address) {
+    client.batch_allocate_budget(
+        admin,
+        &vec![
+            env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_reservations_reduce_available_budget() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    allocate(&env, &client, &admin, &user);
+
+    let rent = client.reserve_funds(
+        &user,
+        &symbol_short!("housing"),
+        &600,
+        &symbol_short!("rent_oct"),
+    );
+    let gym = client.reserve_funds(&user, &symbol_short!("health"), &100, &symbol_short!("gym"));
+    assert_eq!(client.get_reserved(&user), 700);
+    assert_eq!(client.get_available_budget(&user), 300);
+
+    // Reserved funds can't be spent elsewhere
+    assert!(client
+        .try_record_spend(&user, &symbol_short!("general"), &301)
+        .is_err());
+    client.record_spend(&user, &symbol_short!("general"), &200);
+
+    client.release_reservation(&user, &gym);
+    assert_eq!(client.get_available_budget(&user), 200);
+
+    // Converting records the reserved amount as spent
+    assert_eq!(client.convert_reservation(&user, &rent), 200);
+
+    let statement = client.get_budget_statement(&user);
+    assert_eq!(statement.budget, 1000);
+    assert_eq!(statement.spent, 800);
+    assert_eq!(statement.reserved, 0);
+    assert_eq!(statement.available, 200);
+    assert!(statement.reservations.is_empty());
+    assert!(client.try_release_reservation(&user, &rent).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Insufficient category budget")]
+fn test_reservation_limited_by_category_allocation() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.allocate_budget_by_category(
+        &admin,
+        &CategoryBudgetRequest {
+            user: user.clone(),
+            categories: vec![
+                &env,
+                BudgetCategory {
+                    name: symbol_short!("food"),
+                    amount: 300,
+                },
+                BudgetCategory {
+                    name: symbol_short!("travel"),
+                    amount: 700,
+                },
+            ],
+            total_amount: 1000,
+        },
+    );
+
+    let reservation_id =
+        client.reserve_funds(&user, &symbol_short!("food"), &200, &symbol_short!("party"));
+    assert_eq!(
+        client
+            .get_budget_statement(&user)
+            .reservations
+            .get(0)
+            .unwrap()
+            .reservation_id,
+        reservation_id
+    );
+    client.reserve_funds(
+        &user,
+        &symbol_short!("food"),
+        &101,
+        &symbol_short!("dinner"),
+    );
+}
+
+#[test]
+fn test_record_spend_tracks_category_budgets() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.allocate_budget_by_category(
+        &admin,
+        &CategoryBudgetRequest {
+            user: user.clone(),
+            categories: vec![
+                &env,
+                BudgetCategory {
+                    name: symbol_short!("food"),
+                    amount: 300,
+                },
+                BudgetCategory {
+                    name: symbol_short!("travel"),
+                    amount: 700,
+                },
+            ],
+            total_amount: 1000,
+        },
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(
+        client.record_spend(&user, &symbol_short!("food"), &120),
+        SpendOutcome::Recorded(880)
+    );
+    assert_eq!(
+        client.record_spend(&user, &symbol_short!("travel"), &200),
+        SpendOutcome::Recorded(680)
+    );
+    client.record_spend(&user, &symbol_short!("food"), &30);
+
+    assert_eq!(client.get_remaining_budget(&user), 650);
+    assert_eq!(
+        client.get_remaining_category_budget(&user, &symbol_short!("food")),
+        Some(150)
+    );
+    assert_eq!(
+        client.get_remaining_category_budget(&user, &symbol_short!("travel")),
+        Some(500)
+    );
+    assert_eq!(
+        client.get_remaining_category_budget(&user, &symbol_short!("rent")),
+        None
+    );
+
+    let history = client.get_spend_history(&user);
+    assert_eq!(history.len(), 3);
+    let last = history.get(2).unwrap();
+    assert_eq!(last.category, symbol_short!("food"));
+    assert_eq!(last.amount, 30);
+    assert_eq!(last.timestamp, 500);
+
+    // Category limits apply even when the overall budget has room
+    assert!(client
+        .try_record_spend(&user, &symbol_short!("food"), &151)
+        .is_err());
+    assert!(client
+        .try_record_spend(&user, &symbol_short!("rent"), &10)
+        .is_err());
+
+    // Renamed categories keep their spending
+    client.rename_category(&admin, &symbol_short!("food"), &symbol_short!("grocery"));
+    assert_eq!(
+        client.get_remaining_category_budget(&user, &symbol_short!("grocery")),
+        Some(150)
+    );
+}
+
+#[test]
+fn test_close_period_rolls_over_unspent_budget() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.batch_allocate_budget(
+        &admin,
+        &vec![
+            &env,
+            BudgetRequest {
+                user: user.clone(),
+                amount: 1000,
+            },
+        ],
+    );
+    client.set_rollover_policy(&admin, &user, &RolloverPolicy::Capped(250));
+    client.record_spend(&user, &symbol_short!("general"), &600);
+    client.reserve_funds(
+        &user,
+        &symbol_short!("general"),
+        &100,
+        &symbol_short!("inv1"),
+    );
+
+    // The period can only be closed once it has ended
+    assert!(client.try_close_period(&admin, &user).is_err());
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += crate::types::BUDGET_PERIOD_SECONDS);
+    let snapshot = client.close_period(&admin, &user);
+    assert_eq!(snapshot.period, 0);
+    assert_eq!(snapshot.budget, 1000);
+    assert_eq!(snapshot.spent, 600);
+    assert_eq!(snapshot.reserved, 100);
+    assert_eq!(snapshot.unspent, 300);
+    assert_eq!(snapshot.rolled_over, 250);
+    assert_eq!(client.get_period_snapshot(&user, &0), Some(snapshot));
+
+    // The new period keeps the base budget plus the capped rollover
+    let budget = client.get_budget(&user).unwrap();
+    assert_eq!(budget.period, 1);
+    assert_eq!(budget.amount, 1250);
+    assert_eq!(budget.rolled_over, 250);
+    assert_eq!(client.get_spent(&user), 0);
+    assert_eq!(client.get_reserved(&user), 100);
+    assert_eq!(client.get_available_budget(&user), 1150);
+
+    // Forfeiting drops the unspent budget; the base does not compound
+    client.set_rollover_policy(&admin, &user, &RolloverPolicy::Forfeit);
+    client.release_reservation(&user, &1);
+    env.ledger()
+        .with_mut(|li| li.timestamp += crate::types::BUDGET_PERIOD_SECONDS);
+    let snapshot = client.close_period(&admin, &user);
+    assert_eq!(snapshot.rolled_in, 250);
+    assert_eq!(snapshot.unspent, 1250);
+    assert_eq!(snapshot.rolled_over, 0);
+    assert_eq!(client.get_budget(&user).unwrap().amount, 1000);
+    assert_eq!(client.get_closed_periods(&user), vec![&env, 0, 1]);
+    assert_eq!(client.get_period_snapshot(&user, &2), None);
+}
+
+#[test]
+fn test_batch_allocate_by_category() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+    let user4 = Address::generate(&env);
+    let category = |name: Symbol, amount: i128| BudgetCategory { name, amount };
+
+    let requests = vec![
+        &env,
+        CategoryBudgetRequest {
+            user: user1.clone(),
+            categories: vec![
+                &env,
+                category(symbol_short!("food"), 300),
+                category(symbol_short!("travel"), 200),
+            ],
+            total_amount: 500,
+        },
+        // Total does not match the categories
+        CategoryBudgetRequest {
+            user: user2.clone(),
+            categories: vec![&env, category(symbol_short!("food"), 100)],
+            total_amount: 150,
+        },
+        // Negative category amount
+        CategoryBudgetRequest {
+            user: user3.clone(),
+            categories: vec![&env, category(symbol_short!("food"), -1)],
+            total_amount: -1,
+        },
+        // Repeats user1
+        CategoryBudgetRequest {
+            user: user1.clone(),
+            categories: vec![&env, category(symbol_short!("food"), 900)],
+            total_amount: 900,
+        },
+        CategoryBudgetRequest {
+            user: user4.clone(),
+            categories: vec![&env, category(symbol_short!("rent"), 700)],
+            total_amount: 700,
+        },
+    ];
+
+    let result = client.batch_allocate_by_category(&admin, &requests);
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 3);
+    assert_eq!(result.total_amount, 1200);
+    assert_eq!(result.pending_batch_id, None);
+
+    assert_eq!(
+        client.get_category_budget(&user1, &symbol_short!("food")),
+        Some(300)
+    );
+    assert_eq!(client.get_budget(&user1).unwrap().amount, 500);
+    assert_eq!(
+        client.get_category_budget(&user4, &symbol_short!("rent")),
+        Some(700)
+    );
+    assert_eq!(client.get_budget_categories(&user2), None);
+    assert_eq!(client.get_budget_categories(&user3), None);
+}
+
+#[test]
+#[should_panic(expected = "Empty batch")]
+fn test_batch_allocate_by_category_empty() {
+    let (env, contract_id, admin) = create_contract();
+    let client = BudgetAllocationContractClient::new(&env, &contract_id);
+
+    client.batch_allocate_by_category(&admin, &Vec::new(&env));
+}
@@ -0,0 +1,98 @@
+//! Rate limiting for expense recording.
+//!
+//! Caps how many expense entries a user can record per time window so a
+//! compromised key cannot flood a budget. Rejections never panic: the
+//! violation counter and any resulting freeze must survive the call.
+
+use crate::types::{DataKey, ExpenseThrottleConfig, ExpenseWindowState};
+use soroban_sdk::{symbol_short, Address, Env};
+
+pub fn get_config(env: &Env) -> Option<ExpenseThrottleConfig> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ExpenseThrottleConfig)
+}
+
+pub fn set_config(env: &Env, config: &ExpenseThrottleConfig) {
+    if config.max_expenses_per_window == 0 || config.window_seconds == 0 {
+        panic!("Invalid throttle config");
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::ExpenseThrottleConfig, config);
+}
+
+pub fn is_frozen(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BudgetFrozen(user.clone()))
+        .unwrap_or(false)
+}
+
+pub fn unfreeze(env: &Env, user: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BudgetFrozen(user.clone()));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ExpenseWindow(user.clone()));
+}
+
+/// Consumes one expense slot for `user`. Returns `false` when the entry must be
+/// rejected, after recording the violation and emitting a security event.
+pub fn check_expense(env: &Env, user: &Address) -> bool {
+    if is_frozen(env, user) {
+        env.events().publish(
+            (symbol_short!("security"), symbol_short!("frozen")),
+            user.clone(),
+        );
+        return false;
+    }
+
+    let config = match get_config(env) {
+        Some(config) => config,
+        None => return true,
+    };
+
+    let now = env.ledger().timestamp();
+    let key = DataKey::ExpenseWindow(user.clone());
+    let mut state: ExpenseWindowState =
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(ExpenseWindowState {
+                window_start: now,
+                count: 0,
+                violations: 0,
+            });
+
+    if now >= state.window_start.saturating_add(config.window_seconds) {
+        state.window_start = now;
+        state.count = 0;
+    }
+
+    if state.count < config.max_expenses_per_window {
+        state.count += 1;
+        env.storage().persistent().set(&key, &state);
+        return true;
+    }
+
+    state.violations = state.violations.saturating_add(1);
+    env.storage().persistent().set(&key, &state);
+    env.events().publish(
+        (symbol_short!("security"), symbol_short!("throttled")),
+        (user.clone(), state.count, state.violations),
+    );
+
+    if config.freeze_after_violations > 0 && state.violations >= config.freeze_after_violations {
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetFrozen(user.clone()), &true);
+        env.events().publish(
+            (symbol_short!("budget"), symbol_short!("frozen")),
+            (user.clone(), state.violations),
+        );
+    }
+
+    false
+}
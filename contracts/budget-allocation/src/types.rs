@@ -1,5 +1,49 @@
 use soroban_sdk::{contracttype, Address, Map, Symbol, Vec};
 
+/// Maximum number of requests accepted by a single batch allocation
+pub const MAX_BATCH_SIZE: u32 = 100;
+
+/// Seconds a batch awaiting a second approver stays approvable
+pub const PENDING_BATCH_TTL: u64 = 7 * 24 * 60 * 60;
+
+/// Length of a budget period (30 days), used for rollover and once-per-period notifications
+pub const BUDGET_PERIOD_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Maximum number of open reservations per user
+pub const MAX_RESERVATIONS: u32 = 50;
+
+/// Maximum number of spend entries kept in a user's history
+pub const MAX_SPEND_HISTORY: u32 = 100;
+
+/// Maximum number of spending alert thresholds per user
+pub const MAX_SPEND_ALERTS: u32 = 5;
+
+/// Number of recent allocations per user averaged for anomaly detection
+pub const ANOMALY_WINDOW: u32 = 10;
+
+/// Recent allocations a user needs before their allocations are checked for anomalies
+pub const ANOMALY_MIN_SAMPLES: u32 = 3;
+
+/// Fixed-point scale of oracle rates (1 token unit = rate / RATE_SCALE fiat units)
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Error codes attached to per-request failures in batch operations
+#[allow(non_snake_case)]
+pub mod ErrorCode {
+    /// Budget or category amount is negative
+    pub const INVALID_AMOUNT: u32 = 0;
+    /// User already appeared earlier in the same batch
+    pub const DUPLICATE_USER: u32 = 1;
+    /// Category amounts do not add up to the requested total
+    pub const TOTAL_MISMATCH: u32 = 2;
+    /// Amount exceeds the global budget cap or the user's budget limit
+    pub const LIMIT_EXCEEDED: u32 = 3;
+    /// User has no budget to adjust
+    pub const BUDGET_NOT_FOUND: u32 = 4;
+    /// Decrease is larger than the user's budget
+    pub const INSUFFICIENT_BUDGET: u32 = 5;
+}
+
 /// Request structure for setting a user's budget
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -10,6 +54,15 @@ pub struct BudgetRequest {
     pub amount: i128,
 }
 
+/// Signed change to a user's existing budget
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetAdjustment {
+    pub user: Address,
+    pub delta: i128,    // Positive tops the budget up, negative claws it back
+    pub reason: Symbol, // Why the budget was corrected (e.g. "bonus", "clawback")
+}
+
 /// Budget category structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -32,6 +85,26 @@ pub struct CategoryBudgetRequest {
     pub total_amount: i128,
 }
 
+/// Budget profile applied to users who have no budget yet
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultBudget {
+    /// List of budget categories and amounts
+    pub categories: Vec<BudgetCategory>,
+    /// Total budget amount (must equal sum of categories)
+    pub total_amount: i128,
+}
+
+/// Named category split reused across allocations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetTemplate {
+    pub name: Symbol,
+    pub categories: Vec<BudgetCategory>,
+    pub total_amount: i128, // Sum of the category amounts
+    pub updated_at: u64,
+}
+
 /// Stored budget record for a user
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -39,6 +112,18 @@ pub struct BudgetRecord {
     pub user: Address,
     pub amount: i128,
     pub last_updated: u64,
+    pub period: u64,       // Budget period this record belongs to
+    pub rolled_over: i128, // Part of `amount` carried over from the previous period
+}
+
+/// One change of a user's budget amount
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetChange {
+    pub old_amount: i128, // 0 when the user had no budget
+    pub new_amount: i128, // 0 when the budget was removed
+    pub changed_by: Address,
+    pub timestamp: u64,
 }
 
 /// Stored budget categories for a user
@@ -51,6 +136,20 @@ pub struct UserBudgetCategories {
     pub last_updated: u64,
 }
 
+/// Budget state kept for an offboarded user so it can be restored later
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedBudget {
+    pub user: Address,
+    pub amount: Option<i128>,
+    pub categories: Option<Map<Symbol, i128>>, // category name -> amount
+    pub categories_total: i128,
+    pub spent: i128,                       // Spending recorded against the budget
+    pub category_spent: Map<Symbol, i128>, // Spending recorded per category
+    pub reservations: Vec<Reservation>,    // Open commitments at removal
+    pub archived_at: u64,
+}
+
 /// Storage keys for the contract
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -59,13 +158,222 @@ pub enum DataKey {
     Budget(Address),
     BudgetCategories(Address), // User's budget categories
     TotalAllocated,            // Track global stats if needed
+    ExpenseThrottleConfig,
+    ExpenseWindow(Address),  // User's expense rate-limit window
+    BudgetFrozen(Address),   // Set when repeat throttle violations freeze a budget
+    CategoryRemap(Symbol),   // Renamed/merged category -> its replacement
+    ArchivedBudget(Address), // Soft-deleted budget kept for restoration
+    ApprovalThreshold,       // Batch totals above this need a second approver
+    BatchApprover(Address),  // Addresses allowed to approve pending batches
+    LastPendingBatchId,
+    PendingBatch(u64),          // Batch waiting for a second approver
+    BudgetSpent(Address),       // Spending recorded against the current budget
+    LowBudgetAlert,             // Reminders contract and threshold for "budget low"
+    LowBudgetNotified(Address), // Period in which the user was last notified
+    DisplayCurrency(Address),   // User's preferred fiat display currency
+    RateOracle,                 // Oracle adapter quoting fiat rates
+    Reservations(Address),      // Open commitments against the user's budget
+    LastReservationId,
+    CategorySpent(Address),          // Spending recorded per category
+    SpendHistory(Address),           // Most recent spend entries, oldest first
+    RolloverPolicy(Address),         // What happens to unspent budget when a period closes
+    PeriodSnapshot(Address, u64),    // Closed period, keyed by (user, period)
+    ClosedPeriods(Address),          // Periods closed for the user, oldest first
+    LastBatchId,                     // Id of the last batch applied
+    BudgetManager(Address),          // Addresses allowed to run batch allocations
+    BatchRecord(u64),                // Outcome of an applied batch
+    BudgetCap,                       // Largest budget any user may be allocated
+    UserBudgetLimit(Address),        // Largest budget this user may be allocated
+    Treasury,                        // Source of funds for funded allocations
+    FundedBalance(Address, Address), // Escrowed funds, keyed by (user, token)
+    FundedToken(Address),            // Token the user's budget is escrowed in
+    BudgetHolderCount,               // Number of users holding an active budget
+    BudgetHolder(u32),               // Budget holder at a position in the index
+    BudgetHolderIndex(Address),      // Position of a budget holder in the index
+    DefaultBudget,                   // Profile applied by `ensure_budget` to users without one
+    BudgetTemplate(Symbol),          // Named category split, by template name
+    SpendAlerts(Address),            // User's alert thresholds, in bps of the budget consumed
+    BudgetHistoryCount(Address),     // Number of budget changes logged for the user
+    BudgetHistory(Address, u32),     // Logged budget change, keyed by (user, position)
+    AuditContract,                   // Audit contract recording budget adjustments
+    AnomalyMultiple,                 // Deviation from the recent average that flags an allocation
+    RecentAllocations(Address),      // User's most recent allocation amounts, oldest first
+}
+
+/// What happens to a user's unspent budget when a period closes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RolloverPolicy {
+    /// Unspent budget is dropped (default)
+    Forfeit,
+    /// All unspent budget is added to the next period
+    Full,
+    /// Unspent budget is added to the next period up to this amount
+    Capped(i128),
+}
+
+/// Budget and spending of a closed period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodSnapshot {
+    pub user: Address,
+    pub period: u64,
+    pub budget: i128,    // Budget of the period, including `rolled_in`
+    pub rolled_in: i128, // Carried over from the period before
+    pub spent: i128,
+    pub reserved: i128, // Still reserved at close; carried into the next period
+    pub unspent: i128,
+    pub rolled_over: i128, // Carried over into the next period
+    pub closed_at: u64,
+}
+
+/// Where and when to send "budget low" notifications
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LowBudgetAlert {
+    /// Batch payment reminders contract that schedules the notification
+    pub reminders: Address,
+    /// Remaining budget below which the user is notified
+    pub threshold: i128,
+}
+
+/// Delivery hints attached to a reminder request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoutingHints {
+    pub channel: Symbol, // "default" leaves the channel to the notifier
+    pub locale: Symbol,
+    pub priority: u32,
+}
+
+/// Reminder request accepted by the batch payment reminders contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReminderRequest {
+    pub user: Address,
+    pub reminder_id: u64,
+    pub category: Symbol,
+    pub due_date: u64,
+    pub routing: RoutingHints,
+}
+
+/// Result returned by the batch payment reminders contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReminderBatchResult {
+    pub successful_count: u32,
+    pub failed_addresses: Vec<Address>,
+    pub snoozed_count: u32,
+    pub opted_out_count: u32,
+}
+
+/// A single recorded spend
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendRecord {
+    pub category: Symbol,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Budget earmarked for an upcoming, known expense
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    pub reservation_id: u64,
+    pub category: Symbol,
+    pub amount: i128,
+    pub reference: Symbol, // Caller-supplied reference, e.g. an invoice number
+    pub created_at: u64,
+}
+
+/// Snapshot of a user's budget, spending and commitments
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BudgetStatement {
+    pub user: Address,
+    pub budget: i128,
+    pub spent: i128,
+    pub reserved: i128,
+    pub available: i128, // Budget left after spending and reservations
+    pub reservations: Vec<Reservation>,
+}
+
+/// Rate limit applied to expense recording
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpenseThrottleConfig {
+    /// Maximum expense entries a user may record per window
+    pub max_expenses_per_window: u32,
+    /// Length of the rate-limit window in seconds
+    pub window_seconds: u64,
+    /// Violations after which the budget is frozen (0 disables auto-freeze)
+    pub freeze_after_violations: u32,
+}
+
+/// Per-user expense rate-limit window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpenseWindowState {
+    pub window_start: u64,
+    pub count: u32,
+    pub violations: u32,
+}
+
+/// Outcome of recording a spend. Refusals are returned rather than panicking
+/// so the throttle violation they record persists.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SpendOutcome {
+    /// Spend recorded; carries the remaining budget
+    Recorded(i128),
+    /// Refused because the budget is frozen
+    Frozen,
+    /// Refused by the expense throttle, counting as a violation
+    Throttled,
+}
+
+/// Result of processing a single request in a batch allocation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BudgetResult {
+    Success(BudgetRecord),
+    Failure(Address, u32), // user address, error code
 }
 
 /// Result of a batch budget allocation operation
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BatchBudgetResult {
+    pub batch_id: u64, // 0 while the batch awaits a second approver
     pub successful: u32,
     pub failed: u32,
     pub total_amount: i128,
+    pub results: Vec<BudgetResult>, // One per request, in request order
+    pub pending_batch_id: Option<u64>, // Set when the batch awaits a second approver
+}
+
+/// Stored outcome of an applied batch, kept for auditing allocation runs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchRecord {
+    pub batch_id: u64,
+    pub request_count: u32,
+    pub successful: u32,
+    pub failed: u32,
+    pub total_amount: i128,
+    pub completed_at: u64,
+}
+
+/// Batch allocation held back until a second approver signs off
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingBatch {
+    pub batch_id: u64,
+    pub proposer: Address,
+    pub requests: Vec<BudgetRequest>,
+    pub total_amount: i128,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub suppress_anomalies: bool,
 }
@@ -0,0 +1,120 @@
+//! # Budget Integration Test Harness
+//!
+//! Test-only crate that registers the budget allocation, batch payment
+//! reminders and audit contracts in one `Env` and wires them together the
+//! way they are deployed:
+//!
+//! - Spending recorded against a budget schedules "budget low" reminders
+//!   through the reminders contract
+//! - Admin budget adjustments are logged to the audit contract
+//!
+//! The suite in `test.rs` drives allocate → spend → warn → audit flows
+//! across the contract boundaries instead of against test doubles.
+
+#![no_std]
+
+#[cfg(test)]
+mod test;
+
+use audit::{AuditContract, AuditContractClient, AuditLog};
+use batch_payment_reminders::{BatchPaymentRemindersContract, BatchPaymentRemindersContractClient};
+use budget_allocation::{
+    BudgetAllocationContract, BudgetAllocationContractClient, BudgetRequest, LowBudgetAlert,
+};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Symbol, Vec};
+
+/// Largest audit metadata blob the harness' audit contract accepts.
+pub const AUDIT_MAX_METADATA_SIZE: u32 = 1024;
+
+/// Remaining budget under which users get a "budget low" reminder.
+pub const LOW_BUDGET_THRESHOLD: i128 = 200;
+
+/// The three contracts, deployed and wired together, with a shared admin.
+pub struct BudgetHarness {
+    pub env: Env,
+    pub admin: Address,
+    pub budgets: BudgetAllocationContractClient<'static>,
+    pub reminders: BatchPaymentRemindersContractClient<'static>,
+    pub audit: AuditContractClient<'static>,
+}
+
+impl BudgetHarness {
+    /// Registers and initializes every contract with all auths mocked, then
+    /// points budget allocation at the reminders and audit contracts.
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+
+        let budgets =
+            BudgetAllocationContractClient::new(&env, &env.register(BudgetAllocationContract, ()));
+        let reminders = BatchPaymentRemindersContractClient::new(
+            &env,
+            &env.register(BatchPaymentRemindersContract, ()),
+        );
+        let audit = AuditContractClient::new(&env, &env.register(AuditContract, ()));
+
+        budgets.initialize(&admin);
+        // Budget allocation dispatches "budget low" reminders as the reminders admin
+        reminders.initialize(&budgets.address);
+        audit.initialize(&admin, &AUDIT_MAX_METADATA_SIZE);
+
+        budgets.set_low_budget_alert(
+            &admin,
+            &Some(LowBudgetAlert {
+                reminders: reminders.address.clone(),
+                threshold: LOW_BUDGET_THRESHOLD,
+            }),
+        );
+        budgets.set_audit_contract(&admin, &Some(audit.address.clone()));
+
+        BudgetHarness {
+            env,
+            admin,
+            budgets,
+            reminders,
+            audit,
+        }
+    }
+
+    /// Creates a user and allocates them `amount` through a batch of one.
+    pub fn funded_user(&self, amount: i128) -> Address {
+        let user = Address::generate(&self.env);
+        let result = self.budgets.batch_allocate_budget(
+            &self.admin,
+            &vec![
+                &self.env,
+                BudgetRequest {
+                    user: user.clone(),
+                    amount,
+                },
+            ],
+        );
+        assert_eq!(result.successful, 1, "allocation failed");
+        user
+    }
+
+    /// Every entry stored by the audit contract, oldest first.
+    pub fn audit_logs(&self) -> Vec<AuditLog> {
+        let mut logs = Vec::new(&self.env);
+        for index in 1..=self.audit.get_total_audit_logs() {
+            logs.push_back(self.audit.get_audit_log(&index).unwrap());
+        }
+        logs
+    }
+
+    /// Operations recorded by the audit contract, oldest first.
+    pub fn audited_operations(&self) -> Vec<Symbol> {
+        let mut operations = Vec::new(&self.env);
+        for log in self.audit_logs().iter() {
+            operations.push_back(log.operation);
+        }
+        operations
+    }
+}
+
+impl Default for BudgetHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,207 @@
+//! End-to-end flows across budget allocation, reminders and audit.
+
+#![cfg(test)]
+
+use crate::{BudgetHarness, LOW_BUDGET_THRESHOLD};
+use budget_allocation::{
+    BudgetAdjustment, BudgetResult, ErrorCode, SpendOutcome, BUDGET_PERIOD_SECONDS,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    vec,
+    xdr::FromXdr,
+    Address, Symbol, TryFromVal, Val,
+};
+
+/// Number of events in the last invocation published by `contract` whose
+/// first topic is `name`.
+fn count_events(harness: &BudgetHarness, contract: &Address, name: Symbol) -> u32 {
+    let mut count = 0;
+    for (emitter, topics, _) in harness.env.events().all().iter() {
+        if emitter != *contract {
+            continue;
+        }
+        let first: Val = topics.get(0).unwrap();
+        if Symbol::try_from_val(&harness.env, &first) == Ok(name.clone()) {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn sent_reminders(harness: &BudgetHarness) -> u32 {
+    count_events(
+        harness,
+        &harness.reminders.address,
+        symbol_short!("rem_sent"),
+    )
+}
+
+#[test]
+fn test_spend_warns_and_schedules_low_budget_reminder() {
+    let harness = BudgetHarness::new();
+    let user = harness.funded_user(1000);
+    let general = symbol_short!("general");
+    harness
+        .budgets
+        .set_spend_alerts(&user, &vec![&harness.env, 5000u32, 9000]);
+
+    // Half the budget: a spend alert, but still above the reminder threshold
+    assert_eq!(
+        harness.budgets.record_spend(&user, &general, &600),
+        SpendOutcome::Recorded(400)
+    );
+    let budgets = harness.budgets.address.clone();
+    assert_eq!(count_events(&harness, &budgets, symbol_short!("budget")), 2);
+    assert_eq!(sent_reminders(&harness), 0);
+
+    // Crossing the threshold schedules a reminder in the reminders contract
+    assert_eq!(
+        harness.budgets.record_spend(&user, &general, &250),
+        SpendOutcome::Recorded(150)
+    );
+    assert_eq!(sent_reminders(&harness), 1);
+    assert!(harness.budgets.get_remaining_budget(&user) < LOW_BUDGET_THRESHOLD);
+    assert_eq!(harness.budgets.get_low_budget_notified(&user), Some(0));
+
+    // At most one reminder per period
+    harness.budgets.record_spend(&user, &general, &100);
+    assert_eq!(sent_reminders(&harness), 0);
+
+    harness
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp += BUDGET_PERIOD_SECONDS);
+    harness.budgets.record_spend(&user, &general, &10);
+    assert_eq!(sent_reminders(&harness), 1);
+    assert_eq!(harness.budgets.get_low_budget_notified(&user), Some(1));
+}
+
+#[test]
+fn test_opted_out_user_is_not_reminded() {
+    let harness = BudgetHarness::new();
+    let user = harness.funded_user(500);
+    harness
+        .reminders
+        .set_opt_out(&user, &symbol_short!("budgetlow"), &true);
+
+    harness
+        .budgets
+        .record_spend(&user, &symbol_short!("general"), &400);
+    assert_eq!(sent_reminders(&harness), 0);
+    assert_eq!(
+        count_events(
+            &harness,
+            &harness.reminders.address,
+            symbol_short!("rem_skip")
+        ),
+        1
+    );
+    // The reminders contract accepted the batch, so the period is settled
+    assert_eq!(harness.budgets.get_low_budget_notified(&user), Some(0));
+}
+
+#[test]
+fn test_adjustments_are_recorded_by_audit_contract() {
+    let harness = BudgetHarness::new();
+    let user = harness.funded_user(1000);
+    let unfunded = Address::generate(&harness.env);
+
+    let result = harness.budgets.batch_adjust_budgets(
+        &harness.admin,
+        &vec![
+            &harness.env,
+            BudgetAdjustment {
+                user: user.clone(),
+                delta: 250,
+                reason: symbol_short!("bonus"),
+            },
+            BudgetAdjustment {
+                user: unfunded.clone(),
+                delta: 100,
+                reason: symbol_short!("bonus"),
+            },
+        ],
+    );
+    assert_eq!(result.successful, 1);
+    assert_eq!(
+        result.results.get(1).unwrap(),
+        BudgetResult::Failure(unfunded, ErrorCode::BUDGET_NOT_FOUND)
+    );
+
+    // Only the applied adjustment is audited, with its details as metadata
+    let logs = harness.audit_logs();
+    assert_eq!(logs.len(), 1);
+    let log = logs.get(0).unwrap();
+    assert_eq!(log.actor, harness.admin);
+    assert_eq!(
+        log.operation,
+        Symbol::new(&harness.env, "budget_adjustment")
+    );
+    assert_eq!(log.status, symbol_short!("success"));
+    let metadata = log.metadata.unwrap();
+    let (logged_user, delta, amount, reason) =
+        <(Address, i128, i128, Symbol)>::from_xdr(&harness.env, &metadata).unwrap();
+    assert_eq!(logged_user, user);
+    assert_eq!(delta, 250);
+    assert_eq!(amount, 1250);
+    assert_eq!(reason, symbol_short!("bonus"));
+}
+
+#[test]
+fn test_allocate_spend_warn_audit_flow() {
+    let harness = BudgetHarness::new();
+    let user = harness.funded_user(1000);
+    let general = symbol_short!("general");
+
+    // Spend down into low-budget territory and get reminded
+    assert_eq!(
+        harness.budgets.record_spend(&user, &general, &900),
+        SpendOutcome::Recorded(100)
+    );
+    assert_eq!(sent_reminders(&harness), 1);
+
+    // A clawback is audited and shrinks the remaining budget further
+    harness.budgets.batch_adjust_budgets(
+        &harness.admin,
+        &vec![
+            &harness.env,
+            BudgetAdjustment {
+                user: user.clone(),
+                delta: -50,
+                reason: symbol_short!("clawback"),
+            },
+        ],
+    );
+    assert_eq!(harness.budgets.get_remaining_budget(&user), 50);
+
+    // A top-up lifts the budget back over the threshold; further spending
+    // is not reminded again within the period
+    harness.budgets.batch_adjust_budgets(
+        &harness.admin,
+        &vec![
+            &harness.env,
+            BudgetAdjustment {
+                user: user.clone(),
+                delta: 500,
+                reason: symbol_short!("bonus"),
+            },
+        ],
+    );
+    assert_eq!(
+        harness.budgets.record_spend(&user, &general, &400),
+        SpendOutcome::Recorded(150)
+    );
+    assert_eq!(sent_reminders(&harness), 0);
+
+    assert_eq!(
+        harness.audited_operations(),
+        vec![
+            &harness.env,
+            Symbol::new(&harness.env, "budget_adjustment"),
+            Symbol::new(&harness.env, "budget_adjustment"),
+        ]
+    );
+    assert_eq!(harness.budgets.get_spend_history(&user).len(), 2);
+}
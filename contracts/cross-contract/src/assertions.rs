@@ -0,0 +1,34 @@
+//! Return-data assertions.
+//!
+//! A call request can state what the target must return. When the target
+//! succeeds but its return data does not satisfy the predicate, the call is
+//! reported as failed with `CallFailure::AssertionFailed`, so batches with
+//! `continue_on_failure = false` stop before acting on an unexpected result.
+
+use soroban_sdk::Bytes;
+
+use crate::types::ReturnPredicate;
+
+/// Whether `return_data` satisfies `predicate`.
+pub fn holds(predicate: &ReturnPredicate, return_data: &Bytes) -> bool {
+    match predicate {
+        ReturnPredicate::None => true,
+        ReturnPredicate::NonEmpty => !return_data.is_empty(),
+        ReturnPredicate::Equals(expected) => return_data == expected,
+        ReturnPredicate::AtLeast(threshold) => {
+            as_uint(return_data).is_some_and(|value| value >= *threshold)
+        }
+    }
+}
+
+/// Reads return data as a big-endian unsigned integer, if it is 1 to 16
+/// bytes long.
+fn as_uint(data: &Bytes) -> Option<u128> {
+    if data.is_empty() || data.len() > 16 {
+        return None;
+    }
+    Some(
+        data.iter()
+            .fold(0u128, |value, byte| (value << 8) | byte as u128),
+    )
+}
@@ -6,6 +6,11 @@
 
 #![no_std]
 
+mod assertions;
+mod prepayment;
+mod receipts;
+mod snapshots;
+mod timelock;
 mod types;
 mod validation;
 
@@ -13,13 +18,21 @@ mod validation;
 mod test;
 
 use soroban_sdk::{
-    contract, contractimpl, panic_with_error, Address, Bytes, Env, Symbol, Vec,
+    contract, contractimpl, panic_with_error,
+    xdr::{ScErrorType, ToXdr},
+    Address, Bytes, BytesN, ConversionError, Env, InvokeError, Map, Symbol, Val, Vec,
 };
 
+use crate::prepayment::Prepayment;
 pub use crate::types::{
-    BatchCallResult, CallResult, CrossContractCall, CrossContractEvents, DataKey, MAX_BATCH_CALLS,
+    AdminAction, BatchCallResult, CallFailure, CallReceipt, CallResult, CallStatus, CrossContractCall, CrossContractEvents, DataKey,
+    OperatorStats, PrepaidBatchResult, QueuedAction, ReturnPredicate, WhitelistSnapshot, MAX_BATCH_CALLS, MAX_RETURN_DATA_EVENT_BYTES,
+    OPERATOR_SUMMARY_INTERVAL, TIMELOCK_GRACE_PERIOD,
+};
+use crate::validation::{
+    decode_args, is_quarantined, is_view_function, is_whitelisted, payment_rejection,
+    rejection_reason, validate_batch_size, validate_call_request, validate_view_calls,
 };
-use crate::validation::{is_whitelisted, validate_batch_calls, validate_call_request};
 
 /// Error codes for the cross-contract interaction contract
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -41,6 +54,25 @@ pub enum CrossContractError {
     BatchTooLarge = 7,
     /// Cross-contract call failed
     CallFailed = 8,
+    /// Return-data event limit exceeds the allowed maximum
+    InvalidReturnDataLimit = 9,
+    /// Function is not registered as a read-only view
+    ViewNotAllowed = 10,
+    /// Operation must be queued through the timelock
+    TimelockRequired = 11,
+    /// Queued action does not exist (never queued, executed, or cancelled)
+    ActionNotFound = 12,
+    /// Queued action's delay has not elapsed yet
+    ActionNotReady = 13,
+    /// Queued action was not executed within the grace period
+    ActionExpired = 14,
+    /// Whitelist snapshot does not exist
+    SnapshotNotFound = 15,
+    /// Prepayment or spending cap is not positive, or the prepayment exceeds
+    /// the token's per-batch spending cap
+    InvalidPrepayment = 16,
+    /// Call arguments are not XDR-encoded values
+    InvalidArguments = 17,
 }
 
 impl From<CrossContractError> for soroban_sdk::Error {
@@ -69,6 +101,13 @@ impl CrossContractInteraction {
     }
 
     /// Executes a single cross-contract call
+    ///
+    /// Calls to a quarantined contract are not executed and fail with
+    /// `CallFailure::Quarantined`. A call whose return data does not satisfy
+    /// its `expected_return` predicate fails with `CallFailure::AssertionFailed`.
+    /// Single calls are never prepaid, so a call with a `payment` fails with
+    /// `CallFailure::InsufficientPrepayment`. While the timelock is enabled
+    /// the whitelist is enforced regardless of `require_whitelist`.
     pub fn execute_call(
         env: Env,
         caller: Address,
@@ -80,27 +119,50 @@ impl CrossContractInteraction {
         Self::require_admin(&env, &caller);
 
         // Validate the call request
+        let require_whitelist = timelock::whitelist_required(&env, require_whitelist);
         if let Err(e) = validate_call_request(&env, &call, require_whitelist) {
             panic_with_error!(&env, e);
         }
 
-        // Emit call initiated event
-        CrossContractEvents::call_initiated(
+        // Execute the call and handle result
+        let result = if is_quarantined(&env, &call.contract_address) {
+            Self::rejected_call(&env, CallFailure::Quarantined)
+        } else if let Some(failure) = payment_rejection(&call, None) {
+            Self::rejected_call(&env, failure)
+        } else {
+            // Emit call initiated event
+            CrossContractEvents::call_initiated(
+                &env,
+                &caller,
+                &call.contract_address,
+                &call.function_name,
+            );
+            Self::invoke_contract(&env, &call)
+        };
+
+        let receipt = receipts::record(
             &env,
-            &caller,
-            &call.contract_address,
-            &call.function_name,
+            &call,
+            &Self::args_hash(&env, &call.args),
+            result.status,
         );
 
-        // Execute the call and handle result
-        let result = Self::invoke_contract(&env, &call);
-
         // Update statistics
         Self::update_call_stats(&env, result.success);
+        if result.success {
+            Self::record_operator_calls(&env, &caller, 1, 0);
+        } else {
+            Self::record_operator_calls(&env, &caller, 0, 1);
+        }
 
         // Emit appropriate event
         if result.success {
-            CrossContractEvents::call_succeeded(&env, &call.contract_address, &call.function_name);
+            CrossContractEvents::call_succeeded(
+                &env,
+                &receipt,
+                call.call_id,
+                &Self::event_return_data(&env, &result.return_data),
+            );
         } else {
             let error_msg = result
                 .error_message
@@ -108,9 +170,10 @@ impl CrossContractInteraction {
                 .unwrap_or(Symbol::new(&env, "unknown"));
             CrossContractEvents::call_failed(
                 &env,
-                &call.contract_address,
-                &call.function_name,
+                &receipt,
+                call.call_id,
                 &error_msg,
+                result.downstream_error,
             );
         }
 
@@ -118,6 +181,16 @@ impl CrossContractInteraction {
     }
 
     /// Executes a batch of cross-contract calls
+    ///
+    /// An entry with the same target, function and arguments as an earlier
+    /// entry is reported with a `duplicate_call` event. When duplicate
+    /// skipping is enabled it is not executed and its result is
+    /// `CallStatus::DuplicateSkipped`.
+    ///
+    /// Entries that are invalid, not whitelisted (when required, and always
+    /// while the timelock is enabled) or aimed at a quarantined contract are
+    /// not executed; they fail individually with the matching `CallFailure`
+    /// instead of aborting the whole batch.
     pub fn execute_batch(
         env: Env,
         caller: Address,
@@ -129,97 +202,141 @@ impl CrossContractInteraction {
         Self::require_admin(&env, &caller);
 
         // Validate batch
-        if let Err(e) = validate_batch_calls(&env, &calls, require_whitelist) {
+        if let Err(e) = validate_batch_size(&calls) {
             panic_with_error!(&env, e);
         }
 
-        let total_calls = calls.len();
-        let mut successful_calls: u32 = 0;
-        let mut failed_calls: u32 = 0;
-        let mut results: Vec<CallResult> = Vec::new(&env);
+        let require_whitelist = timelock::whitelist_required(&env, require_whitelist);
+        Self::run_batch(&env, &caller, &calls, require_whitelist, None)
+    }
 
-        // Execute each call
-        for i in 0..total_calls {
-            let call = calls.get(i).unwrap();
+    /// Executes a batch whose calls are paid for from a prepayment
+    ///
+    /// `payer` deposits `amount` of `token`, at most the token's per-batch
+    /// spending cap. Each call's target may pull up to the call's `payment`
+    /// from the deposit while it runs; a call whose payment exceeds what is
+    /// left fails with `CallFailure::InsufficientPrepayment` without being
+    /// executed. The unspent deposit is refunded to `payer` once the batch
+    /// completes.
+    pub fn execute_prepaid_batch(
+        env: Env,
+        caller: Address,
+        payer: Address,
+        token: Address,
+        amount: i128,
+        calls: Vec<CrossContractCall>,
+        require_whitelist: bool,
+    ) -> PrepaidBatchResult {
+        // Verify authorization
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        payer.require_auth();
 
-            // Emit call initiated event
-            CrossContractEvents::call_initiated(
-                &env,
-                &caller,
-                &call.contract_address,
-                &call.function_name,
-            );
+        // Validate batch
+        if let Err(e) = validate_batch_size(&calls) {
+            panic_with_error!(&env, e);
+        }
 
-            // Execute the call
-            let result = Self::invoke_contract(&env, &call);
+        let require_whitelist = timelock::whitelist_required(&env, require_whitelist);
+        let mut prepaid = Prepayment::deposit(&env, &payer, &token, amount);
+        let batch = Self::run_batch(&env, &caller, &calls, require_whitelist, Some(&mut prepaid));
+        let refunded = prepaid.refund(&env, &payer);
 
-            // Update counters
-            if result.success {
-                successful_calls += 1;
-                CrossContractEvents::call_succeeded(
-                    &env,
-                    &call.contract_address,
-                    &call.function_name,
-                );
-            } else {
-                failed_calls += 1;
-                let error_msg = result
-                    .error_message
-                    .clone()
-                    .unwrap_or(Symbol::new(&env, "unknown"));
-                CrossContractEvents::call_failed(
-                    &env,
-                    &call.contract_address,
-                    &call.function_name,
-                    &error_msg,
-                );
+        CrossContractEvents::batch_prepaid(
+            &env,
+            &payer,
+            &token,
+            prepaid.deposited,
+            prepaid.spent,
+            refunded,
+        );
 
-                // Stop batch if continue_on_failure is false
-                if !call.continue_on_failure {
-                    results.push(result);
-                    break;
-                }
-            }
+        PrepaidBatchResult {
+            batch,
+            deposited: prepaid.deposited,
+            spent: prepaid.spent,
+            refunded,
+        }
+    }
 
-            results.push(result);
+    /// Sets the largest prepayment a batch may deposit in `token`; 0
+    /// disables prepaid batches in that token
+    pub fn set_batch_spend_cap(env: Env, caller: Address, token: Address, cap: i128) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        prepayment::set_cap(&env, &token, cap);
+
+        CrossContractEvents::spend_cap_updated(&env, &caller, &token, cap);
+    }
+
+    /// Gets the per-batch spending cap for `token` (0 when prepaid batches
+    /// are disabled for it)
+    pub fn get_batch_spend_cap(env: Env, token: Address) -> i128 {
+        prepayment::get_cap(&env, &token)
+    }
+
+    /// Executes several read-only calls in one invocation so frontends can
+    /// hydrate a dashboard with a single simulated transaction.
+    ///
+    /// Only functions registered via `set_view_function` on whitelisted
+    /// contracts may be called. Views may return any type: each successful
+    /// result carries the returned value XDR-encoded in `return_data`.
+    /// Failures are reported per call; no statistics or events are recorded.
+    pub fn aggregate_views(env: Env, calls: Vec<CrossContractCall>) -> Vec<CallResult> {
+        if let Err(e) = validate_view_calls(&env, &calls) {
+            panic_with_error!(&env, e);
         }
 
-        // Update statistics
-        Self::update_batch_stats(&env, successful_calls, failed_calls);
+        let mut results: Vec<CallResult> = Vec::new(&env);
+        for call in calls.iter() {
+            results.push_back(Self::invoke_view(&env, &call));
+        }
+        results
+    }
 
-        // Emit batch completed event
-        CrossContractEvents::batch_completed(&env, total_calls, successful_calls, failed_calls);
+    /// Allows or revokes a read-only function for `aggregate_views`
+    pub fn set_view_function(
+        env: Env,
+        caller: Address,
+        contract: Address,
+        function_name: Symbol,
+        allowed: bool,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
 
-        BatchCallResult {
-            total_calls,
-            successful_calls,
-            failed_calls,
-            results,
+        let key = DataKey::ViewFunction(contract.clone(), function_name.clone());
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
         }
+
+        CrossContractEvents::view_function_updated(&env, &contract, &function_name, allowed);
+    }
+
+    /// Checks if a function is registered as read-only for `aggregate_views`
+    pub fn is_view_function(env: Env, contract: Address, function_name: Symbol) -> bool {
+        is_view_function(&env, &contract, &function_name)
     }
 
-    /// Adds a contract to the whitelist
+    /// Adds a contract to the whitelist (only while the timelock is disabled)
     pub fn whitelist_contract(env: Env, caller: Address, contract: Address) {
         caller.require_auth();
         Self::require_admin(&env, &caller);
+        timelock::require_disabled(&env);
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Whitelist(contract.clone()), &true);
-
-        CrossContractEvents::contract_whitelisted(&env, &contract);
+        Self::apply_action(&env, AdminAction::Whitelist(contract));
     }
 
-    /// Removes a contract from the whitelist
+    /// Removes a contract from the whitelist (only while the timelock is disabled)
     pub fn remove_from_whitelist(env: Env, caller: Address, contract: Address) {
         caller.require_auth();
         Self::require_admin(&env, &caller);
+        timelock::require_disabled(&env);
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Whitelist(contract.clone()));
-
-        CrossContractEvents::contract_removed(&env, &contract);
+        Self::apply_action(&env, AdminAction::RemoveFromWhitelist(contract));
     }
 
     /// Checks if a contract is whitelisted
@@ -227,6 +344,117 @@ impl CrossContractInteraction {
         is_whitelisted(&env, &contract)
     }
 
+    /// Gets the whitelisted contracts, in the order they were added
+    pub fn get_whitelisted_contracts(env: Env) -> Vec<Address> {
+        snapshots::members(&env)
+    }
+
+    /// Saves the current whitelist as a snapshot; returns its snapshot ID
+    pub fn snapshot_whitelist(env: Env, caller: Address) -> u64 {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        snapshots::take(&env, &caller)
+    }
+
+    /// Restores the whitelist to a snapshot (only while the timelock is
+    /// disabled; otherwise queue a `RollbackWhitelist` action)
+    pub fn rollback_whitelist(env: Env, caller: Address, snapshot_id: u64) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        timelock::require_disabled(&env);
+
+        Self::apply_action(&env, AdminAction::RollbackWhitelist(snapshot_id));
+    }
+
+    /// Gets a whitelist snapshot by ID
+    pub fn get_whitelist_snapshot(env: Env, snapshot_id: u64) -> Option<WhitelistSnapshot> {
+        snapshots::get(&env, snapshot_id)
+    }
+
+    /// Gets up to `limit` whitelist snapshots starting at `start_id`, oldest first
+    pub fn get_whitelist_snapshots(env: Env, start_id: u64, limit: u32) -> Vec<WhitelistSnapshot> {
+        snapshots::page(&env, start_id, limit)
+    }
+
+    /// Gets the number of whitelist snapshots taken
+    pub fn get_snapshot_count(env: Env) -> u64 {
+        snapshots::get_last_id(&env)
+    }
+
+    /// Puts a contract into or releases it from quarantine. Calls to a
+    /// quarantined contract are refused. Unlike whitelist changes this takes
+    /// effect immediately, even with the timelock enabled, so a misbehaving
+    /// target can be isolated at once.
+    pub fn set_quarantined(env: Env, caller: Address, contract: Address, quarantined: bool) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let key = DataKey::Quarantined(contract.clone());
+        if quarantined {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        CrossContractEvents::quarantine_updated(&env, &contract, quarantined);
+    }
+
+    /// Checks if a contract is quarantined
+    pub fn is_quarantined(env: Env, contract: Address) -> bool {
+        is_quarantined(&env, &contract)
+    }
+
+    /// Sets how many bytes of downstream return data are forwarded in
+    /// `call_succeeded` events; 0 disables forwarding
+    pub fn set_return_data_event_limit(env: Env, caller: Address, max_bytes: u32) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if max_bytes > MAX_RETURN_DATA_EVENT_BYTES {
+            panic_with_error!(&env, CrossContractError::InvalidReturnDataLimit);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReturnDataEventLimit, &max_bytes);
+
+        CrossContractEvents::return_data_limit_updated(&env, &caller, max_bytes);
+    }
+
+    /// Enables or disables skipping of duplicate entries in `execute_batch`
+    pub fn set_skip_duplicate_calls(env: Env, caller: Address, enabled: bool) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::SkipDuplicateCalls, &enabled);
+
+        CrossContractEvents::skip_duplicates_updated(&env, &caller, enabled);
+    }
+
+    /// Whether duplicate batch entries are skipped (disabled by default)
+    pub fn get_skip_duplicate_calls(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::SkipDuplicateCalls)
+            .unwrap_or(false)
+    }
+
+    /// Gets the return-data forwarding limit (0 when disabled)
+    pub fn get_return_data_event_limit(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReturnDataEventLimit)
+            .unwrap_or(0)
+    }
+
+    /// Gets the receipt of the last call executed under `call_id`
+    pub fn get_call_receipt(env: Env, call_id: u64) -> Option<CallReceipt> {
+        receipts::get(&env, call_id)
+    }
+
     /// Gets the admin address
     pub fn get_admin(env: Env) -> Address {
         env.storage()
@@ -235,12 +463,58 @@ impl CrossContractInteraction {
             .unwrap_or_else(|| panic_with_error!(&env, CrossContractError::NotInitialized))
     }
 
-    /// Sets a new admin address
+    /// Sets a new admin address (only while the timelock is disabled)
     pub fn set_admin(env: Env, current_admin: Address, new_admin: Address) {
         current_admin.require_auth();
         Self::require_admin(&env, &current_admin);
+        timelock::require_disabled(&env);
 
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Self::apply_action(&env, AdminAction::SetAdmin(new_admin));
+    }
+
+    /// Enables the timelock with the given delay in seconds. Once enabled,
+    /// the delay can only be changed through a queued `SetTimelockDelay`.
+    pub fn set_timelock_delay(env: Env, caller: Address, delay: u64) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        timelock::require_disabled(&env);
+
+        Self::apply_action(&env, AdminAction::SetTimelockDelay(delay));
+    }
+
+    /// Gets the timelock delay in seconds (0 when disabled)
+    pub fn get_timelock_delay(env: Env) -> u64 {
+        timelock::get_delay(&env)
+    }
+
+    /// Queues a sensitive admin action; returns its action ID
+    pub fn queue_action(env: Env, caller: Address, action: AdminAction) -> u64 {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        timelock::queue(&env, &caller, action)
+    }
+
+    /// Executes a queued admin action once its delay has elapsed
+    pub fn execute_action(env: Env, caller: Address, action_id: u64) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let action = timelock::take_ready(&env, action_id);
+        Self::apply_action(&env, action);
+    }
+
+    /// Cancels a queued admin action
+    pub fn cancel_action(env: Env, caller: Address, action_id: u64) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        timelock::cancel(&env, &caller, action_id);
+    }
+
+    /// Gets a queued admin action by ID
+    pub fn get_queued_action(env: Env, action_id: u64) -> Option<QueuedAction> {
+        timelock::get_action(&env, action_id)
     }
 
     /// Gets total number of calls executed
@@ -267,6 +541,14 @@ impl CrossContractInteraction {
             .unwrap_or(0)
     }
 
+    /// Gets the call statistics of an operator
+    pub fn get_operator_stats(env: Env, operator: Address) -> OperatorStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OperatorStats(operator))
+            .unwrap_or_default()
+    }
+
     // Private helper functions
 
     /// Invokes an external contract
@@ -275,23 +557,256 @@ impl CrossContractInteraction {
         let result = env.try_invoke_contract::<Bytes, soroban_sdk::Error>(
             &call.contract_address,
             &call.function_name,
-            call.args.clone(),
+            Self::call_args(env, call),
         );
 
+        Self::call_result(env, call, result)
+    }
+
+    /// Invokes a read-only view, which may return any type; its value is
+    /// returned XDR-encoded
+    fn invoke_view(env: &Env, call: &CrossContractCall) -> CallResult {
+        let result = env
+            .try_invoke_contract::<Val, soroban_sdk::Error>(
+                &call.contract_address,
+                &call.function_name,
+                Self::call_args(env, call),
+            )
+            .map(|returned| returned.map(|val| val.to_xdr(env)));
+
+        Self::call_result(env, call, result)
+    }
+
+    /// Decodes the arguments of a call that passed validation
+    fn call_args(env: &Env, call: &CrossContractCall) -> Vec<Val> {
+        decode_args(env, &call.args).unwrap_or_else(|err| panic_with_error!(env, err))
+    }
+
+    /// Classifies the outcome of an invocation into a `CallResult`
+    fn call_result(
+        env: &Env,
+        call: &CrossContractCall,
+        result: Result<Result<Bytes, ConversionError>, Result<soroban_sdk::Error, InvokeError>>,
+    ) -> CallResult {
         match result {
+            Ok(Ok(return_data)) if !assertions::holds(&call.expected_return, &return_data) => {
+                CallResult {
+                    success: false,
+                    status: CallStatus::Failed,
+                    failure: CallFailure::AssertionFailed,
+                    return_data: Some(return_data),
+                    error_message: Some(Symbol::new(env, "assertion_failed")),
+                    downstream_error: None,
+                }
+            }
             Ok(Ok(return_data)) => CallResult {
                 success: true,
+                status: CallStatus::Succeeded,
+                failure: CallFailure::None,
                 return_data: Some(return_data),
                 error_message: None,
+                downstream_error: None,
+            },
+            // The downstream contract failed with its own error code
+            Err(Ok(error)) if error.is_type(ScErrorType::Contract) => CallResult {
+                success: false,
+                status: CallStatus::Failed,
+                failure: CallFailure::TargetReturnedError,
+                return_data: None,
+                error_message: Some(Symbol::new(env, "contract_error")),
+                downstream_error: Some(error.get_code()),
+            },
+            // The call returned something other than `Bytes`
+            Ok(Err(_)) => CallResult {
+                success: false,
+                status: CallStatus::Failed,
+                failure: CallFailure::TargetReturnedError,
+                return_data: None,
+                error_message: Some(Symbol::new(env, "call_failed")),
+                downstream_error: None,
             },
-            Ok(Err(_)) | Err(_) => CallResult {
+            Err(_) => CallResult {
                 success: false,
+                status: CallStatus::Failed,
+                failure: CallFailure::TargetPanicked,
                 return_data: None,
                 error_message: Some(Symbol::new(env, "call_failed")),
+                downstream_error: None,
             },
         }
     }
 
+    /// Executes the calls of a validated batch, funding calls with a payment
+    /// from `prepayment` when the batch is prepaid
+    fn run_batch(
+        env: &Env,
+        caller: &Address,
+        calls: &Vec<CrossContractCall>,
+        require_whitelist: bool,
+        mut prepayment: Option<&mut Prepayment>,
+    ) -> BatchCallResult {
+        let total_calls = calls.len();
+        let mut successful_calls: u32 = 0;
+        let mut failed_calls: u32 = 0;
+        let mut skipped_calls: u32 = 0;
+        let mut results: Vec<CallResult> = Vec::new(env);
+        let skip_duplicates = Self::get_skip_duplicate_calls(env.clone());
+        let mut seen: Map<(Address, Symbol, BytesN<32>), bool> = Map::new(env);
+
+        // Execute each call
+        for i in 0..total_calls {
+            let call = calls.get(i).unwrap();
+
+            let args_hash = Self::args_hash(env, &call.args);
+            let call_key = (
+                call.contract_address.clone(),
+                call.function_name.clone(),
+                args_hash.clone(),
+            );
+            if seen.contains_key(call_key.clone()) {
+                CrossContractEvents::duplicate_call(
+                    env,
+                    i,
+                    &call.contract_address,
+                    &call.function_name,
+                    skip_duplicates,
+                );
+                if skip_duplicates {
+                    receipts::record(env, &call, &args_hash, CallStatus::DuplicateSkipped);
+                    skipped_calls += 1;
+                    results.push_back(CallResult {
+                        success: false,
+                        status: CallStatus::DuplicateSkipped,
+                        failure: CallFailure::None,
+                        return_data: None,
+                        error_message: None,
+                        downstream_error: None,
+                    });
+                    continue;
+                }
+            } else {
+                seen.set(call_key, true);
+            }
+
+            // Execute the call unless it is rejected up front
+            let result = match rejection_reason(env, &call, require_whitelist) {
+                Some(failure) => Self::rejected_call(env, failure),
+                None => match payment_rejection(
+                    &call,
+                    prepayment.as_ref().map(|prepaid| prepaid.remaining()),
+                ) {
+                    Some(failure) => Self::rejected_call(env, failure),
+                    None => {
+                        // Emit call initiated event
+                        CrossContractEvents::call_initiated(
+                            env,
+                            caller,
+                            &call.contract_address,
+                            &call.function_name,
+                        );
+                        match prepayment.as_mut() {
+                            Some(prepaid) if call.payment > 0 => {
+                                prepaid.fund(env, &call.contract_address, call.payment, || {
+                                    Self::invoke_contract(env, &call)
+                                })
+                            }
+                            _ => Self::invoke_contract(env, &call),
+                        }
+                    }
+                },
+            };
+
+            let receipt = receipts::record(env, &call, &args_hash, result.status);
+
+            // Update counters
+            if result.success {
+                successful_calls += 1;
+                CrossContractEvents::call_succeeded(
+                    env,
+                    &receipt,
+                    call.call_id,
+                    &Self::event_return_data(env, &result.return_data),
+                );
+            } else {
+                failed_calls += 1;
+                let error_msg = result
+                    .error_message
+                    .clone()
+                    .unwrap_or(Symbol::new(env, "unknown"));
+                CrossContractEvents::call_failed(
+                    env,
+                    &receipt,
+                    call.call_id,
+                    &error_msg,
+                    result.downstream_error,
+                );
+
+                // Stop batch if continue_on_failure is false
+                if !call.continue_on_failure {
+                    results.push_back(result);
+                    break;
+                }
+            }
+
+            results.push_back(result);
+        }
+
+        // Update statistics
+        Self::update_batch_stats(env, successful_calls, failed_calls);
+        Self::record_operator_calls(env, caller, successful_calls, failed_calls);
+
+        // Emit batch completed event
+        CrossContractEvents::batch_completed(env, total_calls, successful_calls, failed_calls);
+
+        BatchCallResult {
+            total_calls,
+            successful_calls,
+            failed_calls,
+            skipped_calls,
+            results,
+        }
+    }
+
+    /// Result for a call that was refused without being executed
+    fn rejected_call(env: &Env, failure: CallFailure) -> CallResult {
+        let error_message = match failure {
+            CallFailure::NotWhitelisted => "not_whitelisted",
+            CallFailure::Quarantined => "quarantined",
+            CallFailure::InsufficientPrepayment => "insufficient_prepayment",
+            _ => "validation_failed",
+        };
+        CallResult {
+            success: false,
+            status: CallStatus::Failed,
+            failure,
+            return_data: None,
+            error_message: Some(Symbol::new(env, error_message)),
+            downstream_error: None,
+        }
+    }
+
+    /// Hashes a call's encoded arguments for duplicate detection
+    fn args_hash(env: &Env, args: &Vec<Bytes>) -> BytesN<32> {
+        env.crypto().sha256(&args.clone().to_xdr(env)).into()
+    }
+
+    /// Truncates return data to the configured event limit, or drops it when
+    /// forwarding is disabled
+    fn event_return_data(env: &Env, return_data: &Option<Bytes>) -> Option<Bytes> {
+        let limit = Self::get_return_data_event_limit(env.clone());
+        if limit == 0 {
+            return None;
+        }
+
+        return_data.as_ref().map(|data| {
+            if data.len() > limit {
+                data.slice(0..limit)
+            } else {
+                data.clone()
+            }
+        })
+    }
+
     /// Updates call statistics for a single call
     fn update_call_stats(env: &Env, success: bool) {
         let total_calls: u64 = env
@@ -354,6 +869,59 @@ impl CrossContractInteraction {
             .set(&DataKey::FailedCalls, &(total_failed + failed as u64));
     }
 
+    /// Updates an operator's call statistics and emits an `operator_summary`
+    /// event each time its total crosses a multiple of `OPERATOR_SUMMARY_INTERVAL`
+    fn record_operator_calls(env: &Env, operator: &Address, successful: u32, failed: u32) {
+        let calls = successful as u64 + failed as u64;
+        if calls == 0 {
+            return;
+        }
+
+        let key = DataKey::OperatorStats(operator.clone());
+        let mut stats: OperatorStats = env.storage().persistent().get(&key).unwrap_or_default();
+        let previous_total = stats.total_calls;
+
+        stats.total_calls += calls;
+        stats.successful_calls += successful as u64;
+        stats.failed_calls += failed as u64;
+        stats.success_rate_bps = (stats.successful_calls * 10_000 / stats.total_calls) as u32;
+        stats.last_call_at = env.ledger().timestamp();
+
+        env.storage().persistent().set(&key, &stats);
+
+        if stats.total_calls / OPERATOR_SUMMARY_INTERVAL
+            > previous_total / OPERATOR_SUMMARY_INTERVAL
+        {
+            CrossContractEvents::operator_summary(env, operator, &stats);
+        }
+    }
+
+    /// Applies an admin action, whether called directly or via the timelock
+    fn apply_action(env: &Env, action: AdminAction) {
+        match action {
+            AdminAction::Whitelist(contract) => {
+                snapshots::add(env, &contract);
+                CrossContractEvents::contract_whitelisted(env, &contract);
+            }
+            AdminAction::RemoveFromWhitelist(contract) => {
+                snapshots::remove(env, &contract);
+                CrossContractEvents::contract_removed(env, &contract);
+            }
+            AdminAction::RollbackWhitelist(snapshot_id) => {
+                snapshots::rollback(env, snapshot_id);
+            }
+            AdminAction::SetAdmin(new_admin) => {
+                env.storage().instance().set(&DataKey::Admin, &new_admin);
+            }
+            AdminAction::SetTimelockDelay(delay) => {
+                env.storage()
+                    .instance()
+                    .set(&DataKey::TimelockDelay, &delay);
+                CrossContractEvents::timelock_delay_updated(env, delay);
+            }
+        }
+    }
+
     /// Requires that the caller is the admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
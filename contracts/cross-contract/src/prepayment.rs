@@ -0,0 +1,96 @@
+//! Batch prepayments.
+//!
+//! A payer deposits tokens for a single batch and the contract acts as the
+//! payer for the batch's calls: each call's target is allowed to pull up to
+//! the call's `payment` from the deposit while it runs. Whatever the calls
+//! did not pull is refunded to the payer when the batch settles, so no
+//! account other than the payer ever funds downstream calls.
+
+use soroban_sdk::{panic_with_error, token, Address, Env};
+
+use crate::types::{CallResult, DataKey};
+use crate::CrossContractError;
+
+pub fn get_cap(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BatchSpendCap(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Sets the largest prepayment a batch may deposit in `token`; 0 disables
+/// prepaid batches in that token
+pub fn set_cap(env: &Env, token: &Address, cap: i128) {
+    if cap < 0 {
+        panic_with_error!(env, CrossContractError::InvalidPrepayment);
+    }
+
+    let key = DataKey::BatchSpendCap(token.clone());
+    if cap == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &cap);
+    }
+}
+
+/// Tokens deposited for one batch and how much of them its calls pulled
+pub struct Prepayment {
+    pub token: Address,
+    pub deposited: i128,
+    pub spent: i128,
+}
+
+impl Prepayment {
+    /// Moves `amount` of `token` from the payer into the contract, within
+    /// the token's per-batch cap
+    pub fn deposit(env: &Env, payer: &Address, token: &Address, amount: i128) -> Self {
+        if amount <= 0 || amount > get_cap(env, token) {
+            panic_with_error!(env, CrossContractError::InvalidPrepayment);
+        }
+
+        token::Client::new(env, token).transfer(payer, &env.current_contract_address(), &amount);
+        Prepayment {
+            token: token.clone(),
+            deposited: amount,
+            spent: 0,
+        }
+    }
+
+    pub fn remaining(&self) -> i128 {
+        self.deposited - self.spent
+    }
+
+    /// Lets `target` pull up to `amount` of the deposit while `invoke` runs,
+    /// then revokes the allowance and records what was pulled
+    pub fn fund(
+        &mut self,
+        env: &Env,
+        target: &Address,
+        amount: i128,
+        invoke: impl FnOnce() -> CallResult,
+    ) -> CallResult {
+        let client = token::Client::new(env, &self.token);
+        let this = env.current_contract_address();
+        let balance_before = client.balance(&this);
+
+        client.approve(&this, target, &amount, &env.ledger().sequence());
+        let result = invoke();
+        client.approve(&this, target, &0, &env.ledger().sequence());
+
+        self.spent += balance_before - client.balance(&this);
+        result
+    }
+
+    /// Returns the unspent deposit to the payer
+    pub fn refund(&self, env: &Env, payer: &Address) -> i128 {
+        let refunded = self.remaining();
+        if refunded > 0 {
+            token::Client::new(env, &self.token).transfer(
+                &env.current_contract_address(),
+                payer,
+                &refunded,
+            );
+        }
+        refunded
+    }
+}
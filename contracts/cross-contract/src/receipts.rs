@@ -0,0 +1,49 @@
+//! Execution receipts for off-chain workflow engines.
+//!
+//! Every executed or rejected call gets a receipt hash over its target,
+//! function, arguments hash, status and ledger, published with the call
+//! event. Calls tagged with a `call_id` also keep their last receipt in
+//! storage, so an engine can check which on-chain action settled a job.
+
+use soroban_sdk::{xdr::ToXdr, Bytes, BytesN, Env};
+
+use crate::types::{CallReceipt, CallStatus, CrossContractCall, DataKey};
+
+pub fn get(env: &Env, call_id: u64) -> Option<CallReceipt> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CallReceipt(call_id))
+}
+
+/// Builds the receipt for `call` finishing with `status` in the current
+/// ledger, storing it under the call's ID if it has one.
+pub fn record(
+    env: &Env,
+    call: &CrossContractCall,
+    args_hash: &BytesN<32>,
+    status: CallStatus,
+) -> CallReceipt {
+    let ledger = env.ledger().sequence();
+
+    let mut preimage = Bytes::new(env);
+    preimage.append(&call.contract_address.clone().to_xdr(env));
+    preimage.append(&call.function_name.clone().to_xdr(env));
+    preimage.append(&Bytes::from_array(env, &args_hash.to_array()));
+    preimage.append(&Bytes::from_array(env, &(status as u32).to_be_bytes()));
+    preimage.append(&Bytes::from_array(env, &ledger.to_be_bytes()));
+
+    let receipt = CallReceipt {
+        receipt_hash: env.crypto().sha256(&preimage).into(),
+        target: call.contract_address.clone(),
+        function_name: call.function_name.clone(),
+        args_hash: args_hash.clone(),
+        status,
+        ledger,
+    };
+    if let Some(call_id) = call.call_id {
+        env.storage()
+            .persistent()
+            .set(&DataKey::CallReceipt(call_id), &receipt);
+    }
+    receipt
+}
@@ -0,0 +1,130 @@
+//! Versioned whitelist snapshots.
+//!
+//! The whitelisted contracts are tracked in a list next to the per-contract
+//! flags so the whole set can be captured with `snapshot_whitelist` and
+//! restored later with a rollback, e.g. after a large whitelist change went
+//! wrong. Contracts whitelisted before the list existed are not part of it
+//! until they are whitelisted again.
+
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+use crate::types::{CrossContractEvents, DataKey, WhitelistSnapshot};
+use crate::CrossContractError;
+
+/// Returns the currently whitelisted contracts, in the order they were added.
+pub fn members(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WhitelistMembers)
+        .unwrap_or(Vec::new(env))
+}
+
+fn save_members(env: &Env, members: &Vec<Address>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WhitelistMembers, members);
+}
+
+/// Whitelists `contract` and tracks it in the member list.
+pub fn add(env: &Env, contract: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Whitelist(contract.clone()), &true);
+
+    let mut members = members(env);
+    if !members.contains(contract) {
+        members.push_back(contract.clone());
+        save_members(env, &members);
+    }
+}
+
+/// Removes `contract` from the whitelist and the member list.
+pub fn remove(env: &Env, contract: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Whitelist(contract.clone()));
+
+    let mut members = members(env);
+    if let Some(index) = members.first_index_of(contract) {
+        members.remove(index);
+        save_members(env, &members);
+    }
+}
+
+pub fn get_last_id(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::LastSnapshotId)
+        .unwrap_or(0)
+}
+
+pub fn get(env: &Env, snapshot_id: u64) -> Option<WhitelistSnapshot> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WhitelistSnapshot(snapshot_id))
+}
+
+/// Stores the current whitelist as a new snapshot and returns its ID.
+pub fn take(env: &Env, caller: &Address) -> u64 {
+    let snapshot_id = get_last_id(env) + 1;
+    let snapshot = WhitelistSnapshot {
+        snapshot_id,
+        contracts: members(env),
+        created_at: env.ledger().timestamp(),
+        created_by: caller.clone(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::WhitelistSnapshot(snapshot_id), &snapshot);
+    env.storage()
+        .instance()
+        .set(&DataKey::LastSnapshotId, &snapshot_id);
+
+    CrossContractEvents::whitelist_snapshot_taken(env, snapshot_id, snapshot.contracts.len());
+    snapshot_id
+}
+
+/// Returns up to `limit` snapshots starting at `start_id`, oldest first.
+pub fn page(env: &Env, start_id: u64, limit: u32) -> Vec<WhitelistSnapshot> {
+    let mut snapshots = Vec::new(env);
+    let last_id = get_last_id(env);
+    let mut snapshot_id = start_id.max(1);
+    while snapshot_id <= last_id && snapshots.len() < limit {
+        if let Some(snapshot) = get(env, snapshot_id) {
+            snapshots.push_back(snapshot);
+        }
+        snapshot_id += 1;
+    }
+    snapshots
+}
+
+/// Restores the whitelist to the contents of `snapshot_id`, emitting the
+/// contracts that were added back and removed.
+pub fn rollback(env: &Env, snapshot_id: u64) {
+    let snapshot = get(env, snapshot_id)
+        .unwrap_or_else(|| panic_with_error!(env, CrossContractError::SnapshotNotFound));
+    let current = members(env);
+
+    let mut removed = Vec::new(env);
+    for contract in current.iter() {
+        if !snapshot.contracts.contains(&contract) {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Whitelist(contract.clone()));
+            removed.push_back(contract);
+        }
+    }
+
+    let mut added = Vec::new(env);
+    for contract in snapshot.contracts.iter() {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Whitelist(contract.clone()), &true);
+        if !current.contains(&contract) {
+            added.push_back(contract);
+        }
+    }
+
+    save_members(env, &snapshot.contracts);
+    CrossContractEvents::whitelist_rolled_back(env, snapshot_id, &added, &removed);
+}
@@ -2,12 +2,21 @@
 
 #![cfg(test)]
 
+extern crate std;
+
 use crate::{
-    types::{CallResult, CrossContractCall, MAX_BATCH_CALLS},
+    types::{
+        AdminAction, CallFailure, CallStatus, CrossContractCall, OperatorStats, ReturnPredicate,
+        MAX_BATCH_CALLS,
+    },
     CrossContractError, CrossContractInteraction, CrossContractInteractionClient,
 };
 use soroban_sdk::{
-    contract, contractimpl, testutils::Address as _, Address, Bytes, Env, Symbol, Vec,
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::{self, StellarAssetClient},
+    xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Vec,
 };
 
 // Mock external contract for testing
@@ -30,6 +39,41 @@ impl MockExternalContract {
     pub fn no_params(_env: Env) -> Symbol {
         Symbol::new(&_env, "success")
     }
+
+    /// Function returning raw bytes
+    pub fn bytes_result(env: Env) -> Bytes {
+        Bytes::from_array(&env, &[1, 2, 3, 4, 5, 6, 7, 8])
+    }
+
+    /// Getter returning a balance
+    pub fn balance(_env: Env) -> i128 {
+        1_234_567_890_123
+    }
+}
+
+// Mock paid service pulling a fixed fee from the caller's allowance
+#[contract]
+pub struct MockPaidService;
+
+#[contractimpl]
+impl MockPaidService {
+    pub fn configure(env: Env, token: Address, payer: Address, fee: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("config"), &(token, payer, fee));
+    }
+
+    /// Pulls the fee from the configured payer
+    pub fn collect(env: Env) -> Bytes {
+        let (token, payer, fee): (Address, Address, i128) = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("config"))
+            .unwrap();
+        let this = env.current_contract_address();
+        token::Client::new(&env, &token).transfer_from(&this, &payer, &this, &fee);
+        Bytes::new(&env)
+    }
 }
 
 fn create_test_env() -> (Env, Address, Address, Address) {
@@ -99,6 +143,74 @@ fn test_remove_from_whitelist() {
     assert!(!client.is_whitelisted(&external_contract));
 }
 
+#[test]
+fn test_whitelist_snapshot_and_rollback() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let kept = Address::generate(&env);
+    let dropped = Address::generate(&env);
+    let added_later = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.whitelist_contract(&admin, &kept);
+    client.whitelist_contract(&admin, &dropped);
+    let snapshot_id = client.snapshot_whitelist(&admin);
+    assert_eq!(snapshot_id, 1);
+
+    // A whitelist change that went wrong
+    client.remove_from_whitelist(&admin, &dropped);
+    client.whitelist_contract(&admin, &added_later);
+    assert_eq!(client.get_whitelisted_contracts().len(), 2);
+
+    client.rollback_whitelist(&admin, &snapshot_id);
+
+    // The rollback event carries the diff it applied
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (added, removed): (Vec<Address>, Vec<Address>) =
+        TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!(added, Vec::from_array(&env, [dropped.clone()]));
+    assert_eq!(removed, Vec::from_array(&env, [added_later.clone()]));
+
+    assert!(client.is_whitelisted(&kept));
+    assert!(client.is_whitelisted(&dropped));
+    assert!(!client.is_whitelisted(&added_later));
+    assert_eq!(client.get_whitelisted_contracts().len(), 2);
+
+    // Snapshots can be enumerated
+    client.snapshot_whitelist(&admin);
+    assert_eq!(client.get_snapshot_count(), 2);
+    let snapshots = client.get_whitelist_snapshots(&1, &10);
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots.get(0).unwrap().contracts.len(), 2);
+    assert_eq!(client.get_whitelist_snapshots(&2, &10).len(), 1);
+
+    assert_eq!(
+        client.try_rollback_whitelist(&admin, &3),
+        Err(Ok(CrossContractError::SnapshotNotFound.into()))
+    );
+}
+
+#[test]
+fn test_whitelist_rollback_is_timelocked() {
+    let (env, admin, _, external_contract) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    let snapshot_id = client.snapshot_whitelist(&admin);
+    client.whitelist_contract(&admin, &external_contract);
+    client.set_timelock_delay(&admin, &3600);
+
+    assert!(client.try_rollback_whitelist(&admin, &snapshot_id).is_err());
+
+    let action_id = client.queue_action(&admin, &AdminAction::RollbackWhitelist(snapshot_id));
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.execute_action(&admin, &action_id);
+    assert!(!client.is_whitelisted(&external_contract));
+}
+
 #[test]
 fn test_execute_call_without_whitelist() {
     let (env, admin, _, _) = create_test_env();
@@ -115,9 +227,12 @@ fn test_execute_call_without_whitelist() {
         function_name: Symbol::new(&env, "test_function"),
         args: Vec::new(&env),
         continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     };
 
-    let result = client.execute_call(&admin, &call, &false);
+    client.execute_call(&admin, &call, &false);
 
     // The call should execute (though it may fail due to argument mismatch)
     assert_eq!(client.get_total_calls(), 1);
@@ -138,6 +253,9 @@ fn test_execute_call_with_whitelist_not_whitelisted() {
         function_name: Symbol::new(&env, "test_function"),
         args: Vec::new(&env),
         continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     };
 
     // Should panic because contract is not whitelisted
@@ -164,9 +282,12 @@ fn test_execute_call_with_whitelist_whitelisted() {
         function_name: Symbol::new(&env, "no_params"),
         args: Vec::new(&env),
         continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     };
 
-    let result = client.execute_call(&admin, &call, &true);
+    client.execute_call(&admin, &call, &true);
 
     assert_eq!(client.get_total_calls(), 1);
 }
@@ -208,6 +329,9 @@ fn test_execute_batch_too_large() {
             function_name: Symbol::new(&env, "test_function"),
             args: Vec::new(&env),
             continue_on_failure: true,
+            call_id: None,
+            expected_return: ReturnPredicate::None,
+            payment: 0,
         });
     }
 
@@ -237,6 +361,9 @@ fn test_execute_batch_continue_on_failure() {
         function_name: Symbol::new(&env, "no_params"),
         args: Vec::new(&env),
         continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     });
 
     // Add a call that will fail
@@ -245,6 +372,9 @@ fn test_execute_batch_continue_on_failure() {
         function_name: Symbol::new(&env, "failing_function"),
         args: Vec::new(&env),
         continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     });
 
     // Add another call that will succeed
@@ -253,6 +383,9 @@ fn test_execute_batch_continue_on_failure() {
         function_name: Symbol::new(&env, "no_params"),
         args: Vec::new(&env),
         continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     });
 
     let result = client.execute_batch(&admin, &calls, &false);
@@ -277,9 +410,12 @@ fn test_execute_batch_stop_on_failure() {
     // Add a call that will succeed
     calls.push_back(CrossContractCall {
         contract_address: external_id.clone(),
-        function_name: Symbol::new(&env, "no_params"),
+        function_name: Symbol::new(&env, "bytes_result"),
         args: Vec::new(&env),
         continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     });
 
     // Add a call that will fail with continue_on_failure = false
@@ -288,6 +424,9 @@ fn test_execute_batch_stop_on_failure() {
         function_name: Symbol::new(&env, "failing_function"),
         args: Vec::new(&env),
         continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     });
 
     // Add another call (should not be executed)
@@ -296,6 +435,9 @@ fn test_execute_batch_stop_on_failure() {
         function_name: Symbol::new(&env, "no_params"),
         args: Vec::new(&env),
         continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     });
 
     let result = client.execute_batch(&admin, &calls, &false);
@@ -320,6 +462,103 @@ fn test_set_admin() {
     assert_eq!(client.get_admin(), user);
 }
 
+#[test]
+fn test_timelocked_admin_actions() {
+    let (env, admin, user, external_contract) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.set_timelock_delay(&admin, &3600);
+    assert_eq!(client.get_timelock_delay(), 3600);
+
+    let whitelist_id =
+        client.queue_action(&admin, &AdminAction::Whitelist(external_contract.clone()));
+    let admin_id = client.queue_action(&admin, &AdminAction::SetAdmin(user.clone()));
+    let queued = client.get_queued_action(&whitelist_id).unwrap();
+    assert_eq!(queued.eta, env.ledger().timestamp() + 3600);
+
+    // Not executable before the delay has elapsed
+    assert!(client.try_execute_action(&admin, &whitelist_id).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.execute_action(&admin, &whitelist_id);
+    assert!(client.is_whitelisted(&external_contract));
+    assert_eq!(client.get_queued_action(&whitelist_id), None);
+
+    // A cancelled action can no longer be executed
+    client.cancel_action(&admin, &admin_id);
+    assert!(client.try_execute_action(&admin, &admin_id).is_err());
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_timelock_blocks_direct_admin_changes() {
+    let (env, admin, user, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.set_timelock_delay(&admin, &3600);
+
+    client.set_admin(&admin, &user);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_timelocked_action_expires() {
+    let (env, admin, user, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.set_timelock_delay(&admin, &3600);
+    let action_id = client.queue_action(&admin, &AdminAction::SetAdmin(user));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += 3600 + crate::TIMELOCK_GRACE_PERIOD + 1);
+    client.execute_action(&admin, &action_id);
+}
+
+#[test]
+fn test_timelock_enforces_whitelist_on_calls() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    client.set_timelock_delay(&admin, &3600);
+
+    let call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "bytes_result"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+
+    // Opting out of the whitelist has no effect while the timelock is on
+    assert_eq!(
+        client.try_execute_call(&admin, &call, &false).err(),
+        Some(Ok(CrossContractError::ContractNotWhitelisted.into()))
+    );
+    let result = client.execute_batch(&admin, &soroban_sdk::vec![&env, call.clone()], &false);
+    assert_eq!(
+        result.results.get(0).unwrap().failure,
+        CallFailure::NotWhitelisted
+    );
+
+    // Whitelisting has to go through the queue
+    let action_id = client.queue_action(&admin, &AdminAction::Whitelist(external_id.clone()));
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.execute_action(&admin, &action_id);
+    assert!(client.execute_call(&admin, &call, &false).success);
+}
+
 #[test]
 fn test_statistics_tracking() {
     let (env, admin, _, _) = create_test_env();
@@ -336,9 +575,12 @@ fn test_statistics_tracking() {
     for _ in 0..3 {
         calls.push_back(CrossContractCall {
             contract_address: external_id.clone(),
-            function_name: Symbol::new(&env, "no_params"),
+            function_name: Symbol::new(&env, "bytes_result"),
             args: Vec::new(&env),
             continue_on_failure: true,
+            call_id: None,
+            expected_return: ReturnPredicate::None,
+            payment: 0,
         });
     }
 
@@ -349,6 +591,9 @@ fn test_statistics_tracking() {
             function_name: Symbol::new(&env, "failing_function"),
             args: Vec::new(&env),
             continue_on_failure: true,
+            call_id: None,
+            expected_return: ReturnPredicate::None,
+            payment: 0,
         });
     }
 
@@ -374,11 +619,675 @@ fn test_events_emitted() {
         function_name: Symbol::new(&env, "no_params"),
         args: Vec::new(&env),
         continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
     };
 
     client.execute_call(&admin, &call, &false);
 
     // Verify events were emitted
     let events = env.events().all();
-    assert!(events.len() > 0);
+    assert!(!events.is_empty());
+}
+
+#[test]
+fn test_set_return_data_event_limit() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    assert_eq!(client.get_return_data_event_limit(), 0);
+
+    client.set_return_data_event_limit(&admin, &64);
+    assert_eq!(client.get_return_data_event_limit(), 64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_set_return_data_event_limit_too_large() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    client.initialize(&admin);
+    client.set_return_data_event_limit(&admin, &(crate::MAX_RETURN_DATA_EVENT_BYTES + 1));
+}
+
+#[test]
+fn test_call_succeeded_event_forwards_truncated_return_data() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    client.set_return_data_event_limit(&admin, &4);
+
+    let call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "bytes_result"),
+        args: Vec::new(&env),
+        continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+
+    let result = client.execute_call(&admin, &call, &false);
+    assert!(result.success);
+
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (_, _, forwarded): (Address, Symbol, Option<Bytes>) =
+        TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!(forwarded, Some(Bytes::from_array(&env, &[1, 2, 3, 4])));
+}
+
+#[test]
+fn test_failed_call_surfaces_downstream_error_code() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+
+    let call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "failing_function"),
+        args: Vec::new(&env),
+        continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+
+    let result = client.execute_call(&admin, &call, &false);
+    assert!(!result.success);
+    assert_eq!(result.downstream_error, Some(999));
+    assert_eq!(
+        result.error_message,
+        Some(Symbol::new(&env, "contract_error"))
+    );
+
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (_, _, _, downstream_error): (Address, Symbol, Symbol, Option<u32>) =
+        TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!(downstream_error, Some(999));
+}
+
+#[test]
+fn test_missing_function_has_no_downstream_error() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+
+    let call = CrossContractCall {
+        contract_address: external_id,
+        function_name: Symbol::new(&env, "does_not_exist"),
+        args: Vec::new(&env),
+        continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+
+    let result = client.execute_call(&admin, &call, &false);
+    assert!(!result.success);
+    assert_eq!(result.downstream_error, None);
+    assert_eq!(result.error_message, Some(Symbol::new(&env, "call_failed")));
+}
+
+#[test]
+fn test_aggregate_views() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    client.whitelist_contract(&admin, &external_id);
+    client.set_view_function(
+        &admin,
+        &external_id,
+        &Symbol::new(&env, "bytes_result"),
+        &true,
+    );
+    client.set_view_function(
+        &admin,
+        &external_id,
+        &Symbol::new(&env, "failing_function"),
+        &true,
+    );
+
+    let mut calls: Vec<CrossContractCall> = Vec::new(&env);
+    calls.push_back(CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "bytes_result"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    });
+    calls.push_back(CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "failing_function"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    });
+
+    let results = client.aggregate_views(&calls);
+    assert_eq!(results.len(), 2);
+    assert!(results.get(0).unwrap().success);
+    assert!(!results.get(1).unwrap().success);
+
+    // Getters of any type are returned XDR-encoded
+    client.set_view_function(&admin, &external_id, &Symbol::new(&env, "balance"), &true);
+    let mut calls: Vec<CrossContractCall> = Vec::new(&env);
+    calls.push_back(CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "balance"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    });
+    let result = client.aggregate_views(&calls).get(0).unwrap();
+    assert!(result.success);
+    let balance = i128::from_xdr(&env, &result.return_data.unwrap()).unwrap();
+    assert_eq!(balance, 1_234_567_890_123);
+
+    // Views are not counted as executed calls
+    assert_eq!(client.get_total_calls(), 0);
+}
+
+#[test]
+fn test_call_arguments_are_xdr_decoded() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    client.whitelist_contract(&admin, &external_id);
+    client.set_view_function(
+        &admin,
+        &external_id,
+        &Symbol::new(&env, "test_function"),
+        &true,
+    );
+
+    let mut call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "test_function"),
+        args: Vec::from_array(&env, [21u32.to_xdr(&env)]),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+    let result = client
+        .aggregate_views(&Vec::from_array(&env, [call.clone()]))
+        .get(0)
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(
+        u32::from_xdr(&env, &result.return_data.unwrap()).unwrap(),
+        42
+    );
+
+    // Arguments that are not XDR-encoded values are rejected up front
+    call.args = Vec::from_array(&env, [Bytes::from_array(&env, &[1, 2, 3])]);
+    assert!(client.try_execute_call(&admin, &call, &true).is_err());
+    assert_eq!(client.get_total_calls(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_aggregate_views_rejects_unregistered_function() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    client.whitelist_contract(&admin, &external_id);
+    client.set_view_function(&admin, &external_id, &Symbol::new(&env, "no_params"), &true);
+    client.set_view_function(
+        &admin,
+        &external_id,
+        &Symbol::new(&env, "no_params"),
+        &false,
+    );
+    assert!(!client.is_view_function(&external_id, &Symbol::new(&env, "no_params")));
+
+    let mut calls: Vec<CrossContractCall> = Vec::new(&env);
+    calls.push_back(CrossContractCall {
+        contract_address: external_id,
+        function_name: Symbol::new(&env, "no_params"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    });
+
+    client.aggregate_views(&calls);
+}
+
+#[test]
+fn test_execute_batch_skips_duplicate_calls() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+
+    let bytes_call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "bytes_result"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+    let mut calls: Vec<CrossContractCall> = Vec::new(&env);
+    calls.push_back(bytes_call.clone());
+    calls.push_back(CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "no_params"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    });
+    calls.push_back(bytes_call);
+
+    // Disabled by default: the duplicate is reported but still executed
+    assert!(!client.get_skip_duplicate_calls());
+    let result = client.execute_batch(&admin, &calls, &false);
+    assert_eq!(result.skipped_calls, 0);
+    assert_eq!(result.results.get(2).unwrap().status, CallStatus::Succeeded);
+
+    client.set_skip_duplicate_calls(&admin, &true);
+    let result = client.execute_batch(&admin, &calls, &false);
+    assert_eq!(result.total_calls, 3);
+    assert_eq!(result.skipped_calls, 1);
+    assert_eq!(result.results.len(), 3);
+    assert_eq!(result.results.get(0).unwrap().status, CallStatus::Succeeded);
+    assert_eq!(
+        result.results.get(2).unwrap().status,
+        CallStatus::DuplicateSkipped
+    );
+    assert!(!result.results.get(2).unwrap().success);
+}
+
+#[test]
+fn test_operator_stats_and_summary() {
+    let (env, admin, _, _) = create_test_env();
+    env.budget().reset_unlimited();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    assert_eq!(client.get_operator_stats(&admin), OperatorStats::default());
+
+    let ok_call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "bytes_result"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+    let failing_call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "failing_function"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.execute_call(&admin, &ok_call, &false);
+    client.execute_call(&admin, &failing_call, &false);
+
+    let stats = client.get_operator_stats(&admin);
+    assert_eq!(stats.total_calls, 2);
+    assert_eq!(stats.successful_calls, 1);
+    assert_eq!(stats.failed_calls, 1);
+    assert_eq!(stats.success_rate_bps, 5_000);
+    assert_eq!(stats.last_call_at, 1_000);
+
+    let summary_topic = Symbol::new(&env, "operator_summary");
+    let has_summary = |env: &Env| {
+        env.events().all().iter().any(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get(0).unwrap()).ok() == Some(summary_topic.clone())
+        })
+    };
+    assert!(!has_summary(&env));
+
+    let mut calls: Vec<CrossContractCall> = Vec::new(&env);
+    for _ in 0..MAX_BATCH_CALLS {
+        calls.push_back(ok_call.clone());
+    }
+    client.execute_batch(&admin, &calls, &false);
+    assert!(!has_summary(&env));
+
+    // Crossing 100 calls emits the periodic summary
+    client.execute_batch(&admin, &calls, &false);
+    assert!(has_summary(&env));
+
+    let stats = client.get_operator_stats(&admin);
+    assert_eq!(stats.total_calls, 102);
+    assert_eq!(stats.successful_calls, 101);
+    assert_eq!(stats.success_rate_bps, 9_901);
+}
+
+#[test]
+fn test_execute_batch_classifies_failures() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+    let quarantined_id = env.register_contract(None, MockExternalContract);
+    let unlisted_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    client.whitelist_contract(&admin, &external_id);
+    client.whitelist_contract(&admin, &quarantined_id);
+    client.set_quarantined(&admin, &quarantined_id, &true);
+    assert!(client.is_quarantined(&quarantined_id));
+
+    let call = |contract: &Address, function: &str| CrossContractCall {
+        contract_address: contract.clone(),
+        function_name: Symbol::new(&env, function),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+    let mut calls: Vec<CrossContractCall> = Vec::new(&env);
+    calls.push_back(call(&external_id, "bytes_result"));
+    calls.push_back(call(&unlisted_id, "bytes_result"));
+    calls.push_back(call(&quarantined_id, "bytes_result"));
+    calls.push_back(call(&external_id, "failing_function"));
+    calls.push_back(call(&external_id, "does_not_exist"));
+
+    let result = client.execute_batch(&admin, &calls, &true);
+    assert_eq!(result.successful_calls, 1);
+    assert_eq!(result.failed_calls, 4);
+
+    let failures: std::vec::Vec<CallFailure> = result.results.iter().map(|r| r.failure).collect();
+    assert_eq!(
+        failures,
+        std::vec![
+            CallFailure::None,
+            CallFailure::NotWhitelisted,
+            CallFailure::Quarantined,
+            CallFailure::TargetReturnedError,
+            CallFailure::TargetPanicked,
+        ]
+    );
+
+    // Releasing the quarantine lets calls through again
+    client.set_quarantined(&admin, &quarantined_id, &false);
+    let result = client.execute_call(&admin, &call(&quarantined_id, "bytes_result"), &true);
+    assert_eq!(result.failure, CallFailure::None);
+}
+
+#[test]
+fn test_execute_call_refuses_quarantined_contract() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+    client.set_quarantined(&admin, &external_id, &true);
+
+    let call = CrossContractCall {
+        contract_address: external_id,
+        function_name: Symbol::new(&env, "no_params"),
+        args: Vec::new(&env),
+        continue_on_failure: false,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+    let result = client.execute_call(&admin, &call, &false);
+    assert!(!result.success);
+    assert_eq!(result.failure, CallFailure::Quarantined);
+    assert_eq!(result.error_message, Some(Symbol::new(&env, "quarantined")));
+    assert_eq!(client.get_failed_calls(), 1);
+}
+
+#[test]
+fn test_call_receipt_stored_per_call_id_and_published() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+
+    let mut call = CrossContractCall {
+        contract_address: external_id.clone(),
+        function_name: Symbol::new(&env, "failing_function"),
+        args: Vec::new(&env),
+        continue_on_failure: false,
+        call_id: Some(7),
+        expected_return: ReturnPredicate::None,
+        payment: 0,
+    };
+    client.execute_call(&admin, &call, &false);
+    let failed = client.get_call_receipt(&7).unwrap();
+    assert_eq!(failed.status, CallStatus::Failed);
+
+    // A retry of the job replaces its receipt
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    call.function_name = Symbol::new(&env, "bytes_result");
+    client.execute_call(&admin, &call, &false);
+
+    let (_, topics, _) = env.events().all().last().unwrap();
+    let receipt = client.get_call_receipt(&7).unwrap();
+    assert_eq!(receipt.status, CallStatus::Succeeded);
+    assert_eq!(receipt.target, external_id);
+    assert_eq!(receipt.ledger, env.ledger().sequence());
+    assert_ne!(receipt.receipt_hash, failed.receipt_hash);
+    let published: BytesN<32> = TryFromVal::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(published, receipt.receipt_hash);
+
+    // Untagged calls publish a receipt hash but store nothing
+    call.call_id = None;
+    client.execute_call(&admin, &call, &false);
+    assert_eq!(client.get_call_receipt(&7), Some(receipt));
+}
+
+#[test]
+fn test_return_predicates_mark_unexpected_results_failed() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+
+    let external_id = env.register_contract(None, MockExternalContract);
+
+    client.initialize(&admin);
+
+    // `bytes_result` returns 0x0102030405060708
+    let returned = Bytes::from_array(&env, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    let predicates = [
+        (ReturnPredicate::NonEmpty, true),
+        (ReturnPredicate::Equals(returned.clone()), true),
+        (
+            ReturnPredicate::Equals(Bytes::from_array(&env, &[1, 2, 3])),
+            false,
+        ),
+        (ReturnPredicate::AtLeast(0x0102030405060708), true),
+        (ReturnPredicate::AtLeast(0x0102030405060709), false),
+    ];
+
+    let mut calls = Vec::new(&env);
+    for (predicate, _) in predicates.iter() {
+        calls.push_back(CrossContractCall {
+            contract_address: external_id.clone(),
+            function_name: Symbol::new(&env, "bytes_result"),
+            args: Vec::new(&env),
+            continue_on_failure: true,
+            call_id: None,
+            expected_return: predicate.clone(),
+            payment: 0,
+        });
+    }
+    let batch = client.execute_batch(&admin, &calls, &false);
+    assert_eq!(batch.successful_calls, 3);
+    assert_eq!(batch.failed_calls, 2);
+    for (i, (_, holds)) in predicates.iter().enumerate() {
+        let result = batch.results.get(i as u32).unwrap();
+        assert_eq!(result.success, *holds);
+        assert_eq!(result.return_data, Some(returned.clone()));
+        if !holds {
+            assert_eq!(result.status, CallStatus::Failed);
+            assert_eq!(result.failure, CallFailure::AssertionFailed);
+            assert_eq!(
+                result.error_message,
+                Some(Symbol::new(&env, "assertion_failed"))
+            );
+        }
+    }
+
+    // A single call failing its predicate counts as a failed call and is
+    // receipted as such
+    let call = CrossContractCall {
+        contract_address: external_id,
+        function_name: Symbol::new(&env, "bytes_result"),
+        args: Vec::new(&env),
+        continue_on_failure: false,
+        call_id: Some(3),
+        expected_return: ReturnPredicate::AtLeast(u128::MAX),
+        payment: 0,
+    };
+    let result = client.execute_call(&admin, &call, &false);
+    assert!(!result.success);
+    assert_eq!(result.failure, CallFailure::AssertionFailed);
+    assert_eq!(
+        client.get_call_receipt(&3).unwrap().status,
+        CallStatus::Failed
+    );
+    assert_eq!(client.get_failed_calls(), 3);
+}
+
+#[test]
+fn test_prepaid_batch_funds_calls_and_refunds_unspent() {
+    let (env, admin, payer, _) = create_test_env();
+    let contract_id = env.register_contract(None, CrossContractInteraction);
+    let client = CrossContractInteractionClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token_id).mint(&payer, &1_000);
+    let balances = token::Client::new(&env, &token_id);
+
+    let service_id = env.register_contract(None, MockPaidService);
+    MockPaidServiceClient::new(&env, &service_id).configure(&token_id, &contract_id, &300);
+
+    let paid_call = |payment: i128| CrossContractCall {
+        contract_address: service_id.clone(),
+        function_name: Symbol::new(&env, "collect"),
+        args: Vec::new(&env),
+        continue_on_failure: true,
+        call_id: None,
+        expected_return: ReturnPredicate::None,
+        payment,
+    };
+    let calls = Vec::from_array(
+        &env,
+        [
+            paid_call(300),
+            paid_call(100),
+            paid_call(600),
+            paid_call(400),
+        ],
+    );
+
+    // Prepaid batches are disabled until the admin caps the token
+    let result = client.try_execute_prepaid_batch(&admin, &payer, &token_id, &800, &calls, &false);
+    assert_eq!(
+        result.err(),
+        Some(Ok(CrossContractError::InvalidPrepayment.into()))
+    );
+    client.set_batch_spend_cap(&admin, &token_id, &800);
+    assert_eq!(client.get_batch_spend_cap(&token_id), 800);
+    let result = client.try_execute_prepaid_batch(&admin, &payer, &token_id, &900, &calls, &false);
+    assert_eq!(
+        result.err(),
+        Some(Ok(CrossContractError::InvalidPrepayment.into()))
+    );
+
+    let result = client.execute_prepaid_batch(&admin, &payer, &token_id, &800, &calls, &false);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "batch_prepaid"), payer.clone()).into_val(&env)
+    );
+    let settled: (Address, i128, i128, i128) = TryFromVal::try_from_val(&env, &data).unwrap();
+    assert_eq!(settled, (token_id.clone(), 800, 600, 200));
+
+    // The first call pulls its fee; the second's allowance is below the fee,
+    // so the token rejects the pull and the call fails; the third needs more
+    // than is left of the deposit and is not executed
+    let statuses: [(bool, CallFailure); 4] = [
+        (true, CallFailure::None),
+        (false, CallFailure::TargetReturnedError),
+        (false, CallFailure::InsufficientPrepayment),
+        (true, CallFailure::None),
+    ];
+    for (i, (success, failure)) in statuses.iter().enumerate() {
+        let call_result = result.batch.results.get(i as u32).unwrap();
+        assert_eq!(call_result.success, *success);
+        assert_eq!(call_result.failure, *failure);
+    }
+    assert_eq!(result.deposited, 800);
+    assert_eq!(result.spent, 600);
+    assert_eq!(result.refunded, 200);
+    assert_eq!(balances.balance(&payer), 400);
+    assert_eq!(balances.balance(&service_id), 600);
+    assert_eq!(balances.balance(&contract_id), 0);
+    assert_eq!(balances.allowance(&contract_id, &service_id), 0);
+
+    // Single calls are never prepaid
+    let single = client.execute_call(&admin, &paid_call(300), &false);
+    assert!(!single.success);
+    assert_eq!(single.failure, CallFailure::InsufficientPrepayment);
+    assert_eq!(balances.balance(&service_id), 600);
 }
@@ -0,0 +1,101 @@
+//! Timelock for sensitive admin operations.
+//!
+//! Once a delay is configured, whitelist changes (including rollbacks to a
+//! snapshot) and admin transfers must be queued and can only be executed
+//! after the delay has elapsed, giving watchers time to react (and the admin
+//! time to cancel) if the admin key is compromised. Calls are held to the
+//! whitelist for as long as the timelock is enabled, so a compromised key
+//! cannot reach unlisted contracts by skipping the whitelist check.
+
+use soroban_sdk::{panic_with_error, Address, Env};
+
+use crate::types::{
+    AdminAction, CrossContractEvents, DataKey, QueuedAction, TIMELOCK_GRACE_PERIOD,
+};
+use crate::CrossContractError;
+
+/// Returns the timelock delay in seconds (0 when the timelock is disabled).
+pub fn get_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TimelockDelay)
+        .unwrap_or(0)
+}
+
+/// Rejects direct admin calls while the timelock is enabled.
+pub fn require_disabled(env: &Env) {
+    if get_delay(env) > 0 {
+        panic_with_error!(env, CrossContractError::TimelockRequired);
+    }
+}
+
+/// Whether calls must target whitelisted contracts: always while the
+/// timelock is enabled, otherwise as the caller requested.
+pub fn whitelist_required(env: &Env, requested: bool) -> bool {
+    requested || get_delay(env) > 0
+}
+
+pub fn get_action(env: &Env, action_id: u64) -> Option<QueuedAction> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::QueuedAction(action_id))
+}
+
+/// Queues `action`, executable once the current delay has elapsed.
+pub fn queue(env: &Env, caller: &Address, action: AdminAction) -> u64 {
+    let action_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastActionId)
+        .unwrap_or(0)
+        + 1;
+    let eta = env.ledger().timestamp() + get_delay(env);
+
+    let queued = QueuedAction {
+        action_id,
+        action: action.clone(),
+        eta,
+        queued_by: caller.clone(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::QueuedAction(action_id), &queued);
+    env.storage()
+        .instance()
+        .set(&DataKey::LastActionId, &action_id);
+
+    CrossContractEvents::action_queued(env, action_id, &action, eta);
+    action_id
+}
+
+/// Removes a ready, unexpired action from the queue and returns it.
+pub fn take_ready(env: &Env, action_id: u64) -> AdminAction {
+    let queued = get_action(env, action_id)
+        .unwrap_or_else(|| panic_with_error!(env, CrossContractError::ActionNotFound));
+
+    let now = env.ledger().timestamp();
+    if now < queued.eta {
+        panic_with_error!(env, CrossContractError::ActionNotReady);
+    }
+    if now > queued.eta + TIMELOCK_GRACE_PERIOD {
+        panic_with_error!(env, CrossContractError::ActionExpired);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::QueuedAction(action_id));
+    CrossContractEvents::action_executed(env, action_id, &queued.action);
+    queued.action
+}
+
+/// Drops a queued action without executing it.
+pub fn cancel(env: &Env, caller: &Address, action_id: u64) {
+    if get_action(env, action_id).is_none() {
+        panic_with_error!(env, CrossContractError::ActionNotFound);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::QueuedAction(action_id));
+    CrossContractEvents::action_cancelled(env, caller, action_id);
+}
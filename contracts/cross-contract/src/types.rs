@@ -1,10 +1,19 @@
 //! Type definitions for cross-contract interactions
 
-use soroban_sdk::{contracttype, Address, Bytes, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Symbol, Vec};
 
 /// Maximum number of cross-contract calls in a batch
 pub const MAX_BATCH_CALLS: u32 = 50;
 
+/// Upper bound for the number of return-data bytes forwarded in `call_succeeded` events
+pub const MAX_RETURN_DATA_EVENT_BYTES: u32 = 256;
+
+/// Window (in seconds) after an action's eta during which it can still be executed
+pub const TIMELOCK_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60;
+
+/// Number of calls between two `operator_summary` events for the same operator
+pub const OPERATOR_SUMMARY_INTERVAL: u64 = 100;
+
 /// Storage keys for the contract
 #[derive(Clone)]
 #[contracttype]
@@ -19,6 +28,32 @@ pub enum DataKey {
     FailedCalls,
     /// Whitelist of allowed contract addresses
     Whitelist(Address),
+    /// Max return-data bytes forwarded in `call_succeeded` events (0 disables forwarding)
+    ReturnDataEventLimit,
+    /// Read-only functions callable through `aggregate_views` (contract, function)
+    ViewFunction(Address, Symbol),
+    /// Delay (in seconds) before queued admin actions become executable (0 disables the timelock)
+    TimelockDelay,
+    /// Last assigned queued-action ID
+    LastActionId,
+    /// Admin action waiting out the timelock, by action ID
+    QueuedAction(u64),
+    /// Whether repeated (target, function, args) entries in a batch are skipped
+    SkipDuplicateCalls,
+    /// Call statistics of an operator (the caller of `execute_call`/`execute_batch`)
+    OperatorStats(Address),
+    /// Contracts whose calls are refused until released from quarantine
+    Quarantined(Address),
+    /// Currently whitelisted contracts, in the order they were added
+    WhitelistMembers,
+    /// Last assigned whitelist snapshot ID
+    LastSnapshotId,
+    /// Saved copy of the whitelist, by snapshot ID
+    WhitelistSnapshot(u64),
+    /// Receipt of the last call executed under a call ID
+    CallReceipt(u64),
+    /// Largest prepayment a single batch may deposit, per token (unset disables prepaid batches)
+    BatchSpendCap(Address),
 }
 
 /// Request for a cross-contract call
@@ -29,10 +64,115 @@ pub struct CrossContractCall {
     pub contract_address: Address,
     /// Function name to call
     pub function_name: Symbol,
-    /// Arguments for the function call (encoded)
+    /// Arguments for the function call, each an XDR-encoded value
     pub args: Vec<Bytes>,
     /// Whether to continue on failure
     pub continue_on_failure: bool,
+    /// Caller-chosen ID (e.g. an off-chain job ID) under which the call's
+    /// receipt is stored
+    pub call_id: Option<u64>,
+    /// Condition the return data must satisfy for the call to count as
+    /// succeeded
+    pub expected_return: ReturnPredicate,
+    /// Tokens the target may pull from the batch prepayment (0 for none)
+    pub payment: i128,
+}
+
+/// Expectation about the data returned by a successful call
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ReturnPredicate {
+    /// Any return data is accepted
+    None,
+    /// The return data must not be empty
+    NonEmpty,
+    /// The return data must equal the given bytes
+    Equals(Bytes),
+    /// The return data, read as a big-endian unsigned integer of at most 16
+    /// bytes, must be at least the given threshold
+    AtLeast(u128),
+}
+
+/// Sensitive admin operation that is routed through the timelock
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum AdminAction {
+    /// Add a contract to the whitelist
+    Whitelist(Address),
+    /// Remove a contract from the whitelist
+    RemoveFromWhitelist(Address),
+    /// Hand the admin role to a new address
+    SetAdmin(Address),
+    /// Change the timelock delay (in seconds)
+    SetTimelockDelay(u64),
+    /// Restore the whitelist to a snapshot, by snapshot ID
+    RollbackWhitelist(u64),
+}
+
+/// Admin action waiting for its timelock to elapse
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct QueuedAction {
+    /// Action ID
+    pub action_id: u64,
+    /// Operation to perform
+    pub action: AdminAction,
+    /// Earliest ledger timestamp at which the action can be executed
+    pub eta: u64,
+    /// Admin that queued the action
+    pub queued_by: Address,
+}
+
+/// Saved copy of the whitelist
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct WhitelistSnapshot {
+    /// Snapshot ID
+    pub snapshot_id: u64,
+    /// Contracts whitelisted when the snapshot was taken
+    pub contracts: Vec<Address>,
+    /// Ledger timestamp at which the snapshot was taken
+    pub created_at: u64,
+    /// Admin that took the snapshot
+    pub created_by: Address,
+}
+
+/// Outcome of a single entry in a call or batch
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum CallStatus {
+    /// The call was executed and succeeded
+    Succeeded = 0,
+    /// The call was executed and failed
+    Failed = 1,
+    /// The call repeated an earlier entry of the batch and was not executed
+    DuplicateSkipped = 2,
+}
+
+/// Why a call failed, so automation can decide per class whether to retry,
+/// alert or skip
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum CallFailure {
+    /// The call did not fail (succeeded or was skipped as a duplicate)
+    None = 0,
+    /// The call request was invalid and was not executed
+    ValidationFailed = 1,
+    /// The target is not whitelisted and the call was not executed
+    NotWhitelisted = 2,
+    /// The target trapped or the host rejected the call (panic, missing
+    /// function, exhausted budget)
+    TargetPanicked = 3,
+    /// The target returned a contract error or a value that is not `Bytes`
+    TargetReturnedError = 4,
+    /// The target is quarantined and the call was not executed
+    Quarantined = 5,
+    /// The target succeeded but its return data failed the call's
+    /// `expected_return` predicate
+    AssertionFailed = 6,
+    /// The call needs a payment but the batch is not prepaid, or too little
+    /// of the prepayment is left; the call was not executed
+    InsufficientPrepayment = 7,
 }
 
 /// Result of a single cross-contract call
@@ -41,10 +181,35 @@ pub struct CrossContractCall {
 pub struct CallResult {
     /// Whether the call succeeded
     pub success: bool,
-    /// Return data from the call (if successful)
+    /// Outcome of the call, distinguishing skipped duplicates from failures
+    pub status: CallStatus,
+    /// Failure class (`CallFailure::None` unless `status` is `Failed`)
+    pub failure: CallFailure,
+    /// Return data from the call (if successful, or if it failed the
+    /// return predicate)
     pub return_data: Option<Bytes>,
     /// Error message (if failed)
     pub error_message: Option<Symbol>,
+    /// Contract error code raised by the downstream contract (if any)
+    pub downstream_error: Option<u32>,
+}
+
+/// Verifiable record of a call's outcome
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CallReceipt {
+    /// SHA-256 over target, function, args hash, status and ledger
+    pub receipt_hash: BytesN<32>,
+    /// Target contract address
+    pub target: Address,
+    /// Function called
+    pub function_name: Symbol,
+    /// SHA-256 of the XDR-encoded arguments
+    pub args_hash: BytesN<32>,
+    /// Outcome of the call
+    pub status: CallStatus,
+    /// Ledger sequence in which the call ran
+    pub ledger: u32,
 }
 
 /// Result of a batch of cross-contract calls
@@ -57,10 +222,42 @@ pub struct BatchCallResult {
     pub successful_calls: u32,
     /// Number of failed calls
     pub failed_calls: u32,
+    /// Number of duplicate calls skipped without being executed
+    pub skipped_calls: u32,
     /// Individual call results
     pub results: Vec<CallResult>,
 }
 
+/// Result of a batch paid for from a prepayment
+#[derive(Clone)]
+#[contracttype]
+pub struct PrepaidBatchResult {
+    /// Results of the batch's calls
+    pub batch: BatchCallResult,
+    /// Amount deposited by the payer
+    pub deposited: i128,
+    /// Amount pulled by the batch's calls
+    pub spent: i128,
+    /// Unspent amount returned to the payer
+    pub refunded: i128,
+}
+
+/// Call statistics tracked per operator
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct OperatorStats {
+    /// Total number of calls executed by the operator
+    pub total_calls: u64,
+    /// Number of successful calls
+    pub successful_calls: u64,
+    /// Number of failed calls
+    pub failed_calls: u64,
+    /// Share of successful calls in basis points (10000 = 100%)
+    pub success_rate_bps: u32,
+    /// Ledger timestamp of the operator's last call
+    pub last_call_at: u64,
+}
+
 /// Events emitted by the cross-contract module
 pub struct CrossContractEvents;
 
@@ -78,28 +275,43 @@ impl CrossContractEvents {
         );
     }
 
-    /// Emit event when a cross-contract call succeeds
+    /// Emit event when a cross-contract call succeeds, optionally carrying the
+    /// (truncated) return data; the call ID and receipt hash are topics
     pub fn call_succeeded(
         env: &soroban_sdk::Env,
-        target: &Address,
-        function: &Symbol,
+        receipt: &CallReceipt,
+        call_id: Option<u64>,
+        return_data: &Option<Bytes>,
     ) {
+        let (target, function) = (&receipt.target, &receipt.function_name);
         env.events().publish(
-            (Symbol::new(env, "call_succeeded"),),
-            (target, function),
+            (
+                Symbol::new(env, "call_succeeded"),
+                call_id,
+                receipt.receipt_hash.clone(),
+            ),
+            (target, function, return_data.clone()),
         );
     }
 
-    /// Emit event when a cross-contract call fails
+    /// Emit event when a cross-contract call fails, carrying the downstream
+    /// contract error code when one was raised; the call ID and receipt hash
+    /// are topics
     pub fn call_failed(
         env: &soroban_sdk::Env,
-        target: &Address,
-        function: &Symbol,
+        receipt: &CallReceipt,
+        call_id: Option<u64>,
         error: &Symbol,
+        downstream_error: Option<u32>,
     ) {
+        let (target, function) = (&receipt.target, &receipt.function_name);
         env.events().publish(
-            (Symbol::new(env, "call_failed"),),
-            (target, function, error),
+            (
+                Symbol::new(env, "call_failed"),
+                call_id,
+                receipt.receipt_hash.clone(),
+            ),
+            (target, function, error, downstream_error),
         );
     }
 
@@ -116,6 +328,41 @@ impl CrossContractEvents {
         );
     }
 
+    /// Emit periodic summary of an operator's call statistics
+    pub fn operator_summary(env: &soroban_sdk::Env, operator: &Address, stats: &OperatorStats) {
+        env.events().publish(
+            (Symbol::new(env, "operator_summary"), operator.clone()),
+            (
+                stats.total_calls,
+                stats.successful_calls,
+                stats.failed_calls,
+                stats.success_rate_bps,
+            ),
+        );
+    }
+
+    /// Emit event when a batch repeats an earlier (target, function, args) entry
+    pub fn duplicate_call(
+        env: &soroban_sdk::Env,
+        index: u32,
+        target: &Address,
+        function: &Symbol,
+        skipped: bool,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "duplicate_call"),),
+            (index, target, function, skipped),
+        );
+    }
+
+    /// Emit event when duplicate skipping is switched on or off
+    pub fn skip_duplicates_updated(env: &soroban_sdk::Env, admin: &Address, enabled: bool) {
+        env.events().publish(
+            (Symbol::new(env, "skip_duplicates_updated"), admin),
+            enabled,
+        );
+    }
+
     /// Emit event when a contract is whitelisted
     pub fn contract_whitelisted(env: &soroban_sdk::Env, contract: &Address) {
         env.events().publish(
@@ -124,6 +371,116 @@ impl CrossContractEvents {
         );
     }
 
+    /// Emit event when the return-data forwarding limit changes
+    pub fn return_data_limit_updated(env: &soroban_sdk::Env, admin: &Address, max_bytes: u32) {
+        env.events().publish(
+            (Symbol::new(env, "return_data_limit_updated"), admin),
+            max_bytes,
+        );
+    }
+
+    /// Emit event when a read-only function is allowed or revoked for `aggregate_views`
+    pub fn view_function_updated(
+        env: &soroban_sdk::Env,
+        contract: &Address,
+        function: &Symbol,
+        allowed: bool,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "view_function_updated"),),
+            (contract, function, allowed),
+        );
+    }
+
+    /// Emit event when an admin action is queued behind the timelock
+    pub fn action_queued(
+        env: &soroban_sdk::Env,
+        action_id: u64,
+        action: &AdminAction,
+        eta: u64,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "action_queued"), action_id),
+            (action.clone(), eta),
+        );
+    }
+
+    /// Emit event when a queued admin action is executed
+    pub fn action_executed(env: &soroban_sdk::Env, action_id: u64, action: &AdminAction) {
+        env.events().publish(
+            (Symbol::new(env, "action_executed"), action_id),
+            action.clone(),
+        );
+    }
+
+    /// Emit event when a queued admin action is cancelled
+    pub fn action_cancelled(env: &soroban_sdk::Env, admin: &Address, action_id: u64) {
+        env.events().publish(
+            (Symbol::new(env, "action_cancelled"), action_id),
+            admin,
+        );
+    }
+
+    /// Emit event when the timelock delay changes
+    pub fn timelock_delay_updated(env: &soroban_sdk::Env, delay: u64) {
+        env.events()
+            .publish((Symbol::new(env, "timelock_delay_updated"),), delay);
+    }
+
+    /// Emit event when a contract is put into or released from quarantine
+    pub fn quarantine_updated(env: &soroban_sdk::Env, contract: &Address, quarantined: bool) {
+        env.events().publish(
+            (Symbol::new(env, "quarantine_updated"),),
+            (contract, quarantined),
+        );
+    }
+
+    /// Emit event when the whitelist is saved as a snapshot
+    pub fn whitelist_snapshot_taken(env: &soroban_sdk::Env, snapshot_id: u64, contracts: u32) {
+        env.events().publish(
+            (Symbol::new(env, "whitelist_snapshot_taken"), snapshot_id),
+            contracts,
+        );
+    }
+
+    /// Emit event when the whitelist is rolled back to a snapshot, carrying
+    /// the contracts added back and removed by the rollback
+    pub fn whitelist_rolled_back(
+        env: &soroban_sdk::Env,
+        snapshot_id: u64,
+        added: &Vec<Address>,
+        removed: &Vec<Address>,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "whitelist_rolled_back"), snapshot_id),
+            (added.clone(), removed.clone()),
+        );
+    }
+
+    /// Emit event when a token's per-batch spending cap changes
+    pub fn spend_cap_updated(env: &soroban_sdk::Env, admin: &Address, token: &Address, cap: i128) {
+        env.events().publish(
+            (Symbol::new(env, "spend_cap_updated"), admin),
+            (token, cap),
+        );
+    }
+
+    /// Emit event when a prepaid batch is settled and its unspent amount
+    /// refunded to the payer
+    pub fn batch_prepaid(
+        env: &soroban_sdk::Env,
+        payer: &Address,
+        token: &Address,
+        deposited: i128,
+        spent: i128,
+        refunded: i128,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "batch_prepaid"), payer),
+            (token, deposited, spent, refunded),
+        );
+    }
+
     /// Emit event when a contract is removed from whitelist
     pub fn contract_removed(env: &soroban_sdk::Env, contract: &Address) {
         env.events().publish(
@@ -1,23 +1,23 @@
 //! Validation utilities for cross-contract interactions
 
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use soroban_sdk::{xdr::FromXdr, Address, Bytes, Env, Symbol, Val, Vec};
 
-use crate::types::{CrossContractCall, DataKey};
+use crate::types::{CallFailure, CrossContractCall, DataKey};
 use crate::CrossContractError;
 
 /// Validates a contract address
-pub fn validate_contract_address(env: &Env, address: &Address) -> Result<(), CrossContractError> {
+pub fn validate_contract_address(address: &Address) -> Result<(), CrossContractError> {
     // Check if address is valid (non-zero)
-    if address.to_string().len() == 0 {
+    if address.to_string().is_empty() {
         return Err(CrossContractError::InvalidContractAddress);
     }
     Ok(())
 }
 
 /// Validates a function name
-pub fn validate_function_name(function_name: &Symbol) -> Result<(), CrossContractError> {
+pub fn validate_function_name(env: &Env, function_name: &Symbol) -> Result<(), CrossContractError> {
     // Check if function name is not empty
-    if function_name.to_string().len() == 0 {
+    if *function_name == Symbol::new(env, "") {
         return Err(CrossContractError::InvalidFunctionName);
     }
     Ok(())
@@ -30,10 +30,13 @@ pub fn validate_call_request(
     require_whitelist: bool,
 ) -> Result<(), CrossContractError> {
     // Validate contract address
-    validate_contract_address(env, &call.contract_address)?;
+    validate_contract_address(&call.contract_address)?;
 
     // Validate function name
-    validate_function_name(&call.function_name)?;
+    validate_function_name(env, &call.function_name)?;
+
+    // Validate arguments
+    decode_args(env, &call.args)?;
 
     // Check whitelist if required
     if require_whitelist && !is_whitelisted(env, &call.contract_address) {
@@ -43,12 +46,18 @@ pub fn validate_call_request(
     Ok(())
 }
 
-/// Validates a batch of cross-contract calls
-pub fn validate_batch_calls(
-    env: &Env,
-    calls: &Vec<CrossContractCall>,
-    require_whitelist: bool,
-) -> Result<(), CrossContractError> {
+/// Decodes a call's XDR-encoded arguments into the values passed to the target
+pub fn decode_args(env: &Env, args: &Vec<Bytes>) -> Result<Vec<Val>, CrossContractError> {
+    let mut values = Vec::new(env);
+    for arg in args.iter() {
+        let value = Val::from_xdr(env, &arg).map_err(|_| CrossContractError::InvalidArguments)?;
+        values.push_back(value);
+    }
+    Ok(values)
+}
+
+/// Validates the size of a batch of cross-contract calls
+pub fn validate_batch_size(calls: &Vec<CrossContractCall>) -> Result<(), CrossContractError> {
     let call_count = calls.len();
 
     // Check if batch is empty
@@ -61,8 +70,19 @@ pub fn validate_batch_calls(
         return Err(CrossContractError::BatchTooLarge);
     }
 
+    Ok(())
+}
+
+/// Validates a batch of cross-contract calls
+pub fn validate_batch_calls(
+    env: &Env,
+    calls: &Vec<CrossContractCall>,
+    require_whitelist: bool,
+) -> Result<(), CrossContractError> {
+    validate_batch_size(calls)?;
+
     // Validate each call
-    for i in 0..call_count {
+    for i in 0..calls.len() {
         let call = calls.get(i).unwrap();
         validate_call_request(env, &call, require_whitelist)?;
     }
@@ -70,6 +90,69 @@ pub fn validate_batch_calls(
     Ok(())
 }
 
+/// Validates a batch of read-only calls for `aggregate_views`: every target
+/// must be whitelisted and every function registered as a view
+pub fn validate_view_calls(
+    env: &Env,
+    calls: &Vec<CrossContractCall>,
+) -> Result<(), CrossContractError> {
+    validate_batch_calls(env, calls, true)?;
+
+    for call in calls.iter() {
+        if !is_view_function(env, &call.contract_address, &call.function_name) {
+            return Err(CrossContractError::ViewNotAllowed);
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks if a function is registered as read-only for a contract
+pub fn is_view_function(env: &Env, contract: &Address, function_name: &Symbol) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ViewFunction(
+            contract.clone(),
+            function_name.clone(),
+        ))
+        .unwrap_or(false)
+}
+
+/// Classifies why a batch entry must not be executed, if it must not
+pub fn rejection_reason(
+    env: &Env,
+    call: &CrossContractCall,
+    require_whitelist: bool,
+) -> Option<CallFailure> {
+    match validate_call_request(env, call, require_whitelist) {
+        Err(CrossContractError::ContractNotWhitelisted) => Some(CallFailure::NotWhitelisted),
+        Err(_) => Some(CallFailure::ValidationFailed),
+        Ok(()) if is_quarantined(env, &call.contract_address) => Some(CallFailure::Quarantined),
+        Ok(()) => None,
+    }
+}
+
+/// Classifies why a call's payment cannot be covered, given what is left of
+/// the batch prepayment (`None` when the batch is not prepaid)
+pub fn payment_rejection(call: &CrossContractCall, remaining: Option<i128>) -> Option<CallFailure> {
+    if call.payment < 0 {
+        return Some(CallFailure::ValidationFailed);
+    }
+    match remaining {
+        _ if call.payment == 0 => None,
+        Some(remaining) if call.payment <= remaining => None,
+        _ => Some(CallFailure::InsufficientPrepayment),
+    }
+}
+
+/// Checks if a contract address is quarantined
+pub fn is_quarantined(env: &Env, contract: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Quarantined(contract.clone()))
+        .unwrap_or(false)
+}
+
 /// Checks if a contract address is whitelisted
 pub fn is_whitelisted(env: &Env, contract: &Address) -> bool {
     env.storage()
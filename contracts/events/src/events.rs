@@ -11,6 +11,7 @@ pub enum OperationType {
     Stake,
     Unstake,
     ClaimReward,
+    EmergencyWithdraw,
 }
 
 // ─── Event Topics ─────────────────────────────────────────────────────────────
@@ -24,6 +25,10 @@ pub fn topic_initialize() -> Symbol { symbol_short!("INIT")      }
 pub fn topic_stake()       -> Symbol { symbol_short!("STAKE")     }
 pub fn topic_unstake()     -> Symbol { symbol_short!("UNSTAKE")   }
 pub fn topic_reward()      -> Symbol { symbol_short!("REWARD")    }
+pub fn topic_emergency()   -> Symbol { symbol_short!("EMERGENCY") }
+pub fn topic_emergency_withdraw() -> Symbol { symbol_short!("EMRG_WDRW") }
+pub fn topic_boost_config() -> Symbol { symbol_short!("BOOST_CFG") }
+pub fn topic_goal_boost()   -> Symbol { symbol_short!("GOALBOOST") }
 
 // ─── Event Payloads ───────────────────────────────────────────────────────────
 
@@ -77,6 +82,66 @@ pub struct UnstakeEventData {
     pub timestamp: u64,
 }
 
+/// Emitted when the admin declares or lifts the emergency state.
+///
+/// Fields
+/// - `admin`      : admin who changed the state
+/// - `active`     : true when the emergency was declared, false when lifted
+/// - `timestamp`  : ledger timestamp
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EmergencyEventData {
+    pub admin:     Address,
+    pub active:    bool,
+    pub timestamp: u64,
+}
+
+/// Emitted when a staker pulls their principal out during an emergency.
+///
+/// Fields
+/// - `staker`     : address of the user withdrawing
+/// - `amount`     : principal returned (no rewards are paid)
+/// - `timestamp`  : ledger timestamp
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EmergencyWithdrawEventData {
+    pub staker:    Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+/// Emitted when the admin configures the savings-goal staking boost.
+///
+/// Fields
+/// - `admin`          : admin who changed the configuration
+/// - `goals_contract` : savings-goals contract queried for completed goals
+/// - `bonus_bps`      : extra reward rate (basis points) for qualifying stakers
+/// - `timestamp`      : ledger timestamp
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BoostConfigEventData {
+    pub admin:          Address,
+    pub goals_contract: Address,
+    pub bonus_bps:      u32,
+    pub timestamp:      u64,
+}
+
+/// Emitted when a staker's cached goal-completion status is refreshed.
+///
+/// Fields
+/// - `staker`          : address whose status was refreshed
+/// - `completed_goals` : completed savings goals reported by the goals contract
+/// - `bonus_bps`       : bonus now applied to the staker's rewards (0 if none)
+/// - `timestamp`       : ledger timestamp
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GoalBoostEventData {
+    pub staker:          Address,
+    pub completed_goals: u32,
+    pub bonus_bps:       u32,
+    pub timestamp:       u64,
+}
+
 // ─── Emit Helpers ─────────────────────────────────────────────────────────────
 // Each public function in lib.rs calls one of these helpers so event emission
 // is always consistent — same topic ordering, same schema version.
@@ -108,6 +173,39 @@ pub fn emit_unstake(env: &Env, data: UnstakeEventData) {
     );
 }
 
+/// Emit an emergency state change event.
+pub fn emit_emergency(env: &Env, data: EmergencyEventData) {
+    env.events().publish(
+        (CONTRACT_TOPIC, topic_emergency()),
+        data,
+    );
+}
+
+/// Emit an emergency withdrawal event.
+pub fn emit_emergency_withdraw(env: &Env, data: EmergencyWithdrawEventData) {
+    validate_emergency_withdraw_event(&data);
+    env.events().publish(
+        (CONTRACT_TOPIC, topic_emergency_withdraw()),
+        data,
+    );
+}
+
+/// Emit a goal-boost configuration event.
+pub fn emit_boost_config(env: &Env, data: BoostConfigEventData) {
+    env.events().publish(
+        (CONTRACT_TOPIC, topic_boost_config()),
+        data,
+    );
+}
+
+/// Emit a goal-boost refresh event.
+pub fn emit_goal_boost(env: &Env, data: GoalBoostEventData) {
+    env.events().publish(
+        (CONTRACT_TOPIC, topic_goal_boost()),
+        data,
+    );
+}
+
 // ─── Validation ───────────────────────────────────────────────────────────────
 // Validation is kept in this module so tests can call it directly without
 // going through the full contract entry points.
@@ -151,4 +249,12 @@ pub fn validate_unstake_event(data: &UnstakeEventData) {
         data.remaining >= 0,
         "event validation: remaining balance cannot be negative"
     );
-}
\ No newline at end of file
+}
+
+/// Panics if the EmergencyWithdrawEventData is invalid.
+pub fn validate_emergency_withdraw_event(data: &EmergencyWithdrawEventData) {
+    assert!(
+        data.amount > 0,
+        "event validation: emergency withdrawal amount must be greater than zero"
+    );
+}
@@ -1,35 +1,44 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, token, Address, Env, Symbol,
+    contract, contractclient, contractimpl, contracttype, token, Address, Env,
 };
 pub mod fee_events;
 
-mod events;
+pub mod events;
 use events::{
-    emit_initialize, emit_stake, emit_unstake,
-    InitializeEventData, StakeEventData, UnstakeEventData,
+    emit_boost_config, emit_emergency, emit_emergency_withdraw, emit_goal_boost, emit_initialize,
+    emit_stake, emit_unstake, BoostConfigEventData, EmergencyEventData,
+    EmergencyWithdrawEventData, GoalBoostEventData, InitializeEventData, StakeEventData,
+    UnstakeEventData,
 };
 
 #[cfg(test)]
-mod test {
+mod test;
+
+#[cfg(test)]
+mod fee_event_tests {
     use super::fee_events::*;
-    use soroban_sdk::{Env, Address};
+    use soroban_sdk::{
+        testutils::{Address as _, Events},
+        Address, Env, TryFromVal,
+    };
 
     #[test]
     fn test_fee_event_logging() {
         let env = Env::default();
         let user = Address::generate(&env);
+        let contract_id = env.register(super::StakingContract, ());
 
-        log_fee_collected(&env, user.clone(), 500);
+        env.as_contract(&contract_id, || log_fee_collected(&env, user.clone(), 500));
 
         let events = env.events().all();
         assert_eq!(events.len(), 1);
 
-        let event = &events[0];
+        let (_, _, data) = events.get(0).unwrap();
 
-        let (logged_user, amount, _timestamp): (Address, i128, u64) =
-            event.data.clone().try_into().unwrap();
+        let (logged_user, amount, _timestamp) =
+            <(Address, i128, u64)>::try_from_val(&env, &data).unwrap();
 
         assert_eq!(logged_user, user);
         assert_eq!(amount, 500);
@@ -46,6 +55,32 @@ pub enum DataKey {
     Stake(Address),
     /// Per-user last-stake timestamp (for reward calculation)
     StakeTs(Address),
+    /// Set while the admin-declared emergency state is active
+    Emergency,
+    /// Savings-goals contract queried for the goal-completion boost
+    GoalsContract,
+    /// Extra reward rate (basis points) for stakers with a completed goal
+    GoalBonusBps,
+    /// Per-user cached goal-completion status:  DataKey::GoalBoost(Address)
+    GoalBoost(Address),
+}
+
+// ─── Constants ────────────────────────────────────────────────────────────────
+
+/// How long (seconds) a cached goal-completion status is trusted before the
+/// goals contract is queried again
+pub const GOAL_BOOST_REFRESH_SECS: u64 = 24 * 60 * 60;
+
+/// Upper bound for the goal-completion bonus (basis points)
+pub const MAX_GOAL_BONUS_BPS: u32 = 10_000;
+
+// ─── Savings Goals Interface ──────────────────────────────────────────────────
+
+/// The subset of the savings-goals contract this contract depends on.
+#[contractclient(name = "SavingsGoalsClient")]
+pub trait SavingsGoalsInterface {
+    /// Number of the user's goals that have reached their target.
+    fn get_completed_goals(env: Env, user: Address) -> u32;
 }
 
 // ─── Contract State ───────────────────────────────────────────────────────────
@@ -64,6 +99,18 @@ pub struct Config {
     pub min_stake: i128,
 }
 
+/// Cached result of the last goal-completion query for a staker.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoalBoost {
+    /// Completed savings goals reported by the goals contract
+    pub completed_goals: u32,
+    /// Ledger timestamp of the query
+    pub checked_at:      u64,
+    /// When a completed goal was first recorded; the bonus accrues from here
+    pub boosted_since:   Option<u64>,
+}
+
 // ─── Contract ─────────────────────────────────────────────────────────────────
 
 #[contract]
@@ -128,6 +175,8 @@ impl StakingContract {
 
         let config = Self::get_config(&env);
 
+        assert!(!Self::is_emergency(env.clone()), "staking is halted during an emergency");
+
         assert!(
             amount >= config.min_stake,
             "amount is below the minimum stake"
@@ -178,6 +227,12 @@ impl StakingContract {
 
         let config = Self::get_config(&env);
 
+        // Rewards may be unreliable during an emergency; only principal can leave
+        assert!(
+            !Self::is_emergency(env.clone()),
+            "unstake is halted during an emergency, use emergency_withdraw"
+        );
+
         assert!(amount > 0, "unstake amount must be greater than zero");
 
         // Fetch current staked balance
@@ -189,8 +244,9 @@ impl StakingContract {
 
         assert!(current >= amount, "insufficient staked balance");
 
-        // Calculate reward based on time elapsed and reward_rate
-        let reward = Self::calculate_reward(&env, &staker, amount, &config);
+        // Calculate reward based on time elapsed, reward_rate and any goal boost
+        let bonus = Self::goal_bonus(&env, &staker);
+        let reward = Self::calculate_reward(&env, &staker, amount, &config, bonus);
 
         let remaining = current - amount;
         let payout    = amount + reward;
@@ -227,6 +283,124 @@ impl StakingContract {
         );
     }
 
+    // ── Emergency ─────────────────────────────────────────────────────────────
+
+    /// Declare (`active = true`) or lift (`active = false`) the emergency state.
+    ///
+    /// While active, `stake` and `unstake` are halted and stakers can only
+    /// leave through `emergency_withdraw`.
+    ///
+    /// Emits: `EmergencyEvent`
+    pub fn set_emergency(env: Env, admin: Address, active: bool) {
+        admin.require_auth();
+
+        let config = Self::get_config(&env);
+        assert!(admin == config.admin, "only the admin can change the emergency state");
+
+        if active {
+            env.storage().instance().set(&DataKey::Emergency, &true);
+        } else {
+            env.storage().instance().remove(&DataKey::Emergency);
+        }
+
+        emit_emergency(
+            &env,
+            EmergencyEventData {
+                admin,
+                active,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Withdraw the caller's full principal without rewards.
+    ///
+    /// Only available while the emergency state is active, so user funds are
+    /// never trapped behind a faulty reward calculation.
+    ///
+    /// Emits: `EmergencyWithdrawEvent`
+    pub fn emergency_withdraw(env: Env, staker: Address) -> i128 {
+        staker.require_auth();
+
+        assert!(Self::is_emergency(env.clone()), "emergency withdrawal is not enabled");
+
+        let config = Self::get_config(&env);
+
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Stake(staker.clone()))
+            .unwrap_or(0);
+
+        assert!(amount > 0, "nothing staked");
+
+        // Clear state before the external call (checks-effects-interactions)
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Stake(staker.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::StakeTs(staker.clone()));
+
+        let token_client = token::Client::new(&env, &config.token);
+        token_client.transfer(&env.current_contract_address(), &staker, &amount);
+
+        emit_emergency_withdraw(
+            &env,
+            EmergencyWithdrawEventData {
+                staker,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        amount
+    }
+
+    // ── Goal Boost ────────────────────────────────────────────────────────────
+
+    /// Configure the savings-goal boost: stakers with at least one completed
+    /// goal in `goals_contract` earn `bonus_bps` on top of `reward_rate`,
+    /// from the time this contract records the completion on.
+    /// A `bonus_bps` of zero turns the boost off.
+    ///
+    /// Emits: `BoostConfigEvent`
+    pub fn set_goal_boost(env: Env, admin: Address, goals_contract: Address, bonus_bps: u32) {
+        admin.require_auth();
+
+        let config = Self::get_config(&env);
+        assert!(admin == config.admin, "only the admin can configure the goal boost");
+        assert!(bonus_bps <= MAX_GOAL_BONUS_BPS, "bonus_bps exceeds the maximum");
+
+        env.storage().instance().set(&DataKey::GoalsContract, &goals_contract);
+        env.storage().instance().set(&DataKey::GoalBonusBps, &bonus_bps);
+
+        emit_boost_config(
+            &env,
+            BoostConfigEventData {
+                admin,
+                goals_contract,
+                bonus_bps,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Re-query the goals contract for `staker` now instead of waiting for
+    /// the cached status to go stale. Returns the cached status afterwards.
+    ///
+    /// Emits: `GoalBoostEvent` when the query succeeds
+    pub fn refresh_goal_boost(env: Env, staker: Address) -> Option<GoalBoost> {
+        Self::refresh_boost(&env, &staker)
+    }
+
+    /// Return the cached goal-completion status for a staker, if any.
+    pub fn get_goal_boost(env: Env, staker: Address) -> Option<GoalBoost> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GoalBoost(staker))
+    }
+
     // ── Views ─────────────────────────────────────────────────────────────────
 
     /// Return the staked balance for a given address.
@@ -237,6 +411,14 @@ impl StakingContract {
             .unwrap_or(0)
     }
 
+    /// Return true while the admin-declared emergency state is active.
+    pub fn is_emergency(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Emergency)
+            .unwrap_or(false)
+    }
+
     /// Return the current contract configuration.
     pub fn get_config(env: &Env) -> Config {
         env.storage()
@@ -247,15 +429,98 @@ impl StakingContract {
 
     // ── Private Helpers ───────────────────────────────────────────────────────
 
+    /// Bonus rate that applies to `staker` and the time it applies from,
+    /// refreshing the cached status first when it is missing or older than
+    /// `GOAL_BOOST_REFRESH_SECS`.
+    fn goal_bonus(env: &Env, staker: &Address) -> Option<(u32, u64)> {
+        let bonus_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::GoalBonusBps)
+            .unwrap_or(0);
+        if bonus_bps == 0 {
+            return None;
+        }
+
+        let cached: Option<GoalBoost> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GoalBoost(staker.clone()));
+        let boost = match cached {
+            Some(boost)
+                if env.ledger().timestamp() < boost.checked_at + GOAL_BOOST_REFRESH_SECS =>
+            {
+                Some(boost)
+            }
+            _ => Self::refresh_boost(env, staker),
+        };
+
+        boost
+            .and_then(|boost| boost.boosted_since)
+            .map(|since| (bonus_bps, since))
+    }
+
+    /// Query the goals contract and cache the result. A failing goals
+    /// contract leaves the previous cache in place so unstaking never breaks.
+    fn refresh_boost(env: &Env, staker: &Address) -> Option<GoalBoost> {
+        let key = DataKey::GoalBoost(staker.clone());
+        let previous: Option<GoalBoost> = env.storage().persistent().get(&key);
+
+        let goals_contract: Address = match env.storage().instance().get(&DataKey::GoalsContract) {
+            Some(address) => address,
+            None => return previous,
+        };
+
+        let client = SavingsGoalsClient::new(env, &goals_contract);
+        let completed_goals = match client.try_get_completed_goals(staker) {
+            Ok(Ok(count)) => count,
+            _ => return previous,
+        };
+
+        // Keep the original recording time while the staker stays boosted
+        let now = env.ledger().timestamp();
+        let boosted_since = match previous.and_then(|boost| boost.boosted_since) {
+            _ if completed_goals == 0 => None,
+            Some(since) => Some(since),
+            None => Some(now),
+        };
+        let boost = GoalBoost {
+            completed_goals,
+            checked_at: now,
+            boosted_since,
+        };
+        env.storage().persistent().set(&key, &boost);
+
+        let bonus_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::GoalBonusBps)
+            .unwrap_or(0);
+        emit_goal_boost(
+            env,
+            GoalBoostEventData {
+                staker: staker.clone(),
+                completed_goals,
+                bonus_bps: if completed_goals > 0 { bonus_bps } else { 0 },
+                timestamp: boost.checked_at,
+            },
+        );
+
+        Some(boost)
+    }
+
     /// Simple time-weighted reward formula:
     ///   reward = amount × (reward_rate / 10_000) × (elapsed_seconds / seconds_per_year)
+    ///          + amount × (bonus_bps / 10_000) × (boosted_seconds / seconds_per_year)
     ///
-    /// Returns 0 if no stake timestamp is recorded.
+    /// where `boosted_seconds` only counts from the later of the stake and
+    /// the recorded goal completion. Returns 0 if no stake timestamp is recorded.
     fn calculate_reward(
         env:    &Env,
         staker: &Address,
         amount: i128,
         config: &Config,
+        bonus:  Option<(u32, u64)>,
     ) -> i128 {
         let stake_ts: u64 = env
             .storage()
@@ -269,7 +534,14 @@ impl StakingContract {
         const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
 
         // reward_rate is in basis points: divide by 10_000
-        (amount * config.reward_rate as i128 * elapsed)
-            / (10_000 * SECONDS_PER_YEAR)
+        let base = amount * config.reward_rate as i128 * elapsed;
+        let boosted = match bonus {
+            Some((bonus_bps, since)) => {
+                let boosted_secs = now.saturating_sub(since.max(stake_ts)) as i128;
+                amount * bonus_bps as i128 * boosted_secs
+            }
+            None => 0,
+        };
+        (base + boosted) / (10_000 * SECONDS_PER_YEAR)
     }
 }
\ No newline at end of file
@@ -2,15 +2,16 @@
 
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger, LedgerInfo},
-    Address, Env, IntoVal,
+    token::StellarAssetClient,
+    Address, Env, IntoVal, Symbol,
 };
 
 use crate::{
     events::{
         validate_initialize_event, validate_stake_event, validate_unstake_event,
-        InitializeEventData, StakeEventData, UnstakeEventData,
-        CONTRACT_TOPIC,
-        topic_initialize, topic_stake, topic_unstake,
+        InitializeEventData, StakeEventData, UnstakeEventData, EmergencyWithdrawEventData,
+        GoalBoostEventData, CONTRACT_TOPIC,
+        topic_initialize, topic_stake, topic_unstake, topic_emergency_withdraw, topic_goal_boost,
     },
     StakingContract, StakingContractClient,
 };
@@ -21,29 +22,30 @@ use crate::{
 fn setup_env() -> Env {
     let env = Env::default();
     env.mock_all_auths();
-    env.ledger().set(LedgerInfo {
-        timestamp:          1_700_000_000,
-        protocol_version:   20,
-        sequence_number:    1,
-        network_id:         Default::default(),
-        base_reserve:       10,
-        min_temp_entry_ttl: 16,
-        min_persistent_entry_ttl: 4096,
-        max_entry_ttl:      6_312_000,
-    });
+    env.ledger().set_timestamp(1_700_000_000);
     env
 }
 
 /// Register the staking contract and return (client, admin, token_address).
-/// For event-only tests we use a dummy token address — no real token client needed.
-fn deploy_contract(env: &Env) -> (StakingContractClient, Address, Address) {
+/// The contract holds a reserve of the staking token to pay rewards from.
+fn deploy_contract(env: &Env) -> (StakingContractClient<'_>, Address, Address) {
     let admin = Address::generate(env);
-    let token = Address::generate(env); // dummy — not backed by a real token contract
-    let contract_id = env.register_contract(None, StakingContract);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let contract_id = env.register(StakingContract, ());
     let client = StakingContractClient::new(env, &contract_id);
+    StellarAssetClient::new(env, &token).mint(&contract_id, &REWARD_RESERVE);
     (client, admin, token)
 }
 
+const REWARD_RESERVE: i128 = 1_000_000;
+
+/// A staker holding enough of the staking token for any test.
+fn funded_staker(env: &Env, token: &Address) -> Address {
+    let staker = Address::generate(env);
+    StellarAssetClient::new(env, token).mint(&staker, &REWARD_RESERVE);
+    staker
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Section 1 — Unit tests for event payload validation
 // These test the validate_* helpers directly, without touching the contract.
@@ -246,7 +248,7 @@ mod emit_stake_event_tests {
         let (client, admin, token) = deploy_contract(&env);
         client.initialize(&admin, &token, &1200_u32, &100_i128);
 
-        let staker = Address::generate(&env);
+        let staker = funded_staker(&env, &token);
         env.events().all(); // clear init event
 
         client.stake(&staker, &500_i128);
@@ -272,7 +274,7 @@ mod emit_stake_event_tests {
         let (client, admin, token) = deploy_contract(&env);
         client.initialize(&admin, &token, &1200_u32, &100_i128);
 
-        let staker = Address::generate(&env);
+        let staker = funded_staker(&env, &token);
         client.stake(&staker, &300_i128);
         client.stake(&staker, &700_i128);
 
@@ -291,7 +293,7 @@ mod emit_stake_event_tests {
         let (client, admin, token) = deploy_contract(&env);
         client.initialize(&admin, &token, &1200_u32, &100_i128);
 
-        let staker = Address::generate(&env);
+        let staker = funded_staker(&env, &token);
         client.stake(&staker, &50_i128); // below min_stake of 100
     }
 }
@@ -300,10 +302,10 @@ mod emit_unstake_event_tests {
     use super::*;
 
     /// Helper that initialises + stakes so we have a balance to unstake.
-    fn setup_with_stake(env: &Env, amount: i128) -> (StakingContractClient, Address) {
+    fn setup_with_stake(env: &Env, amount: i128) -> (StakingContractClient<'_>, Address) {
         let (client, admin, token) = deploy_contract(env);
         client.initialize(&admin, &token, &1200_u32, &100_i128);
-        let staker = Address::generate(env);
+        let staker = funded_staker(env, &token);
         client.stake(&staker, &amount);
         (client, staker)
     }
@@ -344,14 +346,15 @@ mod emit_unstake_event_tests {
 
         client.unstake(&staker, &500_i128);
 
-        let balance = client.get_stake(&staker);
-        assert_eq!(balance, 0);
-
+        // Only the last invocation's events are kept, so read them first
         let events = env.events().all();
         let (_, _, data) = events.last().unwrap();
         let payload: UnstakeEventData = data.into_val(&env);
 
         assert_eq!(payload.remaining, 0);
+
+        let balance = client.get_stake(&staker);
+        assert_eq!(balance, 0);
     }
 
     #[test]
@@ -371,6 +374,197 @@ mod emit_unstake_event_tests {
     }
 }
 
+mod emergency_withdraw_tests {
+    use super::*;
+
+    fn setup_with_stake(env: &Env, amount: i128) -> (StakingContractClient<'_>, Address, Address) {
+        let (client, admin, token) = deploy_contract(env);
+        client.initialize(&admin, &token, &1200_u32, &100_i128);
+        let staker = funded_staker(env, &token);
+        client.stake(&staker, &amount);
+        (client, admin, staker)
+    }
+
+    #[test]
+    fn emergency_withdraw_returns_principal_only() {
+        let env = setup_env();
+        let (client, admin, staker) = setup_with_stake(&env, 1_000);
+
+        // Rewards would have accrued, but none are paid out
+        env.ledger().set(LedgerInfo {
+            timestamp: 1_700_000_000 + 30 * 24 * 60 * 60,
+            ..env.ledger().get()
+        });
+
+        client.set_emergency(&admin, &true);
+        let withdrawn = client.emergency_withdraw(&staker);
+        let events = env.events().all();
+
+        assert_eq!(withdrawn, 1_000);
+        assert_eq!(client.get_stake(&staker), 0);
+
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            topics,
+            soroban_sdk::vec![&env, CONTRACT_TOPIC.into_val(&env), topic_emergency_withdraw().into_val(&env)]
+        );
+        let payload: EmergencyWithdrawEventData = data.into_val(&env);
+        assert_eq!(payload.staker, staker);
+        assert_eq!(payload.amount, 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "emergency withdrawal is not enabled")]
+    fn emergency_withdraw_requires_emergency() {
+        let env = setup_env();
+        let (client, _admin, staker) = setup_with_stake(&env, 1_000);
+        client.emergency_withdraw(&staker);
+    }
+
+    #[test]
+    #[should_panic(expected = "unstake is halted during an emergency")]
+    fn unstake_blocked_during_emergency() {
+        let env = setup_env();
+        let (client, admin, staker) = setup_with_stake(&env, 1_000);
+        client.set_emergency(&admin, &true);
+        client.unstake(&staker, &500_i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "only the admin can change the emergency state")]
+    fn non_admin_cannot_declare_emergency() {
+        let env = setup_env();
+        let (client, _admin, staker) = setup_with_stake(&env, 1_000);
+        client.set_emergency(&staker, &true);
+    }
+}
+
+mod goal_boost_tests {
+    use super::*;
+    use crate::GOAL_BOOST_REFRESH_SECS;
+    use soroban_sdk::{contract, contractimpl};
+
+    /// Stand-in for the savings-goals contract that reports a fixed count.
+    #[contract]
+    pub struct MockGoals;
+
+    #[contractimpl]
+    impl MockGoals {
+        pub fn set_completed(env: Env, count: u32) {
+            env.storage().instance().set(&0u32, &count);
+        }
+
+        pub fn get_completed_goals(env: Env, _user: Address) -> u32 {
+            env.storage().instance().get(&0u32).unwrap_or(0)
+        }
+    }
+
+    fn setup_with_goals(env: &Env) -> (StakingContractClient<'_>, Address, MockGoalsClient<'_>) {
+        let (client, admin, token) = deploy_contract(env);
+        client.initialize(&admin, &token, &1200_u32, &100_i128);
+        let goals_id = env.register(MockGoals, ());
+        let goals = MockGoalsClient::new(env, &goals_id);
+        client.set_goal_boost(&admin, &goals_id, &300_u32);
+        (client, admin, goals)
+    }
+
+    #[test]
+    fn refresh_caches_completed_goals() {
+        let env = setup_env();
+        let (client, _admin, goals) = setup_with_goals(&env);
+        let staker = Address::generate(&env);
+        goals.set_completed(&2);
+
+        let boost = client.refresh_goal_boost(&staker).unwrap();
+        assert_eq!(boost.completed_goals, 2);
+        assert_eq!(boost.checked_at, 1_700_000_000);
+
+        let events = env.events().all();
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(
+            topics,
+            soroban_sdk::vec![&env, CONTRACT_TOPIC.into_val(&env), topic_goal_boost().into_val(&env)]
+        );
+        let payload: GoalBoostEventData = data.into_val(&env);
+        assert_eq!(payload.staker,    staker);
+        assert_eq!(payload.bonus_bps, 300);
+
+        assert_eq!(client.get_goal_boost(&staker), Some(boost));
+    }
+
+    #[test]
+    fn refresh_picks_up_newly_completed_goals() {
+        let env = setup_env();
+        let (client, _admin, goals) = setup_with_goals(&env);
+        let staker = Address::generate(&env);
+
+        assert_eq!(client.refresh_goal_boost(&staker).unwrap().completed_goals, 0);
+
+        goals.set_completed(&1);
+        env.ledger().set(LedgerInfo {
+            timestamp: 1_700_000_000 + GOAL_BOOST_REFRESH_SECS,
+            ..env.ledger().get()
+        });
+        let boost = client.refresh_goal_boost(&staker).unwrap();
+        assert_eq!(boost.completed_goals, 1);
+        assert_eq!(boost.checked_at, 1_700_000_000 + GOAL_BOOST_REFRESH_SECS);
+    }
+
+    #[test]
+    fn bonus_accrues_only_after_completion_is_recorded() {
+        const YEAR: u64 = 365 * 24 * 60 * 60;
+        let env = setup_env();
+        let (client, admin, token) = deploy_contract(&env);
+        client.initialize(&admin, &token, &1200_u32, &100_i128);
+        let goals_id = env.register(MockGoals, ());
+        let goals = MockGoalsClient::new(&env, &goals_id);
+        client.set_goal_boost(&admin, &goals_id, &300_u32);
+
+        let staker = funded_staker(&env, &token);
+        client.stake(&staker, &1_000_000_i128);
+
+        // The goal is completed a year into the stake
+        env.ledger().set(LedgerInfo {
+            timestamp: 1_700_000_000 + YEAR,
+            ..env.ledger().get()
+        });
+        goals.set_completed(&1);
+        let boost = client.refresh_goal_boost(&staker).unwrap();
+        assert_eq!(boost.boosted_since, Some(1_700_000_000 + YEAR));
+
+        env.ledger().set(LedgerInfo {
+            timestamp: 1_700_000_000 + 2 * YEAR,
+            ..env.ledger().get()
+        });
+        client.unstake(&staker, &1_000_000_i128);
+
+        let events = env.events().all();
+        let (_, _, data) = events.last().unwrap();
+        let payload: UnstakeEventData = data.into_val(&env);
+        // 12 % over two years, plus 3 % over the boosted year only
+        assert_eq!(payload.reward, 240_000 + 30_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "only the admin can configure the goal boost")]
+    fn non_admin_cannot_configure_boost() {
+        let env = setup_env();
+        let (client, admin, token) = deploy_contract(&env);
+        client.initialize(&admin, &token, &1200_u32, &100_i128);
+        let stranger = Address::generate(&env);
+        client.set_goal_boost(&stranger, &Address::generate(&env), &300_u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "bonus_bps exceeds the maximum")]
+    fn bonus_above_maximum_panics() {
+        let env = setup_env();
+        let (client, admin, token) = deploy_contract(&env);
+        client.initialize(&admin, &token, &1200_u32, &100_i128);
+        client.set_goal_boost(&admin, &Address::generate(&env), &10_001_u32);
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Section 3 — Schema consistency tests
 // Verify that every event topic is unique and that topic symbols are correct.
@@ -395,9 +589,6 @@ mod event_schema_tests {
         // Ensures nobody accidentally changes the root topic, which would
         // break off-chain indexers subscribed to it.
         let env = setup_env();
-        assert_eq!(
-            CONTRACT_TOPIC.to_string().as_str(),
-            "STAKING",
-        );
+        assert_eq!(CONTRACT_TOPIC, Symbol::new(&env, "STAKING"));
     }
 }
\ No newline at end of file
@@ -6,6 +6,7 @@
 pub mod delegation;
 pub mod errors;
 pub mod fees;
+pub mod pagination;
 // Re-export commonly used types and functions
 pub use errors::{
     ErrorCategory, ErrorContext, ErrorDocumentation, ErrorHelpers, ErrorSeverity, RetryStrategy,
@@ -0,0 +1,75 @@
+//! Pagination helpers shared by the contracts' range queries.
+//!
+//! Paged getters address entries by their 0-based position in an ordered
+//! index and never return more than `MAX_PAGE_SIZE` entries at once. A
+//! cursor is the number of positions already read, i.e. the position of the
+//! next entry; indexes split into buckets (e.g. one per day) pack the bucket
+//! into the cursor's high half. Entries read through `read_page` have their
+//! TTL extended, so data that is still being read stays alive.
+//!
+//! Contracts include this file by path and each uses only some of it.
+#![allow(dead_code)]
+
+use core::ops::Range;
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val, Vec};
+
+/// Largest number of entries a single page may hold
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Whether `limit` is an accepted page size (1 to `MAX_PAGE_SIZE`).
+pub fn is_valid_page_size(limit: u32) -> bool {
+    limit > 0 && limit <= MAX_PAGE_SIZE
+}
+
+/// Positions of the page of up to `limit` entries starting at `start` in an
+/// index of `len` entries. Limits above `MAX_PAGE_SIZE` are capped, and the
+/// range is empty once `start` reaches the end of the index.
+pub fn page_range(start: u64, limit: u32, len: u64) -> Range<u64> {
+    let start = start.min(len);
+    let end = len.min(start.saturating_add(limit.min(MAX_PAGE_SIZE) as u64));
+    start..end
+}
+
+/// Packs a bucket and the position of the next entry within it into a cursor.
+pub fn encode_cursor(bucket: u32, position: u32) -> u64 {
+    ((bucket as u64) << 32) | position as u64
+}
+
+/// Splits a cursor made by `encode_cursor` into its bucket and position.
+pub fn decode_cursor(cursor: u64) -> (u32, u32) {
+    ((cursor >> 32) as u32, cursor as u32)
+}
+
+/// Reads the persistent entry under `key`, extending its TTL if it exists.
+pub fn read_entry<K, V>(env: &Env, key: &K, ttl_threshold: u32, ttl_extend_to: u32) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let value = env.storage().persistent().get::<K, V>(key)?;
+    env.storage()
+        .persistent()
+        .extend_ttl(key, ttl_threshold, ttl_extend_to);
+    Some(value)
+}
+
+/// Reads the persistent entries under `keys` in order, skipping missing
+/// ones and extending the TTL of every entry read.
+pub fn read_page<K, V>(
+    env: &Env,
+    keys: impl Iterator<Item = K>,
+    ttl_threshold: u32,
+    ttl_extend_to: u32,
+) -> Vec<V>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let mut entries = Vec::new(env);
+    for key in keys {
+        if let Some(entry) = read_entry(env, &key, ttl_threshold, ttl_extend_to) {
+            entries.push_back(entry);
+        }
+    }
+    entries
+}
@@ -0,0 +1,132 @@
+//! Projected cash-flow calendar of a sender's recurring payments.
+//!
+//! Every payment is indexed under each address that pays for it: the sender,
+//! or each funder of a shared payment. Projections follow the same rules as
+//! execution: an overdue payment is due now and its missed occurrences are
+//! skipped, and funders of a shared payment see only their own leg.
+
+use crate::schedule;
+use crate::shared;
+use crate::types::{DataKey, RecurringPayment, UpcomingPayment};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Upper bound on the occurrences returned by a single projection.
+pub const MAX_UPCOMING_PAYMENTS: u32 = 200;
+
+/// Adds `payment_id` to the payments paid for by `payer`.
+pub fn index(env: &Env, payer: &Address, payment_id: u64) {
+    let key = DataKey::PayerPayments(payer.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(payment_id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+/// Removes `payment_id` from the payments paid for by `payer`.
+pub fn unindex(env: &Env, payer: &Address, payment_id: u64) {
+    let key = DataKey::PayerPayments(payer.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    if let Some(position) = ids.first_index_of(payment_id) {
+        ids.remove(position);
+        env.storage().persistent().set(&key, &ids);
+    }
+}
+
+/// Occurrences of `payer`'s active payments due within `horizon` seconds of
+/// now, in chronological order, capped at `MAX_UPCOMING_PAYMENTS`.
+pub fn upcoming(env: &Env, payer: &Address, horizon: u64) -> Vec<UpcomingPayment> {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PayerPayments(payer.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let now = env.ledger().timestamp();
+    let until = now.saturating_add(horizon);
+    let mut calendar = Vec::new(env);
+
+    for payment_id in ids.iter() {
+        let Some(payment) = env
+            .storage()
+            .instance()
+            .get::<_, RecurringPayment>(&DataKey::Payment(payment_id))
+        else {
+            continue;
+        };
+        if !payment.active {
+            continue;
+        }
+        let amount = payer_amount(env, payment_id, &payment, payer);
+
+        let mut scheduled = payment.next_execution;
+        let mut execution_time = scheduled.max(now);
+        while execution_time <= until {
+            let kept = insert(
+                &mut calendar,
+                UpcomingPayment {
+                    payment_id,
+                    recipient: payment.recipient.clone(),
+                    token: payment.token.clone(),
+                    amount,
+                    execution_time,
+                    category: payment.category.clone(),
+                },
+            );
+            if !kept {
+                // Later occurrences of this payment would be dropped too
+                break;
+            }
+            scheduled = schedule::next_execution(
+                &payment.schedule,
+                payment.interval,
+                scheduled,
+                execution_time,
+            );
+            execution_time = scheduled;
+        }
+    }
+    calendar
+}
+
+/// Portion of each execution paid by `payer`.
+fn payer_amount(env: &Env, payment_id: u64, payment: &RecurringPayment, payer: &Address) -> i128 {
+    let Some(funders) = env
+        .storage()
+        .instance()
+        .get(&DataKey::PaymentFunders(payment_id))
+    else {
+        return payment.amount;
+    };
+    let legs = shared::leg_amounts(env, payment.amount, &funders);
+    for (share, leg) in funders.iter().zip(legs.iter()) {
+        if share.funder == *payer {
+            return leg;
+        }
+    }
+    0
+}
+
+/// Inserts `entry` after every occurrence due at or before it, dropping the
+/// latest occurrence once the calendar is full. Returns false if `entry`
+/// itself did not fit.
+fn insert(calendar: &mut Vec<UpcomingPayment>, entry: UpcomingPayment) -> bool {
+    let position = calendar
+        .iter()
+        .position(|other| other.execution_time > entry.execution_time)
+        .unwrap_or(calendar.len() as usize) as u32;
+    if position >= MAX_UPCOMING_PAYMENTS {
+        return false;
+    }
+    calendar.insert(position, entry);
+    if calendar.len() > MAX_UPCOMING_PAYMENTS {
+        calendar.pop_back();
+    }
+    true
+}
@@ -1,18 +1,28 @@
 #![no_std]
+// `create_payment` takes the full schedule plus optional metadata.
+#![allow(clippy::too_many_arguments)]
 
+mod calendar;
+mod receipts;
+mod schedule;
+mod shared;
 #[cfg(test)]
 mod test;
 mod types;
 
-use crate::types::{DataKey, RecurringPayment};
-use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env};
+use crate::types::{
+    DataKey, ExecutionPreview, FunderShare, PaymentHealth, PaymentReceipt, RecurringPayment,
+    Schedule, UpcomingPayment,
+};
+use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, BytesN, Env, Symbol, Vec};
 
 #[contract]
 pub struct RecurringPaymentContract;
 
 #[contractimpl]
 impl RecurringPaymentContract {
-    /// Creates a new recurring payment schedule.
+    /// Creates a new recurring payment schedule. Executions draw on an
+    /// allowance the sender grants this contract.
     ///
     /// # Arguments
     /// * `sender`     - The address funding the payments (must authorize)
@@ -21,6 +31,8 @@ impl RecurringPaymentContract {
     /// * `amount`     - Amount transferred on each execution (must be > 0)
     /// * `interval`   - Seconds between executions (must be > 0)
     /// * `start_time` - Ledger timestamp of the first allowed execution
+    /// * `category`   - Optional spending category; indexed per sender
+    /// * `memo`       - Optional reference emitted with every execution
     ///
     /// # Returns
     /// The unique payment ID assigned to this schedule.
@@ -32,15 +44,129 @@ impl RecurringPaymentContract {
         amount: i128,
         interval: u64,
         start_time: u64,
+        category: Option<Symbol>,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        Self::create(
+            &env,
+            sender,
+            recipient,
+            token,
+            amount,
+            Schedule::Interval,
+            interval,
+            start_time,
+            category,
+            memo,
+        )
+    }
+
+    /// Creates a recurring payment that follows a calendar schedule, e.g.
+    /// "monthly on the 31st" or "every Friday".
+    ///
+    /// Executions happen at the time of day of `start_time`, starting on the
+    /// first matching day at or after it.
+    ///
+    /// # Arguments
+    /// * `schedule`   - `MonthlyOnDay(1..=31)` or `WeeklyOnWeekday(0..=6)`
+    /// * `start_time` - Earliest ledger timestamp for the first execution
+    ///
+    /// The other arguments are the same as for `create_payment`.
+    pub fn create_scheduled_payment(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        schedule: Schedule,
+        start_time: u64,
+        category: Option<Symbol>,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        if schedule == Schedule::Interval {
+            panic!("Use create_payment for fixed intervals");
+        }
+        Self::create(
+            &env, sender, recipient, token, amount, schedule, 0, start_time, category, memo,
+        )
+    }
+
+    /// Creates a recurring payment drawn proportionally from several funders,
+    /// e.g. rent split between housemates.
+    ///
+    /// Every funder must authorize joining the payment and grant this contract
+    /// an allowance covering their share. All legs execute together: if one
+    /// funder cannot cover their leg, the execution is skipped and a `short`
+    /// event names that funder.
+    ///
+    /// # Arguments
+    /// * `funders` - Funders and their shares in bps (must add up to 10000);
+    ///   the first funder is recorded as the payment's sender. Any funder may
+    ///   cancel the payment or leave it
+    ///
+    /// The other arguments are the same as for `create_payment`.
+    pub fn create_shared_payment(
+        env: Env,
+        funders: Vec<FunderShare>,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        interval: u64,
+        start_time: u64,
+        category: Option<Symbol>,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        shared::validate(&funders);
+        for share in funders.iter().skip(1) {
+            share.funder.require_auth();
+        }
+
+        let payment_id = Self::create(
+            &env,
+            funders.get(0).unwrap().funder,
+            recipient,
+            token,
+            amount,
+            Schedule::Interval,
+            interval,
+            start_time,
+            category,
+            memo,
+        );
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PaymentFunders(payment_id), &funders);
+        for share in funders.iter().skip(1) {
+            calendar::index(&env, &share.funder, payment_id);
+        }
+
+        env.events().publish(
+            (symbol_short!("recur"), symbol_short!("shared"), payment_id),
+            funders,
+        );
+
+        payment_id
+    }
+
+    fn create(
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        schedule: Schedule,
+        interval: u64,
+        start_time: u64,
+        category: Option<Symbol>,
+        memo: Option<BytesN<32>>,
     ) -> u64 {
         sender.require_auth();
 
         if amount <= 0 {
             panic!("Amount must be positive");
         }
-        if interval == 0 {
-            panic!("Interval must be positive");
-        }
+        schedule::validate(&schedule, interval);
 
         let mut count: u64 = env
             .storage()
@@ -55,26 +181,66 @@ impl RecurringPaymentContract {
             token,
             amount,
             interval,
-            next_execution: start_time,
+            next_execution: schedule::first_execution(&schedule, start_time),
+            schedule,
             active: true,
+            category: category.clone(),
+            memo,
         };
 
         env.storage()
             .instance()
             .set(&DataKey::Payment(count), &payment);
         env.storage().instance().set(&DataKey::PaymentCount, &count);
+        calendar::index(env, &sender, count);
+
+        if let Some(category) = &category {
+            let key = DataKey::CategoryPayments(sender.clone(), category.clone());
+            let mut ids: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(env));
+            ids.push_back(count);
+            env.storage().persistent().set(&key, &ids);
+        }
 
         env.events().publish(
             (symbol_short!("recur"), symbol_short!("created"), count),
-            sender,
+            (sender, category),
         );
 
         count
     }
 
+    /// Executes a due payment and stores a receipt of it. Anyone may
+    /// trigger a due payment; the receipt records no executor.
+    ///
+    /// A shared payment with a funder who cannot cover their leg is left due
+    /// and unpaid; see `create_shared_payment`.
+    ///
     /// # Arguments
     /// * `payment_id` - The ID returned by `create_payment`
     pub fn execute_payment(env: Env, payment_id: u64) {
+        Self::execute(&env, payment_id, None);
+    }
+
+    /// Executes a due payment like `execute_payment`, recording `executor`
+    /// on the receipt.
+    ///
+    /// # Arguments
+    /// * `executor`   - The address triggering the execution (must authorize)
+    /// * `payment_id` - The ID returned by `create_payment`
+    ///
+    /// # Returns
+    /// The ID of the execution receipt, or `None` if a shared payment was
+    /// skipped because a funder could not cover their leg.
+    pub fn execute_payment_by(env: Env, executor: Address, payment_id: u64) -> Option<u64> {
+        executor.require_auth();
+        Self::execute(&env, payment_id, Some(executor)).map(|receipt| receipt.receipt_id)
+    }
+
+    fn execute(env: &Env, payment_id: u64, executor: Option<Address>) -> Option<PaymentReceipt> {
         let mut payment: RecurringPayment = env
             .storage()
             .instance()
@@ -90,26 +256,35 @@ impl RecurringPaymentContract {
             panic!("Too early for next execution");
         }
 
-        // Transfer tokens from sender to recipient.
-        let token_client = token::Client::new(&env, &payment.token);
-        token_client.transfer(&payment.sender, &payment.recipient, &payment.amount);
-
-        // Update next execution time
-        payment.next_execution += payment.interval;
-
-        // If the execution was delayed, we might want to skip or catch up.
-        // For simplicity, we just add the interval to the scheduled time.
-        // If current_time is way past next_execution, catch up.
-        if payment.next_execution <= current_time {
-            // Option 1: Catch up to the next interval in the future
-            // (current_time - scheduled) / interval * interval + scheduled + interval
-            let intervals_passed = (current_time - payment.next_execution) / payment.interval;
-            payment.next_execution += (intervals_passed + 1) * payment.interval;
+        let funders = Self::funders(env, payment_id);
+        if let Some(funders) = &funders {
+            if !Self::execute_shared(env, payment_id, &payment, funders) {
+                return None;
+            }
+        } else {
+            // Draw on the sender's allowance, as `get_payment_health` checks.
+            let token_client = token::Client::new(env, &payment.token);
+            token_client.transfer_from(
+                &env.current_contract_address(),
+                &payment.sender,
+                &payment.recipient,
+                &payment.amount,
+            );
         }
 
+        // Move to the next occurrence in the future; if the execution was
+        // delayed, missed occurrences are skipped rather than paid twice.
+        payment.next_execution = schedule::next_execution(
+            &payment.schedule,
+            payment.interval,
+            payment.next_execution,
+            current_time,
+        );
+
         env.storage()
             .instance()
             .set(&DataKey::Payment(payment_id), &payment);
+        let receipt = receipts::record(env, payment_id, &payment, &funders, executor);
 
         env.events().publish(
             (
@@ -117,8 +292,50 @@ impl RecurringPaymentContract {
                 symbol_short!("executed"),
                 payment_id,
             ),
-            (payment.amount, payment.next_execution),
+            (
+                payment.amount,
+                payment.next_execution,
+                payment.category,
+                payment.memo,
+            ),
         );
+
+        Some(receipt)
+    }
+
+    /// Draws every funder's leg of a shared payment. All legs are checked
+    /// before the first transfer; if a funder is short, no tokens move, a
+    /// `short` event reports the funder's index, address and health, and
+    /// false is returned.
+    fn execute_shared(
+        env: &Env,
+        payment_id: u64,
+        payment: &RecurringPayment,
+        funders: &Vec<FunderShare>,
+    ) -> bool {
+        let legs = shared::leg_amounts(env, payment.amount, funders);
+
+        for (index, (share, leg)) in funders.iter().zip(legs.iter()).enumerate() {
+            let health = shared::leg_health(env, &payment.token, &share.funder, leg);
+            if health != PaymentHealth::Funded {
+                env.events().publish(
+                    (symbol_short!("recur"), symbol_short!("short"), payment_id),
+                    (index as u32, share.funder, health),
+                );
+                return false;
+            }
+        }
+
+        let token_client = token::Client::new(env, &payment.token);
+        let spender = env.current_contract_address();
+        for (share, leg) in funders.iter().zip(legs.iter()) {
+            token_client.transfer_from(&spender, &share.funder, &payment.recipient, &leg);
+            env.events().publish(
+                (symbol_short!("recur"), symbol_short!("leg"), payment_id),
+                (share.funder, leg),
+            );
+        }
+        true
     }
 
     /// Cancels a recurring payment. Only the original sender may cancel.
@@ -153,6 +370,134 @@ impl RecurringPaymentContract {
         );
     }
 
+    /// Cancels a shared payment on behalf of any of its funders.
+    ///
+    /// # Arguments
+    /// * `funder`     - A funder of the payment (must authorize)
+    /// * `payment_id` - The ID returned by `create_shared_payment`
+    pub fn cancel_shared_payment(env: Env, funder: Address, payment_id: u64) {
+        funder.require_auth();
+
+        let funders = Self::funders(&env, payment_id).expect("Not a shared payment");
+        if !funders.iter().any(|share| share.funder == funder) {
+            panic!("Not a funder of this payment");
+        }
+
+        let mut payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+        if !payment.active {
+            panic!("Payment is already canceled");
+        }
+
+        payment.active = false;
+        env.storage()
+            .instance()
+            .set(&DataKey::Payment(payment_id), &payment);
+
+        env.events().publish(
+            (
+                symbol_short!("recur"),
+                symbol_short!("canceled"),
+                payment_id,
+            ),
+            funder,
+        );
+    }
+
+    /// Removes a funder from a shared payment. The remaining funders' shares
+    /// are scaled up to cover the leaver's, keeping their proportions. If the
+    /// sender leaves, the next funder becomes the sender; if the last funder
+    /// leaves, the payment is canceled.
+    ///
+    /// # Arguments
+    /// * `funder`     - The funder leaving (must authorize)
+    /// * `payment_id` - The ID returned by `create_shared_payment`
+    pub fn leave_shared_payment(env: Env, funder: Address, payment_id: u64) {
+        funder.require_auth();
+
+        let funders = Self::funders(&env, payment_id).expect("Not a shared payment");
+        let index = funders
+            .iter()
+            .position(|share| share.funder == funder)
+            .expect("Not a funder of this payment") as u32;
+
+        let mut payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+        if !payment.active {
+            panic!("Payment is not active");
+        }
+
+        let remaining = shared::remove(&funders, index);
+        calendar::unindex(&env, &funder, payment_id);
+        if remaining.is_empty() {
+            payment.active = false;
+        } else if index == 0 {
+            let sender = remaining.get(0).unwrap().funder;
+            if let Some(category) = &payment.category {
+                Self::move_category(&env, &payment.sender, &sender, category, payment_id);
+            }
+            payment.sender = sender;
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Payment(payment_id), &payment);
+        env.storage()
+            .instance()
+            .set(&DataKey::PaymentFunders(payment_id), &remaining);
+
+        env.events().publish(
+            (symbol_short!("recur"), symbol_short!("left"), payment_id),
+            (funder, remaining),
+        );
+    }
+
+    /// Returns the receipt of an execution.
+    ///
+    /// # Arguments
+    /// * `receipt_id` - The ID returned by `execute_payment_by`
+    pub fn get_receipt(env: Env, receipt_id: u64) -> PaymentReceipt {
+        receipts::get(&env, receipt_id).expect("Receipt not found")
+    }
+
+    /// Returns receipts of executions `payer` funded, oldest first.
+    ///
+    /// # Arguments
+    /// * `payer` - The sender, or a funder of a shared payment
+    /// * `start` - Number of receipts to skip
+    /// * `limit` - Maximum number of receipts returned, capped at
+    ///   `MAX_RECEIPT_PAGE_SIZE`
+    pub fn get_payer_receipts(
+        env: Env,
+        payer: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<PaymentReceipt> {
+        receipts::page(&env, DataKey::PayerReceipts(payer), start, limit)
+    }
+
+    /// Returns receipts of executions paid to `payee`, oldest first.
+    ///
+    /// # Arguments
+    /// * `payee` - The recipient of the payments
+    /// * `start` - Number of receipts to skip
+    /// * `limit` - Maximum number of receipts returned, capped at
+    ///   `MAX_RECEIPT_PAGE_SIZE`
+    pub fn get_payee_receipts(
+        env: Env,
+        payee: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<PaymentReceipt> {
+        receipts::page(&env, DataKey::PayeeReceipts(payee), start, limit)
+    }
+
     /// Returns the full details of a payment schedule.
     ///
     /// # Arguments
@@ -163,4 +508,200 @@ impl RecurringPaymentContract {
             .get(&DataKey::Payment(payment_id))
             .expect("Payment not found")
     }
+
+    /// Returns the funders of a shared payment; empty for single-sender payments.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID returned by `create_shared_payment`
+    pub fn get_payment_funders(env: Env, payment_id: u64) -> Vec<FunderShare> {
+        Self::funders(&env, payment_id).unwrap_or(Vec::new(&env))
+    }
+
+    /// Checks whether each funder can cover their leg of the next execution,
+    /// in the order of `get_payment_funders`. Single-sender payments return
+    /// one entry for the sender.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID returned by `create_payment` or `create_shared_payment`
+    pub fn get_funder_health(env: Env, payment_id: u64) -> Vec<PaymentHealth> {
+        let payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+
+        let funders = match Self::funders(&env, payment_id) {
+            Some(funders) => funders,
+            None => return Vec::from_array(&env, [Self::payment_health(&env, &payment)]),
+        };
+
+        let legs = shared::leg_amounts(&env, payment.amount, &funders);
+        let mut results = Vec::new(&env);
+        for (share, leg) in funders.iter().zip(legs.iter()) {
+            results.push_back(if payment.active {
+                shared::leg_health(&env, &payment.token, &share.funder, leg)
+            } else {
+                PaymentHealth::Inactive
+            });
+        }
+        results
+    }
+
+    /// Returns the IDs of a sender's payments tagged with `category`.
+    ///
+    /// # Arguments
+    /// * `sender`   - The address funding the payments
+    /// * `category` - The category passed to `create_payment`
+    pub fn get_payments_by_category(env: Env, sender: Address, category: Symbol) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CategoryPayments(sender, category))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Projects the executions of all of a sender's active payments within
+    /// the next `horizon_seconds`, so the app can render a cash-flow calendar
+    /// from one call.
+    ///
+    /// Occurrences are returned in chronological order, at most
+    /// `MAX_UPCOMING_PAYMENTS` of them. Overdue payments are reported as due
+    /// now. Funders of a shared payment see it with their own leg's amount.
+    ///
+    /// # Arguments
+    /// * `sender`          - The address funding the payments
+    /// * `horizon_seconds` - How far ahead of the current ledger time to project
+    pub fn get_upcoming_payments(
+        env: Env,
+        sender: Address,
+        horizon_seconds: u64,
+    ) -> Vec<UpcomingPayment> {
+        calendar::upcoming(&env, &sender, horizon_seconds)
+    }
+
+    /// Checks whether the sender can cover the payment's next execution so the
+    /// app can warn before a payment fails.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID returned by `create_payment`
+    pub fn get_payment_health(env: Env, payment_id: u64) -> PaymentHealth {
+        let payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+
+        // Shared payments report the first funder that cannot cover their leg
+        if payment.active && Self::funders(&env, payment_id).is_some() {
+            for health in Self::get_funder_health(env.clone(), payment_id).iter() {
+                if health != PaymentHealth::Funded {
+                    return health;
+                }
+            }
+            return PaymentHealth::Funded;
+        }
+
+        Self::payment_health(&env, &payment)
+    }
+
+    /// Projects what `execute_payment` would do if invoked now, without
+    /// moving any tokens, so apps and keepers can check a payment before
+    /// executing it.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID returned by `create_payment`
+    pub fn preview_execution(env: Env, payment_id: u64) -> ExecutionPreview {
+        let payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+
+        let now = env.ledger().timestamp();
+        let due = now >= payment.next_execution;
+        let health = Self::get_payment_health(env.clone(), payment_id);
+
+        let mut preview = ExecutionPreview {
+            payment_id,
+            executable: due && health == PaymentHealth::Funded,
+            due,
+            health,
+            intervals_caught_up: 0,
+            next_execution: payment.next_execution,
+            total_amount: 0,
+        };
+        if due && payment.active {
+            preview.intervals_caught_up = schedule::occurrences_due(
+                &payment.schedule,
+                payment.interval,
+                payment.next_execution,
+                now,
+            );
+            preview.next_execution = schedule::next_execution(
+                &payment.schedule,
+                payment.interval,
+                payment.next_execution,
+                now,
+            );
+            preview.total_amount = payment.amount;
+        }
+        preview
+    }
+
+    /// Batch variant of `get_payment_health`; results follow the order of `payment_ids`.
+    ///
+    /// # Arguments
+    /// * `payment_ids` - IDs returned by `create_payment`
+    pub fn get_payments_health(env: Env, payment_ids: Vec<u64>) -> Vec<PaymentHealth> {
+        let mut results = Vec::new(&env);
+        for payment_id in payment_ids.iter() {
+            results.push_back(Self::get_payment_health(env.clone(), payment_id));
+        }
+        results
+    }
+
+    /// Moves `payment_id` from `from`'s category index to `to`'s.
+    fn move_category(env: &Env, from: &Address, to: &Address, category: &Symbol, payment_id: u64) {
+        let key = DataKey::CategoryPayments(from.clone(), category.clone());
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if let Some(position) = ids.first_index_of(payment_id) {
+            ids.remove(position);
+        }
+        env.storage().persistent().set(&key, &ids);
+
+        let key = DataKey::CategoryPayments(to.clone(), category.clone());
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(payment_id);
+        env.storage().persistent().set(&key, &ids);
+    }
+
+    fn funders(env: &Env, payment_id: u64) -> Option<Vec<FunderShare>> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PaymentFunders(payment_id))
+    }
+
+    fn payment_health(env: &Env, payment: &RecurringPayment) -> PaymentHealth {
+        if !payment.active {
+            return PaymentHealth::Inactive;
+        }
+
+        let token_client = token::Client::new(env, &payment.token);
+        if token_client.balance(&payment.sender) < payment.amount {
+            return PaymentHealth::Underfunded;
+        }
+        let allowance = token_client.allowance(&payment.sender, &env.current_contract_address());
+        if allowance < payment.amount {
+            return PaymentHealth::AllowanceMissing;
+        }
+
+        PaymentHealth::Funded
+    }
 }
@@ -0,0 +1,103 @@
+//! Receipts of executed payments.
+//!
+//! Every successful execution stores a receipt and appends its id to the
+//! payee's index and to the index of every payer: the sender, or each funder
+//! of a shared payment.
+
+use crate::types::{DataKey, FunderShare, PaymentReceipt, RecurringPayment};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Largest number of receipts a single page may hold
+pub const MAX_RECEIPT_PAGE_SIZE: u32 = 100;
+
+/// Stores the receipt of an execution and indexes it for both sides.
+pub fn record(
+    env: &Env,
+    payment_id: u64,
+    payment: &RecurringPayment,
+    funders: &Option<Vec<FunderShare>>,
+    executor: Option<Address>,
+) -> PaymentReceipt {
+    let receipt_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ReceiptCount)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::ReceiptCount, &receipt_id);
+
+    let receipt = PaymentReceipt {
+        receipt_id,
+        payment_id,
+        sender: payment.sender.clone(),
+        recipient: payment.recipient.clone(),
+        token: payment.token.clone(),
+        amount: payment.amount,
+        fee: 0,
+        executed_at: env.ledger().timestamp(),
+        executor,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Receipt(receipt_id), &receipt);
+
+    match funders {
+        Some(funders) => {
+            for share in funders.iter() {
+                append(env, DataKey::PayerReceipts(share.funder), receipt_id);
+            }
+        }
+        None => append(
+            env,
+            DataKey::PayerReceipts(payment.sender.clone()),
+            receipt_id,
+        ),
+    }
+    append(
+        env,
+        DataKey::PayeeReceipts(payment.recipient.clone()),
+        receipt_id,
+    );
+
+    receipt
+}
+
+pub fn get(env: &Env, receipt_id: u64) -> Option<PaymentReceipt> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Receipt(receipt_id))
+}
+
+/// Up to `limit` receipts from an index, oldest first, skipping `start`.
+/// Limits above `MAX_RECEIPT_PAGE_SIZE` are capped.
+pub fn page(env: &Env, index: DataKey, start: u32, limit: u32) -> Vec<PaymentReceipt> {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&index)
+        .unwrap_or(Vec::new(env));
+
+    let mut receipts = Vec::new(env);
+    for receipt_id in ids
+        .iter()
+        .skip(start as usize)
+        .take(limit.min(MAX_RECEIPT_PAGE_SIZE) as usize)
+    {
+        if let Some(receipt) = get(env, receipt_id) {
+            receipts.push_back(receipt);
+        }
+    }
+    receipts
+}
+
+fn append(env: &Env, index: DataKey, receipt_id: u64) {
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&index)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(receipt_id);
+    env.storage().persistent().set(&index, &ids);
+}
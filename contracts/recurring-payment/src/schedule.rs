@@ -0,0 +1,149 @@
+//! Calendar arithmetic for recurring payment schedules.
+//!
+//! All dates are UTC days counted from the Unix epoch. Calendar schedules
+//! keep the time of day of the payment's first execution, and "monthly on
+//! day N" falls back to the last day of months shorter than N days.
+
+use crate::types::Schedule;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Panics if the schedule's parameters are out of range.
+pub fn validate(schedule: &Schedule, interval: u64) {
+    match schedule {
+        Schedule::Interval => {
+            if interval == 0 {
+                panic!("Interval must be positive");
+            }
+        }
+        Schedule::MonthlyOnDay(day) => {
+            if *day == 0 || *day > 31 {
+                panic!("Day of month must be between 1 and 31");
+            }
+        }
+        Schedule::WeeklyOnWeekday(weekday) => {
+            if *weekday > 6 {
+                panic!("Weekday must be between 0 and 6");
+            }
+        }
+    }
+}
+
+/// First execution time at or after `start_time`.
+pub fn first_execution(schedule: &Schedule, start_time: u64) -> u64 {
+    match schedule {
+        Schedule::Interval => start_time,
+        _ => {
+            let time_of_day = start_time % SECONDS_PER_DAY;
+            let day = matching_day(schedule, start_time / SECONDS_PER_DAY);
+            day * SECONDS_PER_DAY + time_of_day
+        }
+    }
+}
+
+/// Next execution time after the one scheduled at `scheduled`, skipping any
+/// occurrences that are already at or before `now`.
+pub fn next_execution(schedule: &Schedule, interval: u64, scheduled: u64, now: u64) -> u64 {
+    match schedule {
+        Schedule::Interval => {
+            let next = scheduled + interval;
+            if next > now {
+                return next;
+            }
+            let intervals_passed = (now - next) / interval;
+            next + (intervals_passed + 1) * interval
+        }
+        _ => {
+            let time_of_day = scheduled % SECONDS_PER_DAY;
+            let after = scheduled.max(now);
+            let next =
+                matching_day(schedule, after / SECONDS_PER_DAY) * SECONDS_PER_DAY + time_of_day;
+            if next > after {
+                return next;
+            }
+            matching_day(schedule, after / SECONDS_PER_DAY + 1) * SECONDS_PER_DAY + time_of_day
+        }
+    }
+}
+
+/// Number of occurrences from the one scheduled at `scheduled` up to and
+/// including `now`; 0 if it is not due yet.
+pub fn occurrences_due(schedule: &Schedule, interval: u64, scheduled: u64, now: u64) -> u32 {
+    if scheduled > now {
+        return 0;
+    }
+    if *schedule == Schedule::Interval {
+        return ((now - scheduled) / interval + 1).min(u32::MAX as u64) as u32;
+    }
+
+    let mut count: u32 = 0;
+    let mut occurrence = scheduled;
+    while occurrence <= now && count < u32::MAX {
+        count += 1;
+        occurrence = next_execution(schedule, interval, occurrence, occurrence);
+    }
+    count
+}
+
+/// First day at or after `day` on which a calendar schedule fires.
+fn matching_day(schedule: &Schedule, day: u64) -> u64 {
+    match schedule {
+        Schedule::Interval => day,
+        Schedule::WeeklyOnWeekday(weekday) => {
+            // 1970-01-01 was a Thursday; Monday is weekday 0.
+            let current = (day + 3) % 7;
+            day + (*weekday as u64 + 7 - current) % 7
+        }
+        Schedule::MonthlyOnDay(target) => {
+            let (year, month, day_of_month) = civil_from_days(day);
+            let this_month = (*target).min(days_in_month(year, month));
+            if this_month >= day_of_month {
+                return days_from_civil(year, month, this_month);
+            }
+            let (year, month) = if month == 12 {
+                (year + 1, 1)
+            } else {
+                (year, month + 1)
+            };
+            days_from_civil(year, month, (*target).min(days_in_month(year, month)))
+        }
+    }
+}
+
+fn is_leap_year(year: u64) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converts days since the epoch to a (year, month, day) civil date.
+fn civil_from_days(days: u64) -> (u64, u32, u32) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a (year, month, day) civil date to days since the epoch.
+fn days_from_civil(year: u64, month: u32, day: u32) -> u64 {
+    let year = year - u64::from(month <= 2);
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 } as u64;
+    let doy = (153 * mp + 2) / 5 + day as u64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,92 @@
+//! Shared (household) payments drawn from several funders.
+//!
+//! Each funder covers `share_bps / 10_000` of every execution through an
+//! allowance granted to this contract. Legs are rounded down and the last
+//! funder pays the rounding remainder so the recipient always receives the
+//! full amount.
+
+use crate::types::{FunderShare, PaymentHealth};
+use soroban_sdk::{token, Address, Env, Vec};
+
+/// Shares of all funders must add up to this many basis points.
+pub const TOTAL_SHARE_BPS: u32 = 10_000;
+
+/// Panics if the funder list is empty, repeats a funder, or its shares do
+/// not add up to 100%.
+pub fn validate(funders: &Vec<FunderShare>) {
+    if funders.is_empty() {
+        panic!("At least one funder is required");
+    }
+
+    let mut total: u32 = 0;
+    for (i, share) in funders.iter().enumerate() {
+        if share.share_bps == 0 {
+            panic!("Funder share must be positive");
+        }
+        for other in funders.iter().skip(i + 1) {
+            if other.funder == share.funder {
+                panic!("Duplicate funder");
+            }
+        }
+        total = total.saturating_add(share.share_bps);
+    }
+
+    if total != TOTAL_SHARE_BPS {
+        panic!("Funder shares must add up to 10000 bps");
+    }
+}
+
+/// `funders` without the one at `index`, with the remaining shares scaled
+/// back up to `TOTAL_SHARE_BPS` in proportion. The last remaining funder
+/// takes the rounding remainder.
+pub fn remove(funders: &Vec<FunderShare>, index: u32) -> Vec<FunderShare> {
+    let mut remaining = funders.clone();
+    remaining.remove_unchecked(index);
+    if remaining.is_empty() {
+        return remaining;
+    }
+
+    let kept = TOTAL_SHARE_BPS - funders.get_unchecked(index).share_bps;
+    let last = remaining.len() - 1;
+    let mut unassigned = TOTAL_SHARE_BPS;
+    for i in 0..remaining.len() {
+        let mut share = remaining.get_unchecked(i);
+        share.share_bps = if i == last {
+            unassigned
+        } else {
+            (share.share_bps as u64 * TOTAL_SHARE_BPS as u64 / kept as u64) as u32
+        };
+        unassigned -= share.share_bps;
+        remaining.set(i, share);
+    }
+    remaining
+}
+
+/// Amount drawn from each funder, in the order of `funders`.
+pub fn leg_amounts(env: &Env, amount: i128, funders: &Vec<FunderShare>) -> Vec<i128> {
+    let mut legs = Vec::new(env);
+    let mut remaining = amount;
+    let last = funders.len() - 1;
+    for (i, share) in funders.iter().enumerate() {
+        let leg = if i as u32 == last {
+            remaining
+        } else {
+            amount * share.share_bps as i128 / TOTAL_SHARE_BPS as i128
+        };
+        remaining -= leg;
+        legs.push_back(leg);
+    }
+    legs
+}
+
+/// Whether `funder` can cover a leg of `amount`.
+pub fn leg_health(env: &Env, token: &Address, funder: &Address, amount: i128) -> PaymentHealth {
+    let token_client = token::Client::new(env, token);
+    if token_client.balance(funder) < amount {
+        return PaymentHealth::Underfunded;
+    }
+    if token_client.allowance(funder, &env.current_contract_address()) < amount {
+        return PaymentHealth::AllowanceMissing;
+    }
+    PaymentHealth::Funded
+}
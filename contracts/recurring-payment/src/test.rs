@@ -1,32 +1,43 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::{token, Address, Env};
+use crate::types::{FunderShare, PaymentHealth, Schedule};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{symbol_short, token, Address, BytesN, Env, IntoVal, TryFromVal};
 
-fn create_token_contract<'a>(e: &Env, admin: &Address) -> (Address, token::Client<'a>) {
-    let addr = e.register_stellar_asset_contract(admin.clone());
-    (addr.clone(), token::Client::new(e, &addr))
+fn create_token_contract<'a>(
+    e: &Env,
+    admin: &Address,
+) -> (Address, token::Client<'a>, token::StellarAssetClient<'a>) {
+    let addr = e
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (
+        addr.clone(),
+        token::Client::new(e, &addr),
+        token::StellarAssetClient::new(e, &addr),
+    )
 }
 
 #[test]
 fn test_recurring_payment_flow() {
     let env = Env::default();
-    env.mock_all_auths();
+    env.mock_all_auths_allowing_non_root_auth();
 
     let admin = Address::generate(&env);
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
 
-    let (token_addr, token_client) = create_token_contract(&env, &admin);
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
     let amount = 1000i128;
     let interval = 3600u64; // 1 hour
     let start_time = 1000u64;
 
-    token_client.mint(&sender, &5000i128);
+    token_admin.mint(&sender, &5000i128);
 
-    let contract_id = env.register_contract(None, RecurringPaymentContract);
+    let contract_id = env.register(RecurringPaymentContract, ());
     let client = RecurringPaymentContractClient::new(&env, &contract_id);
+    token_client.approve(&sender, &contract_id, &5000, &1000);
 
     // 1. Create payment
     let payment_id = client.create_payment(
@@ -36,6 +47,8 @@ fn test_recurring_payment_flow() {
         &amount,
         &interval,
         &start_time,
+        &None,
+        &None,
     );
     assert_eq!(payment_id, 1);
 
@@ -77,30 +90,31 @@ fn test_create_with_zero_amount() {
     let recipient = Address::generate(&env);
     let token = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, RecurringPaymentContract);
+    let contract_id = env.register(RecurringPaymentContract, ());
     let client = RecurringPaymentContractClient::new(&env, &contract_id);
 
-    client.create_payment(&sender, &recipient, &token, &0, &3600, &1000);
+    client.create_payment(&sender, &recipient, &token, &0, &3600, &1000, &None, &None);
 }
 
 #[test]
 fn test_execute_with_delay() {
     let env = Env::default();
-    env.mock_all_auths();
+    env.mock_all_auths_allowing_non_root_auth();
 
     let admin = Address::generate(&env);
     let sender = Address::generate(&env);
     let recipient = Address::generate(&env);
 
-    let (token_addr, token_client) = create_token_contract(&env, &admin);
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
     let amount = 1000i128;
     let interval = 3600u64;
     let start_time = 1000u64;
 
-    token_client.mint(&sender, &5000i128);
+    token_admin.mint(&sender, &5000i128);
 
-    let contract_id = env.register_contract(None, RecurringPaymentContract);
+    let contract_id = env.register(RecurringPaymentContract, ());
     let client = RecurringPaymentContractClient::new(&env, &contract_id);
+    token_client.approve(&sender, &contract_id, &5000, &1000);
 
     client.create_payment(
         &sender,
@@ -109,6 +123,8 @@ fn test_execute_with_delay() {
         &amount,
         &interval,
         &start_time,
+        &None,
+        &None,
     );
 
     // Set time way ahead (e.g., 2.5 intervals ahead)
@@ -120,3 +136,633 @@ fn test_execute_with_delay() {
     assert_eq!(payment.next_execution, start_time + 3 * interval);
     assert_eq!(token_client.balance(&recipient), 1000);
 }
+
+#[test]
+fn test_payment_category_and_memo() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let (token_addr, _token_client, token_admin) = create_token_contract(&env, &admin);
+    token_admin.mint(&sender, &5000i128);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    let rent = symbol_short!("rent");
+    let memo = BytesN::from_array(&env, &[7u8; 32]);
+
+    let rent_id = client.create_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &3600,
+        &1000,
+        &Some(rent.clone()),
+        &Some(memo.clone()),
+    );
+    let untagged_id = client.create_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &500,
+        &3600,
+        &1000,
+        &None,
+        &None,
+    );
+
+    let payment = client.get_payment(&rent_id);
+    assert_eq!(payment.category, Some(rent.clone()));
+    assert_eq!(payment.memo, Some(memo));
+    assert_eq!(client.get_payment(&untagged_id).category, None);
+
+    let ids = client.get_payments_by_category(&sender, &rent);
+    assert_eq!(ids.len(), 1);
+    assert_eq!(ids.get(0).unwrap(), rent_id);
+    assert_eq!(client.get_payments_by_category(&recipient, &rent).len(), 0);
+}
+
+#[test]
+fn test_payment_health() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    let payment_id = client.create_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &3600,
+        &1000,
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.get_payment_health(&payment_id),
+        PaymentHealth::Underfunded
+    );
+
+    token_admin.mint(&sender, &5000i128);
+    assert_eq!(
+        client.get_payment_health(&payment_id),
+        PaymentHealth::AllowanceMissing
+    );
+
+    token_client.approve(&sender, &contract_id, &1000, &1000);
+    assert_eq!(
+        client.get_payment_health(&payment_id),
+        PaymentHealth::Funded
+    );
+
+    let other_id = client.create_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &2000,
+        &3600,
+        &1000,
+        &None,
+        &None,
+    );
+    client.cancel_payment(&other_id);
+
+    let health = client.get_payments_health(&soroban_sdk::vec![&env, payment_id, other_id]);
+    assert_eq!(
+        health,
+        soroban_sdk::vec![&env, PaymentHealth::Funded, PaymentHealth::Inactive]
+    );
+}
+
+#[test]
+fn test_monthly_schedule_clamps_to_month_end() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
+    token_admin.mint(&sender, &5000i128);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+    token_client.approve(&sender, &contract_id, &5000, &1000);
+
+    // 2024-01-31 10:00 UTC, monthly on the 31st
+    let start_time = 1_706_695_200u64;
+    let payment_id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &Schedule::MonthlyOnDay(31),
+        &start_time,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_payment(&payment_id).next_execution, start_time);
+
+    env.ledger().set_timestamp(start_time);
+    client.execute_payment(&payment_id);
+    // 2024-02-29 10:00 UTC (leap year, shorter month)
+    assert_eq!(
+        client.get_payment(&payment_id).next_execution,
+        1_709_200_800
+    );
+
+    env.ledger().set_timestamp(1_709_200_800);
+    client.execute_payment(&payment_id);
+    // 2024-03-31 10:00 UTC, back on the 31st
+    assert_eq!(
+        client.get_payment(&payment_id).next_execution,
+        1_711_879_200
+    );
+    assert_eq!(token_client.balance(&recipient), 2000);
+}
+
+#[test]
+fn test_weekly_schedule_catches_up() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
+    token_admin.mint(&sender, &5000i128);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+    token_client.approve(&sender, &contract_id, &5000, &1000);
+
+    // Wednesday 2024-01-03, every Friday
+    let payment_id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &Schedule::WeeklyOnWeekday(4),
+        &1_704_240_000,
+        &None,
+        &None,
+    );
+    // Friday 2024-01-05
+    let payment = client.get_payment(&payment_id);
+    assert_eq!(payment.next_execution, 1_704_412_800);
+    assert_eq!(payment.interval, 0);
+
+    // Executed late on Saturday 2024-01-20: missed Fridays are skipped
+    env.ledger().set_timestamp(1_705_708_800);
+    client.execute_payment(&payment_id);
+    assert_eq!(
+        client.get_payment(&payment_id).next_execution,
+        1_706_227_200
+    );
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Day of month must be between 1 and 31")]
+fn test_monthly_schedule_rejects_invalid_day() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let (token_addr, _, _) = create_token_contract(&env, &admin);
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    client.create_scheduled_payment(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token_addr,
+        &1000,
+        &Schedule::MonthlyOnDay(32),
+        &0,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+fn test_shared_payment_splits_between_funders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let landlord = Address::generate(&env);
+
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    let funders = soroban_sdk::vec![
+        &env,
+        FunderShare {
+            funder: alice.clone(),
+            share_bps: 6_000,
+        },
+        FunderShare {
+            funder: bob.clone(),
+            share_bps: 4_000,
+        },
+    ];
+    let payment_id = client.create_shared_payment(
+        &funders,
+        &landlord,
+        &token_addr,
+        &1001,
+        &3600,
+        &1000,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_payment(&payment_id).sender, alice);
+    assert_eq!(client.get_payment_funders(&payment_id), funders);
+
+    token_admin.mint(&alice, &5000);
+    token_admin.mint(&bob, &5000);
+    token_client.approve(&alice, &contract_id, &5000, &1000);
+    assert_eq!(
+        client.get_funder_health(&payment_id),
+        soroban_sdk::vec![&env, PaymentHealth::Funded, PaymentHealth::AllowanceMissing]
+    );
+    assert_eq!(
+        client.get_payment_health(&payment_id),
+        PaymentHealth::AllowanceMissing
+    );
+
+    // Bob's leg is not covered, so nothing moves and he is named as short
+    env.ledger().set_timestamp(1000);
+    client.execute_payment(&payment_id);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("recur"), symbol_short!("short"), payment_id).into_val(&env)
+    );
+    assert_eq!(
+        <(u32, Address, PaymentHealth)>::try_from_val(&env, &data).unwrap(),
+        (1, bob.clone(), PaymentHealth::AllowanceMissing)
+    );
+    assert_eq!(client.get_payment(&payment_id).next_execution, 1000);
+    assert_eq!(client.get_payer_receipts(&alice, &0, &10).len(), 0);
+    assert_eq!(token_client.balance(&landlord), 0);
+    assert_eq!(token_client.balance(&alice), 5000);
+
+    token_client.approve(&bob, &contract_id, &5000, &1000);
+    client.execute_payment(&payment_id);
+
+    // Alice pays 60% rounded down, Bob covers the remainder
+    assert_eq!(token_client.balance(&alice), 5000 - 600);
+    assert_eq!(token_client.balance(&bob), 5000 - 401);
+    assert_eq!(token_client.balance(&landlord), 1001);
+    assert_eq!(client.get_payment(&payment_id).next_execution, 1000 + 3600);
+
+    // Both funders see the execution in their history
+    assert_eq!(client.get_payer_receipts(&alice, &0, &10).len(), 1);
+    assert_eq!(client.get_payer_receipts(&bob, &0, &10).len(), 1);
+}
+
+#[test]
+fn test_execution_receipts_by_payer_and_payee() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
+    token_admin.mint(&sender, &5000);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+    token_client.approve(&sender, &contract_id, &5000, &1000);
+
+    let payment_id = client.create_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &3600,
+        &1000,
+        &None,
+        &None,
+    );
+
+    env.ledger().set_timestamp(1000);
+    let first = client.execute_payment_by(&keeper, &payment_id).unwrap();
+    env.ledger().set_timestamp(1000 + 3600);
+    client.execute_payment(&payment_id);
+    let second = first + 1;
+    assert_eq!(first, 1);
+
+    let receipt = client.get_receipt(&first);
+    assert_eq!(receipt.payment_id, payment_id);
+    assert_eq!(receipt.sender, sender);
+    assert_eq!(receipt.recipient, recipient);
+    assert_eq!(receipt.amount, 1000);
+    assert_eq!(receipt.fee, 0);
+    assert_eq!(receipt.executed_at, 1000);
+    assert_eq!(receipt.executor, Some(keeper));
+    assert_eq!(client.get_receipt(&second).executor, None);
+
+    let paid = client.get_payer_receipts(&sender, &0, &10);
+    assert_eq!(paid.len(), 2);
+    assert_eq!(paid.get(0).unwrap(), receipt);
+    assert_eq!(client.get_payee_receipts(&recipient, &0, &10), paid);
+
+    // Pages skip `start` receipts and return at most `limit`
+    let page = client.get_payee_receipts(&recipient, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().receipt_id, second);
+    assert_eq!(client.get_payer_receipts(&recipient, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_funders_can_leave_or_cancel_shared_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let (token_addr, _, _) = create_token_contract(&env, &admin);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    let share = |funder: &Address, share_bps: u32| FunderShare {
+        funder: funder.clone(),
+        share_bps,
+    };
+    let rent = symbol_short!("rent");
+    let payment_id = client.create_shared_payment(
+        &soroban_sdk::vec![
+            &env,
+            share(&alice, 5_000),
+            share(&bob, 3_000),
+            share(&carol, 2_000)
+        ],
+        &landlord,
+        &token_addr,
+        &1000,
+        &3600,
+        &0,
+        &Some(rent.clone()),
+        &None,
+    );
+
+    // The sender leaves: Bob takes over and the shares keep their 3:2 ratio
+    client.leave_shared_payment(&alice, &payment_id);
+    assert_eq!(
+        client.get_payment_funders(&payment_id),
+        soroban_sdk::vec![&env, share(&bob, 6_000), share(&carol, 4_000)]
+    );
+    assert_eq!(client.get_payment(&payment_id).sender, bob);
+    assert!(client.get_upcoming_payments(&alice, &3600).is_empty());
+    assert!(client.get_payments_by_category(&alice, &rent).is_empty());
+    assert_eq!(
+        client.get_payments_by_category(&bob, &rent),
+        soroban_sdk::vec![&env, payment_id]
+    );
+    assert!(client
+        .try_leave_shared_payment(&alice, &payment_id)
+        .is_err());
+
+    // Any remaining funder, not only the sender, may cancel
+    client.cancel_shared_payment(&carol, &payment_id);
+    assert!(!client.get_payment(&payment_id).active);
+}
+
+#[test]
+#[should_panic(expected = "Funder shares must add up to 10000 bps")]
+fn test_shared_payment_rejects_incomplete_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let (token_addr, _, _) = create_token_contract(&env, &admin);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    let funders = soroban_sdk::vec![
+        &env,
+        FunderShare {
+            funder: alice.clone(),
+            share_bps: 5_000,
+        },
+        FunderShare {
+            funder: bob,
+            share_bps: 4_000,
+        },
+    ];
+    client.create_shared_payment(
+        &funders,
+        &landlord,
+        &token_addr,
+        &1000,
+        &3600,
+        &1000,
+        &None,
+        &None,
+    );
+}
+
+#[test]
+fn test_upcoming_payments_calendar() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let gym = Address::generate(&env);
+    let (token_addr, _, _) = create_token_contract(&env, &admin);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    let gym_id = client.create_payment(
+        &alice,
+        &gym,
+        &token_addr,
+        &100,
+        &3600,
+        &1000,
+        &Some(symbol_short!("health")),
+        &None,
+    );
+    let funders = soroban_sdk::vec![
+        &env,
+        FunderShare {
+            funder: alice.clone(),
+            share_bps: 6_000,
+        },
+        FunderShare {
+            funder: bob.clone(),
+            share_bps: 4_000,
+        },
+    ];
+    let rent_id = client.create_shared_payment(
+        &funders,
+        &landlord,
+        &token_addr,
+        &1001,
+        &5000,
+        &3000,
+        &None,
+        &None,
+    );
+    let canceled_id =
+        client.create_payment(&alice, &gym, &token_addr, &50, &600, &1000, &None, &None);
+    client.cancel_payment(&canceled_id);
+
+    // The gym payment is overdue, so it is due now and its next occurrence
+    // is one interval after the missed one
+    env.ledger().set_timestamp(2000);
+    let calendar = client.get_upcoming_payments(&alice, &7200);
+    let expected = [
+        (gym_id, 2000, 100),
+        (rent_id, 3000, 600),
+        (gym_id, 4600, 100),
+        (rent_id, 8000, 600),
+        (gym_id, 8200, 100),
+    ];
+    assert_eq!(calendar.len(), expected.len() as u32);
+    for (entry, (payment_id, execution_time, amount)) in calendar.iter().zip(expected) {
+        assert_eq!(entry.payment_id, payment_id);
+        assert_eq!(entry.execution_time, execution_time);
+        assert_eq!(entry.amount, amount);
+    }
+    assert_eq!(
+        calendar.get(0).unwrap().category,
+        Some(symbol_short!("health"))
+    );
+    assert_eq!(calendar.get(1).unwrap().recipient, landlord);
+
+    // Bob only sees his leg of the shared rent
+    let calendar = client.get_upcoming_payments(&bob, &7200);
+    assert_eq!(calendar.len(), 2);
+    assert_eq!(calendar.get(0).unwrap().amount, 401);
+    assert_eq!(calendar.get(1).unwrap().execution_time, 8000);
+
+    assert_eq!(client.get_upcoming_payments(&landlord, &7200).len(), 0);
+}
+
+#[test]
+fn test_preview_execution_projects_catch_up() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_addr, token_client, token_admin) = create_token_contract(&env, &admin);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(&env, &contract_id);
+
+    let payment_id = client.create_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &3600,
+        &1000,
+        &None,
+        &None,
+    );
+
+    // Not due yet: nothing would move
+    env.ledger().set_timestamp(999);
+    let preview = client.preview_execution(&payment_id);
+    assert!(!preview.due);
+    assert!(!preview.executable);
+    assert_eq!(preview.intervals_caught_up, 0);
+    assert_eq!(preview.next_execution, 1000);
+    assert_eq!(preview.total_amount, 0);
+
+    // Due two and a half intervals late but unfunded
+    env.ledger().set_timestamp(1000 + 3600 * 2 + 500);
+    let preview = client.preview_execution(&payment_id);
+    assert!(preview.due);
+    assert!(!preview.executable);
+    assert_eq!(preview.health, PaymentHealth::Underfunded);
+    assert_eq!(preview.intervals_caught_up, 3);
+    assert_eq!(preview.next_execution, 1000 + 3600 * 3);
+    assert_eq!(preview.total_amount, 1000);
+
+    // Funded but without an allowance: the execution would fail too
+    token_admin.mint(&sender, &5000);
+    let preview = client.preview_execution(&payment_id);
+    assert!(!preview.executable);
+    assert_eq!(preview.health, PaymentHealth::AllowanceMissing);
+    assert!(client.try_execute_payment(&payment_id).is_err());
+
+    token_client.approve(&sender, &contract_id, &5000, &1000);
+    let preview = client.preview_execution(&payment_id);
+    assert!(preview.executable);
+    assert_eq!(preview.health, PaymentHealth::Funded);
+
+    // The execution matches the preview
+    client.execute_payment(&payment_id);
+    assert_eq!(token_client.balance(&recipient), preview.total_amount);
+    assert_eq!(
+        client.get_payment(&payment_id).next_execution,
+        preview.next_execution
+    );
+
+    let preview = client.preview_execution(&payment_id);
+    assert!(!preview.due);
+    assert_eq!(preview.intervals_caught_up, 0);
+
+    // Calendar schedules count the missed occurrences too: Fridays 2024-01-05,
+    // 12 and 19 have passed by Saturday 2024-01-20
+    let weekly_id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &Schedule::WeeklyOnWeekday(4),
+        &1_704_240_000,
+        &None,
+        &None,
+    );
+    env.ledger().set_timestamp(1_705_708_800);
+    let preview = client.preview_execution(&weekly_id);
+    assert!(preview.executable);
+    assert_eq!(preview.intervals_caught_up, 3);
+    assert_eq!(preview.next_execution, 1_706_227_200);
+
+    client.cancel_payment(&weekly_id);
+    let preview = client.preview_execution(&weekly_id);
+    assert!(!preview.executable);
+    assert_eq!(preview.health, PaymentHealth::Inactive);
+    assert_eq!(preview.total_amount, 0);
+}
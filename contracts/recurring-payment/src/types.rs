@@ -1,10 +1,23 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Symbol};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Payment(u64),
     PaymentCount,
+    /// Payment IDs a sender has tagged with a category
+    CategoryPayments(Address, Symbol),
+    /// Funders of a shared payment and their shares
+    PaymentFunders(u64),
+    /// Receipt of an execution, by receipt ID
+    Receipt(u64),
+    ReceiptCount,
+    /// Receipt IDs of executions an address paid for, oldest first
+    PayerReceipts(Address),
+    /// Receipt IDs of executions an address was paid by, oldest first
+    PayeeReceipts(Address),
+    /// IDs of payments an address pays for, as sender or funder
+    PayerPayments(Address),
 }
 
 #[contracttype]
@@ -14,7 +27,100 @@ pub struct RecurringPayment {
     pub recipient: Address,
     pub token: Address,
     pub amount: i128,
+    /// Seconds between executions for `Schedule::Interval`; 0 for calendar schedules
     pub interval: u64,
+    pub schedule: Schedule,
     pub next_execution: u64,
     pub active: bool,
+    /// Spending category used by the budgeting and expense views
+    pub category: Option<Symbol>,
+    /// Free-form reference (e.g. invoice hash) attached to every execution
+    pub memo: Option<BytesN<32>>,
+}
+
+/// When a recurring payment becomes due
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Schedule {
+    /// Every `interval` seconds after the start time
+    Interval,
+    /// On day N (1-31) of every month, or the month's last day if it is shorter
+    MonthlyOnDay(u32),
+    /// On weekday W of every week, 0 = Monday through 6 = Sunday
+    WeeklyOnWeekday(u32),
+}
+
+/// Whether a payment's sender can cover its next execution
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaymentHealth {
+    /// Balance and allowance cover the next execution
+    Funded = 0,
+    /// Sender balance is below the payment amount
+    Underfunded = 1,
+    /// Allowance granted to this contract is below the payment amount
+    AllowanceMissing = 2,
+    /// Payment was canceled and will not execute again
+    Inactive = 3,
+}
+
+/// A funder's share of a shared payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunderShare {
+    pub funder: Address,
+    /// Portion of every execution paid by this funder, in basis points
+    pub share_bps: u32,
+}
+
+/// Record of a single successful execution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentReceipt {
+    pub receipt_id: u64,
+    pub payment_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Execution fee charged on top of `amount`; the contract charges no
+    /// fee, so this is always 0
+    pub fee: i128,
+    pub executed_at: u64,
+    /// Address that triggered the execution, if it was recorded through
+    /// `execute_payment_by`
+    pub executor: Option<Address>,
+}
+
+/// A projected execution of a recurring payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpcomingPayment {
+    pub payment_id: u64,
+    pub recipient: Address,
+    pub token: Address,
+    /// Amount drawn from the queried payer; their leg for shared payments
+    pub amount: i128,
+    pub execution_time: u64,
+    pub category: Option<Symbol>,
+}
+
+/// Dry run of `execute_payment` at the current ledger time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionPreview {
+    pub payment_id: u64,
+    /// Whether `execute_payment` would succeed now
+    pub executable: bool,
+    /// Whether the scheduled execution time has been reached
+    pub due: bool,
+    /// Whether the payer can cover the execution, as in `get_payment_health`
+    pub health: PaymentHealth,
+    /// Scheduled occurrences the execution settles; only one is paid and the
+    /// rest are skipped. 0 if the payment is not due or inactive
+    pub intervals_caught_up: u32,
+    /// `next_execution` once executed; unchanged if not due or inactive
+    pub next_execution: u64,
+    /// Tokens moved to the recipient by the execution
+    pub total_amount: i128,
 }
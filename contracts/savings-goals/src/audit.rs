@@ -0,0 +1,43 @@
+//! Audit trail for admin corrections made on behalf of goal owners.
+
+use soroban_sdk::{contractclient, symbol_short, xdr::ToXdr, Address, Bytes, Env, Symbol};
+
+use crate::types::{DataKey, MilestoneAchievement};
+
+/// Interface of the audit contract that records admin corrections.
+#[contractclient(name = "AuditClient")]
+pub trait AuditInterface {
+    fn log_audit(
+        env: Env,
+        actor: Address,
+        operation: Symbol,
+        status: Symbol,
+        metadata: Option<Bytes>,
+    );
+}
+
+pub fn get_audit_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::AuditContract)
+}
+
+/// Records a milestone marked by `admin` for the goal owner. A failing audit
+/// call reverts the correction so none goes unrecorded.
+pub fn log_milestone_correction(env: &Env, admin: &Address, achievement: &MilestoneAchievement) {
+    let Some(audit_contract) = get_audit_contract(env) else {
+        return;
+    };
+
+    let metadata = (
+        achievement.milestone_id,
+        achievement.goal_id,
+        achievement.user.clone(),
+        achievement.milestone_percentage,
+    )
+        .to_xdr(env);
+    AuditClient::new(env, &audit_contract).log_audit(
+        admin,
+        &Symbol::new(env, "milestone_correction"),
+        &symbol_short!("success"),
+        &Some(metadata),
+    );
+}
@@ -0,0 +1,216 @@
+//! Donation matching campaigns: a sponsor commits matching funds that are
+//! paid into tagged goals alongside each owner contribution until the
+//! campaign's escrow runs out, it ends, or the sponsor closes it.
+//!
+//! The committed tokens are held by the contract. Matches move from the
+//! campaign escrow into the goal's escrow but stay locked there until the
+//! goal completes; if funds leave the goal earlier, through a withdrawal or
+//! a cancellation, its matches go back to the campaign. Closing the campaign
+//! returns what is left to the sponsor.
+
+use soroban_sdk::{panic_with_error, token, Address, Env};
+
+use crate::cancellation;
+use crate::types::{
+    DataKey, GoalEvents, MatchingCampaign, SavingsGoal, BPS_DENOMINATOR, MAX_MATCH_BPS,
+};
+use crate::SavingsGoalError;
+
+pub fn get(env: &Env, campaign_id: u64) -> Option<MatchingCampaign> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Campaign(campaign_id))
+}
+
+fn load(env: &Env, campaign_id: u64) -> MatchingCampaign {
+    get(env, campaign_id)
+        .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::CampaignNotFound))
+}
+
+fn save(env: &Env, campaign: &MatchingCampaign) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Campaign(campaign.campaign_id), campaign);
+}
+
+fn is_open(env: &Env, campaign: &MatchingCampaign) -> bool {
+    campaign.is_active && env.ledger().timestamp() < campaign.ends_at
+}
+
+pub fn goal_campaign(env: &Env, goal_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GoalCampaign(goal_id))
+}
+
+/// Opens a campaign, transferring `committed` of `token` from the sponsor
+/// into escrow for matches.
+pub fn create(
+    env: &Env,
+    sponsor: &Address,
+    token: &Address,
+    match_bps: u32,
+    committed: i128,
+    ends_at: u64,
+) -> MatchingCampaign {
+    if match_bps == 0 || match_bps > MAX_MATCH_BPS || ends_at <= env.ledger().timestamp() {
+        panic_with_error!(env, SavingsGoalError::InvalidCampaign);
+    }
+    if committed <= 0 {
+        panic_with_error!(env, SavingsGoalError::InvalidAmount);
+    }
+
+    let campaign_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastCampaignId)
+        .unwrap_or(0)
+        + 1;
+    token::Client::new(env, token).transfer(sponsor, &env.current_contract_address(), &committed);
+
+    let campaign = MatchingCampaign {
+        campaign_id,
+        sponsor: sponsor.clone(),
+        token: token.clone(),
+        match_bps,
+        committed,
+        remaining: committed,
+        total_matched: 0,
+        matched_contributions: 0,
+        tagged_goals: 0,
+        ends_at,
+        is_active: true,
+    };
+    save(env, &campaign);
+    env.storage()
+        .instance()
+        .set(&DataKey::LastCampaignId, &campaign_id);
+
+    GoalEvents::campaign_created(env, &campaign);
+    campaign
+}
+
+/// Tags a goal so its owner's contributions are matched by the campaign.
+/// Sponsors cannot tag their own goals.
+pub fn tag_goal(env: &Env, owner: &Address, goal_id: u64, campaign_id: u64) {
+    let mut campaign = load(env, campaign_id);
+    if !is_open(env, &campaign) {
+        panic_with_error!(env, SavingsGoalError::CampaignClosed);
+    }
+    if campaign.sponsor == *owner || goal_campaign(env, goal_id).is_some() {
+        panic_with_error!(env, SavingsGoalError::InvalidCampaign);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::GoalCampaign(goal_id), &campaign_id);
+    campaign.tagged_goals += 1;
+    save(env, &campaign);
+
+    GoalEvents::campaign_goal_tagged(env, campaign_id, goal_id);
+}
+
+/// Draws the match for a contribution of `amount` of `token` to `goal_id`
+/// from the campaign's escrow. Only contributions in the campaign's token
+/// are matched. Returns the campaign and matched amount, if any.
+pub fn take_match(env: &Env, goal_id: u64, token: &Address, amount: i128) -> Option<(u64, i128)> {
+    let campaign_id = goal_campaign(env, goal_id)?;
+    let mut campaign = get(env, campaign_id)?;
+    if !is_open(env, &campaign) || campaign.token != *token {
+        return None;
+    }
+
+    let matched = (amount * campaign.match_bps as i128 / BPS_DENOMINATOR).min(campaign.remaining);
+    if matched <= 0 {
+        return None;
+    }
+
+    campaign.remaining -= matched;
+    campaign.total_matched += matched;
+    campaign.matched_contributions += 1;
+    save(env, &campaign);
+
+    Some((campaign_id, matched))
+}
+
+/// Matches credited to the goal that are still locked: all of them until the
+/// goal reaches its target, none afterwards.
+pub fn locked_match(env: &Env, goal: &SavingsGoal) -> i128 {
+    if goal.current_amount >= goal.target_amount {
+        return 0;
+    }
+    env.storage()
+        .persistent()
+        .get(&DataKey::GoalMatched(goal.goal_id))
+        .unwrap_or(0)
+}
+
+/// Locks a match of `amount` credited to the goal's escrow.
+pub fn lock_match(env: &Env, goal_id: u64, amount: i128) {
+    let key = DataKey::GoalMatched(goal_id);
+    let locked: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(locked + amount));
+}
+
+/// Settles the goal's matches before funds leave it. Once the goal has
+/// completed they are the owner's to keep; before that they are taken out
+/// of the goal's escrow and balance and go back to the campaign's escrow, or
+/// to the sponsor if the campaign is closed. The caller saves the goal.
+/// Returns the amount given back.
+pub fn settle(env: &Env, goal: &mut SavingsGoal) -> i128 {
+    let returned = locked_match(env, goal);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::GoalMatched(goal.goal_id));
+    if returned == 0 {
+        return 0;
+    }
+
+    // Matches are only locked on tagged goals
+    let campaign_id = goal_campaign(env, goal.goal_id).unwrap();
+    let mut campaign = load(env, campaign_id);
+    campaign.total_matched -= returned;
+    if campaign.is_active {
+        campaign.remaining += returned;
+    } else {
+        token::Client::new(env, &campaign.token).transfer(
+            &env.current_contract_address(),
+            &campaign.sponsor,
+            &returned,
+        );
+    }
+    save(env, &campaign);
+
+    cancellation::release_escrow(env, goal.goal_id, returned);
+    goal.current_amount -= returned;
+
+    GoalEvents::campaign_match_returned(env, goal.goal_id, campaign_id, returned);
+    returned
+}
+
+/// Closes the campaign and transfers the unspent escrow back to the sponsor.
+/// Returns the refunded amount.
+pub fn close(env: &Env, sponsor: &Address, campaign_id: u64) -> i128 {
+    let mut campaign = load(env, campaign_id);
+    if campaign.sponsor != *sponsor {
+        panic_with_error!(env, SavingsGoalError::Unauthorized);
+    }
+    if !campaign.is_active {
+        panic_with_error!(env, SavingsGoalError::CampaignClosed);
+    }
+
+    let refund = campaign.remaining;
+    campaign.remaining = 0;
+    campaign.is_active = false;
+    save(env, &campaign);
+    if refund > 0 {
+        token::Client::new(env, &campaign.token).transfer(
+            &env.current_contract_address(),
+            sponsor,
+            &refund,
+        );
+    }
+
+    GoalEvents::campaign_closed(env, campaign_id, sponsor, refund);
+    refund
+}
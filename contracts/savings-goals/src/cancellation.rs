@@ -0,0 +1,109 @@
+//! Goal cancellation with refunds of escrowed contributions.
+//!
+//! Token contributions, funder credits and campaign matches are tracked per
+//! goal as they are credited, so a cancelled goal refunds exactly what was
+//! paid in (net of the insurance skim) to its owner. Matches still locked
+//! go back to their campaign instead.
+
+use soroban_sdk::{token, Address, Env, Vec};
+
+use crate::campaigns;
+use crate::peg;
+use crate::storage;
+use crate::summary;
+use crate::types::{DataKey, ErrorCode, GoalEvents, SavingsGoal};
+use crate::withdrawals;
+
+pub fn get_escrow(env: &Env, goal_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GoalEscrow(goal_id))
+        .unwrap_or(0)
+}
+
+/// Adds a token contribution credited to the goal to its escrow.
+pub fn add_escrow(env: &Env, goal_id: u64, amount: i128) {
+    env.storage().persistent().set(
+        &DataKey::GoalEscrow(goal_id),
+        &(get_escrow(env, goal_id) + amount),
+    );
+}
+
+/// Removes withdrawn tokens from the goal's escrow.
+pub fn release_escrow(env: &Env, goal_id: u64, amount: i128) {
+    let remaining = get_escrow(env, goal_id) - amount;
+    if remaining > 0 {
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalEscrow(goal_id), &remaining);
+    } else {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::GoalEscrow(goal_id));
+    }
+}
+
+/// Deactivates the goal, refunds its escrow to the owner and drops it from
+/// the owner's goal list. Unless the admin cancels or the withdrawal
+/// guardian co-signed, the refund passes the guardian check withdrawals do.
+/// Returns the refunded amount, or the error code the cancellation fails
+/// with.
+pub fn cancel(
+    env: &Env,
+    caller: &Address,
+    is_admin: bool,
+    cosigned: bool,
+    goal_id: u64,
+) -> Result<i128, u32> {
+    let mut goal: SavingsGoal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Goal(goal_id))
+        .ok_or(ErrorCode::GOAL_NOT_FOUND)?;
+    if !is_admin && goal.user != *caller {
+        return Err(ErrorCode::UNAUTHORIZED_USER);
+    }
+    if !goal.is_active {
+        return Err(ErrorCode::GOAL_NOT_ACTIVE);
+    }
+    let refunded = get_escrow(env, goal_id) - campaigns::locked_match(env, &goal);
+    if !is_admin && !cosigned && !withdrawals::try_unapproved(env, &goal.user, refunded) {
+        return Err(ErrorCode::GUARDIAN_APPROVAL_REQUIRED);
+    }
+
+    goal.is_active = false;
+    campaigns::settle(env, &mut goal);
+    storage::save_goal(env, &goal);
+
+    if refunded > 0 {
+        let goal_token: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GoalToken(goal_id))
+            .unwrap();
+        token::Client::new(env, &goal_token).transfer(
+            &env.current_contract_address(),
+            &goal.user,
+            &refunded,
+        );
+        env.storage()
+            .persistent()
+            .remove(&DataKey::GoalEscrow(goal_id));
+    }
+
+    let user_goals_key = DataKey::UserGoals(goal.user.clone());
+    let mut user_goals: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&user_goals_key)
+        .unwrap_or(Vec::new(env));
+    if let Some(index) = user_goals.first_index_of(goal_id) {
+        user_goals.remove(index);
+        env.storage().persistent().set(&user_goals_key, &user_goals);
+    }
+    summary::on_goal_dropped(env, &goal);
+    peg::remove_peg(env, goal_id);
+
+    GoalEvents::goal_cancelled(env, goal_id, caller, refunded);
+    Ok(refunded)
+}
@@ -0,0 +1,48 @@
+//! Per-goal cooldown between milestone claim batches, so bots can't generate
+//! unbounded milestone-check workload against popular goals.
+
+use soroban_sdk::Env;
+
+use crate::types::DataKey;
+
+/// Returns the cooldown window in ledgers (0 disables the cooldown).
+pub fn get_window(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::MilestoneCooldownLedgers)
+        .unwrap_or(0)
+}
+
+pub fn set_window(env: &Env, ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::MilestoneCooldownLedgers, &ledgers);
+}
+
+/// Returns the ledger at which the goal's cooldown ends, if one is running.
+pub fn get_end(env: &Env, goal_id: u64) -> Option<u32> {
+    let until_ledger: u32 = env
+        .storage()
+        .temporary()
+        .get(&DataKey::MilestoneCooldown(goal_id))?;
+    if env.ledger().sequence() < until_ledger {
+        Some(until_ledger)
+    } else {
+        None
+    }
+}
+
+/// Starts the goal's cooldown unless it is disabled or already running.
+pub fn start(env: &Env, goal_id: u64) {
+    let window = get_window(env);
+    if window == 0 || get_end(env, goal_id).is_some() {
+        return;
+    }
+
+    // Temporary storage lets the entry lapse on its own once the window
+    // passes; the stored end ledger keeps the check exact regardless of TTL.
+    let key = DataKey::MilestoneCooldown(goal_id);
+    let until_ledger = env.ledger().sequence().saturating_add(window);
+    env.storage().temporary().set(&key, &until_ledger);
+    env.storage().temporary().extend_ttl(&key, window, window);
+}
@@ -0,0 +1,47 @@
+//! Deadline expiry of savings goals.
+//!
+//! Once the ledger sequence passes a goal's deadline, anyone may sweep it:
+//! the goal is deactivated and an outcome recording whether the target was
+//! met is kept. Expired goals stay in their owner's goal list.
+
+use soroban_sdk::Env;
+
+use crate::storage;
+use crate::types::{DataKey, ErrorCode, GoalEvents, GoalExpiry, SavingsGoal};
+
+pub fn get(env: &Env, goal_id: u64) -> Option<GoalExpiry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GoalExpiry(goal_id))
+}
+
+/// Deactivates a goal past its deadline and records its outcome. Returns
+/// the outcome, or the error code the goal was skipped with.
+pub fn expire(env: &Env, goal_id: u64) -> Result<GoalExpiry, u32> {
+    let mut goal: SavingsGoal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Goal(goal_id))
+        .ok_or(ErrorCode::GOAL_NOT_FOUND)?;
+    if !goal.is_active {
+        return Err(ErrorCode::GOAL_NOT_ACTIVE);
+    }
+    if env.ledger().sequence() as u64 <= goal.deadline {
+        return Err(ErrorCode::DEADLINE_NOT_REACHED);
+    }
+
+    goal.is_active = false;
+    storage::save_goal(env, &goal);
+
+    let expiry = GoalExpiry {
+        expired_at: env.ledger().sequence(),
+        target_met: goal.current_amount >= goal.target_amount,
+        final_amount: goal.current_amount,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::GoalExpiry(goal_id), &expiry);
+
+    GoalEvents::goal_expired(env, goal_id, &expiry);
+    Ok(expiry)
+}
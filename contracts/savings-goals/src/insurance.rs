@@ -0,0 +1,78 @@
+//! Deposit insurance fund: a basis-point skim of each contribution that the
+//! admin can pay out to make users whole after incidents.
+//!
+//! Goals can be held in different tokens, so the fund keeps a balance per
+//! token and pays out in the token it was collected in.
+
+use soroban_sdk::{panic_with_error, token, Address, Env, Symbol};
+
+use crate::types::{DataKey, GoalEvents, BPS_DENOMINATOR, MAX_INSURANCE_BPS};
+use crate::SavingsGoalError;
+
+pub fn get_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::InsuranceBps)
+        .unwrap_or(0)
+}
+
+pub fn set_bps(env: &Env, bps: u32) {
+    if bps > MAX_INSURANCE_BPS {
+        panic_with_error!(env, SavingsGoalError::InvalidInsuranceRate);
+    }
+    env.storage().instance().set(&DataKey::InsuranceBps, &bps);
+}
+
+pub fn get_balance(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::InsuranceBalance(token.clone()))
+        .unwrap_or(0)
+}
+
+fn set_balance(env: &Env, token: &Address, balance: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::InsuranceBalance(token.clone()), &balance);
+}
+
+/// Moves the configured share of `amount` of `token` into the fund and
+/// returns the remainder to credit to the goal.
+pub fn skim(env: &Env, goal_id: u64, token: &Address, amount: i128) -> i128 {
+    let premium = amount * get_bps(env) as i128 / BPS_DENOMINATOR;
+    if premium == 0 {
+        return amount;
+    }
+
+    let balance = get_balance(env, token)
+        .checked_add(premium)
+        .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::InvalidAmount));
+    set_balance(env, token, balance);
+    GoalEvents::insurance_collected(env, goal_id, token, premium, balance);
+
+    amount - premium
+}
+
+/// Transfers `amount` of `token` out of the fund to `user`, recording the
+/// reason.
+pub fn payout(
+    env: &Env,
+    admin: &Address,
+    user: &Address,
+    token: &Address,
+    amount: i128,
+    reason: &Symbol,
+) {
+    if amount <= 0 {
+        panic_with_error!(env, SavingsGoalError::InvalidAmount);
+    }
+    let balance = get_balance(env, token);
+    if amount > balance {
+        panic_with_error!(env, SavingsGoalError::InsufficientInsuranceFund);
+    }
+
+    let remaining = balance - amount;
+    set_balance(env, token, remaining);
+    token::Client::new(env, token).transfer(&env.current_contract_address(), user, &amount);
+    GoalEvents::insurance_paid(env, admin, user, token, amount, reason, remaining);
+}
@@ -0,0 +1,24 @@
+//! Admin-tunable goal validation limits (minimum target, deadline horizon).
+
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::types::{DataKey, GoalLimits, MAX_GOAL_AMOUNT};
+use crate::SavingsGoalError;
+
+pub fn get(env: &Env) -> GoalLimits {
+    env.storage()
+        .instance()
+        .get(&DataKey::GoalLimits)
+        .unwrap_or_default()
+}
+
+pub fn set(env: &Env, limits: &GoalLimits) {
+    let valid = limits.min_target_amount > 0
+        && limits.min_target_amount <= MAX_GOAL_AMOUNT
+        && limits.min_deadline_ledgers > 0
+        && limits.max_deadline_ledgers >= limits.min_deadline_ledgers;
+    if !valid {
+        panic_with_error!(env, SavingsGoalError::InvalidGoalLimits);
+    }
+    env.storage().instance().set(&DataKey::GoalLimits, limits);
+}
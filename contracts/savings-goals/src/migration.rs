@@ -0,0 +1,110 @@
+//! Import of goals from a previous deployment of this contract.
+//!
+//! Goals are read through the legacy contract's `get_goal` and recreated
+//! under their original IDs where those are still free; a goal whose ID is
+//! already used here gets the next free ID instead. Every import is recorded
+//! per (source contract, legacy ID), so re-running an import skips goals
+//! already brought over. Tokens held by the legacy contract are not moved.
+
+use soroban_sdk::{contractclient, Address, Bytes, Env, Vec};
+
+use crate::storage;
+use crate::types::{DataKey, GoalEvents, GoalImportResult, LegacyGoal, SavingsGoal};
+
+/// Read interface of the previous savings goals deployment.
+#[contractclient(name = "LegacyGoalsClient")]
+pub trait LegacyGoalsInterface {
+    fn get_goal(env: Env, goal_id: u64) -> Option<LegacyGoal>;
+}
+
+pub fn get_imported_id(env: &Env, source: &Address, legacy_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ImportedGoal(source.clone(), legacy_id))
+}
+
+/// Imports `legacy_id` from `source`, returning false if the source does
+/// not have it.
+fn import_goal(env: &Env, source: &Address, legacy_id: u64, last_goal_id: &mut u64) -> bool {
+    let legacy = match LegacyGoalsClient::new(env, source).try_get_goal(&legacy_id) {
+        Ok(Ok(Some(legacy))) => legacy,
+        _ => return false,
+    };
+
+    // IDs at or below the last goal ID were handed out here already
+    let goal_id = if legacy_id > *last_goal_id {
+        legacy_id
+    } else {
+        *last_goal_id + 1
+    };
+    *last_goal_id = goal_id;
+
+    let goal = SavingsGoal {
+        goal_id,
+        user: legacy.user.clone(),
+        goal_name: legacy.goal_name,
+        target_amount: legacy.target_amount,
+        current_amount: legacy.current_amount,
+        deadline: legacy.deadline,
+        created_at: legacy.created_at,
+        is_active: legacy.is_active,
+        category: None,
+        metadata: Bytes::new(env),
+    };
+    storage::save_goal(env, &goal);
+
+    let user_goals_key = DataKey::UserGoals(legacy.user);
+    let mut user_goals: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&user_goals_key)
+        .unwrap_or(Vec::new(env));
+    user_goals.push_back(goal_id);
+    env.storage().persistent().set(&user_goals_key, &user_goals);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::ImportedGoal(source.clone(), legacy_id), &goal_id);
+    GoalEvents::goal_imported(env, source, legacy_id, goal_id);
+    true
+}
+
+/// Imports the listed goals from `source`.
+pub fn import_goals(env: &Env, source: &Address, goal_ids: &Vec<u64>) -> GoalImportResult {
+    let mut last_goal_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastGoalId)
+        .unwrap_or(0);
+    let mut result = GoalImportResult {
+        imported: 0,
+        already_imported: 0,
+        missing: 0,
+    };
+
+    for legacy_id in goal_ids.iter() {
+        if get_imported_id(env, source, legacy_id).is_some() {
+            result.already_imported += 1;
+        } else if import_goal(env, source, legacy_id, &mut last_goal_id) {
+            result.imported += 1;
+        } else {
+            result.missing += 1;
+        }
+    }
+
+    if result.imported > 0 {
+        env.storage()
+            .instance()
+            .set(&DataKey::LastGoalId, &last_goal_id);
+        let total_goals: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalGoalsCreated)
+            .unwrap_or(0);
+        env.storage().instance().set(
+            &DataKey::TotalGoalsCreated,
+            &(total_goals + result.imported as u64),
+        );
+    }
+    result
+}
@@ -0,0 +1,85 @@
+//! Transfer of goal ownership between accounts.
+//!
+//! Used to migrate goals to a new account: the goal and its milestone
+//! achievement records are reassigned and the goal moves from the old
+//! owner's goal list to the new owner's. Cancelled goals have already left
+//! their owner's list and cannot be transferred. The goal's escrow moves
+//! with it, so unless the withdrawal guardian co-signed, the transfer passes
+//! the same guardian check a withdrawal of the whole escrow would.
+
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::cancellation;
+use crate::storage;
+use crate::types::{DataKey, ErrorCode, GoalEvents, MilestoneAchievement, SavingsGoal};
+use crate::withdrawals;
+
+/// Reassigns `goal_id` from `current_owner` to `new_owner`. Returns the
+/// updated goal, or the error code the transfer fails with.
+pub fn transfer(
+    env: &Env,
+    current_owner: &Address,
+    cosigned: bool,
+    goal_id: u64,
+    new_owner: &Address,
+) -> Result<SavingsGoal, u32> {
+    let mut goal: SavingsGoal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Goal(goal_id))
+        .ok_or(ErrorCode::GOAL_NOT_FOUND)?;
+    if goal.user != *current_owner {
+        return Err(ErrorCode::UNAUTHORIZED_USER);
+    }
+    if new_owner == current_owner {
+        return Err(ErrorCode::INVALID_NEW_OWNER);
+    }
+
+    let old_goals_key = DataKey::UserGoals(current_owner.clone());
+    let mut old_goals: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&old_goals_key)
+        .unwrap_or(Vec::new(env));
+    let Some(index) = old_goals.first_index_of(goal_id) else {
+        return Err(ErrorCode::GOAL_NOT_ACTIVE);
+    };
+    if !cosigned
+        && !withdrawals::try_unapproved(env, current_owner, cancellation::get_escrow(env, goal_id))
+    {
+        return Err(ErrorCode::GUARDIAN_APPROVAL_REQUIRED);
+    }
+    old_goals.remove(index);
+    env.storage().persistent().set(&old_goals_key, &old_goals);
+
+    let new_goals_key = DataKey::UserGoals(new_owner.clone());
+    let mut new_goals: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&new_goals_key)
+        .unwrap_or(Vec::new(env));
+    new_goals.push_back(goal_id);
+    env.storage().persistent().set(&new_goals_key, &new_goals);
+
+    goal.user = new_owner.clone();
+    storage::save_goal(env, &goal);
+
+    let milestone_ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::GoalMilestones(goal_id))
+        .unwrap_or(Vec::new(env));
+    for milestone_id in milestone_ids.iter() {
+        if let Some(mut achievement) = env
+            .storage()
+            .persistent()
+            .get::<_, MilestoneAchievement>(&DataKey::Milestone(milestone_id))
+        {
+            achievement.user = new_owner.clone();
+            storage::save_milestone(env, &achievement);
+        }
+    }
+
+    GoalEvents::ownership_transferred(env, goal_id, current_owner, new_owner);
+    Ok(goal)
+}
@@ -0,0 +1,75 @@
+//! Fiat-pegged goal targets.
+//!
+//! A goal owner can express the target in a fiat currency. The token target
+//! is derived from the oracle adapter's rate when the peg is set and again on
+//! every revaluation. Milestone thresholds are percentages of the target, so
+//! their token amounts move proportionally with each revaluation.
+
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+use crate::storage;
+use crate::types::{DataKey, FiatPeg, GoalEvents, SavingsGoal, MAX_GOAL_AMOUNT, RATE_SCALE};
+
+/// Oracle adapter quoting the token in fiat currencies.
+#[contractclient(name = "RateOracleClient")]
+pub trait RateOracleInterface {
+    /// Fiat value of one token unit in `currency`, scaled by `RATE_SCALE`.
+    fn rate(env: Env, currency: Symbol) -> i128;
+}
+
+pub fn get_oracle(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::RateOracle)
+}
+
+pub fn set_oracle(env: &Env, oracle: &Option<Address>) {
+    match oracle {
+        Some(oracle) => env.storage().instance().set(&DataKey::RateOracle, oracle),
+        None => env.storage().instance().remove(&DataKey::RateOracle),
+    }
+}
+
+pub fn get_peg(env: &Env, goal_id: u64) -> Option<FiatPeg> {
+    env.storage().persistent().get(&DataKey::GoalPeg(goal_id))
+}
+
+pub fn remove_peg(env: &Env, goal_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::GoalPeg(goal_id));
+}
+
+/// Current oracle rate for `currency`, or `None` if no oracle is configured
+/// or it fails to quote a positive rate.
+pub fn current_rate(env: &Env, currency: &Symbol) -> Option<i128> {
+    let oracle = get_oracle(env)?;
+    match RateOracleClient::new(env, &oracle).try_rate(currency) {
+        Ok(Ok(rate)) if rate > 0 => Some(rate),
+        _ => None,
+    }
+}
+
+/// Token amount worth `fiat_amount` at `rate`, if it is a valid goal target.
+pub fn token_target(fiat_amount: i128, rate: i128) -> Option<i128> {
+    let target = fiat_amount.checked_mul(RATE_SCALE)? / rate;
+    (target > 0 && target <= MAX_GOAL_AMOUNT).then_some(target)
+}
+
+/// Re-derives the goal's token target from its peg at `rate`, storing the
+/// goal and peg. Returns false if the rate yields an invalid target.
+pub fn revalue(env: &Env, goal: &mut SavingsGoal, peg: &mut FiatPeg, rate: i128) -> bool {
+    let Some(new_target) = token_target(peg.fiat_target, rate) else {
+        return false;
+    };
+
+    let previous_target = goal.target_amount;
+    goal.target_amount = new_target;
+    peg.rate = rate;
+    peg.revalued_at = env.ledger().timestamp();
+    storage::save_goal(env, goal);
+    env.storage()
+        .persistent()
+        .set(&DataKey::GoalPeg(goal.goal_id), peg);
+
+    GoalEvents::goal_revalued(env, goal.goal_id, peg, previous_target, new_target);
+    true
+}
@@ -0,0 +1,52 @@
+//! Referral qualification: a referred user's first completed goal releases
+//! the referrer's reward through the referral contract.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::types::{DataKey, GoalEvents, SavingsGoal};
+
+/// Interface of the referral contract invoked when a referee qualifies.
+#[contractclient(name = "ReferralClient")]
+pub trait ReferralInterface {
+    fn release_reward(env: Env, referrer: Address, referee: Address, goal_id: u64);
+}
+
+pub fn get_referrer(env: &Env, user: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Referrer(user.clone()))
+}
+
+pub fn get_referral_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::ReferralContract)
+}
+
+/// Called when a goal reaches 100%. Qualifies the owner's referral at most
+/// once; the flag is set before the external call so a goal (or a later
+/// goal of the same user) can never release the reward twice.
+pub fn on_goal_completed(env: &Env, goal: &SavingsGoal) {
+    let referrer = match get_referrer(env, &goal.user) {
+        Some(referrer) => referrer,
+        None => return,
+    };
+
+    let qualified_key = DataKey::ReferralQualified(goal.user.clone());
+    if env.storage().persistent().has(&qualified_key) {
+        return;
+    }
+    env.storage()
+        .persistent()
+        .set(&qualified_key, &goal.goal_id);
+
+    GoalEvents::referral_qualified(env, goal.goal_id, &goal.user, &referrer);
+
+    if let Some(referral_contract) = get_referral_contract(env) {
+        let client = ReferralClient::new(env, &referral_contract);
+        if client
+            .try_release_reward(&referrer, &goal.user, &goal.goal_id)
+            .is_err()
+        {
+            GoalEvents::referral_release_failed(env, goal.goal_id, &goal.user, &referrer);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Milestone rewards minted by a token contract.
+//!
+//! When a reward is configured, every milestone a goal reaches mints
+//! `reward_per_milestone` to the goal owner, whether contributions crossed
+//! it or it was marked in a batch. Minted rewards are recorded per goal, so
+//! each milestone is rewarded at most once. This contract must be a minter
+//! on the reward token. A failing mint is reported through an event and
+//! never fails the contribution or milestone batch.
+
+use soroban_sdk::{contractclient, Address, Env, Vec, U256};
+
+use crate::types::{DataKey, GoalEvents, RewardConfig};
+
+/// Interface of the token contract that mints milestone rewards.
+#[contractclient(name = "RewardTokenClient")]
+pub trait RewardTokenInterface {
+    fn mint(env: Env, minter: Address, to: Address, amount: i128) -> U256;
+}
+
+pub fn get_config(env: &Env) -> Option<RewardConfig> {
+    env.storage().instance().get(&DataKey::RewardConfig)
+}
+
+pub fn set_config(env: &Env, config: &Option<RewardConfig>) {
+    match config {
+        Some(config) => env.storage().instance().set(&DataKey::RewardConfig, config),
+        None => env.storage().instance().remove(&DataKey::RewardConfig),
+    }
+}
+
+/// Mints the configured reward for a milestone the goal has reached, unless
+/// it was already rewarded.
+pub fn on_milestone_reached(env: &Env, goal_id: u64, user: &Address, milestone_percentage: u32) {
+    let Some(config) = get_config(env) else {
+        return;
+    };
+    let key = DataKey::GoalMilestonesRewarded(goal_id);
+    let mut rewarded: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    if rewarded.contains(milestone_percentage) {
+        return;
+    }
+
+    let client = RewardTokenClient::new(env, &config.token);
+    let minted = matches!(
+        client.try_mint(
+            &env.current_contract_address(),
+            user,
+            &config.reward_per_milestone,
+        ),
+        Ok(Ok(_))
+    );
+    if minted {
+        rewarded.push_back(milestone_percentage);
+        env.storage().persistent().set(&key, &rewarded);
+        GoalEvents::milestone_rewarded(
+            env,
+            goal_id,
+            user,
+            milestone_percentage,
+            config.reward_per_milestone,
+        );
+    } else {
+        GoalEvents::milestone_reward_failed(
+            env,
+            goal_id,
+            user,
+            milestone_percentage,
+            config.reward_per_milestone,
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Goal sharing through single-use claim codes.
+//!
+//! The goal owner registers the SHA-256 hash of a code off-chain shared with
+//! a friend. Whoever presents the code before it expires (or only the invited
+//! address, if one was set) joins the goal as a contributor; the code is
+//! consumed on first use and the owner can revoke it until then.
+
+use soroban_sdk::{panic_with_error, Address, Bytes, BytesN, Env};
+
+use crate::types::{ClaimCode, DataKey, GoalEvents};
+use crate::SavingsGoalError;
+
+pub fn get_claim_code(env: &Env, code_hash: &BytesN<32>) -> Option<ClaimCode> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ClaimCode(code_hash.clone()))
+}
+
+/// Stores a new claim code for `goal_id`.
+pub fn create(
+    env: &Env,
+    goal_id: u64,
+    owner: &Address,
+    code_hash: &BytesN<32>,
+    invitee: &Option<Address>,
+    expires_at: u64,
+) {
+    let key = DataKey::ClaimCode(code_hash.clone());
+    if expires_at <= env.ledger().timestamp() || env.storage().persistent().has(&key) {
+        panic_with_error!(env, SavingsGoalError::InvalidClaimCode);
+    }
+
+    let claim = ClaimCode {
+        goal_id,
+        owner: owner.clone(),
+        invitee: invitee.clone(),
+        expires_at,
+    };
+    env.storage().persistent().set(&key, &claim);
+    GoalEvents::claim_code_created(env, goal_id, code_hash, invitee, expires_at);
+}
+
+/// Removes an unused claim code. Only the goal owner may revoke it.
+pub fn revoke(env: &Env, owner: &Address, code_hash: &BytesN<32>) {
+    let claim = get_claim_code(env, code_hash)
+        .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::ClaimCodeNotFound));
+    if claim.owner != *owner {
+        panic_with_error!(env, SavingsGoalError::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ClaimCode(code_hash.clone()));
+    GoalEvents::claim_code_revoked(env, claim.goal_id, code_hash);
+}
+
+/// Consumes the claim code matching `code` and adds `claimer` as a
+/// contributor to its goal. Returns the goal ID.
+pub fn redeem(env: &Env, claimer: &Address, code: &Bytes) -> u64 {
+    let code_hash: BytesN<32> = env.crypto().sha256(code).to_bytes();
+    let claim = get_claim_code(env, &code_hash)
+        .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::ClaimCodeNotFound));
+    if env.ledger().timestamp() >= claim.expires_at {
+        panic_with_error!(env, SavingsGoalError::ClaimCodeExpired);
+    }
+    if claim
+        .invitee
+        .as_ref()
+        .is_some_and(|invitee| invitee != claimer)
+    {
+        panic_with_error!(env, SavingsGoalError::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ClaimCode(code_hash.clone()));
+    env.storage().persistent().set(
+        &DataKey::GoalContributor(claim.goal_id, claimer.clone()),
+        &true,
+    );
+    GoalEvents::claim_code_redeemed(env, claim.goal_id, &code_hash, claimer);
+    claim.goal_id
+}
+
+pub fn is_contributor(env: &Env, goal_id: u64, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GoalContributor(goal_id, address.clone()))
+        .unwrap_or(false)
+}
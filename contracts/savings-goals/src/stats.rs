@@ -0,0 +1,220 @@
+//! Opt-in gamification stats and the savings leaderboard.
+//!
+//! A user's vacation window pauses streak checks: weeks it overlaps count as
+//! neither contributed nor missed.
+
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+use crate::types::{
+    DataKey, LeaderboardEntry, UserStats, VacationWindow, LEADERBOARD_SIZE, MAX_VACATION_WEEKS,
+    SECONDS_PER_MONTH, SECONDS_PER_WEEK,
+};
+use crate::SavingsGoalError;
+
+/// Starts tracking stats for a user. Returns false if already opted in.
+pub fn opt_in(env: &Env, user: &Address) -> bool {
+    let key = DataKey::UserStats(user.clone());
+    if env.storage().persistent().has(&key) {
+        return false;
+    }
+
+    let now = env.ledger().timestamp();
+    let stats = UserStats {
+        user: user.clone(),
+        total_saved: 0,
+        streak_weeks: 0,
+        last_contribution_week: 0,
+        milestones_this_month: 0,
+        stats_month: now / SECONDS_PER_MONTH,
+        vacation_weeks: 0,
+    };
+    env.storage().persistent().set(&key, &stats);
+    true
+}
+
+/// Drops a user's stats and leaderboard position. Returns false if not opted in.
+pub fn opt_out(env: &Env, user: &Address) -> bool {
+    let key = DataKey::UserStats(user.clone());
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+
+    env.storage().persistent().remove(&key);
+    let leaderboard = remove_entry(env, get_leaderboard(env), user);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Leaderboard, &leaderboard);
+    true
+}
+
+pub fn get_user_stats(env: &Env, user: &Address) -> Option<UserStats> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserStats(user.clone()))
+}
+
+pub fn get_leaderboard(env: &Env) -> Vec<LeaderboardEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Leaderboard)
+        .unwrap_or(Vec::new(env))
+}
+
+/// The first `limit` leaderboard positions, highest total first.
+pub fn top_savers(env: &Env, limit: u32) -> Vec<LeaderboardEntry> {
+    let leaderboard = get_leaderboard(env);
+    leaderboard.slice(0..limit.min(leaderboard.len()))
+}
+
+/// Updates totals, streak and leaderboard for an opted-in contributor.
+pub fn record_contribution(env: &Env, user: &Address, amount: i128) {
+    let mut stats = match get_user_stats(env, user) {
+        Some(stats) => stats,
+        None => return,
+    };
+
+    let week = env.ledger().timestamp() / SECONDS_PER_WEEK;
+    if stats.streak_weeks == 0 {
+        stats.streak_weeks = 1;
+    } else if week > stats.last_contribution_week {
+        let first_missed = stats.last_contribution_week + 1;
+        if on_vacation(env, user, first_missed, week - 1) {
+            stats.streak_weeks += 1;
+            stats.vacation_weeks += (week - first_missed) as u32;
+        } else {
+            stats.streak_weeks = 1;
+        }
+    }
+    stats.last_contribution_week = week;
+    stats.total_saved = stats.total_saved.saturating_add(amount);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::UserStats(user.clone()), &stats);
+    update_leaderboard(env, user, stats.total_saved);
+}
+
+/// Whether a contribution this week is needed to keep the streak going.
+pub fn is_streak_at_risk(env: &Env, user: &Address) -> bool {
+    let stats = match get_user_stats(env, user) {
+        Some(stats) if stats.streak_weeks > 0 => stats,
+        _ => return false,
+    };
+
+    let week = env.ledger().timestamp() / SECONDS_PER_WEEK;
+    if week <= stats.last_contribution_week {
+        return false;
+    }
+    // Already broken if an earlier week was missed outside a vacation
+    on_vacation(env, user, stats.last_contribution_week + 1, week - 1)
+        && !on_vacation(env, user, week, week)
+}
+
+pub fn get_vacation(env: &Env, user: &Address) -> Option<VacationWindow> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Vacation(user.clone()))
+}
+
+/// Stores the user's vacation window, replacing any earlier one.
+pub fn set_vacation(env: &Env, user: &Address, start: u64, end: u64) -> VacationWindow {
+    let now = env.ledger().timestamp();
+    if start < now || end <= start || end - start > MAX_VACATION_WEEKS * SECONDS_PER_WEEK {
+        panic_with_error!(env, SavingsGoalError::InvalidVacation);
+    }
+
+    let window = VacationWindow { start, end };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Vacation(user.clone()), &window);
+    window
+}
+
+/// Removes the user's vacation window. Returns false if none was set.
+pub fn clear_vacation(env: &Env, user: &Address) -> bool {
+    let key = DataKey::Vacation(user.clone());
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+    true
+}
+
+/// Whether every week from `first_week` to `last_week` (inclusive) overlaps
+/// the user's vacation window. An empty range is trivially covered.
+fn on_vacation(env: &Env, user: &Address, first_week: u64, last_week: u64) -> bool {
+    if first_week > last_week {
+        return true;
+    }
+    match get_vacation(env, user) {
+        Some(window) => {
+            window.start / SECONDS_PER_WEEK <= first_week
+                && (window.end - 1) / SECONDS_PER_WEEK >= last_week
+        }
+        None => false,
+    }
+}
+
+/// Counts a milestone towards an opted-in user's monthly total.
+pub fn record_milestone(env: &Env, user: &Address) {
+    let mut stats = match get_user_stats(env, user) {
+        Some(stats) => stats,
+        None => return,
+    };
+
+    let month = env.ledger().timestamp() / SECONDS_PER_MONTH;
+    if month != stats.stats_month {
+        stats.stats_month = month;
+        stats.milestones_this_month = 0;
+    }
+    stats.milestones_this_month += 1;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::UserStats(user.clone()), &stats);
+}
+
+fn update_leaderboard(env: &Env, user: &Address, total_saved: i128) {
+    let current = remove_entry(env, get_leaderboard(env), user);
+
+    // Insert keeping descending order; ties keep the earlier entry ahead
+    let mut leaderboard = Vec::new(env);
+    let mut inserted = false;
+    for entry in current.iter() {
+        if !inserted && total_saved > entry.total_saved {
+            leaderboard.push_back(LeaderboardEntry {
+                user: user.clone(),
+                total_saved,
+            });
+            inserted = true;
+        }
+        leaderboard.push_back(entry);
+    }
+    if !inserted {
+        leaderboard.push_back(LeaderboardEntry {
+            user: user.clone(),
+            total_saved,
+        });
+    }
+    while leaderboard.len() > LEADERBOARD_SIZE {
+        leaderboard.pop_back();
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Leaderboard, &leaderboard);
+}
+
+fn remove_entry(
+    env: &Env,
+    leaderboard: Vec<LeaderboardEntry>,
+    user: &Address,
+) -> Vec<LeaderboardEntry> {
+    let mut result = Vec::new(env);
+    for entry in leaderboard.iter() {
+        if entry.user != *user {
+            result.push_back(entry);
+        }
+    }
+    result
+}
@@ -0,0 +1,232 @@
+//! Storage bookkeeping for rent planning.
+//!
+//! Goal and milestone records are written through `save_goal` and
+//! `save_milestone`, which keep running write counters. Byte figures are
+//! estimates from typical entry sizes rather than exact XDR lengths, which
+//! would cost a serialization per write. Goal writes also keep the owner's
+//! savings summary current and bump the TTLs of the entries they touch, so
+//! goals in use are never archived.
+
+use soroban_sdk::{Address, Env, Map, Vec};
+
+use crate::summary;
+use crate::types::{DataKey, MaintenanceReport, MilestoneAchievement, SavingsGoal, StorageStats};
+
+/// Entries whose TTL is below this many ledgers (~30 days) are extended.
+pub const TTL_THRESHOLD_LEDGERS: u32 = 518_400;
+
+/// TTL entries are extended to, in ledgers (~120 days).
+pub const TTL_EXTEND_TO_LEDGERS: u32 = 2_073_600;
+
+/// Approximate size of a goal entry, key included.
+pub const APPROX_GOAL_ENTRY_BYTES: u64 = 192;
+
+/// Approximate size of a milestone achievement entry, key included.
+pub const APPROX_MILESTONE_ENTRY_BYTES: u64 = 176;
+
+/// Approximate size of an empty per-goal index entry, key included.
+pub const APPROX_INDEX_ENTRY_BYTES: u64 = 48;
+
+/// Approximate size of each element of a per-goal index.
+pub const APPROX_INDEX_ELEMENT_BYTES: u64 = 12;
+
+pub fn get_stats(env: &Env) -> StorageStats {
+    env.storage()
+        .instance()
+        .get(&DataKey::StorageStats)
+        .unwrap_or(StorageStats {
+            goal_writes: 0,
+            milestone_writes: 0,
+            approx_bytes_written: 0,
+            entries_extended: 0,
+            last_maintenance_ledger: 0,
+        })
+}
+
+fn set_stats(env: &Env, stats: &StorageStats) {
+    env.storage().instance().set(&DataKey::StorageStats, stats);
+}
+
+pub fn save_goal(env: &Env, goal: &SavingsGoal) {
+    let previous: Option<SavingsGoal> =
+        env.storage().persistent().get(&DataKey::Goal(goal.goal_id));
+    summary::on_goal_saved(env, previous.as_ref(), goal);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Goal(goal.goal_id), goal);
+    extend_goal_ttl(env, goal);
+
+    let mut stats = get_stats(env);
+    stats.goal_writes += 1;
+    stats.approx_bytes_written += APPROX_GOAL_ENTRY_BYTES;
+    set_stats(env, &stats);
+}
+
+pub fn save_milestone(env: &Env, achievement: &MilestoneAchievement) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Milestone(achievement.milestone_id), achievement);
+    extend(
+        env,
+        &DataKey::Milestone(achievement.milestone_id),
+        TTL_THRESHOLD_LEDGERS,
+        TTL_EXTEND_TO_LEDGERS,
+    );
+
+    let mut stats = get_stats(env);
+    stats.milestone_writes += 1;
+    stats.approx_bytes_written += APPROX_MILESTONE_ENTRY_BYTES;
+    set_stats(env, &stats);
+}
+
+/// Extends the TTL of a goal just written, along with its owner's goal list
+/// and its milestone index when they exist.
+pub fn extend_goal_ttl(env: &Env, goal: &SavingsGoal) {
+    extend(
+        env,
+        &DataKey::Goal(goal.goal_id),
+        TTL_THRESHOLD_LEDGERS,
+        TTL_EXTEND_TO_LEDGERS,
+    );
+    for key in [
+        DataKey::UserGoals(goal.user.clone()),
+        DataKey::GoalMilestones(goal.goal_id),
+    ] {
+        if env.storage().persistent().has(&key) {
+            extend(env, &key, TTL_THRESHOLD_LEDGERS, TTL_EXTEND_TO_LEDGERS);
+        }
+    }
+}
+
+/// Extends the TTL of every goal in `start_goal_id..=end_goal_id`, its
+/// milestone indexes and its milestone records, plus the contract instance.
+/// Missing goals are skipped.
+pub fn extend_goals(env: &Env, start_goal_id: u64, end_goal_id: u64) -> MaintenanceReport {
+    let mut report = MaintenanceReport {
+        goals_touched: 0,
+        milestones_touched: 0,
+        entries_touched: 0,
+        approx_bytes: 0,
+    };
+
+    for goal_id in start_goal_id..=end_goal_id {
+        extend_goal_entries(
+            env,
+            goal_id,
+            TTL_THRESHOLD_LEDGERS,
+            TTL_EXTEND_TO_LEDGERS,
+            &mut report,
+        );
+    }
+
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_THRESHOLD_LEDGERS, TTL_EXTEND_TO_LEDGERS);
+
+    record_maintenance(env, &report);
+    report
+}
+
+/// Extends the listed goals, their owners' goal lists, their milestone
+/// indexes and their milestone records to live at least `ledgers` more
+/// ledgers. Missing goals are skipped.
+pub fn bump_goals(env: &Env, goal_ids: &Vec<u64>, ledgers: u32) -> MaintenanceReport {
+    let mut report = MaintenanceReport {
+        goals_touched: 0,
+        milestones_touched: 0,
+        entries_touched: 0,
+        approx_bytes: 0,
+    };
+    let mut owners: Map<Address, bool> = Map::new(env);
+
+    for goal_id in goal_ids.iter() {
+        let Some(goal) = extend_goal_entries(env, goal_id, ledgers, ledgers, &mut report) else {
+            continue;
+        };
+        if owners.contains_key(goal.user.clone()) {
+            continue;
+        }
+        owners.set(goal.user.clone(), true);
+
+        let user_goals_key = DataKey::UserGoals(goal.user);
+        if let Some(user_goals) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<u64>>(&user_goals_key)
+        {
+            extend(env, &user_goals_key, ledgers, ledgers);
+            report.entries_touched += 1;
+            report.approx_bytes += index_bytes(user_goals.len());
+        }
+    }
+
+    env.storage().instance().extend_ttl(ledgers, ledgers);
+
+    record_maintenance(env, &report);
+    report
+}
+
+/// Extends a goal, its milestone indexes and its milestone records, adding
+/// them to `report`. Returns the goal, or `None` if it does not exist.
+fn extend_goal_entries(
+    env: &Env,
+    goal_id: u64,
+    threshold: u32,
+    extend_to: u32,
+    report: &mut MaintenanceReport,
+) -> Option<SavingsGoal> {
+    let goal_key = DataKey::Goal(goal_id);
+    let goal: SavingsGoal = env.storage().persistent().get(&goal_key)?;
+    extend(env, &goal_key, threshold, extend_to);
+    report.goals_touched += 1;
+    report.entries_touched += 1;
+    report.approx_bytes += APPROX_GOAL_ENTRY_BYTES;
+
+    let percent_key = DataKey::GoalMilestonesPercent(goal_id);
+    if let Some(percentages) = env.storage().persistent().get::<_, Vec<u32>>(&percent_key) {
+        extend(env, &percent_key, threshold, extend_to);
+        report.entries_touched += 1;
+        report.approx_bytes += index_bytes(percentages.len());
+    }
+
+    let milestones_key = DataKey::GoalMilestones(goal_id);
+    if let Some(milestone_ids) = env
+        .storage()
+        .persistent()
+        .get::<_, Vec<u64>>(&milestones_key)
+    {
+        extend(env, &milestones_key, threshold, extend_to);
+        report.entries_touched += 1;
+        report.approx_bytes += index_bytes(milestone_ids.len());
+
+        for milestone_id in milestone_ids.iter() {
+            let milestone_key = DataKey::Milestone(milestone_id);
+            if env.storage().persistent().has(&milestone_key) {
+                extend(env, &milestone_key, threshold, extend_to);
+                report.milestones_touched += 1;
+                report.entries_touched += 1;
+                report.approx_bytes += APPROX_MILESTONE_ENTRY_BYTES;
+            }
+        }
+    }
+
+    Some(goal)
+}
+
+fn record_maintenance(env: &Env, report: &MaintenanceReport) {
+    let mut stats = get_stats(env);
+    stats.entries_extended += report.entries_touched as u64;
+    stats.last_maintenance_ledger = env.ledger().sequence();
+    set_stats(env, &stats);
+}
+
+fn extend(env: &Env, key: &DataKey, threshold: u32, extend_to: u32) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, threshold, extend_to);
+}
+
+fn index_bytes(len: u32) -> u64 {
+    APPROX_INDEX_ENTRY_BYTES + APPROX_INDEX_ELEMENT_BYTES * len as u64
+}
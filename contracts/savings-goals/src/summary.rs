@@ -0,0 +1,87 @@
+//! Per-user savings aggregates.
+//!
+//! Every goal write goes through `storage::save_goal`, which hands the
+//! previous and new versions of the goal to `on_goal_saved`. The owner's
+//! totals are adjusted by the difference, so reading a summary never walks
+//! the user's goal list. Cancelled goals leave their owner's list and are
+//! taken out of the totals through `on_goal_dropped`.
+
+use soroban_sdk::{Address, Env};
+
+use crate::types::{DataKey, SavingsGoal, UserSavingsSummary};
+
+pub fn get(env: &Env, user: &Address) -> UserSavingsSummary {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserSummary(user.clone()))
+        .unwrap_or(UserSavingsSummary {
+            total_goals: 0,
+            active_goals: 0,
+            completed_goals: 0,
+            total_target: 0,
+            total_saved: 0,
+            progress_percent: 0,
+        })
+}
+
+/// Moves the owner's totals from `previous` (if the goal existed) to `goal`.
+pub fn on_goal_saved(env: &Env, previous: Option<&SavingsGoal>, goal: &SavingsGoal) {
+    if let Some(previous) = previous {
+        if previous.user == goal.user {
+            let mut summary = get(env, &goal.user);
+            remove(&mut summary, previous);
+            add(&mut summary, goal);
+            set(env, &goal.user, summary);
+            return;
+        }
+        let mut summary = get(env, &previous.user);
+        remove(&mut summary, previous);
+        set(env, &previous.user, summary);
+    }
+
+    let mut summary = get(env, &goal.user);
+    add(&mut summary, goal);
+    set(env, &goal.user, summary);
+}
+
+/// Takes a goal dropped from its owner's goal list out of their totals.
+pub fn on_goal_dropped(env: &Env, goal: &SavingsGoal) {
+    let mut summary = get(env, &goal.user);
+    remove(&mut summary, goal);
+    set(env, &goal.user, summary);
+}
+
+fn add(summary: &mut UserSavingsSummary, goal: &SavingsGoal) {
+    summary.total_goals += 1;
+    if goal.is_active {
+        summary.active_goals += 1;
+    }
+    if goal.current_amount >= goal.target_amount {
+        summary.completed_goals += 1;
+    }
+    summary.total_target += goal.target_amount;
+    summary.total_saved += goal.current_amount;
+}
+
+fn remove(summary: &mut UserSavingsSummary, goal: &SavingsGoal) {
+    summary.total_goals = summary.total_goals.saturating_sub(1);
+    if goal.is_active {
+        summary.active_goals = summary.active_goals.saturating_sub(1);
+    }
+    if goal.current_amount >= goal.target_amount {
+        summary.completed_goals = summary.completed_goals.saturating_sub(1);
+    }
+    summary.total_target -= goal.target_amount;
+    summary.total_saved -= goal.current_amount;
+}
+
+fn set(env: &Env, user: &Address, mut summary: UserSavingsSummary) {
+    summary.progress_percent = if summary.total_target > 0 {
+        (summary.total_saved * 100 / summary.total_target) as u32
+    } else {
+        0
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::UserSummary(user.clone()), &summary);
+}
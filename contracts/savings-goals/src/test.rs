@@ -2,11 +2,20 @@
 
 #![cfg(test)]
 
-use crate::{SavingsGoalsContract, SavingsGoalsContractClient};
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, Vec};
+use crate::storage;
+use crate::{SavingsGoalError, SavingsGoalsContract, SavingsGoalsContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, Env, IntoVal, Symbol, Vec,
+};
 
 use crate::types::{
-    ErrorCode, GoalResult, MilestoneAchievementRequest, MilestoneResult, SavingsGoalRequest,
+    DataKey, ErrorCode, GoalCancelResult, GoalExpiryResult, GoalImportResult, GoalLimits,
+    GoalResult, GoalUpdateRequest, GoalUpdateResult, LegacyGoal, MilestoneAchievementRequest,
+    MilestoneResult, SavingsGoal, SavingsGoalRequest, WithdrawalGuard, WithdrawalOutcome,
+    LEADERBOARD_SIZE, MAX_BATCH_SIZE, RATE_SCALE, SECONDS_PER_WEEK,
 };
 
 /// Helper function to create a test environment with initialized contract.
@@ -23,6 +32,42 @@ fn setup_test_contract() -> (Env, Address, SavingsGoalsContractClient<'static>)
     (env, admin, client)
 }
 
+/// Test utility: registers a Stellar asset contract to contribute in.
+fn create_token(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
+
+/// Test utility: mints `amount` to `user` and contributes it to the goal.
+fn contribute(
+    client: &SavingsGoalsContractClient,
+    token: &Address,
+    user: &Address,
+    goal_id: &u64,
+    amount: &i128,
+) -> SavingsGoal {
+    StellarAssetClient::new(&client.env, token).mint(user, amount);
+    client.contribute_to_goal(user, goal_id, token, amount)
+}
+
+/// Test utility: overwrite a goal's `current_amount` directly in contract storage.
+///
+/// Bypasses contribution logic (and milestone auto-detection) so tests can set up
+/// arbitrary progress states.
+fn set_goal_current_amount(
+    env: &Env,
+    client: &SavingsGoalsContractClient,
+    goal_id: u64,
+    amount: i128,
+) {
+    env.as_contract(&client.address, || {
+        let key = DataKey::Goal(goal_id);
+        let mut goal: SavingsGoal = env.storage().persistent().get(&key).unwrap();
+        goal.current_amount = amount;
+        env.storage().persistent().set(&key, &goal);
+    });
+}
+
 /// Helper function to create a valid savings goal request.
 fn create_valid_request(
     env: &Env,
@@ -37,6 +82,8 @@ fn create_valid_request(
         target_amount: amount,
         deadline: current_ledger + 1000,
         initial_contribution: amount / 10, // 10% initial contribution
+        category: None,
+        metadata: Bytes::new(env),
     }
 }
 
@@ -75,6 +122,8 @@ fn test_batch_set_savings_goals_single_user() {
             target_amount: 100_000_000,
             deadline: env.ledger().sequence() as u64 + 1000,
             initial_contribution: 25_000_000,
+            category: None,
+            metadata: Bytes::new(&env),
         });
         let result = client.batch_set_savings_goals(&admin, &requests);
         assert_eq!(result.successful, 1);
@@ -103,10 +152,10 @@ fn test_batch_set_savings_goals_single_user() {
             .get(&crate::types::DataKey::GoalMilestonesPercent(1))
             .unwrap();
         assert_eq!(triggered.len(), 4);
-        assert!(triggered.contains(&25));
-        assert!(triggered.contains(&50));
-        assert!(triggered.contains(&75));
-        assert!(triggered.contains(&100));
+        assert!(triggered.contains(25));
+        assert!(triggered.contains(50));
+        assert!(triggered.contains(75));
+        assert!(triggered.contains(100));
     }
     let (env, admin, client) = setup_test_contract();
     let user = Address::generate(&env);
@@ -202,6 +251,72 @@ fn test_batch_set_savings_goals_with_invalid_requests() {
     }
 }
 
+#[test]
+fn test_batch_update_goals_partial_failures() {
+    let (env, admin, client) = setup_test_contract();
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &owner, "car", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &other, "house", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &owner, "bike", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let update =
+        |goal_id: u64, target_amount: Option<i128>, deadline: Option<u64>| GoalUpdateRequest {
+            goal_id,
+            target_amount,
+            deadline,
+            goal_name: None,
+            category: None,
+            metadata: None,
+        };
+    let new_deadline = env.ledger().sequence() as u64 + 2000;
+    let mut requests: Vec<GoalUpdateRequest> = Vec::new(&env);
+    requests.push_back(GoalUpdateRequest {
+        goal_name: Some(Symbol::new(&env, "electric_car")),
+        ..update(1, Some(200_000_000), Some(new_deadline))
+    });
+    requests.push_back(update(2, Some(200_000_000), None));
+    requests.push_back(update(3, Some(1000), None));
+    requests.push_back(update(3, None, None));
+    requests.push_back(update(99, None, Some(new_deadline)));
+
+    let result = client.batch_update_goals(&owner, &requests);
+    assert_eq!(result.total_requests, 5);
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 4);
+
+    let goal = client.get_goal(&1).unwrap();
+    assert_eq!(goal.target_amount, 200_000_000);
+    assert_eq!(goal.deadline, new_deadline);
+    assert_eq!(goal.goal_name, Symbol::new(&env, "electric_car"));
+
+    let expected = [
+        (2, ErrorCode::UNAUTHORIZED_USER),
+        (3, ErrorCode::INVALID_AMOUNT),
+        (3, ErrorCode::NO_CHANGES),
+        (99, ErrorCode::GOAL_NOT_FOUND),
+    ];
+    for (index, (goal_id, error_code)) in expected.iter().enumerate() {
+        match result.results.get(index as u32 + 1).unwrap() {
+            GoalUpdateResult::Success(_) => panic!("Expected update to fail"),
+            GoalUpdateResult::Failure(id, code) => {
+                assert_eq!(id, *goal_id);
+                assert_eq!(code, *error_code);
+            }
+        }
+    }
+    assert_eq!(client.get_goal(&3).unwrap().target_amount, 100_000_000);
+
+    // The admin may update any goal
+    let mut requests: Vec<GoalUpdateRequest> = Vec::new(&env);
+    requests.push_back(update(2, Some(200_000_000), None));
+    assert_eq!(client.batch_update_goals(&admin, &requests).successful, 1);
+    assert_eq!(client.get_goal(&2).unwrap().target_amount, 200_000_000);
+}
+
 #[test]
 fn test_batch_set_savings_goals_invalid_deadline() {
     let (env, admin, client) = setup_test_contract();
@@ -313,6 +428,195 @@ fn test_get_user_goals() {
     assert_eq!(user_goals.get(1).unwrap(), 2);
 }
 
+#[test]
+fn test_get_user_goals_detailed_and_by_status() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    for name in ["vacation", "house", "car"] {
+        requests.push_back(create_valid_request(&env, &user, name, 100_000_000));
+    }
+    client.batch_set_savings_goals(&admin, &requests);
+
+    let page = client.get_user_goals_detailed(&user, &1, &5);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().goal_name, Symbol::new(&env, "house"));
+    assert_eq!(page.get(1).unwrap().goal_id, 3);
+    assert!(client.get_user_goals_detailed(&user, &3, &5).is_empty());
+
+    env.as_contract(&client.address, || {
+        let key = DataKey::Goal(2);
+        let mut goal: SavingsGoal = env.storage().persistent().get(&key).unwrap();
+        goal.is_active = false;
+        env.storage().persistent().set(&key, &goal);
+    });
+    let active = client.get_user_goals_by_status(&user, &true);
+    assert_eq!(active.len(), 2);
+    assert_eq!(active.get(1).unwrap().goal_id, 3);
+    let inactive = client.get_user_goals_by_status(&user, &false);
+    assert_eq!(inactive.len(), 1);
+    assert_eq!(inactive.get(0).unwrap().goal_id, 2);
+}
+
+#[test]
+fn test_goal_categories_and_metadata() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let travel = Symbol::new(&env, "travel");
+    let emergency = Symbol::new(&env, "emergency");
+
+    let mut requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    for (name, category) in [
+        ("japan", Some(travel.clone())),
+        ("rainy_day", Some(emergency.clone())),
+        ("car", None),
+        ("peru", Some(travel.clone())),
+    ] {
+        requests.push_back(SavingsGoalRequest {
+            category,
+            metadata: Bytes::from_slice(&env, b"{\"icon\":\"plane\"}"),
+            ..create_valid_request(&env, &user, name, 100_000_000)
+        });
+    }
+    // Oversized metadata is rejected
+    requests.push_back(SavingsGoalRequest {
+        metadata: Bytes::from_array(&env, &[0u8; 257]),
+        ..create_valid_request(&env, &user, "too_big", 100_000_000)
+    });
+    let result = client.batch_set_savings_goals(&admin, &requests);
+    assert_eq!(result.successful, 4);
+    match result.results.get(4).unwrap() {
+        GoalResult::Success(_) => panic!("Expected oversized metadata to fail"),
+        GoalResult::Failure(_, error_code) => assert_eq!(error_code, ErrorCode::INVALID_METADATA),
+    }
+
+    let goal = client.get_goal(&1).unwrap();
+    assert_eq!(goal.category, Some(travel.clone()));
+    assert_eq!(
+        goal.metadata,
+        Bytes::from_slice(&env, b"{\"icon\":\"plane\"}")
+    );
+
+    let trips = client.get_goals_by_category(&user, &travel);
+    assert_eq!(trips.len(), 2);
+    assert_eq!(trips.get(0).unwrap().goal_id, 1);
+    assert_eq!(trips.get(1).unwrap().goal_id, 4);
+
+    // Updates can recategorize a goal and replace its metadata
+    let mut updates: Vec<GoalUpdateRequest> = Vec::new(&env);
+    updates.push_back(GoalUpdateRequest {
+        goal_id: 3,
+        target_amount: None,
+        deadline: None,
+        goal_name: None,
+        category: Some(emergency.clone()),
+        metadata: Some(Bytes::new(&env)),
+    });
+    updates.push_back(GoalUpdateRequest {
+        goal_id: 1,
+        target_amount: None,
+        deadline: None,
+        goal_name: None,
+        category: None,
+        metadata: Some(Bytes::from_array(&env, &[0u8; 257])),
+    });
+    let result = client.batch_update_goals(&user, &updates);
+    assert_eq!(result.successful, 1);
+    match result.results.get(1).unwrap() {
+        GoalUpdateResult::Success(_) => panic!("Expected oversized metadata to fail"),
+        GoalUpdateResult::Failure(_, code) => assert_eq!(code, ErrorCode::INVALID_METADATA),
+    }
+
+    let emergencies = client.get_goals_by_category(&user, &emergency);
+    assert_eq!(emergencies.len(), 2);
+    assert_eq!(emergencies.get(1).unwrap().goal_id, 3);
+    assert!(emergencies.get(1).unwrap().metadata.is_empty());
+    assert!(client
+        .get_goals_by_category(&user, &Symbol::new(&env, "education"))
+        .is_empty());
+}
+
+#[test]
+fn test_user_savings_summary_tracks_goal_writes() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, "vacation", 100_000_000));
+    requests.push_back(create_valid_request(&env, &user, "house", 200_000_000));
+    requests.push_back(create_valid_request(&env, &user, "car", 300_000_000));
+    requests.push_back(create_valid_request(&env, &other, "bike", 50_000_000));
+    client.batch_set_savings_goals(&admin, &requests);
+
+    let summary = client.get_user_savings_summary(&user);
+    assert_eq!(summary.total_goals, 3);
+    assert_eq!(summary.active_goals, 3);
+    assert_eq!(summary.completed_goals, 0);
+    assert_eq!(summary.total_target, 600_000_000);
+    assert_eq!(summary.total_saved, 60_000_000);
+    assert_eq!(summary.progress_percent, 10);
+
+    // Filling a goal counts it as completed
+    contribute(&client, &token, &user, &1, &90_000_000);
+    let summary = client.get_user_savings_summary(&user);
+    assert_eq!(summary.completed_goals, 1);
+    assert_eq!(summary.total_saved, 150_000_000);
+    assert_eq!(summary.progress_percent, 25);
+
+    // Target updates replace the goal's previous contribution to the totals
+    let mut updates: Vec<GoalUpdateRequest> = Vec::new(&env);
+    updates.push_back(GoalUpdateRequest {
+        goal_id: 2,
+        target_amount: Some(120_000_000),
+        deadline: None,
+        goal_name: None,
+        category: None,
+        metadata: None,
+    });
+    client.batch_update_goals(&user, &updates);
+    assert_eq!(
+        client.get_user_savings_summary(&user).total_target,
+        520_000_000
+    );
+
+    // Cancelled goals leave the owner's totals
+    client.cancel_goal(&user, &3);
+    let summary = client.get_user_savings_summary(&user);
+    assert_eq!(summary.total_goals, 2);
+    assert_eq!(summary.active_goals, 2);
+    assert_eq!(summary.completed_goals, 1);
+    assert_eq!(summary.total_target, 220_000_000);
+    assert_eq!(summary.total_saved, 120_000_000);
+    assert_eq!(summary.progress_percent, 54);
+
+    let summary = client.get_user_savings_summary(&other);
+    assert_eq!(summary.total_goals, 1);
+    assert_eq!(summary.total_saved, 5_000_000);
+    let unknown = client.get_user_savings_summary(&Address::generate(&env));
+    assert_eq!(unknown.total_goals, 0);
+    assert_eq!(unknown.progress_percent, 0);
+}
+
+#[test]
+fn test_get_completed_goals() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, "vacation", 100_000_000));
+    requests.push_back(create_valid_request(&env, &user, "house", 500_000_000));
+    client.batch_set_savings_goals(&admin, &requests);
+    assert_eq!(client.get_completed_goals(&user), 0);
+
+    contribute(&client, &token, &user, &1, &90_000_000);
+    contribute(&client, &token, &user, &2, &449_999_999);
+    assert_eq!(client.get_completed_goals(&user), 1);
+}
+
 #[test]
 fn test_batch_metrics() {
     let (env, admin, client) = setup_test_contract();
@@ -477,7 +781,7 @@ fn test_batch_mark_single_milestone() {
     // Update goal's current_amount to meet milestone
     let mut goal = client.get_goal(&1).unwrap();
     goal.current_amount = 25_000_000; // 25% of 100_000_000
-    crate::SavingsGoalsContract::test_set_goal_current_amount(env.clone(), 1, 25_000_000);
+    set_goal_current_amount(&env, &client, 1, 25_000_000);
 
     // Mark a milestone
     let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
@@ -510,7 +814,7 @@ fn test_batch_mark_multiple_milestones() {
     // Update goal's current_amount to meet all milestones
     let mut goal = client.get_goal(&1).unwrap();
     goal.current_amount = 75_000_000; // 75% of 100_000_000
-    crate::SavingsGoalsContract::test_set_goal_current_amount(env.clone(), 1, 75_000_000);
+    set_goal_current_amount(&env, &client, 1, 75_000_000);
 
     // Mark multiple milestones in one batch
     let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
@@ -679,6 +983,7 @@ fn test_milestone_duplicate_percentage() {
     let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
     goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
     client.batch_set_savings_goals(&admin, &goal_requests);
+    set_goal_current_amount(&env, &client, 1, 50_000_000);
 
     // Mark first milestone
     let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
@@ -712,6 +1017,84 @@ fn test_milestone_duplicate_percentage() {
     }
 }
 
+#[test]
+fn test_milestone_cooldown_blocks_repeat_batches() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    set_goal_current_amount(&env, &client, 1, 60_000_000);
+    client.set_milestone_cooldown(&admin, &100);
+    assert_eq!(client.get_milestone_cooldown(), 100);
+
+    // Several claims for the same goal within one batch are fine
+    let mut first_batch: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    for percentage in [25u32, 50] {
+        first_batch.push_back(MilestoneAchievementRequest {
+            goal_id: 1,
+            user: user.clone(),
+            milestone_percentage: percentage,
+            achieved_at: env.ledger().sequence() as u64,
+        });
+    }
+    let result = client.batch_mark_milestones(&user, &first_batch);
+    assert_eq!(result.successful, 2);
+    let cooldown_end = client.get_milestone_cooldown_end(&1).unwrap();
+    assert_eq!(cooldown_end, env.ledger().sequence() + 100);
+
+    // A second batch inside the window is rejected
+    set_goal_current_amount(&env, &client, 1, 80_000_000);
+    let mut second_batch: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    second_batch.push_back(MilestoneAchievementRequest {
+        goal_id: 1,
+        user: user.clone(),
+        milestone_percentage: 75,
+        achieved_at: env.ledger().sequence() as u64,
+    });
+    let result = client.batch_mark_milestones(&user, &second_batch);
+    assert_eq!(result.failed, 1);
+    match &result.results.get(0).unwrap() {
+        MilestoneResult::Failure(_, code) => assert_eq!(code, &ErrorCode::COOLDOWN_ACTIVE),
+        _ => panic!("Expected failure"),
+    }
+
+    // Once the window passes the claim goes through
+    env.ledger()
+        .with_mut(|li| li.sequence_number = cooldown_end);
+    assert_eq!(client.get_milestone_cooldown_end(&1), None);
+    let result = client.batch_mark_milestones(&user, &second_batch);
+    assert_eq!(result.successful, 1);
+}
+
+#[test]
+fn test_milestone_cooldown_ignores_non_owner_claims() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let bot = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    set_goal_current_amount(&env, &client, 1, 30_000_000);
+    client.set_milestone_cooldown(&admin, &100);
+
+    let mut requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    requests.push_back(MilestoneAchievementRequest {
+        goal_id: 1,
+        user: user.clone(),
+        milestone_percentage: 25,
+        achieved_at: env.ledger().sequence() as u64,
+    });
+
+    // A stranger's attempt must not lock the owner out
+    client.batch_mark_milestones(&bot, &requests);
+    assert_eq!(client.get_milestone_cooldown_end(&1), None);
+    let result = client.batch_mark_milestones(&user, &requests);
+    assert_eq!(result.successful, 1);
+}
+
 #[test]
 fn test_milestone_partial_failures() {
     let (env, admin, client) = setup_test_contract();
@@ -724,7 +1107,7 @@ fn test_milestone_partial_failures() {
     // Update goal's current_amount to meet valid milestones
     let mut goal = client.get_goal(&1).unwrap();
     goal.current_amount = 75_000_000; // 75% of 100_000_000
-    crate::SavingsGoalsContract::test_set_goal_current_amount(env.clone(), 1, 75_000_000);
+    set_goal_current_amount(&env, &client, 1, 75_000_000);
 
     // Create a batch with mixed valid and invalid requests
     let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
@@ -786,7 +1169,7 @@ fn test_milestone_retrieve_milestone() {
     // Update goal's current_amount to meet milestone
     let mut goal = client.get_goal(&1).unwrap();
     goal.current_amount = 50_000_000; // 50% of 100_000_000
-    crate::SavingsGoalsContract::test_set_goal_current_amount(env.clone(), 1, 50_000_000);
+    set_goal_current_amount(&env, &client, 1, 50_000_000);
 
     // Mark a milestone
     let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
@@ -830,7 +1213,7 @@ fn test_milestone_batch_too_large() {
     // Update goal's current_amount to meet milestone
     let mut goal = client.get_goal(&1).unwrap();
     goal.current_amount = 50_000_000; // 50% of 100_000_000
-    crate::SavingsGoalsContract::test_set_goal_current_amount(env.clone(), 1, 50_000_000);
+    set_goal_current_amount(&env, &client, 1, 50_000_000);
 
     // Create batch exceeding MAX_BATCH_SIZE
     let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
@@ -845,3 +1228,1776 @@ fn test_milestone_batch_too_large() {
 
     client.batch_mark_milestones(&user, &milestone_requests);
 }
+
+// ==================== Contribution & Adjustment Tests ====================
+
+#[test]
+fn test_contribute_to_goal() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let goal = contribute(&client, &token, &user, &1, &40_000_000);
+    assert_eq!(goal.current_amount, 50_000_000);
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 50_000_000);
+
+    // 25% and 50% thresholds were crossed automatically
+    let triggered: Vec<u32> = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GoalMilestonesPercent(1))
+            .unwrap()
+    });
+    assert!(triggered.contains(25));
+    assert!(triggered.contains(50));
+    assert!(!triggered.contains(75));
+}
+
+#[test]
+fn test_contribute_to_goal_transfers_tokens() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    assert_eq!(client.get_goal_token(&1), None);
+
+    StellarAssetClient::new(&env, &token).mint(&user, &30_000_000);
+    client.contribute_to_goal(&user, &1, &token, &20_000_000);
+
+    let balances = TokenClient::new(&env, &token);
+    assert_eq!(balances.balance(&user), 10_000_000);
+    assert_eq!(balances.balance(&client.address), 20_000_000);
+    assert_eq!(client.get_goal_token(&1), Some(token));
+
+    // The goal stays in the token of its first contribution
+    let other_token = create_token(&env);
+    StellarAssetClient::new(&env, &other_token).mint(&user, &10_000_000);
+    let error = client
+        .try_contribute_to_goal(&user, &1, &other_token, &10_000_000)
+        .err()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        error,
+        soroban_sdk::Error::from_contract_error(SavingsGoalError::TokenMismatch as u32)
+    );
+}
+
+#[test]
+fn test_transfer_goal_ownership() {
+    let (env, admin, client) = setup_test_contract();
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    let mut car = create_valid_request(&env, &owner, "car", 100_000_000);
+    car.initial_contribution = 30_000_000;
+    goal_requests.push_back(car);
+    goal_requests.push_back(create_valid_request(&env, &owner, "bike", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &new_owner, "house", 200_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    // The 30% initial contribution reached the 25% milestone
+    client.repair_goal_milestones(&admin, &1);
+    let milestone_id = client.get_goal_milestones(&1).get(0).unwrap();
+
+    let goal = client.transfer_goal_ownership(&owner, &1, &new_owner);
+    assert_eq!(goal.user, new_owner);
+    assert_eq!(client.get_goal(&1).unwrap().user, new_owner);
+    assert_eq!(client.get_milestone(&milestone_id).unwrap().user, new_owner);
+    assert_eq!(client.get_user_goals(&owner), Vec::from_array(&env, [2u64]));
+    assert_eq!(
+        client.get_user_goals(&new_owner),
+        Vec::from_array(&env, [3u64, 1])
+    );
+
+    // Both owners' savings summaries follow the goal
+    let summary = client.get_user_savings_summary(&owner);
+    assert_eq!(summary.total_goals, 1);
+    assert_eq!(summary.total_saved, 10_000_000);
+    let summary = client.get_user_savings_summary(&new_owner);
+    assert_eq!(summary.total_goals, 2);
+    assert_eq!(summary.total_target, 300_000_000);
+    assert_eq!(summary.total_saved, 50_000_000);
+
+    // The previous owner no longer controls the goal
+    assert_eq!(
+        client
+            .try_transfer_goal_ownership(&owner, &1, &owner)
+            .unwrap_err(),
+        Ok(SavingsGoalError::Unauthorized.into())
+    );
+    assert_eq!(
+        client
+            .try_transfer_goal_ownership(&owner, &2, &owner)
+            .unwrap_err(),
+        Ok(SavingsGoalError::InvalidOwner.into())
+    );
+    assert_eq!(
+        client
+            .try_transfer_goal_ownership(&owner, &99, &new_owner)
+            .unwrap_err(),
+        Ok(SavingsGoalError::GoalNotFound.into())
+    );
+
+    // Cancelled goals stay with their owner
+    client.cancel_goal(&owner, &2);
+    assert_eq!(
+        client
+            .try_transfer_goal_ownership(&owner, &2, &new_owner)
+            .unwrap_err(),
+        Ok(SavingsGoalError::GoalNotActive.into())
+    );
+}
+
+#[test]
+fn test_cancel_goal_refunds_escrow() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &owner, "car", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &owner, "bike", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &other, "house", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    contribute(&client, &token, &owner, &1, &20_000_000);
+    assert_eq!(client.get_goal_escrow(&1), 20_000_000);
+
+    // Only the token contribution is refunded, not the initial amount
+    assert_eq!(client.cancel_goal(&owner, &1), 20_000_000);
+    let balances = TokenClient::new(&env, &token);
+    assert_eq!(balances.balance(&owner), 20_000_000);
+    assert_eq!(balances.balance(&client.address), 0);
+    assert_eq!(client.get_goal_escrow(&1), 0);
+    assert!(!client.get_goal(&1).unwrap().is_active);
+    assert_eq!(client.get_user_goals(&owner), Vec::from_array(&env, [2u64]));
+    assert!(client
+        .try_contribute_to_goal(&owner, &1, &token, &1_000_000)
+        .is_err());
+
+    let results = client.batch_cancel_goals(&owner, &Vec::from_array(&env, [1u64, 2, 3, 99]));
+    assert_eq!(
+        results,
+        Vec::from_array(
+            &env,
+            [
+                GoalCancelResult::Failure(1, ErrorCode::GOAL_NOT_ACTIVE),
+                GoalCancelResult::Success(2, 0),
+                GoalCancelResult::Failure(3, ErrorCode::UNAUTHORIZED_USER),
+                GoalCancelResult::Failure(99, ErrorCode::GOAL_NOT_FOUND),
+            ]
+        )
+    );
+
+    // The admin may cancel any goal
+    assert_eq!(client.cancel_goal(&admin, &3), 0);
+    assert!(client.get_user_goals(&other).is_empty());
+}
+
+#[test]
+fn test_guardian_cosigned_withdrawals() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let owner = Address::generate(&env);
+    let guardian = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &owner, "car", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    contribute(&client, &token, &owner, &1, &60_000_000);
+
+    // Without a guardian any escrowed amount is paid out directly
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &5_000_000),
+        WithdrawalOutcome::Executed(5_000_000)
+    );
+    let balances = TokenClient::new(&env, &token);
+    assert_eq!(balances.balance(&owner), 5_000_000);
+    assert_eq!(client.get_goal_escrow(&1), 55_000_000);
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 65_000_000);
+
+    let guard = WithdrawalGuard {
+        guardian: guardian.clone(),
+        threshold: 10_000_000,
+        approval_ledgers: 100,
+        window_ledgers: 1_000,
+    };
+    client.set_withdrawal_guard(&admin, &Some(guard.clone()));
+    assert_eq!(client.get_withdrawal_guard(), Some(guard));
+
+    // Small withdrawals still go straight through
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &10_000_000),
+        WithdrawalOutcome::Executed(10_000_000)
+    );
+    assert_eq!(client.get_withdrawn_in_window(&owner), 10_000_000);
+
+    // Once the window's total would pass the threshold, even small ones
+    // wait for the guardian
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &20_000_000),
+        WithdrawalOutcome::Pending(1)
+    );
+    let request = client.get_withdrawal_request(&1).unwrap();
+    assert_eq!(request.amount, 20_000_000);
+    assert_eq!(request.expires_at, request.requested_at + 100);
+    assert_eq!(balances.balance(&owner), 15_000_000);
+    assert_eq!(
+        client.try_approve_withdrawal(&owner, &1).unwrap_err(),
+        Ok(SavingsGoalError::Unauthorized.into())
+    );
+    assert_eq!(
+        client.try_expire_withdrawal(&1).unwrap_err(),
+        Ok(SavingsGoalError::WithdrawalNotExpired.into())
+    );
+
+    assert_eq!(client.approve_withdrawal(&guardian, &1), 20_000_000);
+    let events = env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    assert_eq!(
+        topics,
+        (symbol_short!("withdraw"), symbol_short!("executed"), 1u64).into_val(&env)
+    );
+    let (_, amount, approved_by): (Address, i128, Option<Address>) = data.into_val(&env);
+    assert_eq!(amount, 20_000_000);
+    assert_eq!(approved_by, Some(guardian.clone()));
+
+    assert_eq!(balances.balance(&owner), 35_000_000);
+    assert_eq!(client.get_goal_escrow(&1), 25_000_000);
+    assert!(client.get_withdrawal_request(&1).is_none());
+    assert_eq!(
+        client.try_approve_withdrawal(&guardian, &1).unwrap_err(),
+        Ok(SavingsGoalError::WithdrawalNotFound.into())
+    );
+
+    // Withdrawals are capped by the escrowed tokens
+    assert_eq!(
+        client
+            .try_request_withdrawal(&owner, &1, &30_000_000)
+            .unwrap_err(),
+        Ok(SavingsGoalError::InsufficientEscrow.into())
+    );
+
+    // Unapproved requests lapse after the approval window
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &25_000_000),
+        WithdrawalOutcome::Pending(2)
+    );
+    env.ledger().with_mut(|li| li.sequence_number += 101);
+    assert_eq!(
+        client.try_approve_withdrawal(&guardian, &2).unwrap_err(),
+        Ok(SavingsGoalError::WithdrawalExpired.into())
+    );
+    assert_eq!(client.expire_withdrawal(&2).amount, 25_000_000);
+    assert!(client.get_withdrawal_request(&2).is_none());
+    assert_eq!(client.get_goal_escrow(&1), 25_000_000);
+}
+
+#[test]
+fn test_split_withdrawals_need_cosigning_within_window() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let owner = Address::generate(&env);
+    let guardian = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &owner, "car", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    contribute(&client, &token, &owner, &1, &60_000_000);
+
+    client.set_withdrawal_guard(
+        &admin,
+        &Some(WithdrawalGuard {
+            guardian: guardian.clone(),
+            threshold: 10_000_000,
+            approval_ledgers: 100,
+            window_ledgers: 1_000,
+        }),
+    );
+
+    // Splitting a large withdrawal into small ones does not avoid co-signing
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &6_000_000),
+        WithdrawalOutcome::Executed(6_000_000)
+    );
+    env.ledger().with_mut(|li| li.sequence_number += 500);
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &4_000_000),
+        WithdrawalOutcome::Executed(4_000_000)
+    );
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &1),
+        WithdrawalOutcome::Pending(1)
+    );
+
+    // Approved withdrawals do not count against the window
+    client.approve_withdrawal(&guardian, &1);
+    assert_eq!(client.get_withdrawn_in_window(&owner), 10_000_000);
+
+    // Amounts leave the window as it rolls past them
+    env.ledger().with_mut(|li| li.sequence_number += 500);
+    assert_eq!(client.get_withdrawn_in_window(&owner), 4_000_000);
+    assert_eq!(
+        client.request_withdrawal(&owner, &1, &6_000_000),
+        WithdrawalOutcome::Executed(6_000_000)
+    );
+    assert_eq!(client.get_withdrawn_in_window(&owner), 10_000_000);
+}
+
+#[test]
+fn test_cancel_and_transfer_need_guardian_above_threshold() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let owner = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &owner, "car", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &owner, "bike", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    contribute(&client, &token, &owner, &1, &60_000_000);
+    contribute(&client, &token, &owner, &2, &4_000_000);
+
+    client.set_withdrawal_guard(
+        &admin,
+        &Some(WithdrawalGuard {
+            guardian: guardian.clone(),
+            threshold: 10_000_000,
+            approval_ledgers: 100,
+            window_ledgers: 1_000,
+        }),
+    );
+
+    // A stolen owner key can neither refund nor move a large escrow
+    assert_eq!(
+        client.try_cancel_goal(&owner, &1).unwrap_err(),
+        Ok(SavingsGoalError::GuardianApprovalRequired.into())
+    );
+    assert_eq!(
+        client
+            .try_transfer_goal_ownership(&owner, &1, &attacker)
+            .unwrap_err(),
+        Ok(SavingsGoalError::GuardianApprovalRequired.into())
+    );
+    assert_eq!(
+        client
+            .try_transfer_goal_with_guardian(&owner, &attacker, &1, &attacker)
+            .unwrap_err(),
+        Ok(SavingsGoalError::Unauthorized.into())
+    );
+    let results = client.batch_cancel_goals(&owner, &Vec::from_array(&env, [1u64]));
+    assert_eq!(
+        results.get(0).unwrap(),
+        GoalCancelResult::Failure(1, ErrorCode::GUARDIAN_APPROVAL_REQUIRED)
+    );
+    assert!(client.get_goal(&1).unwrap().is_active);
+    assert_eq!(client.get_goal_escrow(&1), 60_000_000);
+
+    // Small refunds go through and count against the window
+    assert_eq!(client.cancel_goal(&owner, &2), 4_000_000);
+    assert_eq!(client.get_withdrawn_in_window(&owner), 4_000_000);
+
+    // With the guardian's co-signature both go through
+    let new_owner = Address::generate(&env);
+    let goal = client.transfer_goal_with_guardian(&owner, &guardian, &1, &new_owner);
+    assert_eq!(goal.user, new_owner);
+    assert_eq!(
+        client.try_cancel_goal(&new_owner, &1).unwrap_err(),
+        Ok(SavingsGoalError::GuardianApprovalRequired.into())
+    );
+    assert_eq!(
+        client.cancel_goal_with_guardian(&new_owner, &guardian, &1),
+        60_000_000
+    );
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&new_owner),
+        60_000_000
+    );
+    assert_eq!(client.get_withdrawn_in_window(&new_owner), 0);
+}
+
+#[test]
+fn test_process_expired_goals() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    for name in ["car", "bike", "house"] {
+        goal_requests.push_back(create_valid_request(&env, &user, name, 100_000_000));
+    }
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    let deadline = client.get_goal(&1).unwrap().deadline;
+
+    env.as_contract(&client.address, || {
+        let key = DataKey::Goal(1);
+        let mut goal: SavingsGoal = env.storage().persistent().get(&key).unwrap();
+        goal.current_amount = goal.target_amount;
+        env.storage().persistent().set(&key, &goal);
+    });
+    let mut updates: Vec<GoalUpdateRequest> = Vec::new(&env);
+    updates.push_back(GoalUpdateRequest {
+        goal_id: 3,
+        target_amount: None,
+        deadline: Some(deadline + 500),
+        goal_name: None,
+        category: None,
+        metadata: None,
+    });
+    client.batch_update_goals(&user, &updates);
+
+    // Nothing is due on the deadline itself
+    env.ledger()
+        .with_mut(|li| li.sequence_number = deadline as u32);
+    let results = client.process_expired_goals(&keeper, &Vec::from_array(&env, [1u64]));
+    assert_eq!(
+        results.get(0).unwrap(),
+        GoalExpiryResult::Failure(1, ErrorCode::DEADLINE_NOT_REACHED)
+    );
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = deadline as u32 + 1);
+    let results = client.process_expired_goals(&keeper, &Vec::from_array(&env, [1u64, 2, 3, 99]));
+    assert_eq!(
+        results,
+        Vec::from_array(
+            &env,
+            [
+                GoalExpiryResult::Expired(1, true),
+                GoalExpiryResult::Expired(2, false),
+                GoalExpiryResult::Failure(3, ErrorCode::DEADLINE_NOT_REACHED),
+                GoalExpiryResult::Failure(99, ErrorCode::GOAL_NOT_FOUND),
+            ]
+        )
+    );
+
+    let expiry = client.get_goal_expiry(&2).unwrap();
+    assert!(!expiry.target_met);
+    assert_eq!(expiry.final_amount, 10_000_000);
+    assert_eq!(expiry.expired_at, deadline as u32 + 1);
+    assert!(client.get_goal_expiry(&3).is_none());
+
+    // Expired goals stay listed as inactive and are not swept twice
+    assert_eq!(client.get_user_goals_by_status(&user, &false).len(), 2);
+    let results = client.process_expired_goals(&keeper, &Vec::from_array(&env, [2u64]));
+    assert_eq!(
+        results.get(0).unwrap(),
+        GoalExpiryResult::Failure(2, ErrorCode::GOAL_NOT_ACTIVE)
+    );
+}
+
+#[test]
+fn test_maintenance_extends_goal_ttls() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "car", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &user, "bike", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    set_goal_current_amount(&env, &client, 1, 25_000_000);
+
+    let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    milestone_requests.push_back(MilestoneAchievementRequest {
+        goal_id: 1,
+        user: user.clone(),
+        milestone_percentage: 25,
+        achieved_at: env.ledger().sequence() as u64,
+    });
+    client.batch_mark_milestones(&user, &milestone_requests);
+
+    let stats = client.get_storage_stats();
+    assert_eq!(stats.goal_writes, 2);
+    assert_eq!(stats.milestone_writes, 1);
+    assert_eq!(
+        stats.approx_bytes_written,
+        2 * storage::APPROX_GOAL_ENTRY_BYTES + storage::APPROX_MILESTONE_ENTRY_BYTES
+    );
+
+    // Goal IDs past the last goal are skipped
+    let report = client.maintenance(&admin, &1, &10);
+    assert_eq!(report.goals_touched, 2);
+    assert_eq!(report.milestones_touched, 1);
+    // Both goals and their percentage indexes, plus goal 1's milestone and
+    // its milestone index
+    assert_eq!(report.entries_touched, 6);
+    env.as_contract(&client.address, || {
+        for key in [DataKey::Goal(2), DataKey::Milestone(1)] {
+            assert_eq!(
+                env.storage().persistent().get_ttl(&key),
+                storage::TTL_EXTEND_TO_LEDGERS
+            );
+        }
+    });
+
+    let stats = client.get_storage_stats();
+    assert_eq!(stats.entries_extended, 6);
+    assert_eq!(stats.last_maintenance_ledger, env.ledger().sequence());
+
+    assert!(client.try_maintenance(&user, &1, &10).is_err());
+    assert_eq!(
+        client.try_maintenance(&admin, &1, &(MAX_BATCH_SIZE as u64 + 1)),
+        Err(Ok(SavingsGoalError::BatchTooLarge.into()))
+    );
+}
+
+#[test]
+fn test_goal_writes_and_bump_goals_extend_ttls() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "car", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &user, "bike", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    // Writing a goal extends it along with its owner's goal list
+    contribute(&client, &token, &user, &1, &10_000_000);
+    env.as_contract(&client.address, || {
+        for key in [DataKey::Goal(1), DataKey::UserGoals(user.clone())] {
+            assert_eq!(
+                env.storage().persistent().get_ttl(&key),
+                storage::TTL_EXTEND_TO_LEDGERS
+            );
+        }
+    });
+
+    let ledgers = storage::TTL_EXTEND_TO_LEDGERS + 100_000;
+    let report = client.bump_goals(&admin, &Vec::from_array(&env, [1u64, 2, 99]), &ledgers);
+    assert_eq!(report.goals_touched, 2);
+    assert_eq!(report.milestones_touched, 0);
+    // Both goals and their percentage indexes, plus the shared goal list
+    assert_eq!(report.entries_touched, 5);
+    env.as_contract(&client.address, || {
+        for key in [
+            DataKey::Goal(1),
+            DataKey::Goal(2),
+            DataKey::GoalMilestonesPercent(2),
+            DataKey::UserGoals(user.clone()),
+        ] {
+            assert_eq!(env.storage().persistent().get_ttl(&key), ledgers);
+        }
+    });
+    assert_eq!(client.get_storage_stats().entries_extended, 5);
+
+    assert!(client
+        .try_bump_goals(&user, &Vec::from_array(&env, [1u64]), &ledgers)
+        .is_err());
+    assert_eq!(
+        client.try_bump_goals(&admin, &Vec::new(&env), &ledgers),
+        Err(Ok(SavingsGoalError::EmptyBatch.into()))
+    );
+    assert_eq!(
+        client.try_bump_goals(&admin, &Vec::from_array(&env, [1u64]), &0),
+        Err(Ok(SavingsGoalError::InvalidBatch.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_contribute_to_goal_not_owner() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    contribute(&client, &token, &other, &1, &10_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_contribute_to_goal_invalid_amount() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    client.contribute_to_goal(&user, &1, &token, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_contribute_to_missing_goal() {
+    let (env, _admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    contribute(&client, &token, &user, &42, &10_000_000);
+}
+
+#[test]
+fn test_admin_adjust_goal() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let goal = client.admin_adjust_goal(&admin, &1, &65_000_000, &symbol_short!("deposit"));
+    assert_eq!(goal.current_amount, 75_000_000);
+
+    let goal = client.admin_adjust_goal(&admin, &1, &-5_000_000, &symbol_short!("refund"));
+    assert_eq!(goal.current_amount, 70_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_admin_adjust_goal_below_zero() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    client.admin_adjust_goal(&admin, &1, &-20_000_000, &symbol_short!("fix"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_admin_adjust_goal_unauthorized() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    client.admin_adjust_goal(&user, &1, &10_000_000, &symbol_short!("fix"));
+}
+
+// ============================================================================
+// Gamification Stats Tests
+// ============================================================================
+
+#[test]
+fn test_stats_only_tracked_after_opt_in() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    contribute(&client, &token, &user, &1, &10_000_000);
+    assert!(client.get_user_stats(&user).is_none());
+    assert_eq!(client.get_leaderboard(&LEADERBOARD_SIZE).len(), 0);
+
+    client.opt_in_stats(&user);
+    contribute(&client, &token, &user, &1, &20_000_000);
+
+    let stats = client.get_user_stats(&user).unwrap();
+    assert_eq!(stats.total_saved, 20_000_000);
+    assert_eq!(stats.streak_weeks, 1);
+    // 10% initial + 10M + 20M = 40% crosses the 25% threshold
+    assert_eq!(stats.milestones_this_month, 1);
+}
+
+#[test]
+fn test_stats_weekly_streak() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 1_000_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    client.opt_in_stats(&user);
+
+    contribute(&client, &token, &user, &1, &1_000_000);
+    contribute(&client, &token, &user, &1, &1_000_000);
+    assert_eq!(client.get_user_stats(&user).unwrap().streak_weeks, 1);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + SECONDS_PER_WEEK);
+    contribute(&client, &token, &user, &1, &1_000_000);
+    assert_eq!(client.get_user_stats(&user).unwrap().streak_weeks, 2);
+
+    // Skipping a week resets the streak
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 2 * SECONDS_PER_WEEK);
+    contribute(&client, &token, &user, &1, &1_000_000);
+    let stats = client.get_user_stats(&user).unwrap();
+    assert_eq!(stats.streak_weeks, 1);
+    assert_eq!(stats.total_saved, 4_000_000);
+}
+
+#[test]
+fn test_vacation_keeps_streak_alive() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 1_000_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    client.opt_in_stats(&user);
+
+    let now = env.ledger().timestamp();
+    contribute(&client, &token, &user, &1, &1_000_000);
+    client.set_vacation(
+        &user,
+        &(now + SECONDS_PER_WEEK),
+        &(now + 3 * SECONDS_PER_WEEK),
+    );
+    assert!(client.get_vacation(&user).is_some());
+
+    // Away for two weeks: no contribution needed
+    env.ledger().set_timestamp(now + 2 * SECONDS_PER_WEEK);
+    assert!(!client.is_streak_at_risk(&user));
+
+    // Back the week after: the missed weeks are bridged
+    env.ledger().set_timestamp(now + 3 * SECONDS_PER_WEEK);
+    assert!(client.is_streak_at_risk(&user));
+    contribute(&client, &token, &user, &1, &1_000_000);
+    let stats = client.get_user_stats(&user).unwrap();
+    assert_eq!(stats.streak_weeks, 2);
+    assert_eq!(stats.vacation_weeks, 2);
+    assert!(!client.is_streak_at_risk(&user));
+
+    // Without a vacation, a missed week still resets the streak
+    client.clear_vacation(&user);
+    env.ledger().set_timestamp(now + 5 * SECONDS_PER_WEEK);
+    contribute(&client, &token, &user, &1, &1_000_000);
+    assert_eq!(client.get_user_stats(&user).unwrap().streak_weeks, 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_vacation_rejects_overlong_window() {
+    let (env, _admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let now = env.ledger().timestamp();
+    client.set_vacation(&user, &now, &(now + 9 * SECONDS_PER_WEEK));
+}
+
+#[test]
+fn test_claim_code_adds_contributor_once() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let owner = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &owner, "trip", 1_000_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    // A stranger can't contribute to someone else's goal
+    assert!(client
+        .try_contribute_to_goal(&friend, &1, &token, &1_000_000)
+        .is_err());
+
+    let code = Bytes::from_slice(&env, b"beach-2026");
+    let code_hash = env.crypto().sha256(&code).to_bytes();
+    let expires_at = env.ledger().timestamp() + 86_400;
+    client.create_claim_code(&owner, &1, &code_hash, &None, &expires_at);
+    assert_eq!(client.get_claim_code(&code_hash).unwrap().goal_id, 1);
+
+    assert_eq!(client.redeem_claim_code(&friend, &code), 1);
+    assert!(client.is_goal_contributor(&1, &friend));
+    assert!(client.get_claim_code(&code_hash).is_none());
+
+    let before = client.get_goal(&1).unwrap().current_amount;
+    let goal = contribute(&client, &token, &friend, &1, &1_000_000);
+    assert_eq!(goal.current_amount, before + 1_000_000);
+
+    // Codes are single-use
+    let other = Address::generate(&env);
+    assert!(client.try_redeem_claim_code(&other, &code).is_err());
+    assert!(!client.is_goal_contributor(&1, &other));
+}
+
+#[test]
+fn test_claim_code_invitee_expiry_and_revoke() {
+    let (env, admin, client) = setup_test_contract();
+    let owner = Address::generate(&env);
+    let invitee = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &owner, "trip", 1_000_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let now = env.ledger().timestamp();
+    let code = Bytes::from_slice(&env, b"for-invitee");
+    let code_hash = env.crypto().sha256(&code).to_bytes();
+    client.create_claim_code(&owner, &1, &code_hash, &Some(invitee.clone()), &(now + 100));
+
+    // Only the owner can create or revoke codes
+    assert!(client
+        .try_create_claim_code(&stranger, &1, &code_hash, &None, &(now + 100))
+        .is_err());
+    assert!(client.try_revoke_claim_code(&stranger, &code_hash).is_err());
+
+    // Only the invitee can redeem, and only before expiry
+    assert!(client.try_redeem_claim_code(&stranger, &code).is_err());
+    env.ledger().set_timestamp(now + 100);
+    assert!(client.try_redeem_claim_code(&invitee, &code).is_err());
+    assert!(!client.is_goal_contributor(&1, &invitee));
+
+    // A revoked code can't be redeemed
+    let code = Bytes::from_slice(&env, b"revoked");
+    let code_hash = env.crypto().sha256(&code).to_bytes();
+    client.create_claim_code(&owner, &1, &code_hash, &None, &(now + 1_000));
+    client.revoke_claim_code(&owner, &code_hash);
+    assert!(client.try_redeem_claim_code(&invitee, &code).is_err());
+}
+
+#[test]
+fn test_leaderboard_ordering_and_bound() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+
+    let mut users: Vec<Address> = Vec::new(&env);
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    for _ in 0..(LEADERBOARD_SIZE + 2) {
+        let user = Address::generate(&env);
+        goal_requests.push_back(create_valid_request(&env, &user, "savings", 1_000_000_000));
+        users.push_back(user);
+    }
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    for (i, user) in users.iter().enumerate() {
+        client.opt_in_stats(&user);
+        contribute(
+            &client,
+            &token,
+            &user,
+            &(i as u64 + 1),
+            &((i as i128 + 1) * 1_000_000),
+        );
+    }
+
+    let leaderboard = client.get_leaderboard(&(LEADERBOARD_SIZE + 5));
+    assert_eq!(leaderboard.len(), LEADERBOARD_SIZE);
+    assert_eq!(leaderboard.get(0).unwrap().user, users.last().unwrap());
+    for i in 1..leaderboard.len() {
+        assert!(
+            leaderboard.get(i - 1).unwrap().total_saved >= leaderboard.get(i).unwrap().total_saved
+        );
+    }
+
+    // A limit returns only the top positions
+    let top_three = client.get_leaderboard(&3);
+    assert_eq!(top_three, leaderboard.slice(0..3));
+    assert_eq!(
+        top_three.get(2).unwrap().total_saved,
+        (LEADERBOARD_SIZE as i128) * 1_000_000
+    );
+    assert!(client.get_leaderboard(&0).is_empty());
+
+    // Opting out removes the user from the leaderboard
+    let top = users.last().unwrap();
+    client.opt_out_stats(&top);
+    assert!(client.get_user_stats(&top).is_none());
+    assert!(client
+        .get_leaderboard(&LEADERBOARD_SIZE)
+        .iter()
+        .all(|entry| entry.user != top));
+}
+
+// ==================== Referral Tests ====================
+
+mod mock_referral {
+    use crate::referral::ReferralInterface;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Referral contract double that counts reward releases.
+    #[contract]
+    pub struct MockReferral;
+
+    #[contractimpl]
+    impl ReferralInterface for MockReferral {
+        fn release_reward(env: Env, _referrer: Address, _referee: Address, _goal_id: u64) {
+            let key = symbol_short!("released");
+            let count: u32 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(count + 1));
+        }
+    }
+}
+
+fn referral_release_count(env: &Env, referral_contract: &Address) -> u32 {
+    env.as_contract(referral_contract, || {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("released"))
+            .unwrap_or(0)
+    })
+}
+
+#[test]
+fn test_referral_qualifies_on_first_completed_goal_only() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let referral_contract = env.register(mock_referral::MockReferral, ());
+    client.set_referral_contract(&admin, &referral_contract);
+    client.register_referral(&user, &referrer);
+    assert_eq!(client.get_referrer(&user), Some(referrer.clone()));
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "first", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &user, "second", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    contribute(&client, &token, &user, &1, &50_000_000);
+    assert_eq!(referral_release_count(&env, &referral_contract), 0);
+    assert!(client.get_referral_qualified_goal(&user).is_none());
+
+    contribute(&client, &token, &user, &1, &40_000_000);
+    assert_eq!(referral_release_count(&env, &referral_contract), 1);
+    assert_eq!(client.get_referral_qualified_goal(&user), Some(1));
+
+    // Further contributions and later completed goals never release again
+    contribute(&client, &token, &user, &1, &10_000_000);
+    contribute(&client, &token, &user, &2, &90_000_000);
+    assert_eq!(referral_release_count(&env, &referral_contract), 1);
+    assert_eq!(client.get_referral_qualified_goal(&user), Some(1));
+}
+
+#[test]
+fn test_unreferred_user_never_qualifies() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let referral_contract = env.register(mock_referral::MockReferral, ());
+    client.set_referral_contract(&admin, &referral_contract);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    contribute(&client, &token, &user, &1, &90_000_000);
+    assert_eq!(referral_release_count(&env, &referral_contract), 0);
+    assert!(client.get_referral_qualified_goal(&user).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_register_referral_twice_fails() {
+    let (env, _admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    client.register_referral(&user, &Address::generate(&env));
+    client.register_referral(&user, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_self_referral_fails() {
+    let (env, _admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    client.register_referral(&user, &user);
+}
+
+// ==================== Funder Tests ====================
+
+#[test]
+fn test_fund_goal_by_allowed_funder() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let funder = Address::generate(&env);
+    client.set_goal_funder(&admin, &funder, &true);
+    assert!(client.is_goal_funder(&funder));
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let token = create_token(&env);
+    StellarAssetClient::new(&env, &token).mint(&client.address, &40_000_000);
+    assert_eq!(
+        client.fund_goal(&funder, &1, &token, &40_000_000),
+        50_000_000
+    );
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 50_000_000);
+    assert_eq!(client.get_goal_token(&1), Some(token));
+    assert_eq!(client.get_goal_escrow(&1), 40_000_000);
+
+    let triggered: Vec<u32> = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GoalMilestonesPercent(1))
+            .unwrap()
+    });
+    assert!(triggered.contains(50));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_fund_goal_by_revoked_funder_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let funder = Address::generate(&env);
+    client.set_goal_funder(&admin, &funder, &true);
+    client.set_goal_funder(&admin, &funder, &false);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    client.fund_goal(&funder, &1, &create_token(&env), &40_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_fund_goal_in_other_token_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let funder = Address::generate(&env);
+    client.set_goal_funder(&admin, &funder, &true);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    contribute(&client, &token, &user, &1, &10_000_000);
+
+    client.fund_goal(&funder, &1, &create_token(&env), &40_000_000);
+}
+
+// ==================== Insurance Tests ====================
+
+#[test]
+fn test_insurance_skims_contributions() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    client.set_insurance_bps(&admin, &100);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    // 1% of 40_000_000 goes to the fund
+    let goal = contribute(&client, &token, &user, &1, &40_000_000);
+    assert_eq!(goal.current_amount, 10_000_000 + 39_600_000);
+    assert_eq!(client.get_insurance_balance(&token), 400_000);
+
+    client.payout_insurance(&admin, &user, &token, &150_000, &symbol_short!("incident"));
+    assert_eq!(client.get_insurance_balance(&token), 250_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 150_000);
+
+    // Goals in another token fill a separate fund
+    let other_token = create_token(&env);
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "trip", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    contribute(&client, &other_token, &user, &2, &10_000_000);
+    assert_eq!(client.get_insurance_balance(&other_token), 100_000);
+    assert_eq!(client.get_insurance_balance(&token), 250_000);
+    assert_eq!(
+        client
+            .try_payout_insurance(
+                &admin,
+                &user,
+                &other_token,
+                &200_000,
+                &symbol_short!("incident"),
+            )
+            .unwrap_err(),
+        Ok(SavingsGoalError::InsufficientInsuranceFund.into())
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_insurance_payout_exceeding_fund_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    client.payout_insurance(
+        &admin,
+        &user,
+        &create_token(&env),
+        &1,
+        &symbol_short!("incident"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_insurance_rate_above_max_fails() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.set_insurance_bps(&admin, &(crate::types::MAX_INSURANCE_BPS + 1));
+}
+
+// ==================== Matching Campaign Tests ====================
+
+#[test]
+fn test_matching_campaign_matches_until_escrow_runs_out() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    // 1:1 up to 15_000_000
+    let ends_at = env.ledger().timestamp() + 1_000;
+    StellarAssetClient::new(&env, &token).mint(&sponsor, &15_000_000);
+    let campaign_id =
+        client.create_matching_campaign(&sponsor, &token, &10_000, &15_000_000, &ends_at);
+    client.tag_goal_campaign(&user, &1, &campaign_id);
+    assert_eq!(client.get_goal_campaign(&1), Some(campaign_id));
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&client.address),
+        15_000_000
+    );
+
+    let goal = contribute(&client, &token, &user, &1, &10_000_000);
+    // 10_000_000 initial + 10_000_000 contribution + 10_000_000 match
+    assert_eq!(goal.current_amount, 30_000_000);
+    assert_eq!(client.get_goal_escrow(&1), 20_000_000);
+
+    // Only 5_000_000 left in escrow
+    let goal = contribute(&client, &token, &user, &1, &10_000_000);
+    assert_eq!(goal.current_amount, 45_000_000);
+
+    let campaign = client.get_matching_campaign(&campaign_id).unwrap();
+    assert_eq!(campaign.remaining, 0);
+    assert_eq!(campaign.total_matched, 15_000_000);
+    assert_eq!(campaign.matched_contributions, 2);
+    assert_eq!(campaign.tagged_goals, 1);
+
+    // Escrow exhausted: no further match
+    let goal = contribute(&client, &token, &user, &1, &10_000_000);
+    assert_eq!(goal.current_amount, 55_000_000);
+}
+
+#[test]
+fn test_matching_campaign_stops_after_end_and_close() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let ends_at = env.ledger().timestamp() + 1_000;
+    StellarAssetClient::new(&env, &token).mint(&sponsor, &50_000_000);
+    let campaign_id =
+        client.create_matching_campaign(&sponsor, &token, &5_000, &50_000_000, &ends_at);
+    client.tag_goal_campaign(&user, &1, &campaign_id);
+
+    // 1:2 match while the campaign runs
+    let goal = contribute(&client, &token, &user, &1, &10_000_000);
+    assert_eq!(goal.current_amount, 25_000_000);
+
+    env.ledger().with_mut(|li| li.timestamp = ends_at);
+    let goal = contribute(&client, &token, &user, &1, &10_000_000);
+    assert_eq!(goal.current_amount, 35_000_000);
+
+    // Sponsor reclaims the unspent escrow
+    assert_eq!(
+        client.close_matching_campaign(&sponsor, &campaign_id),
+        45_000_000
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&sponsor), 45_000_000);
+    assert!(
+        !client
+            .get_matching_campaign(&campaign_id)
+            .unwrap()
+            .is_active
+    );
+}
+
+#[test]
+fn test_matches_stay_locked_until_goal_completes() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    let balances = TokenClient::new(&env, &token);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &user, "house", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let ends_at = env.ledger().timestamp() + 1_000;
+    StellarAssetClient::new(&env, &token).mint(&sponsor, &100_000_000);
+    let campaign_id =
+        client.create_matching_campaign(&sponsor, &token, &10_000, &100_000_000, &ends_at);
+    client.tag_goal_campaign(&user, &1, &campaign_id);
+    client.tag_goal_campaign(&user, &2, &campaign_id);
+
+    contribute(&client, &token, &user, &1, &20_000_000);
+    assert_eq!(client.get_goal_escrow(&1), 40_000_000);
+
+    // Only the owner's own tokens can be withdrawn early
+    assert_eq!(
+        client
+            .try_request_withdrawal(&user, &1, &20_000_001)
+            .unwrap_err(),
+        Ok(SavingsGoalError::InsufficientEscrow.into())
+    );
+
+    // An early withdrawal gives the match back to the campaign
+    client.request_withdrawal(&user, &1, &5_000_000);
+    assert_eq!(balances.balance(&user), 5_000_000);
+    assert_eq!(client.get_goal_escrow(&1), 15_000_000);
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 25_000_000);
+    let campaign = client.get_matching_campaign(&campaign_id).unwrap();
+    assert_eq!(campaign.remaining, 100_000_000);
+    assert_eq!(campaign.total_matched, 0);
+
+    // So does a cancellation
+    contribute(&client, &token, &user, &1, &10_000_000);
+    assert_eq!(client.cancel_goal(&user, &1), 25_000_000);
+    assert_eq!(balances.balance(&user), 30_000_000);
+    let campaign = client.get_matching_campaign(&campaign_id).unwrap();
+    assert_eq!(campaign.remaining, 100_000_000);
+
+    // Once the goal completes its matches can be withdrawn
+    contribute(&client, &token, &user, &2, &45_000_000);
+    assert_eq!(client.get_goal(&2).unwrap().current_amount, 100_000_000);
+    client.request_withdrawal(&user, &2, &90_000_000);
+    assert_eq!(balances.balance(&user), 120_000_000);
+    let campaign = client.get_matching_campaign(&campaign_id).unwrap();
+    assert_eq!(campaign.total_matched, 45_000_000);
+    assert_eq!(campaign.remaining, 55_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_tag_goal_with_closed_campaign_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let ends_at = env.ledger().timestamp() + 1_000;
+    StellarAssetClient::new(&env, &token).mint(&sponsor, &10_000_000);
+    let campaign_id =
+        client.create_matching_campaign(&sponsor, &token, &10_000, &10_000_000, &ends_at);
+    client.close_matching_campaign(&sponsor, &campaign_id);
+
+    client.tag_goal_campaign(&user, &1, &campaign_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_sponsor_cannot_tag_own_goal() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let sponsor = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &sponsor, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let ends_at = env.ledger().timestamp() + 1_000;
+    StellarAssetClient::new(&env, &token).mint(&sponsor, &10_000_000);
+    let campaign_id =
+        client.create_matching_campaign(&sponsor, &token, &10_000, &10_000_000, &ends_at);
+
+    client.tag_goal_campaign(&sponsor, &1, &campaign_id);
+}
+
+#[test]
+fn test_goal_limits_apply_to_new_goals() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    assert_eq!(client.get_goal_limits(), GoalLimits::default());
+
+    client.set_goal_limits(
+        &admin,
+        &GoalLimits {
+            min_target_amount: 1_000,
+            min_deadline_ledgers: 500,
+            max_deadline_ledgers: 2_000,
+        },
+    );
+
+    let mut requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    // Below the default minimum but above the configured one
+    requests.push_back(create_valid_request(&env, &user, "small", 5_000));
+    // Deadline (current + 1000) is inside the horizon, then too close
+    let mut too_close = create_valid_request(&env, &user, "soon", 5_000);
+    too_close.deadline = env.ledger().sequence() as u64 + 100;
+    requests.push_back(too_close);
+
+    let result = client.batch_set_savings_goals(&admin, &requests);
+    assert_eq!(result.successful, 1);
+    match &result.results.get(1).unwrap() {
+        GoalResult::Failure(_, error_code) => {
+            assert_eq!(*error_code, ErrorCode::INVALID_DEADLINE);
+        }
+        GoalResult::Success(_) => panic!("Expected failure"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_inconsistent_goal_limits_rejected() {
+    let (_env, admin, client) = setup_test_contract();
+
+    client.set_goal_limits(
+        &admin,
+        &GoalLimits {
+            min_target_amount: 1_000,
+            min_deadline_ledgers: 2_000,
+            max_deadline_ledgers: 1_000,
+        },
+    );
+}
+
+mod audit_mock {
+    use crate::AuditInterface;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Bytes, Env, Symbol};
+
+    /// Audit double that counts the entries it receives.
+    #[contract]
+    pub struct RecordingAudit;
+
+    #[contractimpl]
+    impl AuditInterface for RecordingAudit {
+        fn log_audit(
+            env: Env,
+            _actor: Address,
+            _operation: Symbol,
+            _status: Symbol,
+            _metadata: Option<Bytes>,
+        ) {
+            let key = symbol_short!("entries");
+            let entries: u32 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(entries + 1));
+        }
+    }
+
+    #[contractimpl]
+    impl RecordingAudit {
+        pub fn entries(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("entries"))
+                .unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_admin_marks_milestones_on_behalf_of_owner() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let audit_id = env.register(audit_mock::RecordingAudit, ());
+    client.set_audit_contract(&admin, &audit_id);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    set_goal_current_amount(&env, &client, 1, 50_000_000);
+
+    let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    for percentage in [25u32, 50] {
+        milestone_requests.push_back(MilestoneAchievementRequest {
+            goal_id: 1,
+            user: user.clone(),
+            milestone_percentage: percentage,
+            achieved_at: env.ledger().sequence() as u64,
+        });
+    }
+
+    let result = client.admin_batch_mark_milestones(&admin, &milestone_requests);
+
+    assert_eq!(result.successful, 2);
+    match result.results.get(0).unwrap() {
+        MilestoneResult::Success(achievement) => {
+            assert_eq!(achievement.user, user);
+            assert_eq!(achievement.marked_by, Some(admin.clone()));
+        }
+        MilestoneResult::Failure(_, _) => panic!("Expected success"),
+    }
+    assert_eq!(
+        audit_mock::RecordingAuditClient::new(&env, &audit_id).entries(),
+        2
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_admin_batch_mark_milestones_requires_admin() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    set_goal_current_amount(&env, &client, 1, 50_000_000);
+
+    let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    milestone_requests.push_back(MilestoneAchievementRequest {
+        goal_id: 1,
+        user: user.clone(),
+        milestone_percentage: 25,
+        achieved_at: env.ledger().sequence() as u64,
+    });
+
+    client.admin_batch_mark_milestones(&user, &milestone_requests);
+}
+
+#[test]
+fn test_repair_goal_milestones_reconciles_state() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "savings", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+    set_goal_current_amount(&env, &client, 1, 25_000_000);
+
+    let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    milestone_requests.push_back(MilestoneAchievementRequest {
+        goal_id: 1,
+        user: user.clone(),
+        milestone_percentage: 25,
+        achieved_at: env.ledger().sequence() as u64,
+    });
+    client.batch_mark_milestones(&user, &milestone_requests);
+
+    // Simulate a bad state: 100% marked without being reached, 50% reached but unmarked
+    set_goal_current_amount(&env, &client, 1, 60_000_000);
+    env.as_contract(&client.address, || {
+        let mut triggered: Vec<u32> = Vec::new(&env);
+        triggered.push_back(25);
+        triggered.push_back(100);
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalMilestonesPercent(1), &triggered);
+    });
+
+    let result = client.repair_goal_milestones(&admin, &1);
+    assert_eq!(result.added, soroban_sdk::vec![&env, 50u32]);
+    assert_eq!(result.removed, soroban_sdk::vec![&env, 100u32]);
+    assert_eq!(result.created_milestone_ids, soroban_sdk::vec![&env, 2u64]);
+
+    let repaired = client.get_milestone(&2).unwrap();
+    assert_eq!(repaired.milestone_percentage, 50);
+    assert_eq!(repaired.marked_by, Some(admin.clone()));
+    assert_eq!(client.get_total_milestones_achieved(), 2);
+    env.as_contract(&client.address, || {
+        let triggered: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GoalMilestonesPercent(1))
+            .unwrap();
+        assert_eq!(triggered, soroban_sdk::vec![&env, 25u32, 50u32]);
+    });
+
+    // Repairing a consistent goal changes nothing
+    let result = client.repair_goal_milestones(&admin, &1);
+    assert!(result.added.is_empty());
+    assert!(result.removed.is_empty());
+    assert!(result.created_milestone_ids.is_empty());
+}
+
+// ==================== Fiat Peg Tests ====================
+
+mod mock_oracle {
+    use crate::peg::RateOracleInterface;
+    use soroban_sdk::{contract, contractimpl, Env, Symbol};
+
+    /// Rate oracle double quoting a single settable rate for every currency.
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_rate(env: Env, rate: i128) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "rate"), &rate);
+        }
+    }
+
+    #[contractimpl]
+    impl RateOracleInterface for MockOracle {
+        fn rate(env: Env, _currency: Symbol) -> i128 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "rate"))
+                .unwrap()
+        }
+    }
+}
+
+#[test]
+fn test_pegged_goal_revalued_with_oracle_rate() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let oracle = env.register(mock_oracle::MockOracle, ());
+    let oracle_client = mock_oracle::MockOracleClient::new(&env, &oracle);
+    oracle_client.set_rate(&RATE_SCALE);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "laptop", 300_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let usd = symbol_short!("USD");
+    assert_eq!(
+        client
+            .try_set_goal_peg(&user, &1, &usd, &Some(100_000_000))
+            .err()
+            .unwrap()
+            .unwrap(),
+        soroban_sdk::Error::from_contract_error(SavingsGoalError::OracleUnavailable as u32)
+    );
+
+    client.set_rate_oracle(&admin, &Some(oracle.clone()));
+    client.set_goal_peg(&user, &1, &usd, &Some(100_000_000));
+    assert_eq!(client.get_goal(&1).unwrap().target_amount, 100_000_000);
+    // 30M initial contribution + 10M = 40% saved
+    contribute(&client, &token, &user, &1, &10_000_000);
+
+    // Unchanged rate: nothing to revalue
+    let mut goal_ids: Vec<u64> = Vec::new(&env);
+    goal_ids.push_back(1);
+    assert_eq!(client.revalue_goals(&admin, &goal_ids), 0);
+
+    // The token doubles in value, halving the token target to 50M (80% saved)
+    oracle_client.set_rate(&(RATE_SCALE * 2));
+    let stranger = Address::generate(&env);
+    assert_eq!(client.revalue_goals(&stranger, &goal_ids), 0);
+    assert_eq!(client.revalue_goals(&user, &goal_ids), 1);
+
+    assert_eq!(client.get_goal(&1).unwrap().target_amount, 50_000_000);
+    let peg = client.get_goal_peg(&1).unwrap();
+    assert_eq!(peg.rate, RATE_SCALE * 2);
+    assert_eq!(peg.fiat_target, 100_000_000);
+    let triggered: Vec<u32> = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GoalMilestonesPercent(1))
+            .unwrap()
+    });
+    assert_eq!(triggered, Vec::from_array(&env, [25u32, 50, 75]));
+
+    client.set_goal_peg(&user, &1, &usd, &None);
+    assert!(client.get_goal_peg(&1).is_none());
+    assert_eq!(client.revalue_goals(&admin, &goal_ids), 0);
+}
+
+// ==================== Milestone Reward Tests ====================
+
+mod mock_reward_token {
+    use crate::rewards::RewardTokenInterface;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, U256};
+
+    /// Reward token double that tracks minted balances and can be made to fail.
+    #[contract]
+    pub struct MockRewardToken;
+
+    #[contractimpl]
+    impl MockRewardToken {
+        pub fn set_failing(env: Env, failing: bool) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("failing"), &failing);
+        }
+
+        pub fn balance(env: Env, owner: Address) -> i128 {
+            env.storage().persistent().get(&owner).unwrap_or(0)
+        }
+    }
+
+    #[contractimpl]
+    impl RewardTokenInterface for MockRewardToken {
+        fn mint(env: Env, minter: Address, to: Address, amount: i128) -> U256 {
+            minter.require_auth();
+            if env
+                .storage()
+                .instance()
+                .get(&symbol_short!("failing"))
+                .unwrap_or(false)
+            {
+                panic!("mint cap exceeded");
+            }
+            let balance = Self::balance(env.clone(), to.clone()) + amount;
+            env.storage().persistent().set(&to, &balance);
+            U256::from_u32(&env, 0)
+        }
+    }
+}
+
+#[test]
+fn test_milestones_mint_rewards_with_failure_isolation() {
+    let (env, admin, client) = setup_test_contract();
+    let token = create_token(&env);
+    let user = Address::generate(&env);
+    let reward_token = env.register(mock_reward_token::MockRewardToken, ());
+    let reward_client = mock_reward_token::MockRewardTokenClient::new(&env, &reward_token);
+
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &user, "car", 100_000_000));
+    goal_requests.push_back(create_valid_request(&env, &user, "bike", 100_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    client.set_reward_config(&admin, &reward_token, &5_000_000);
+    assert_eq!(
+        client.get_reward_config().unwrap().reward_per_milestone,
+        5_000_000
+    );
+
+    // Contributions crossing 25% and 50% mint both rewards
+    contribute(&client, &token, &user, &1, &40_000_000);
+    assert_eq!(reward_client.balance(&user), 10_000_000);
+
+    // Marking the same milestones in a batch rewards nothing again
+    let mut milestone_requests: Vec<MilestoneAchievementRequest> = Vec::new(&env);
+    milestone_requests.push_back(MilestoneAchievementRequest {
+        goal_id: 1,
+        user: user.clone(),
+        milestone_percentage: 25,
+        achieved_at: env.ledger().sequence() as u64,
+    });
+    let result = client.batch_mark_milestones(&user, &milestone_requests);
+    assert_eq!(result.failed, 1);
+    assert_eq!(reward_client.balance(&user), 10_000_000);
+
+    // A failing mint does not fail the contribution
+    reward_client.set_failing(&true);
+    let goal = contribute(&client, &token, &user, &2, &15_000_000);
+    assert_eq!(goal.current_amount, 25_000_000);
+    assert_eq!(reward_client.balance(&user), 10_000_000);
+
+    client.set_reward_config(&admin, &reward_token, &0);
+    assert!(client.get_reward_config().is_none());
+}
+
+// ==================== Migration Tests ====================
+
+mod mock_legacy {
+    use crate::migration::LegacyGoalsInterface;
+    use crate::types::LegacyGoal;
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    /// Previous deployment double serving stored legacy goals.
+    #[contract]
+    pub struct MockLegacyGoals;
+
+    #[contractimpl]
+    impl MockLegacyGoals {
+        pub fn put_goal(env: Env, goal: LegacyGoal) {
+            env.storage().instance().set(&goal.goal_id, &goal);
+        }
+    }
+
+    #[contractimpl]
+    impl LegacyGoalsInterface for MockLegacyGoals {
+        fn get_goal(env: Env, goal_id: u64) -> Option<LegacyGoal> {
+            env.storage().instance().get(&goal_id)
+        }
+    }
+}
+
+#[test]
+fn test_import_goals_from_legacy_contract() {
+    let (env, admin, client) = setup_test_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let legacy = env.register(mock_legacy::MockLegacyGoals, ());
+    let legacy_client = mock_legacy::MockLegacyGoalsClient::new(&env, &legacy);
+
+    for (goal_id, user, current_amount) in [(1u64, &alice, 40_000_000i128), (5, &bob, 0)] {
+        legacy_client.put_goal(&LegacyGoal {
+            goal_id,
+            user: user.clone(),
+            goal_name: symbol_short!("legacy"),
+            target_amount: 100_000_000,
+            current_amount,
+            deadline: 1_000_000,
+            created_at: 7,
+            is_active: true,
+        });
+    }
+
+    // Goal 1 is already taken here
+    let mut goal_requests: Vec<SavingsGoalRequest> = Vec::new(&env);
+    goal_requests.push_back(create_valid_request(&env, &bob, "house", 500_000_000));
+    client.batch_set_savings_goals(&admin, &goal_requests);
+
+    let mut goal_ids: Vec<u64> = Vec::new(&env);
+    for goal_id in [1u64, 5, 7] {
+        goal_ids.push_back(goal_id);
+    }
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_import_goals(&stranger, &legacy, &goal_ids),
+        Err(Ok(SavingsGoalError::Unauthorized.into()))
+    );
+
+    let result = client.import_goals(&admin, &legacy, &goal_ids);
+    assert_eq!(
+        result,
+        GoalImportResult {
+            imported: 2,
+            already_imported: 0,
+            missing: 1,
+        }
+    );
+
+    // Legacy goal 1 moves to the next free ID, goal 5 keeps its ID
+    assert_eq!(client.get_imported_goal_id(&legacy, &1), Some(2));
+    assert_eq!(client.get_imported_goal_id(&legacy, &5), Some(5));
+    assert_eq!(client.get_imported_goal_id(&legacy, &7), None);
+
+    let goal = client.get_goal(&2).unwrap();
+    assert_eq!(goal.user, alice);
+    assert_eq!(goal.current_amount, 40_000_000);
+    assert_eq!(goal.created_at, 7);
+    assert_eq!(goal.category, None);
+    assert_eq!(client.get_user_goals(&alice), Vec::from_array(&env, [2]));
+    assert_eq!(client.get_user_goals(&bob), Vec::from_array(&env, [1, 5]));
+    assert_eq!(
+        client.get_user_savings_summary(&alice).total_saved,
+        40_000_000
+    );
+
+    // Re-running the import skips goals already brought over
+    let result = client.import_goals(&admin, &legacy, &goal_ids);
+    assert_eq!(result.imported, 0);
+    assert_eq!(result.already_imported, 2);
+    assert_eq!(client.get_user_goals(&alice).len(), 1);
+
+    // New goals are numbered after the imported ones
+    let result = client.batch_set_savings_goals(&admin, &goal_requests);
+    match result.results.get(0).unwrap() {
+        GoalResult::Success(goal) => assert_eq!(goal.goal_id, 6),
+        GoalResult::Failure(_, _) => panic!("Expected goal creation to succeed"),
+    }
+}
@@ -1,16 +1,46 @@
 //! Data types and events for batch savings goal operations.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, Symbol, Vec};
 
 /// Maximum number of user-goal pairs in a single batch for optimization.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
-/// Minimum goal amount (1 XLM in stroops)
+/// Default minimum goal amount (1 XLM in stroops)
 pub const MIN_GOAL_AMOUNT: i128 = 10_000_000;
 
 /// Maximum goal amount (1 billion XLM in stroops)
 pub const MAX_GOAL_AMOUNT: i128 = 1_000_000_000_000_000_000;
 
+/// Default furthest a deadline may be set ahead of the current ledger (~5 years)
+pub const DEFAULT_MAX_DEADLINE_LEDGERS: u64 = 31_536_000;
+
+/// Maximum size of a goal's app-defined metadata, in bytes
+pub const MAX_GOAL_METADATA_BYTES: u32 = 256;
+
+/// Number of entries kept on the savings leaderboard.
+pub const LEADERBOARD_SIZE: u32 = 10;
+
+/// Seconds in a week, used for contribution streaks.
+pub const SECONDS_PER_WEEK: u64 = 604_800;
+
+/// Longest vacation window a user can set, in weeks.
+pub const MAX_VACATION_WEEKS: u64 = 8;
+
+/// Seconds in a (30-day) stats month, used for monthly milestone counts.
+pub const SECONDS_PER_MONTH: u64 = 2_592_000;
+
+/// Basis-point denominator (100% = 10_000 bps)
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Maximum share of a contribution skimmed into the insurance fund (5%)
+pub const MAX_INSURANCE_BPS: u32 = 500;
+
+/// Maximum match rate for a matching campaign (10:1)
+pub const MAX_MATCH_BPS: u32 = 100_000;
+
+/// Fixed-point scale of oracle rates (1 token unit = rate / RATE_SCALE fiat units)
+pub const RATE_SCALE: i128 = 10_000_000;
+
 /// Represents a savings goal request for a user.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -25,6 +55,10 @@ pub struct SavingsGoalRequest {
     pub deadline: u64,
     /// Initial contribution amount (optional, can be 0)
     pub initial_contribution: i128,
+    /// Category the goal is grouped under (e.g. "travel", "emergency")
+    pub category: Option<Symbol>,
+    /// App-defined metadata, at most MAX_GOAL_METADATA_BYTES (empty for none)
+    pub metadata: Bytes,
 }
 
 /// Represents a created savings goal.
@@ -47,11 +81,42 @@ pub struct SavingsGoal {
     pub created_at: u64,
     /// Whether the goal is active
     pub is_active: bool,
+    /// Category the goal is grouped under
+    pub category: Option<Symbol>,
+    /// App-defined metadata (empty for none)
+    pub metadata: Bytes,
+}
+
+/// A goal as stored by the previous deployment, read during imports.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LegacyGoal {
+    pub goal_id: u64,
+    pub user: Address,
+    pub goal_name: Symbol,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub deadline: u64,
+    pub created_at: u64,
+    pub is_active: bool,
+}
+
+/// Outcome of importing a list of goals from a previous deployment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct GoalImportResult {
+    /// Goals recreated in this contract
+    pub imported: u32,
+    /// Goals skipped because an earlier import brought them over
+    pub already_imported: u32,
+    /// Goals the source contract could not return
+    pub missing: u32,
 }
 
 /// Result of processing a single goal creation.
 #[derive(Clone, Debug)]
 #[contracttype]
+#[allow(clippy::large_enum_variant)]
 pub enum GoalResult {
     Success(SavingsGoal),
     Failure(Address, u32), // user address, error code
@@ -95,6 +160,77 @@ pub struct BatchGoalResult {
     pub metrics: BatchGoalMetrics,
 }
 
+/// Changes requested to an existing goal; `None` fields are left unchanged.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct GoalUpdateRequest {
+    /// Goal ID to update
+    pub goal_id: u64,
+    /// New target amount (in stroops)
+    pub target_amount: Option<i128>,
+    /// New deadline (ledger sequence number)
+    pub deadline: Option<u64>,
+    /// New goal name/description
+    pub goal_name: Option<Symbol>,
+    /// New category
+    pub category: Option<Symbol>,
+    /// New metadata, at most MAX_GOAL_METADATA_BYTES
+    pub metadata: Option<Bytes>,
+}
+
+/// Result of processing a single goal update.
+#[derive(Clone, Debug)]
+#[contracttype]
+#[allow(clippy::large_enum_variant)]
+pub enum GoalUpdateResult {
+    Success(SavingsGoal),
+    Failure(u64, u32), // goal_id, error_code
+}
+
+/// Result of a batch goal update.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchGoalUpdateResult {
+    /// Batch ID
+    pub batch_id: u64,
+    /// Total number of requests
+    pub total_requests: u32,
+    /// Number of goals updated
+    pub successful: u32,
+    /// Number of failed updates
+    pub failed: u32,
+    /// Individual update results
+    pub results: Vec<GoalUpdateResult>,
+}
+
+/// Result of cancelling a single goal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum GoalCancelResult {
+    Success(u64, i128), // goal_id, refunded amount
+    Failure(u64, u32),  // goal_id, error_code
+}
+
+/// Outcome recorded when a goal expires at its deadline.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct GoalExpiry {
+    /// Ledger sequence at which the goal was swept
+    pub expired_at: u32,
+    /// Whether the saved amount reached the target
+    pub target_met: bool,
+    /// Saved amount when the goal expired (in stroops)
+    pub final_amount: i128,
+}
+
+/// Result of processing a single goal for expiry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum GoalExpiryResult {
+    Expired(u64, bool), // goal_id, target met
+    Failure(u64, u32),  // goal_id, error_code
+}
+
 /// Represents a milestone achievement request for a goal.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -125,6 +261,8 @@ pub struct MilestoneAchievement {
     pub goal_amount_at_achievement: i128,
     /// Ledger sequence when milestone was achieved
     pub achieved_at: u64,
+    /// Admin who marked the milestone on the owner's behalf, if any
+    pub marked_by: Option<Address>,
 }
 
 /// Result of processing a single milestone achievement.
@@ -171,6 +309,238 @@ pub struct BatchMilestoneResult {
     pub metrics: BatchMilestoneMetrics,
 }
 
+/// Outcome of reconciling a goal's milestone state with its current amount.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MilestoneRepairResult {
+    /// Goal ID
+    pub goal_id: u64,
+    /// Thresholds added to the goal's marked percentages
+    pub added: Vec<u32>,
+    /// Thresholds removed because the goal no longer reaches them
+    pub removed: Vec<u32>,
+    /// IDs of the achievement records created for thresholds that had none
+    pub created_milestone_ids: Vec<u64>,
+}
+
+/// Running counters of goal and milestone storage writes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct StorageStats {
+    /// Goal records written (created or updated)
+    pub goal_writes: u64,
+    /// Milestone achievement records written
+    pub milestone_writes: u64,
+    /// Approximate bytes written across all goal and milestone writes
+    pub approx_bytes_written: u64,
+    /// Entries whose TTL was extended by maintenance runs
+    pub entries_extended: u64,
+    /// Ledger sequence of the last maintenance run (0 if none)
+    pub last_maintenance_ledger: u32,
+}
+
+/// Totals over all of a user's goals, maintained on every goal write.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct UserSavingsSummary {
+    /// Number of goals the user owns
+    pub total_goals: u32,
+    /// Goals that are still active
+    pub active_goals: u32,
+    /// Goals whose saved amount has reached the target
+    pub completed_goals: u32,
+    /// Sum of goal targets (in stroops)
+    pub total_target: i128,
+    /// Sum of saved amounts (in stroops)
+    pub total_saved: i128,
+    /// Total saved as a percentage of the total target
+    pub progress_percent: u32,
+}
+
+/// Guardian that must co-sign withdrawals once an owner's withdrawals within
+/// a rolling window exceed a threshold.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct WithdrawalGuard {
+    /// Address that approves large withdrawals
+    pub guardian: Address,
+    /// Most an owner may withdraw (in stroops) without approval per window
+    pub threshold: i128,
+    /// Ledgers the guardian has to approve a pending withdrawal
+    pub approval_ledgers: u32,
+    /// Length in ledgers of the rolling window unapproved withdrawals are
+    /// summed over
+    pub window_ledgers: u32,
+}
+
+/// Withdrawal waiting for the guardian's approval.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct WithdrawalRequest {
+    pub request_id: u64,
+    pub goal_id: u64,
+    /// Goal owner receiving the tokens
+    pub owner: Address,
+    /// Amount to withdraw (in stroops)
+    pub amount: i128,
+    /// Ledger sequence the request was made at
+    pub requested_at: u32,
+    /// Last ledger sequence the request can be approved at
+    pub expires_at: u32,
+}
+
+/// Result of a withdrawal request.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum WithdrawalOutcome {
+    /// Withdrawn immediately (amount)
+    Executed(i128),
+    /// Held for guardian approval (request_id)
+    Pending(u64),
+}
+
+/// Entries touched by a single maintenance run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MaintenanceReport {
+    /// Goals whose TTL was extended
+    pub goals_touched: u32,
+    /// Milestone achievement records whose TTL was extended
+    pub milestones_touched: u32,
+    /// All entries extended, including per-goal milestone indexes
+    pub entries_touched: u32,
+    /// Approximate size of the extended entries
+    pub approx_bytes: u64,
+}
+
+/// Opt-in gamification stats for a user.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct UserStats {
+    /// User's address
+    pub user: Address,
+    /// Total contributed across all goals since opting in (in stroops)
+    pub total_saved: i128,
+    /// Consecutive weeks with at least one contribution
+    pub streak_weeks: u32,
+    /// Week index (timestamp / week) of the last contribution
+    pub last_contribution_week: u64,
+    /// Milestones reached during `stats_month`
+    pub milestones_this_month: u32,
+    /// Month index (timestamp / 30 days) that `milestones_this_month` refers to
+    pub stats_month: u64,
+    /// Missed weeks bridged by a vacation window without breaking the streak
+    pub vacation_weeks: u32,
+}
+
+/// Single-use invitation to contribute to a goal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ClaimCode {
+    /// Goal the code grants access to
+    pub goal_id: u64,
+    /// Goal owner who created the code
+    pub owner: Address,
+    /// Only address allowed to redeem the code; anyone holding it if `None`
+    pub invitee: Option<Address>,
+    /// Ledger timestamp from which the code can no longer be redeemed
+    pub expires_at: u64,
+}
+
+/// Fiat amount a goal's token target is derived from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FiatPeg {
+    /// Fiat currency code quoted by the rate oracle (e.g. "USD")
+    pub currency: Symbol,
+    /// Target in fiat units
+    pub fiat_target: i128,
+    /// Oracle rate the current token target was derived from
+    pub rate: i128,
+    /// Ledger timestamp of the last revaluation
+    pub revalued_at: u64,
+}
+
+/// Period during which missed contributions don't break a user's streak.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VacationWindow {
+    /// Start of the window (ledger timestamp)
+    pub start: u64,
+    /// End of the window, exclusive (ledger timestamp)
+    pub end: u64,
+}
+
+/// A single leaderboard position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LeaderboardEntry {
+    /// User's address
+    pub user: Address,
+    /// Total saved by the user
+    pub total_saved: i128,
+}
+
+/// A sponsor's donation matching campaign.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MatchingCampaign {
+    /// Unique campaign ID
+    pub campaign_id: u64,
+    /// Address that committed the matching funds
+    pub sponsor: Address,
+    /// Token the matching funds are held and paid in
+    pub token: Address,
+    /// Match paid per contribution, in bps of the contribution (10_000 = 1:1)
+    pub match_bps: u32,
+    /// Matching funds committed by the sponsor; caps the total matched
+    pub committed: i128,
+    /// Matching funds still held in escrow
+    pub remaining: i128,
+    /// Total matched into goals so far
+    pub total_matched: i128,
+    /// Number of contributions that received a match
+    pub matched_contributions: u32,
+    /// Number of goals tagged with the campaign
+    pub tagged_goals: u32,
+    /// Timestamp from which contributions are no longer matched
+    pub ends_at: u64,
+    /// False once the sponsor has closed the campaign
+    pub is_active: bool,
+}
+
+/// Reward minted to the goal owner for each achieved milestone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RewardConfig {
+    /// Token contract minting the rewards; this contract must be a minter
+    pub token: Address,
+    /// Amount minted per achieved milestone
+    pub reward_per_milestone: i128,
+}
+
+/// Validation limits for new goals, tunable by the admin per deployment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct GoalLimits {
+    /// Smallest accepted target amount (capped by MAX_GOAL_AMOUNT)
+    pub min_target_amount: i128,
+    /// Closest a deadline may be to the current ledger, in ledgers
+    pub min_deadline_ledgers: u64,
+    /// Furthest a deadline may be from the current ledger, in ledgers
+    pub max_deadline_ledgers: u64,
+}
+
+impl Default for GoalLimits {
+    fn default() -> Self {
+        Self {
+            min_target_amount: MIN_GOAL_AMOUNT,
+            min_deadline_ledgers: 1,
+            max_deadline_ledgers: DEFAULT_MAX_DEADLINE_LEDGERS,
+        }
+    }
+}
+
 /// Storage keys for contract state.
 #[derive(Clone)]
 #[contracttype]
@@ -199,6 +569,72 @@ pub enum DataKey {
     GoalMilestonesPercent(u64),
     /// Total milestones achieved lifetime
     TotalMilestonesAchieved,
+    /// Gamification stats for opted-in users
+    UserStats(Address),
+    /// Top savers ordered by total saved (bounded by LEADERBOARD_SIZE)
+    Leaderboard,
+    /// Referral contract notified when referred users complete a goal
+    ReferralContract,
+    /// Who referred a user (user address -> referrer address)
+    Referrer(Address),
+    /// Goal ID that qualified a referred user (set once)
+    ReferralQualified(Address),
+    /// Contracts allowed to fund goals on behalf of their owners (e.g. matching programs)
+    GoalFunder(Address),
+    /// Share of each contribution skimmed into the insurance fund, in bps
+    InsuranceBps,
+    /// Insurance fund balance per token
+    InsuranceBalance(Address),
+    /// Ledgers a goal must wait between milestone claim batches (0 = disabled)
+    MilestoneCooldownLedgers,
+    /// Ledger at which a goal's milestone claim cooldown ends (temporary storage)
+    MilestoneCooldown(u64),
+    /// Last created matching campaign ID
+    LastCampaignId,
+    /// Stored matching campaign by campaign_id
+    Campaign(u64),
+    /// Matching campaign a goal is tagged with (goal_id -> campaign_id)
+    GoalCampaign(u64),
+    /// Campaign matches credited to a goal, locked until it completes
+    GoalMatched(u64),
+    /// Milestone percentages of a goal whose reward has been minted
+    GoalMilestonesRewarded(u64),
+    /// Admin-set goal validation limits (defaults apply when unset)
+    GoalLimits,
+    /// Audit contract that records admin milestone corrections
+    AuditContract,
+    /// User's vacation window, pausing streak checks
+    Vacation(Address),
+    /// Unused claim code by the SHA-256 hash of the code
+    ClaimCode(BytesN<32>),
+    /// Set when an address joined a goal as contributor (goal_id, address)
+    GoalContributor(u64, Address),
+    /// Token a goal's contributions are held in, fixed by its first contribution
+    GoalToken(u64),
+    /// Oracle adapter quoting fiat rates for pegged goals
+    RateOracle,
+    /// Fiat peg of a goal's target (goal_id -> FiatPeg)
+    GoalPeg(u64),
+    /// Token contributions held for a goal, refunded if it is cancelled
+    GoalEscrow(u64),
+    /// Outcome of a goal swept after its deadline (goal_id -> GoalExpiry)
+    GoalExpiry(u64),
+    /// Running storage write counters (StorageStats)
+    StorageStats,
+    /// Token and amount minted to goal owners per achieved milestone
+    RewardConfig,
+    /// Running totals over a user's goals (UserSavingsSummary)
+    UserSummary(Address),
+    /// Guardian co-signing large withdrawals (WithdrawalGuard)
+    WithdrawalGuard,
+    /// Last created withdrawal request ID
+    LastWithdrawalId,
+    /// Pending withdrawal by request_id
+    Withdrawal(u64),
+    /// Owner's unapproved withdrawals as (ledger, amount), within the guard's window
+    RecentWithdrawals(Address),
+    /// Goal ID an imported goal was given (source contract, legacy goal_id)
+    ImportedGoal(Address, u64),
 }
 
 /// Error codes for goal validation and creation.
@@ -225,6 +661,19 @@ pub mod ErrorCode {
     pub const UNAUTHORIZED_USER: u32 = 8;
     /// Goal has already achieved this milestone
     pub const MILESTONE_ALREADY_ACHIEVED: u32 = 9;
+    /// Goal is cooling down after a recent milestone claim batch
+    pub const COOLDOWN_ACTIVE: u32 = 11;
+    /// Update request leaves every field unchanged
+    pub const NO_CHANGES: u32 = 12;
+    /// Goal deadline has not passed yet
+    pub const DEADLINE_NOT_REACHED: u32 = 13;
+    /// New owner is the goal's current owner
+    pub const INVALID_NEW_OWNER: u32 = 14;
+    /// Goal metadata exceeds MAX_GOAL_METADATA_BYTES
+    pub const INVALID_METADATA: u32 = 15;
+    /// Refund or transfer of the goal's escrow needs the withdrawal
+    /// guardian's co-signature
+    pub const GUARDIAN_APPROVAL_REQUIRED: u32 = 16;
 }
 
 /// Events emitted by the savings goals contract.
@@ -265,12 +714,419 @@ impl GoalEvents {
             .publish(topics, (successful, failed, total_amount));
     }
 
+    /// Event emitted when an existing goal is updated.
+    pub fn goal_updated(env: &Env, batch_id: u64, goal: &SavingsGoal) {
+        let topics = (symbol_short!("goal"), symbol_short!("updated"), batch_id);
+        env.events().publish(
+            topics,
+            (
+                goal.goal_id,
+                goal.target_amount,
+                goal.deadline,
+                goal.goal_name.clone(),
+            ),
+        );
+    }
+
+    /// Event emitted when a goal update fails.
+    pub fn goal_update_failed(env: &Env, batch_id: u64, goal_id: u64, error_code: u32) {
+        let topics = (symbol_short!("goal"), symbol_short!("updfailed"), batch_id);
+        env.events().publish(topics, (goal_id, error_code));
+    }
+
+    /// Event emitted when a batch goal update completes.
+    pub fn update_batch_completed(env: &Env, batch_id: u64, successful: u32, failed: u32) {
+        let topics = (
+            symbol_short!("update"),
+            symbol_short!("completed"),
+            batch_id,
+        );
+        env.events().publish(topics, (successful, failed));
+    }
+
     /// Event emitted for high-value goals (>= 10,000 XLM).
     pub fn high_value_goal(env: &Env, batch_id: u64, goal_id: u64, amount: i128) {
         let topics = (symbol_short!("goal"), symbol_short!("highval"), batch_id);
         env.events().publish(topics, (goal_id, amount));
     }
 
+    /// Event emitted when the goal owner contributes to a goal.
+    pub fn contribution_made(
+        env: &Env,
+        goal_id: u64,
+        user: &Address,
+        amount: i128,
+        new_amount: i128,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("contrib"), goal_id);
+        env.events()
+            .publish(topics, (user.clone(), amount, new_amount));
+    }
+
+    /// Event emitted when an authorized funder credits a goal.
+    pub fn goal_funded(env: &Env, goal_id: u64, funder: &Address, amount: i128, new_amount: i128) {
+        let topics = (symbol_short!("goal"), symbol_short!("funded"), goal_id);
+        env.events()
+            .publish(topics, (funder.clone(), amount, new_amount));
+    }
+
+    /// Event emitted when a contribution premium is added to the insurance fund.
+    pub fn insurance_collected(
+        env: &Env,
+        goal_id: u64,
+        token: &Address,
+        premium: i128,
+        fund_balance: i128,
+    ) {
+        let topics = (symbol_short!("insure"), symbol_short!("collect"), goal_id);
+        env.events()
+            .publish(topics, (token.clone(), premium, fund_balance));
+    }
+
+    /// Event emitted when a sponsor opens a matching campaign.
+    pub fn campaign_created(env: &Env, campaign: &MatchingCampaign) {
+        let topics = (
+            symbol_short!("campaign"),
+            symbol_short!("created"),
+            campaign.campaign_id,
+        );
+        env.events().publish(
+            topics,
+            (
+                campaign.sponsor.clone(),
+                campaign.match_bps,
+                campaign.committed,
+                campaign.ends_at,
+            ),
+        );
+    }
+
+    /// Event emitted when a goal is tagged with a matching campaign.
+    pub fn campaign_goal_tagged(env: &Env, campaign_id: u64, goal_id: u64) {
+        let topics = (
+            symbol_short!("campaign"),
+            symbol_short!("tagged"),
+            campaign_id,
+        );
+        env.events().publish(topics, goal_id);
+    }
+
+    /// Event emitted when a campaign matches a contribution.
+    pub fn contribution_matched(
+        env: &Env,
+        goal_id: u64,
+        campaign_id: u64,
+        amount: i128,
+        new_amount: i128,
+    ) {
+        let topics = (symbol_short!("campaign"), symbol_short!("matched"), goal_id);
+        env.events()
+            .publish(topics, (campaign_id, amount, new_amount));
+    }
+
+    /// Event emitted when a goal's locked matches go back to the campaign
+    /// because funds left the goal before it completed.
+    pub fn campaign_match_returned(env: &Env, goal_id: u64, campaign_id: u64, amount: i128) {
+        let topics = (
+            symbol_short!("campaign"),
+            symbol_short!("returned"),
+            goal_id,
+        );
+        env.events().publish(topics, (campaign_id, amount));
+    }
+
+    /// Event emitted when a sponsor closes a campaign and reclaims unspent escrow.
+    pub fn campaign_closed(env: &Env, campaign_id: u64, sponsor: &Address, refund: i128) {
+        let topics = (
+            symbol_short!("campaign"),
+            symbol_short!("closed"),
+            campaign_id,
+        );
+        env.events().publish(topics, (sponsor.clone(), refund));
+    }
+
+    /// Event emitted when the goal validation limits change.
+    pub fn goal_limits_updated(env: &Env, admin: &Address, limits: &GoalLimits) {
+        let topics = (symbol_short!("goal"), symbol_short!("limits"));
+        env.events()
+            .publish(topics, (admin.clone(), limits.clone()));
+    }
+
+    /// Event emitted when the milestone claim cooldown window changes.
+    pub fn milestone_cooldown_updated(env: &Env, admin: &Address, ledgers: u32) {
+        let topics = (symbol_short!("milestone"), symbol_short!("cooldown"));
+        env.events().publish(topics, (admin.clone(), ledgers));
+    }
+
+    /// Event emitted when the insurance skim rate changes.
+    pub fn insurance_rate_updated(env: &Env, admin: &Address, bps: u32) {
+        let topics = (symbol_short!("insure"), symbol_short!("rate"));
+        env.events().publish(topics, (admin.clone(), bps));
+    }
+
+    /// Event emitted when the insurance fund pays a user.
+    pub fn insurance_paid(
+        env: &Env,
+        admin: &Address,
+        user: &Address,
+        token: &Address,
+        amount: i128,
+        reason: &Symbol,
+        fund_balance: i128,
+    ) {
+        let topics = (
+            symbol_short!("insure"),
+            symbol_short!("payout"),
+            user.clone(),
+        );
+        env.events().publish(
+            topics,
+            (
+                admin.clone(),
+                token.clone(),
+                amount,
+                reason.clone(),
+                fund_balance,
+            ),
+        );
+    }
+
+    /// Event emitted when an admin corrects a goal's saved amount.
+    pub fn goal_adjusted(
+        env: &Env,
+        goal_id: u64,
+        admin: &Address,
+        previous_amount: i128,
+        new_amount: i128,
+        reason: &Symbol,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("adjusted"), goal_id);
+        env.events().publish(
+            topics,
+            (admin.clone(), previous_amount, new_amount, reason.clone()),
+        );
+    }
+
+    /// Event emitted when a user opts in to or out of gamification stats.
+    pub fn stats_opt_changed(env: &Env, user: &Address, opted_in: bool) {
+        let topics = (symbol_short!("stats"), symbol_short!("opt"));
+        env.events().publish(topics, (user.clone(), opted_in));
+    }
+
+    /// Event emitted when a goal owner creates a claim code.
+    pub fn claim_code_created(
+        env: &Env,
+        goal_id: u64,
+        code_hash: &BytesN<32>,
+        invitee: &Option<Address>,
+        expires_at: u64,
+    ) {
+        let topics = (symbol_short!("claim"), symbol_short!("created"), goal_id);
+        env.events()
+            .publish(topics, (code_hash.clone(), invitee.clone(), expires_at));
+    }
+
+    /// Event emitted when a claim code is redeemed and the claimer joins the goal.
+    pub fn claim_code_redeemed(env: &Env, goal_id: u64, code_hash: &BytesN<32>, claimer: &Address) {
+        let topics = (symbol_short!("claim"), symbol_short!("redeemed"), goal_id);
+        env.events()
+            .publish(topics, (code_hash.clone(), claimer.clone()));
+    }
+
+    /// Event emitted when a goal owner revokes an unused claim code.
+    pub fn claim_code_revoked(env: &Env, goal_id: u64, code_hash: &BytesN<32>) {
+        let topics = (symbol_short!("claim"), symbol_short!("revoked"), goal_id);
+        env.events().publish(topics, code_hash.clone());
+    }
+
+    /// Event emitted when a goal is cancelled and its escrow refunded.
+    pub fn goal_cancelled(env: &Env, goal_id: u64, caller: &Address, refunded: i128) {
+        let topics = (symbol_short!("goal"), symbol_short!("cancelled"), goal_id);
+        env.events().publish(topics, (caller.clone(), refunded));
+    }
+
+    /// Event emitted when a goal is moved to a new owner.
+    pub fn ownership_transferred(
+        env: &Env,
+        goal_id: u64,
+        previous_owner: &Address,
+        new_owner: &Address,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("owner"), goal_id);
+        env.events()
+            .publish(topics, (previous_owner.clone(), new_owner.clone()));
+    }
+
+    /// Event emitted when the admin sets or clears the withdrawal guardian.
+    pub fn withdrawal_guard_updated(env: &Env, admin: &Address, guard: Option<WithdrawalGuard>) {
+        let topics = (symbol_short!("withdraw"), symbol_short!("guard"));
+        env.events().publish(topics, (admin.clone(), guard));
+    }
+
+    /// Event emitted when a withdrawal is held for guardian approval.
+    pub fn withdrawal_requested(env: &Env, request: &WithdrawalRequest) {
+        let topics = (
+            symbol_short!("withdraw"),
+            symbol_short!("requested"),
+            request.goal_id,
+        );
+        env.events().publish(
+            topics,
+            (
+                request.request_id,
+                request.owner.clone(),
+                request.amount,
+                request.expires_at,
+            ),
+        );
+    }
+
+    /// Event emitted when tokens are withdrawn from a goal.
+    pub fn withdrawal_executed(
+        env: &Env,
+        goal_id: u64,
+        owner: &Address,
+        amount: i128,
+        approved_by: Option<Address>,
+    ) {
+        let topics = (
+            symbol_short!("withdraw"),
+            symbol_short!("executed"),
+            goal_id,
+        );
+        env.events()
+            .publish(topics, (owner.clone(), amount, approved_by));
+    }
+
+    /// Event emitted when a pending withdrawal lapses without approval.
+    pub fn withdrawal_expired(env: &Env, request: &WithdrawalRequest) {
+        let topics = (
+            symbol_short!("withdraw"),
+            symbol_short!("expired"),
+            request.goal_id,
+        );
+        env.events()
+            .publish(topics, (request.request_id, request.owner.clone()));
+    }
+
+    /// Event emitted when a goal is imported from a previous deployment.
+    pub fn goal_imported(env: &Env, source: &Address, legacy_id: u64, goal_id: u64) {
+        let topics = (symbol_short!("migrate"), symbol_short!("imported"), goal_id);
+        env.events().publish(topics, (source.clone(), legacy_id));
+    }
+
+    /// Event emitted when an admin extends the TTLs of a list of goals.
+    pub fn goals_bumped(env: &Env, admin: &Address, ledgers: u32, report: &MaintenanceReport) {
+        let topics = (symbol_short!("storage"), symbol_short!("bump"));
+        env.events()
+            .publish(topics, (admin.clone(), ledgers, report.clone()));
+    }
+
+    /// Event emitted when an admin extends the TTLs of a range of goals.
+    pub fn storage_maintained(
+        env: &Env,
+        admin: &Address,
+        start_goal_id: u64,
+        end_goal_id: u64,
+        report: &MaintenanceReport,
+    ) {
+        let topics = (symbol_short!("storage"), symbol_short!("maintain"));
+        env.events().publish(
+            topics,
+            (admin.clone(), start_goal_id, end_goal_id, report.clone()),
+        );
+    }
+
+    /// Event emitted when a goal is deactivated after its deadline.
+    pub fn goal_expired(env: &Env, goal_id: u64, expiry: &GoalExpiry) {
+        let topics = (symbol_short!("goal"), symbol_short!("expired"), goal_id);
+        env.events().publish(topics, expiry.clone());
+    }
+
+    /// Event emitted when a goal owner sets or removes a fiat peg.
+    pub fn goal_pegged(env: &Env, goal_id: u64, peg: Option<FiatPeg>) {
+        let topics = (symbol_short!("goal"), symbol_short!("pegged"), goal_id);
+        env.events().publish(topics, peg);
+    }
+
+    /// Event emitted when a pegged goal's token target is re-derived.
+    pub fn goal_revalued(
+        env: &Env,
+        goal_id: u64,
+        peg: &FiatPeg,
+        previous_target: i128,
+        new_target: i128,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("revalued"), goal_id);
+        env.events().publish(
+            topics,
+            (peg.currency.clone(), peg.rate, previous_target, new_target),
+        );
+    }
+
+    /// Event emitted when a user sets or clears their vacation window.
+    pub fn vacation_updated(env: &Env, user: &Address, window: Option<VacationWindow>) {
+        let topics = (symbol_short!("vacation"), user.clone());
+        env.events().publish(topics, window);
+    }
+
+    /// Event emitted when a user records who referred them.
+    pub fn referral_registered(env: &Env, user: &Address, referrer: &Address) {
+        let topics = (symbol_short!("referral"), symbol_short!("set"));
+        env.events()
+            .publish(topics, (user.clone(), referrer.clone()));
+    }
+
+    /// Event emitted when a referred user completes their first goal.
+    pub fn referral_qualified(env: &Env, goal_id: u64, user: &Address, referrer: &Address) {
+        let topics = (
+            symbol_short!("referral"),
+            Symbol::new(env, "referral_qualified"),
+            goal_id,
+        );
+        env.events()
+            .publish(topics, (user.clone(), referrer.clone()));
+    }
+
+    /// Event emitted when the referral contract rejects a reward release.
+    pub fn referral_release_failed(env: &Env, goal_id: u64, user: &Address, referrer: &Address) {
+        let topics = (symbol_short!("referral"), symbol_short!("failed"), goal_id);
+        env.events()
+            .publish(topics, (user.clone(), referrer.clone()));
+    }
+
+    /// Event emitted when the admin sets or clears the milestone reward.
+    pub fn reward_config_updated(env: &Env, admin: &Address, config: Option<RewardConfig>) {
+        let topics = (symbol_short!("reward"), symbol_short!("config"));
+        env.events().publish(topics, (admin.clone(), config));
+    }
+
+    /// Event emitted when a milestone reward is minted to the goal owner.
+    pub fn milestone_rewarded(
+        env: &Env,
+        goal_id: u64,
+        user: &Address,
+        milestone_percentage: u32,
+        amount: i128,
+    ) {
+        let topics = (symbol_short!("reward"), symbol_short!("minted"), goal_id);
+        env.events()
+            .publish(topics, (user.clone(), milestone_percentage, amount));
+    }
+
+    /// Event emitted when minting a milestone reward fails.
+    pub fn milestone_reward_failed(
+        env: &Env,
+        goal_id: u64,
+        user: &Address,
+        milestone_percentage: u32,
+        amount: i128,
+    ) {
+        let topics = (symbol_short!("reward"), symbol_short!("failed"), goal_id);
+        env.events()
+            .publish(topics, (user.clone(), milestone_percentage, amount));
+    }
+
     /// Event emitted when batch milestone achievement starts.
     pub fn milestone_batch_started(env: &Env, batch_id: u64, request_count: u32) {
         let topics = (symbol_short!("milestone"), symbol_short!("start"));
@@ -309,6 +1165,35 @@ impl GoalEvents {
         env.events().publish(topics, (goal_id, error_code));
     }
 
+    /// Event emitted when a missing achievement record is recreated.
+    pub fn milestone_repaired(env: &Env, goal_id: u64, milestone_id: u64, milestone_percent: u32) {
+        let topics = (
+            symbol_short!("milestone"),
+            symbol_short!("repaired"),
+            goal_id,
+        );
+        env.events()
+            .publish(topics, (milestone_id, milestone_percent));
+    }
+
+    /// Event emitted when a goal's milestone state has been reconciled.
+    pub fn milestones_reconciled(env: &Env, admin: &Address, result: &MilestoneRepairResult) {
+        let topics = (
+            symbol_short!("milestone"),
+            symbol_short!("reconcile"),
+            result.goal_id,
+        );
+        env.events().publish(
+            topics,
+            (
+                admin.clone(),
+                result.added.clone(),
+                result.removed.clone(),
+                result.created_milestone_ids.clone(),
+            ),
+        );
+    }
+
     /// Event emitted when batch milestone achievement completes.
     pub fn milestone_batch_completed(
         env: &Env,
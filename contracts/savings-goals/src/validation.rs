@@ -3,16 +3,20 @@
 use soroban_sdk::{Address, Env};
 
 use crate::types::{
-    DataKey, ErrorCode, MilestoneAchievementRequest, SavingsGoal, SavingsGoalRequest,
-    MAX_GOAL_AMOUNT, MIN_GOAL_AMOUNT,
+    DataKey, ErrorCode, GoalLimits, GoalUpdateRequest, MilestoneAchievementRequest, SavingsGoal,
+    SavingsGoalRequest, MAX_GOAL_AMOUNT, MAX_GOAL_METADATA_BYTES,
 };
 
-/// Validates a savings goal request.
+/// Validates a savings goal request against the configured goal limits.
 ///
 /// # Returns
 /// * `Ok(())` if valid
 /// * `Err(error_code)` if invalid
-pub fn validate_goal_request(env: &Env, request: &SavingsGoalRequest) -> Result<(), u32> {
+pub fn validate_goal_request(
+    env: &Env,
+    request: &SavingsGoalRequest,
+    limits: &GoalLimits,
+) -> Result<(), u32> {
     // Validate user address - ensure it's not empty/invalid
     // Note: Soroban SDK doesn't provide a direct way to validate Address format,
     // but we can check basic properties
@@ -26,12 +30,12 @@ pub fn validate_goal_request(env: &Env, request: &SavingsGoalRequest) -> Result<
     // Note: Symbol doesn't have to_string() in no_std environment
 
     // Validate target amount
-    if !is_valid_amount(request.target_amount) {
+    if !is_valid_amount(request.target_amount, limits) {
         return Err(ErrorCode::INVALID_AMOUNT);
     }
 
     // Validate deadline
-    if !is_valid_deadline(env, request.deadline) {
+    if !is_valid_deadline(env, request.deadline, limits) {
         return Err(ErrorCode::INVALID_DEADLINE);
     }
 
@@ -40,9 +44,82 @@ pub fn validate_goal_request(env: &Env, request: &SavingsGoalRequest) -> Result<
         return Err(ErrorCode::INVALID_INITIAL_CONTRIBUTION);
     }
 
+    if request.metadata.len() > MAX_GOAL_METADATA_BYTES {
+        return Err(ErrorCode::INVALID_METADATA);
+    }
+
     Ok(())
 }
 
+/// Validates a goal update request against the configured goal limits.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - The address requesting the update
+/// * `is_admin` - Whether the caller may update goals it doesn't own
+/// * `request` - The update request
+/// * `limits` - The goal limits in effect
+///
+/// # Returns
+/// * `Ok(goal)` with the changes applied if valid
+/// * `Err(error_code)` if invalid
+pub fn validate_goal_update(
+    env: &Env,
+    caller: &Address,
+    is_admin: bool,
+    request: &GoalUpdateRequest,
+    limits: &GoalLimits,
+) -> Result<SavingsGoal, u32> {
+    let mut goal: SavingsGoal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Goal(request.goal_id))
+        .ok_or(ErrorCode::GOAL_NOT_FOUND)?;
+
+    if !is_admin && goal.user != *caller {
+        return Err(ErrorCode::UNAUTHORIZED_USER);
+    }
+    if !goal.is_active {
+        return Err(ErrorCode::GOAL_NOT_ACTIVE);
+    }
+
+    let mut changed = false;
+    if let Some(target_amount) = request.target_amount {
+        if !is_valid_amount(target_amount, limits) {
+            return Err(ErrorCode::INVALID_AMOUNT);
+        }
+        changed |= target_amount != goal.target_amount;
+        goal.target_amount = target_amount;
+    }
+    if let Some(deadline) = request.deadline {
+        if !is_valid_deadline(env, deadline, limits) {
+            return Err(ErrorCode::INVALID_DEADLINE);
+        }
+        changed |= deadline != goal.deadline;
+        goal.deadline = deadline;
+    }
+    if let Some(goal_name) = request.goal_name.clone() {
+        changed |= goal_name != goal.goal_name;
+        goal.goal_name = goal_name;
+    }
+    if let Some(category) = request.category.clone() {
+        changed |= goal.category.as_ref() != Some(&category);
+        goal.category = Some(category);
+    }
+    if let Some(metadata) = request.metadata.clone() {
+        if metadata.len() > MAX_GOAL_METADATA_BYTES {
+            return Err(ErrorCode::INVALID_METADATA);
+        }
+        changed |= metadata != goal.metadata;
+        goal.metadata = metadata;
+    }
+
+    if !changed {
+        return Err(ErrorCode::NO_CHANGES);
+    }
+    Ok(goal)
+}
+
 /// Validates that an address is valid.
 ///
 /// In Soroban, all Address instances are valid by construction,
@@ -58,33 +135,34 @@ fn is_valid_address(_address: &Address) -> bool {
 ///
 /// # Arguments
 /// * `amount` - The amount to validate
+/// * `limits` - The goal limits in effect
 ///
 /// # Returns
-/// * `true` if amount is >= MIN_GOAL_AMOUNT and <= MAX_GOAL_AMOUNT
-pub fn is_valid_amount(amount: i128) -> bool {
-    amount >= MIN_GOAL_AMOUNT && amount <= MAX_GOAL_AMOUNT
+/// * `true` if amount is >= the configured minimum and <= MAX_GOAL_AMOUNT
+pub fn is_valid_amount(amount: i128, limits: &GoalLimits) -> bool {
+    amount >= limits.min_target_amount && amount <= MAX_GOAL_AMOUNT
 }
 
-/// Validates that a deadline is in the future but not too far.
+/// Validates that a deadline is within the configured horizon.
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `deadline` - The deadline ledger sequence number
+/// * `limits` - The goal limits in effect
 ///
 /// # Returns
 /// * `true` if deadline is valid
-pub fn is_valid_deadline(env: &Env, deadline: u64) -> bool {
+pub fn is_valid_deadline(env: &Env, deadline: u64, limits: &GoalLimits) -> bool {
     let current_ledger = env.ledger().sequence() as u64;
 
-    // Deadline must be in the future
-    if deadline <= current_ledger {
+    // Deadline must be at least the minimum horizon away
+    // Use saturating_add to avoid overflow
+    if deadline < current_ledger.saturating_add(limits.min_deadline_ledgers) {
         return false;
     }
 
-    // Deadline should not be more than ~5 years in the future
-    // Use saturating_add to avoid overflow
-    let max_future_ledgers = 31_536_000u64; // ~5 years
-    if deadline > current_ledger.saturating_add(max_future_ledgers) {
+    // Deadline should not be beyond the maximum horizon
+    if deadline > current_ledger.saturating_add(limits.max_deadline_ledgers) {
         return false;
     }
 
@@ -205,6 +283,7 @@ pub fn is_valid_milestone_percentage(percentage: u32) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MIN_GOAL_AMOUNT;
     use soroban_sdk::{symbol_short, testutils::Address as _, Env};
 
     fn create_valid_request(env: &Env) -> SavingsGoalRequest {
@@ -214,6 +293,8 @@ mod tests {
             target_amount: 100_000_000, // 10 XLM
             deadline: env.ledger().sequence() as u64 + 1000,
             initial_contribution: 10_000_000, // 1 XLM
+            category: None,
+            metadata: soroban_sdk::Bytes::new(env),
         }
     }
 
@@ -221,7 +302,7 @@ mod tests {
     fn test_valid_goal_request() {
         let env = Env::default();
         let request = create_valid_request(&env);
-        assert!(validate_goal_request(&env, &request).is_ok());
+        assert!(validate_goal_request(&env, &request, &GoalLimits::default()).is_ok());
     }
 
     #[test]
@@ -230,7 +311,7 @@ mod tests {
         let mut request = create_valid_request(&env);
         request.target_amount = 1000; // Below minimum
         assert_eq!(
-            validate_goal_request(&env, &request),
+            validate_goal_request(&env, &request, &GoalLimits::default()),
             Err(ErrorCode::INVALID_AMOUNT)
         );
     }
@@ -241,7 +322,7 @@ mod tests {
         let mut request = create_valid_request(&env);
         request.target_amount = -1000;
         assert_eq!(
-            validate_goal_request(&env, &request),
+            validate_goal_request(&env, &request, &GoalLimits::default()),
             Err(ErrorCode::INVALID_AMOUNT)
         );
     }
@@ -252,7 +333,7 @@ mod tests {
         let mut request = create_valid_request(&env);
         request.deadline = 0; // Past deadline
         assert_eq!(
-            validate_goal_request(&env, &request),
+            validate_goal_request(&env, &request, &GoalLimits::default()),
             Err(ErrorCode::INVALID_DEADLINE)
         );
     }
@@ -263,7 +344,7 @@ mod tests {
         let mut request = create_valid_request(&env);
         request.initial_contribution = -1000;
         assert_eq!(
-            validate_goal_request(&env, &request),
+            validate_goal_request(&env, &request, &GoalLimits::default()),
             Err(ErrorCode::INVALID_INITIAL_CONTRIBUTION)
         );
     }
@@ -274,30 +355,50 @@ mod tests {
         let mut request = create_valid_request(&env);
         request.initial_contribution = request.target_amount + 1;
         assert_eq!(
-            validate_goal_request(&env, &request),
+            validate_goal_request(&env, &request, &GoalLimits::default()),
             Err(ErrorCode::INVALID_INITIAL_CONTRIBUTION)
         );
     }
 
     #[test]
     fn test_is_valid_amount() {
-        assert!(is_valid_amount(MIN_GOAL_AMOUNT));
-        assert!(is_valid_amount(MAX_GOAL_AMOUNT));
-        assert!(is_valid_amount(100_000_000));
-        assert!(!is_valid_amount(MIN_GOAL_AMOUNT - 1));
-        assert!(!is_valid_amount(MAX_GOAL_AMOUNT + 1));
-        assert!(!is_valid_amount(-1000));
+        let limits = GoalLimits::default();
+        assert!(is_valid_amount(MIN_GOAL_AMOUNT, &limits));
+        assert!(is_valid_amount(MAX_GOAL_AMOUNT, &limits));
+        assert!(is_valid_amount(100_000_000, &limits));
+        assert!(!is_valid_amount(MIN_GOAL_AMOUNT - 1, &limits));
+        assert!(!is_valid_amount(MAX_GOAL_AMOUNT + 1, &limits));
+        assert!(!is_valid_amount(-1000, &limits));
     }
 
     #[test]
     fn test_is_valid_deadline() {
         let env = Env::default();
         let current = env.ledger().sequence() as u64;
+        let limits = GoalLimits::default();
 
-        assert!(is_valid_deadline(&env, current + 100));
-        assert!(is_valid_deadline(&env, current + 1000000));
-        assert!(!is_valid_deadline(&env, current));
-        assert!(!is_valid_deadline(&env, current - 100));
+        assert!(is_valid_deadline(&env, current + 100, &limits));
+        assert!(is_valid_deadline(&env, current + 1000000, &limits));
+        assert!(!is_valid_deadline(&env, current, &limits));
+        assert!(!is_valid_deadline(&env, current - 100, &limits));
+    }
+
+    #[test]
+    fn test_custom_goal_limits() {
+        let env = Env::default();
+        let current = env.ledger().sequence() as u64;
+        let limits = GoalLimits {
+            min_target_amount: 1_000,
+            min_deadline_ledgers: 100,
+            max_deadline_ledgers: 1_000,
+        };
+
+        assert!(is_valid_amount(1_000, &limits));
+        assert!(!is_valid_amount(999, &limits));
+        assert!(!is_valid_deadline(&env, current + 99, &limits));
+        assert!(is_valid_deadline(&env, current + 100, &limits));
+        assert!(is_valid_deadline(&env, current + 1_000, &limits));
+        assert!(!is_valid_deadline(&env, current + 1_001, &limits));
     }
 
     #[test]
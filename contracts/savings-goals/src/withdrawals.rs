@@ -0,0 +1,224 @@
+//! Withdrawals of escrowed tokens, with guardian co-signing above a
+//! threshold.
+//!
+//! Withdrawals are paid out immediately while the owner's unapproved
+//! withdrawals over the guard's rolling window stay within the configured
+//! threshold. Any that would take the total past it are held as pending
+//! requests until the guardian approves them, or lapse once the approval
+//! window has passed. Cancelling or transferring a goal takes its escrow out
+//! of the guardian's reach too, so both pass the same check: they count
+//! against the window or need the guardian's co-signature.
+
+use soroban_sdk::{panic_with_error, token, Address, Env, Vec};
+
+use crate::campaigns;
+use crate::cancellation;
+use crate::storage;
+use crate::types::{
+    DataKey, GoalEvents, SavingsGoal, WithdrawalGuard, WithdrawalOutcome, WithdrawalRequest,
+};
+use crate::SavingsGoalError;
+
+pub fn get_guard(env: &Env) -> Option<WithdrawalGuard> {
+    env.storage().instance().get(&DataKey::WithdrawalGuard)
+}
+
+pub fn set_guard(env: &Env, guard: &Option<WithdrawalGuard>) {
+    match guard {
+        Some(guard) => {
+            if guard.threshold < 0 || guard.approval_ledgers == 0 || guard.window_ledgers == 0 {
+                panic_with_error!(env, SavingsGoalError::InvalidAmount);
+            }
+            env.storage()
+                .instance()
+                .set(&DataKey::WithdrawalGuard, guard);
+        }
+        None => env.storage().instance().remove(&DataKey::WithdrawalGuard),
+    }
+}
+
+pub fn get_request(env: &Env, request_id: u64) -> Option<WithdrawalRequest> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Withdrawal(request_id))
+}
+
+fn load_request(env: &Env, request_id: u64) -> WithdrawalRequest {
+    get_request(env, request_id)
+        .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::WithdrawalNotFound))
+}
+
+/// Loads an active goal and checks `amount` is covered by its escrow,
+/// leaving out campaign matches that are still locked.
+fn load_withdrawable(env: &Env, goal_id: u64, amount: i128) -> SavingsGoal {
+    let goal: SavingsGoal = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Goal(goal_id))
+        .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::GoalNotFound));
+    if !goal.is_active {
+        panic_with_error!(env, SavingsGoalError::GoalNotActive);
+    }
+    if amount > cancellation::get_escrow(env, goal_id) - campaigns::locked_match(env, &goal) {
+        panic_with_error!(env, SavingsGoalError::InsufficientEscrow);
+    }
+    goal
+}
+
+/// Unapproved withdrawals by `owner` still inside the guard's window, as
+/// `(ledger, amount)` pairs, oldest first.
+fn recent_withdrawals(env: &Env, owner: &Address, guard: &WithdrawalGuard) -> Vec<(u32, i128)> {
+    let now = env.ledger().sequence();
+    let mut recent = Vec::new(env);
+    let withdrawals: Vec<(u32, i128)> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RecentWithdrawals(owner.clone()))
+        .unwrap_or(Vec::new(env));
+    for (ledger, amount) in withdrawals.iter() {
+        if ledger.saturating_add(guard.window_ledgers) > now {
+            recent.push_back((ledger, amount));
+        }
+    }
+    recent
+}
+
+/// Total withdrawn by `owner` without approval within the guard's window.
+pub fn withdrawn_in_window(env: &Env, owner: &Address) -> i128 {
+    match get_guard(env) {
+        Some(guard) => recent_withdrawals(env, owner, &guard)
+            .iter()
+            .map(|(_, amount)| amount)
+            .sum(),
+        None => 0,
+    }
+}
+
+/// Whether `guardian` is the configured withdrawal guardian.
+pub fn is_guardian(env: &Env, guardian: &Address) -> bool {
+    matches!(get_guard(env), Some(guard) if guard.guardian == *guardian)
+}
+
+/// Counts `amount` leaving `owner`'s goals without the guardian's approval
+/// against the guard's window. Returns false, recording nothing, if it would
+/// take the window's total past the threshold and so needs the guardian.
+pub fn try_unapproved(env: &Env, owner: &Address, amount: i128) -> bool {
+    let Some(guard) = get_guard(env) else {
+        return true;
+    };
+
+    let mut recent = recent_withdrawals(env, owner, &guard);
+    let withdrawn: i128 = recent.iter().map(|(_, amount)| amount).sum();
+    match withdrawn.checked_add(amount) {
+        Some(total) if total <= guard.threshold => {
+            if amount > 0 {
+                recent.push_back((env.ledger().sequence(), amount));
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::RecentWithdrawals(owner.clone()), &recent);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Withdraws `amount` to the goal owner, or holds it for the guardian if it
+/// would take the owner's unapproved withdrawals within the guard's window
+/// past the configured threshold.
+pub fn request(env: &Env, owner: &Address, goal_id: u64, amount: i128) -> WithdrawalOutcome {
+    if amount <= 0 {
+        panic_with_error!(env, SavingsGoalError::InvalidAmount);
+    }
+    let goal = load_withdrawable(env, goal_id, amount);
+    if goal.user != *owner {
+        panic_with_error!(env, SavingsGoalError::Unauthorized);
+    }
+
+    if try_unapproved(env, owner, amount) {
+        execute(env, goal, amount, None);
+        return WithdrawalOutcome::Executed(amount);
+    }
+
+    // Only reachable with a guard configured
+    let guard = get_guard(env).unwrap();
+    let request_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastWithdrawalId)
+        .unwrap_or(0)
+        + 1;
+    let requested_at = env.ledger().sequence();
+    let request = WithdrawalRequest {
+        request_id,
+        goal_id,
+        owner: owner.clone(),
+        amount,
+        requested_at,
+        expires_at: requested_at + guard.approval_ledgers,
+    };
+    env.storage()
+        .instance()
+        .set(&DataKey::LastWithdrawalId, &request_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Withdrawal(request_id), &request);
+
+    GoalEvents::withdrawal_requested(env, &request);
+    WithdrawalOutcome::Pending(request_id)
+}
+
+/// Executes a pending withdrawal co-signed by the guardian and returns the
+/// withdrawn amount.
+pub fn approve(env: &Env, guardian: &Address, request_id: u64) -> i128 {
+    if !is_guardian(env, guardian) {
+        panic_with_error!(env, SavingsGoalError::Unauthorized);
+    }
+    let request = load_request(env, request_id);
+    if env.ledger().sequence() > request.expires_at {
+        panic_with_error!(env, SavingsGoalError::WithdrawalExpired);
+    }
+
+    let goal = load_withdrawable(env, request.goal_id, request.amount);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Withdrawal(request_id));
+    execute(env, goal, request.amount, Some(guardian.clone()));
+    request.amount
+}
+
+/// Drops a pending withdrawal whose approval window has passed.
+pub fn expire(env: &Env, request_id: u64) -> WithdrawalRequest {
+    let request = load_request(env, request_id);
+    if env.ledger().sequence() <= request.expires_at {
+        panic_with_error!(env, SavingsGoalError::WithdrawalNotExpired);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Withdrawal(request_id));
+    GoalEvents::withdrawal_expired(env, &request);
+    request
+}
+
+/// Pays out `amount`; withdrawing before the goal completes gives its
+/// locked campaign matches back.
+fn execute(env: &Env, mut goal: SavingsGoal, amount: i128, approved_by: Option<Address>) {
+    campaigns::settle(env, &mut goal);
+    let goal_token: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::GoalToken(goal.goal_id))
+        .unwrap();
+    token::Client::new(env, &goal_token).transfer(
+        &env.current_contract_address(),
+        &goal.user,
+        &amount,
+    );
+    cancellation::release_escrow(env, goal.goal_id, amount);
+
+    goal.current_amount -= amount;
+    storage::save_goal(env, &goal);
+
+    GoalEvents::withdrawal_executed(env, goal.goal_id, &goal.user, amount, approved_by);
+}
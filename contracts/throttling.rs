@@ -1,6 +1,6 @@
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, Address,
-    Env, Map, Vec, U256,
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, xdr::ToXdr, Address, Bytes, Env, Map, Symbol, Vec, U256,
 };
 
 #[derive(Clone)]
@@ -13,8 +13,15 @@ pub enum DataKey {
     GlobalThrottleStats,
     ThrottledWallets,
     TimeWindowData(u64), // timestamp_slot
+    ExemptionExpiry(Address),
+    AuditContract,
+    ConfigHistory,
+    PendingConfig,
 }
 
+/// Number of past configuration changes kept in the history.
+pub const MAX_CONFIG_HISTORY: u32 = 50;
+
 #[derive(Clone)]
 #[contracttype]
 pub struct ThrottleConfig {
@@ -26,6 +33,25 @@ pub struct ThrottleConfig {
     pub exempt_addresses: Vec<Address>,
 }
 
+/// A configuration that took effect, with the admin who made the change.
+#[derive(Clone)]
+#[contracttype]
+pub struct ConfigChange {
+    pub config: ThrottleConfig,
+    pub admin: Address,
+    pub changed_at: u64,
+}
+
+/// A configuration announced in advance that takes effect at `effective_at`.
+#[derive(Clone)]
+#[contracttype]
+pub struct PendingConfig {
+    pub config: ThrottleConfig,
+    pub admin: Address,
+    pub scheduled_at: u64,
+    pub effective_at: u64,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct WalletThrottleState {
@@ -102,6 +128,21 @@ pub enum ThrottleError {
     StorageError = 7,
     Overflow = 8,
     InvalidAddress = 9,
+    InvalidExpiry = 10,
+    InvalidEffectiveTime = 11,
+}
+
+/// Minimal interface of the audit contract used to record exemption changes,
+/// violations and throttle lifts.
+#[contractclient(name = "AuditClient")]
+pub trait AuditInterface {
+    fn log_audit(
+        env: Env,
+        actor: Address,
+        operation: Symbol,
+        status: Symbol,
+        metadata: Option<Bytes>,
+    );
 }
 
 pub struct ThrottleEvents;
@@ -149,14 +190,68 @@ impl ThrottleEvents {
         );
     }
 
-    pub fn wallet_exempted(env: &Env, admin: &Address, wallet: &Address) {
+    pub fn config_scheduled(
+        env: &Env,
+        admin: &Address,
+        config: &ThrottleConfig,
+        effective_at: u64,
+    ) {
+        let topics = (symbol_short!("throttle"), symbol_short!("scheduled"));
+        env.events().publish(
+            topics,
+            (
+                admin.clone(),
+                config.max_transactions_per_window,
+                config.window_size_seconds,
+                config.block_duration_seconds,
+                effective_at,
+            ),
+        );
+    }
+
+    pub fn config_activated(env: &Env, admin: &Address, effective_at: u64) {
+        let topics = (symbol_short!("throttle"), symbol_short!("activated"));
+        env.events().publish(
+            topics,
+            (admin.clone(), effective_at, env.ledger().timestamp()),
+        );
+    }
+
+    pub fn wallet_exempted(env: &Env, admin: &Address, wallet: &Address, expires_at: u64) {
         let topics = (symbol_short!("throttle"), symbol_short!("exempted"));
+        env.events().publish(
+            topics,
+            (
+                admin.clone(),
+                wallet.clone(),
+                expires_at,
+                env.ledger().timestamp(),
+            ),
+        );
+    }
+
+    pub fn exemption_revoked(env: &Env, admin: &Address, wallet: &Address) {
+        let topics = (symbol_short!("throttle"), symbol_short!("unexempt"));
         env.events().publish(
             topics,
             (admin.clone(), wallet.clone(), env.ledger().timestamp()),
         );
     }
 
+    pub fn exemption_expired(env: &Env, wallet: &Address, expired_at: u64) {
+        let topics = (symbol_short!("throttle"), symbol_short!("exp_ended"));
+        env.events().publish(
+            topics,
+            (wallet.clone(), expired_at, env.ledger().timestamp()),
+        );
+    }
+
+    pub fn audit_failed(env: &Env, operation: &Symbol) {
+        let topics = (symbol_short!("throttle"), symbol_short!("audit_err"));
+        env.events()
+            .publish(topics, (operation.clone(), env.ledger().timestamp()));
+    }
+
     pub fn cleanup_performed(env: &Env, cleaned_wallets: u32, freed_space: u64) {
         let topics = (symbol_short!("throttle"), symbol_short!("cleanup"));
         env.events().publish(
@@ -174,6 +269,8 @@ impl ThrottleEvents {
     }
 }
 
+/// Exemptions are granted through `add_exempt_address` only, so the contract
+/// starts with none, whatever `config.exempt_addresses` holds.
 pub fn initialize_throttle_contract(env: &Env, admin: Address, config: ThrottleConfig) {
     if env.storage().instance().has(&DataKey::Admin) {
         panic_with_error!(env, ThrottleError::AlreadyInitialized);
@@ -181,6 +278,10 @@ pub fn initialize_throttle_contract(env: &Env, admin: Address, config: ThrottleC
 
     // Validate configuration
     validate_config(&env, &config);
+    let config = ThrottleConfig {
+        exempt_addresses: Vec::new(env),
+        ..config
+    };
 
     env.storage().instance().set(&DataKey::Admin, &admin);
     env.storage()
@@ -218,7 +319,8 @@ pub fn require_admin(env: &Env, caller: &Address) {
 }
 
 pub fn check_transaction_throttle(env: &Env, wallet_address: Address) -> ThrottleResult {
-    let config = get_throttle_config(env);
+    activate_due_config(env);
+    let mut config = get_throttle_config(env);
 
     // Check if throttling is enabled
     if !config.enabled {
@@ -231,19 +333,23 @@ pub fn check_transaction_throttle(env: &Env, wallet_address: Address) -> Throttl
         };
     }
 
-    // Check if wallet is exempt
+    let current_time = env.ledger().timestamp();
+
+    // Check if wallet is exempt; lapsed exemptions are dropped and the wallet is
+    // throttled normally from here on
     if config.exempt_addresses.contains(&wallet_address) {
-        return ThrottleResult {
-            allowed: true,
-            reason: ThrottleReason::WalletExempt,
-            remaining_transactions: u32::MAX,
-            window_reset_time: 0,
-            throttle_end_time: None,
-        };
+        if is_exemption_active(env, &wallet_address, current_time) {
+            return ThrottleResult {
+                allowed: true,
+                reason: ThrottleReason::WalletExempt,
+                remaining_transactions: u32::MAX,
+                window_reset_time: 0,
+                throttle_end_time: None,
+            };
+        }
+        expire_exemption(env, &mut config, &wallet_address);
     }
 
-    let current_time = env.ledger().timestamp();
-
     // Perform cleanup if needed
     maybe_cleanup_old_data(env, current_time);
 
@@ -264,6 +370,7 @@ pub fn check_transaction_throttle(env: &Env, wallet_address: Address) -> Throttl
             };
         } else {
             // Throttle period expired, reset state
+            record_throttle_lift_audit(env, &wallet_state, current_time);
             wallet_state.is_throttled = false;
             wallet_state.transaction_count = 0;
             wallet_state.window_start = current_time;
@@ -312,6 +419,7 @@ pub fn check_transaction_throttle(env: &Env, wallet_address: Address) -> Throttl
         // Emit events
         ThrottleEvents::throttle_triggered(env, &wallet_address, &violation);
         ThrottleEvents::violation_recorded(env, &wallet_address, wallet_state.violation_count);
+        record_violation_audit(env, &violation, wallet_state.violation_count, &config);
 
         return ThrottleResult {
             allowed: false,
@@ -353,27 +461,92 @@ pub fn check_transaction_throttle(env: &Env, wallet_address: Address) -> Throttl
     }
 }
 
+/// Replaces the throttle limits. Exemptions are managed through
+/// `add_exempt_address` and `remove_exempt_address`, so the ones in force are
+/// kept and `new_config.exempt_addresses` is ignored; lapsed ones are swept.
 pub fn update_throttle_config(env: &Env, caller: Address, new_config: ThrottleConfig) {
     require_admin(env, &caller);
     validate_config(&env, &new_config);
+    activate_due_config(env);
 
+    let mut current = get_throttle_config(env);
+    sweep_lapsed_exemptions(env, &mut current);
+    let new_config = ThrottleConfig {
+        exempt_addresses: current.exempt_addresses,
+        ..new_config
+    };
     env.storage()
         .instance()
         .set(&DataKey::ThrottleConfig, &new_config);
+    record_config_change(env, &new_config, &caller, env.ledger().timestamp());
     ThrottleEvents::config_updated(env, &caller, &new_config);
 }
 
-pub fn add_exempt_address(env: &Env, caller: Address, wallet_address: Address) {
+/// Announces a configuration that takes effect at `effective_at`, replacing
+/// any change still pending. Exemptions are managed through
+/// `add_exempt_address`, so the ones in force at activation are kept.
+pub fn schedule_config(env: &Env, caller: Address, config: ThrottleConfig, effective_at: u64) {
+    require_admin(env, &caller);
+    validate_config(env, &config);
+
+    let current_time = env.ledger().timestamp();
+    if effective_at <= current_time {
+        panic_with_error!(env, ThrottleError::InvalidEffectiveTime);
+    }
+    activate_due_config(env);
+
+    let pending = PendingConfig {
+        config,
+        admin: caller.clone(),
+        scheduled_at: current_time,
+        effective_at,
+    };
+    env.storage()
+        .instance()
+        .set(&DataKey::PendingConfig, &pending);
+    ThrottleEvents::config_scheduled(env, &caller, &pending.config, effective_at);
+}
+
+pub fn get_pending_config(env: &Env) -> Option<PendingConfig> {
+    env.storage().instance().get(&DataKey::PendingConfig)
+}
+
+/// Configurations that took effect, oldest first, bounded by `MAX_CONFIG_HISTORY`.
+pub fn get_config_history(env: &Env) -> Vec<ConfigChange> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ConfigHistory)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Grants (or renews) a time-boxed exemption. The exemption lapses at `expires_at`
+/// and is removed automatically on the wallet's next throttle check.
+pub fn add_exempt_address(env: &Env, caller: Address, wallet_address: Address, expires_at: u64) {
     require_admin(env, &caller);
 
+    if expires_at <= env.ledger().timestamp() {
+        panic_with_error!(env, ThrottleError::InvalidExpiry);
+    }
+
     let mut config = get_throttle_config(env);
     if !config.exempt_addresses.contains(&wallet_address) {
         config.exempt_addresses.push_back(wallet_address.clone());
         env.storage()
             .instance()
             .set(&DataKey::ThrottleConfig, &config);
-        ThrottleEvents::wallet_exempted(env, &caller, &wallet_address);
     }
+    env.storage().persistent().set(
+        &DataKey::ExemptionExpiry(wallet_address.clone()),
+        &expires_at,
+    );
+
+    ThrottleEvents::wallet_exempted(env, &caller, &wallet_address, expires_at);
+    record_exemption_audit(
+        env,
+        Symbol::new(env, "exemption_granted"),
+        &wallet_address,
+        expires_at,
+    );
 }
 
 pub fn remove_exempt_address(env: &Env, caller: Address, wallet_address: Address) {
@@ -396,13 +569,83 @@ pub fn remove_exempt_address(env: &Env, caller: Address, wallet_address: Address
         env.storage()
             .instance()
             .set(&DataKey::ThrottleConfig, &config);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ExemptionExpiry(wallet_address.clone()));
+
+        ThrottleEvents::exemption_revoked(env, &caller, &wallet_address);
+        record_exemption_audit(
+            env,
+            Symbol::new(env, "exemption_revoked"),
+            &wallet_address,
+            env.ledger().timestamp(),
+        );
     }
 }
 
+pub fn get_exemption_expiry(env: &Env, wallet_address: Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ExemptionExpiry(wallet_address))
+}
+
+pub fn set_audit_contract(env: &Env, caller: Address, audit_contract: Address) {
+    require_admin(env, &caller);
+    env.storage()
+        .instance()
+        .set(&DataKey::AuditContract, &audit_contract);
+}
+
+pub fn get_audit_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::AuditContract)
+}
+
 pub fn get_wallet_throttle_info(env: &Env, wallet_address: Address) -> Option<WalletThrottleState> {
     Some(get_wallet_throttle_state(env, &wallet_address))
 }
 
+/// Projects when a blocked wallet may transact again, without consuming quota.
+/// Returns `None` if the wallet is not currently blocked.
+pub fn get_unblock_time(env: &Env, wallet_address: Address) -> Option<u64> {
+    let config = get_throttle_config(env);
+    let current_time = env.ledger().timestamp();
+    if is_unthrottled(env, &config, &wallet_address, current_time) {
+        return None;
+    }
+
+    let state: WalletThrottleState = env
+        .storage()
+        .persistent()
+        .get(&DataKey::WalletThrottleState(wallet_address))?;
+    let unblock_time = state.throttle_start_time + config.block_duration_seconds;
+    if state.is_throttled && current_time < unblock_time {
+        Some(unblock_time)
+    } else {
+        None
+    }
+}
+
+/// Projects when the wallet's current rate-limit window resets, without
+/// consuming quota. Returns `None` if no window is in progress.
+pub fn get_window_reset_time(env: &Env, wallet_address: Address) -> Option<u64> {
+    let config = get_throttle_config(env);
+    let current_time = env.ledger().timestamp();
+    if is_unthrottled(env, &config, &wallet_address, current_time) {
+        return None;
+    }
+
+    let state: WalletThrottleState = env
+        .storage()
+        .persistent()
+        .get(&DataKey::WalletThrottleState(wallet_address))?;
+    let reset_time = state.window_start + config.window_size_seconds;
+    if current_time < reset_time {
+        Some(reset_time)
+    } else {
+        None
+    }
+}
+
 pub fn get_throttled_wallets(env: &Env) -> Vec<Address> {
     env.storage()
         .instance()
@@ -432,9 +675,13 @@ pub fn force_cleanup(env: &Env, caller: Address) {
 pub fn reset_wallet_throttle_state(env: &Env, caller: Address, wallet_address: Address) {
     require_admin(env, &caller);
 
-    let config = get_throttle_config(env);
     let current_time = env.ledger().timestamp();
 
+    let previous_state = get_wallet_throttle_state(env, &wallet_address);
+    if previous_state.is_throttled {
+        record_throttle_lift_audit(env, &previous_state, current_time);
+    }
+
     let reset_state = WalletThrottleState {
         wallet_address: wallet_address.clone(),
         transaction_count: 0,
@@ -467,11 +714,180 @@ fn validate_config(env: &Env, config: &ThrottleConfig) {
     }
 }
 
-fn get_throttle_config(env: &Env) -> ThrottleConfig {
+fn is_unthrottled(
+    env: &Env,
+    config: &ThrottleConfig,
+    wallet_address: &Address,
+    current_time: u64,
+) -> bool {
+    !config.enabled
+        || (config.exempt_addresses.contains(wallet_address)
+            && is_exemption_active(env, wallet_address, current_time))
+}
+
+fn is_exemption_active(env: &Env, wallet_address: &Address, current_time: u64) -> bool {
+    let expiry: Option<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ExemptionExpiry(wallet_address.clone()));
+    matches!(expiry, Some(expires_at) if current_time < expires_at)
+}
+
+/// Drops every exemption whose expiry has passed, auditing each one.
+fn sweep_lapsed_exemptions(env: &Env, config: &mut ThrottleConfig) {
+    let current_time = env.ledger().timestamp();
+    for addr in config.exempt_addresses.clone().iter() {
+        if !is_exemption_active(env, &addr, current_time) {
+            expire_exemption(env, config, &addr);
+        }
+    }
+}
+
+fn expire_exemption(env: &Env, config: &mut ThrottleConfig, wallet_address: &Address) {
+    let expires_at: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ExemptionExpiry(wallet_address.clone()))
+        .unwrap_or(0);
+
+    let mut remaining = Vec::<Address>::new(env);
+    for addr in config.exempt_addresses.iter() {
+        if addr != *wallet_address {
+            remaining.push_back(addr);
+        }
+    }
+    config.exempt_addresses = remaining;
     env.storage()
+        .instance()
+        .set(&DataKey::ThrottleConfig, config);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ExemptionExpiry(wallet_address.clone()));
+
+    ThrottleEvents::exemption_expired(env, wallet_address, expires_at);
+    record_exemption_audit(
+        env,
+        Symbol::new(env, "exemption_expired"),
+        wallet_address,
+        expires_at,
+    );
+}
+
+fn record_exemption_audit(env: &Env, operation: Symbol, wallet_address: &Address, timestamp: u64) {
+    let metadata = (wallet_address.clone(), timestamp).to_xdr(env);
+    record_audit(env, operation, symbol_short!("success"), metadata);
+}
+
+/// Audits a violation with the wallet, transaction counts and block duration.
+fn record_violation_audit(
+    env: &Env,
+    violation: &ThrottleViolation,
+    violation_count: u32,
+    config: &ThrottleConfig,
+) {
+    let metadata = (
+        violation.wallet_address.clone(),
+        violation.transaction_count,
+        violation.max_allowed,
+        violation.window_size,
+        violation_count,
+        config.block_duration_seconds,
+    )
+        .to_xdr(env);
+    record_audit(
+        env,
+        Symbol::new(env, "throttle_violation"),
+        symbol_short!("blocked"),
+        metadata,
+    );
+}
+
+/// Audits a throttle lift with the wallet, its violation count and how long it
+/// was actually blocked.
+fn record_throttle_lift_audit(env: &Env, state: &WalletThrottleState, current_time: u64) {
+    let metadata = (
+        state.wallet_address.clone(),
+        state.violation_count,
+        current_time.saturating_sub(state.throttle_start_time),
+    )
+        .to_xdr(env);
+    record_audit(
+        env,
+        Symbol::new(env, "throttle_lifted"),
+        symbol_short!("success"),
+        metadata,
+    );
+}
+
+/// Best-effort write to the audit contract; a failing audit call must not block
+/// throttle checks, so failures surface as an `audit_err` event instead.
+fn record_audit(env: &Env, operation: Symbol, status: Symbol, metadata: Bytes) {
+    let audit_contract = match get_audit_contract(env) {
+        Some(addr) => addr,
+        None => return,
+    };
+
+    let client = AuditClient::new(env, &audit_contract);
+    let result = client.try_log_audit(
+        &env.current_contract_address(),
+        &operation,
+        &status,
+        &Some(metadata),
+    );
+    if result.is_err() {
+        ThrottleEvents::audit_failed(env, &operation);
+    }
+}
+
+/// The configuration in force: a scheduled change counts from its effective
+/// time on, even before a throttle check has persisted it.
+fn get_throttle_config(env: &Env) -> ThrottleConfig {
+    let config: ThrottleConfig = env
+        .storage()
         .instance()
         .get(&DataKey::ThrottleConfig)
-        .unwrap_or_else(|| panic_with_error!(env, ThrottleError::NotInitialized))
+        .unwrap_or_else(|| panic_with_error!(env, ThrottleError::NotInitialized));
+
+    match get_pending_config(env) {
+        Some(pending) if pending.effective_at <= env.ledger().timestamp() => ThrottleConfig {
+            exempt_addresses: config.exempt_addresses,
+            ..pending.config
+        },
+        _ => config,
+    }
+}
+
+/// Persists a scheduled configuration once its effective time has passed.
+fn activate_due_config(env: &Env) {
+    let Some(pending) = get_pending_config(env) else {
+        return;
+    };
+    if pending.effective_at > env.ledger().timestamp() {
+        return;
+    }
+
+    let config = get_throttle_config(env);
+    env.storage()
+        .instance()
+        .set(&DataKey::ThrottleConfig, &config);
+    env.storage().instance().remove(&DataKey::PendingConfig);
+    record_config_change(env, &config, &pending.admin, pending.effective_at);
+    ThrottleEvents::config_activated(env, &pending.admin, pending.effective_at);
+}
+
+fn record_config_change(env: &Env, config: &ThrottleConfig, admin: &Address, changed_at: u64) {
+    let mut history = get_config_history(env);
+    if history.len() >= MAX_CONFIG_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(ConfigChange {
+        config: config.clone(),
+        admin: admin.clone(),
+        changed_at,
+    });
+    env.storage()
+        .instance()
+        .set(&DataKey::ConfigHistory, &history);
 }
 
 fn get_wallet_throttle_state(env: &Env, wallet_address: &Address) -> WalletThrottleState {
@@ -589,8 +1005,20 @@ impl ThrottleContract {
         update_throttle_config(&env, caller, new_config);
     }
 
-    pub fn add_exempt_address(env: Env, caller: Address, wallet_address: Address) {
-        add_exempt_address(&env, caller, wallet_address);
+    pub fn schedule_config(env: Env, caller: Address, config: ThrottleConfig, effective_at: u64) {
+        schedule_config(&env, caller, config, effective_at);
+    }
+
+    pub fn get_pending_config(env: Env) -> Option<PendingConfig> {
+        get_pending_config(&env)
+    }
+
+    pub fn get_config_history(env: Env) -> Vec<ConfigChange> {
+        get_config_history(&env)
+    }
+
+    pub fn add_exempt_address(env: Env, caller: Address, wallet_address: Address, expires_at: u64) {
+        add_exempt_address(&env, caller, wallet_address, expires_at);
     }
 
     pub fn remove_exempt_address(env: Env, caller: Address, wallet_address: Address) {
@@ -604,6 +1032,14 @@ impl ThrottleContract {
         get_wallet_throttle_info(&env, wallet_address)
     }
 
+    pub fn get_unblock_time(env: Env, wallet_address: Address) -> Option<u64> {
+        get_unblock_time(&env, wallet_address)
+    }
+
+    pub fn get_window_reset_time(env: Env, wallet_address: Address) -> Option<u64> {
+        get_window_reset_time(&env, wallet_address)
+    }
+
     pub fn get_throttled_wallets(env: Env) -> Vec<Address> {
         get_throttled_wallets(&env)
     }
@@ -623,4 +1059,16 @@ impl ThrottleContract {
     pub fn get_throttle_config(env: Env) -> ThrottleConfig {
         get_throttle_config(&env)
     }
+
+    pub fn get_exemption_expiry(env: Env, wallet_address: Address) -> Option<u64> {
+        get_exemption_expiry(&env, wallet_address)
+    }
+
+    pub fn set_audit_contract(env: Env, caller: Address, audit_contract: Address) {
+        set_audit_contract(&env, caller, audit_contract);
+    }
+
+    pub fn get_audit_contract(env: Env) -> Option<Address> {
+        get_audit_contract(&env)
+    }
 }
@@ -1,8 +1,48 @@
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token,
-    Address, Bytes, Env, Map, String, Symbol, Vec, U256,
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec,
+    U256,
 };
 
+#[path = "pagination.rs"]
+mod pagination;
+
+use pagination::{page_range, read_page};
+
+/// Maximum number of contracts that can be registered as transfer hooks.
+pub const MAX_TRANSFER_HOOKS: u32 = 10;
+
+/// Maximum number of accounts processed by a single `sweep_dust` call.
+pub const MAX_DUST_SWEEP_ACCOUNTS: u32 = 50;
+
+/// Maximum number of guardians an account can register for recovery.
+pub const MAX_GUARDIANS: u32 = 10;
+
+/// Seconds between guardians reaching the threshold and the recovery becoming
+/// executable, giving the original key time to cancel.
+pub const RECOVERY_DELAY: u64 = 172_800;
+
+/// Seconds a recovery request stays open for approvals before it lapses,
+/// unless it reaches the approval threshold first.
+pub const RECOVERY_REQUEST_EXPIRY: u64 = 604_800;
+
+/// Maximum number of treasury burns that can be scheduled at the same time.
+pub const MAX_SCHEDULED_BURNS: u32 = 20;
+
+/// Highest inflation rate, in basis points of supply, a single epoch may mint.
+pub const MAX_INFLATION_BPS_PER_EPOCH: u32 = 1_000;
+
+/// Maximum number of buckets a packed-balance deployment may use.
+pub const MAX_BALANCE_BUCKETS: u32 = 65_536;
+
+/// Ledgers left before a mint or burn record read through the history
+/// getters has its TTL extended.
+pub const HISTORY_TTL_THRESHOLD: u32 = 100_000;
+
+/// Ledgers a mint or burn record read through the history getters is kept
+/// alive for.
+pub const HISTORY_TTL_EXTEND_TO: u32 = 500_000;
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -14,10 +54,34 @@ pub enum DataKey {
     BurnCap,
     TotalMinted,
     TotalBurned,
-    MintHistory(u64), // timestamp
-    BurnHistory(u64), // timestamp
+    MintHistory(u64), // position in the mint history
+    BurnHistory(u64), // position in the burn history
     Paused,
     Minters(Address), // authorized minters
+    TransferHooks,    // contracts notified after large transfers
+    HookThreshold,    // minimum transfer amount that triggers hooks
+    DustPolicy,
+    DustSweepConsent(Address), // dust threshold the holder agreed to have swept below
+    MaxTransferAmount,         // largest amount a single transfer may move
+    TransferLimitExempt(Address), // senders not subject to the transfer cap
+    Guardians(Address),        // social-recovery guardians of an account
+    Recovery(Address),         // pending recoveries of an account, one per proposed address
+    ComplianceOfficer,         // sole manager of freezes and the denylist
+    AuditContract,             // receives a log entry for every compliance action
+    Frozen(Address),           // accounts that cannot send tokens
+    Denylisted(Address),       // accounts that can neither send nor receive
+    ScheduledBurns,            // announced treasury burns not yet executed
+    LastScheduledBurnId,
+    BalanceStorage,     // how balances are laid out, fixed at initialization
+    BalanceBucket(u32), // packed balances of the holders hashed to this bucket
+    Features,           // optional behaviors enabled for this deployment
+    AllowanceAlert(Address, Address), // owner, spender -> remaining allowance that triggers a warning
+    InflationSchedule,                // supply growth minted to the staking reward pool
+    InflationEpochsMinted,            // epochs of the current schedule already minted
+    TravelRuleThreshold,              // transfers above this amount need a KYC reference
+    MinterList,                       // current minters, in the order they were added
+    MintHistoryLen,                   // number of mint records
+    BurnHistoryLen,                   // number of burn records
 }
 
 #[derive(Clone)]
@@ -52,6 +116,20 @@ pub struct BurnRecord {
     pub burner: Address,
 }
 
+/// Role and configuration snapshot of a deployment, for operational audits.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RolesView {
+    pub admin: Address,
+    pub minters: Vec<Address>,
+    pub compliance_officer: Option<Address>,
+    pub paused: bool,
+    pub mint_cap: Option<i128>,
+    pub burn_cap: Option<i128>,
+    pub max_transfer_amount: Option<i128>,
+    pub features: Vec<Feature>,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct TokenMetrics {
@@ -63,6 +141,84 @@ pub struct TokenMetrics {
     pub last_burn_time: Option<u64>,
 }
 
+/// Balances below `threshold` may be swept to `treasury` for consenting holders.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DustPolicy {
+    pub threshold: i128,
+    pub treasury: Address,
+}
+
+/// Guardians allowed to recover an account once `threshold` of them approve.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct GuardianConfig {
+    pub guardians: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// Proposed move of an account's balance to `new_address`. `executable_at` is
+/// set once the approval threshold is reached; until then the request lapses
+/// at `expires_at`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct RecoveryRequest {
+    pub new_address: Address,
+    pub approvals: Vec<Address>,
+    pub initiated_at: u64,
+    pub expires_at: u64,
+    pub executable_at: Option<u64>,
+}
+
+/// Treasury burn announced ahead of time. The amount is held by the token
+/// contract from scheduling until it is burned or the burn is cancelled.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ScheduledBurn {
+    pub burn_id: u64,
+    pub amount: i128,
+    pub scheduled_at: u64,
+    pub execute_after: u64,
+}
+
+/// Inflation minted to the staking reward pool. Epochs are counted from
+/// `started_at`, which is reset whenever the schedule is replaced.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct InflationSchedule {
+    pub rate_bps: u32,
+    pub epoch_length: u64,
+    pub reward_pool: Address,
+    pub started_at: u64,
+}
+
+/// Storage layout for balances, chosen once at initialization.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum BalanceStorage {
+    /// One persistent entry per holder.
+    PerAddress,
+    /// Balances grouped into the given number of persistent `Map` entries,
+    /// bucketed by address hash. Cuts the ledger entry count for deployments
+    /// with very many micro-balance accounts, at the cost of larger entries.
+    Packed(u32),
+}
+
+/// Optional behaviors a deployment can switch on. All start disabled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Feature {
+    /// Account freezes and the denylist.
+    Freezing,
+    /// Transfer hook notifications.
+    Hooks,
+    /// The per-transfer amount cap.
+    TransferLimits,
+    /// Fee sponsorship for transfers. No entry point consults it yet; the
+    /// flag is reserved so deployments can opt in before it ships.
+    Sponsorship,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum TokenType {
@@ -90,6 +246,46 @@ pub enum TokenError {
     InvalidRecipient = 12,
     ZeroAddress = 13,
     InvalidMinter = 14,
+    TooManyHooks = 15,
+    DustPolicyNotSet = 16,
+    BatchTooLarge = 17,
+    TransferLimitExceeded = 18,
+    InvalidGuardians = 19,
+    NotGuardian = 20,
+    RecoveryNotFound = 21,
+    RecoveryNotReady = 22,
+    RecoveryMismatch = 23,
+    NotComplianceOfficer = 24,
+    AccountFrozen = 25,
+    AccountDenylisted = 26,
+    RoleConflict = 27,
+    InvalidBurnSchedule = 28,
+    ScheduledBurnNotFound = 29,
+    BurnAlreadyDue = 30,
+    InvalidBalanceStorage = 31,
+    FeatureDisabled = 32,
+    InvalidInflationSchedule = 33,
+    InflationNotConfigured = 34,
+    InflationEpochNotElapsed = 35,
+    ComplianceReferenceRequired = 36,
+}
+
+/// Interface integrations implement to observe token flow.
+#[contractclient(name = "TransferHookClient")]
+pub trait TransferHook {
+    fn on_transfer(env: Env, token: Address, from: Address, to: Address, amount: i128);
+}
+
+/// Audit log sink that records compliance actions.
+#[contractclient(name = "AuditLogClient")]
+pub trait AuditLogger {
+    fn log_audit(
+        env: Env,
+        actor: Address,
+        operation: Symbol,
+        status: Symbol,
+        metadata: Option<Bytes>,
+    );
 }
 
 pub struct TokenEvents;
@@ -164,6 +360,222 @@ impl TokenEvents {
         );
     }
 
+    pub fn hook_registered(env: &Env, admin: &Address, hook: &Address) {
+        let topics = (symbol_short!("hook"), symbol_short!("added"));
+        env.events().publish(
+            topics,
+            (admin.clone(), hook.clone(), env.ledger().timestamp()),
+        );
+    }
+
+    pub fn hook_unregistered(env: &Env, admin: &Address, hook: &Address) {
+        let topics = (symbol_short!("hook"), symbol_short!("removed"));
+        env.events().publish(
+            topics,
+            (admin.clone(), hook.clone(), env.ledger().timestamp()),
+        );
+    }
+
+    pub fn hook_failed(env: &Env, hook: &Address, from: &Address, to: &Address, amount: i128) {
+        let topics = (symbol_short!("hook"), symbol_short!("failed"));
+        env.events().publish(
+            topics,
+            (
+                hook.clone(),
+                from.clone(),
+                to.clone(),
+                amount,
+                env.ledger().timestamp(),
+            ),
+        );
+    }
+
+    pub fn dust_policy_updated(env: &Env, admin: &Address, threshold: i128, treasury: &Address) {
+        let topics = (symbol_short!("dust"), symbol_short!("policy"));
+        env.events()
+            .publish(topics, (admin.clone(), threshold, treasury.clone()));
+    }
+
+    pub fn dust_consent(env: &Env, holder: &Address, consent: bool) {
+        let topics = (symbol_short!("dust"), symbol_short!("consent"));
+        env.events().publish(topics, (holder.clone(), consent));
+    }
+
+    pub fn dust_swept(env: &Env, account: &Address, amount: i128, treasury: &Address) {
+        let topics = (symbol_short!("dust"), symbol_short!("swept"));
+        env.events().publish(
+            topics,
+            (
+                account.clone(),
+                amount,
+                treasury.clone(),
+                env.ledger().timestamp(),
+            ),
+        );
+    }
+
+    pub fn max_transfer_updated(env: &Env, admin: &Address, max_amount: Option<i128>) {
+        let topics = (symbol_short!("limit"), symbol_short!("updated"));
+        env.events().publish(topics, (admin.clone(), max_amount));
+    }
+
+    pub fn transfer_limit_exempt(env: &Env, admin: &Address, account: &Address, exempt: bool) {
+        let topics = (symbol_short!("limit"), symbol_short!("exempt"));
+        env.events()
+            .publish(topics, (admin.clone(), account.clone(), exempt));
+    }
+
+    pub fn guardians_updated(
+        env: &Env,
+        account: &Address,
+        guardians: &Vec<Address>,
+        threshold: u32,
+    ) {
+        let topics = (symbol_short!("recovery"), symbol_short!("guardians"));
+        env.events()
+            .publish(topics, (account.clone(), guardians.clone(), threshold));
+    }
+
+    pub fn recovery_approved(
+        env: &Env,
+        account: &Address,
+        guardian: &Address,
+        new_address: &Address,
+        approvals: u32,
+    ) {
+        let topics = (symbol_short!("recovery"), symbol_short!("approved"));
+        env.events().publish(
+            topics,
+            (
+                account.clone(),
+                guardian.clone(),
+                new_address.clone(),
+                approvals,
+            ),
+        );
+    }
+
+    pub fn recovery_ready(env: &Env, account: &Address, new_address: &Address, executable_at: u64) {
+        let topics = (symbol_short!("recovery"), symbol_short!("ready"));
+        env.events().publish(
+            topics,
+            (account.clone(), new_address.clone(), executable_at),
+        );
+    }
+
+    pub fn recovery_cancelled(env: &Env, account: &Address, new_address: &Address) {
+        let topics = (symbol_short!("recovery"), symbol_short!("cancelled"));
+        env.events()
+            .publish(topics, (account.clone(), new_address.clone()));
+    }
+
+    pub fn recovery_executed(env: &Env, account: &Address, new_address: &Address, amount: i128) {
+        let topics = (symbol_short!("recovery"), symbol_short!("executed"));
+        env.events().publish(
+            topics,
+            (
+                account.clone(),
+                new_address.clone(),
+                amount,
+                env.ledger().timestamp(),
+            ),
+        );
+    }
+
+    pub fn compliance_officer_updated(env: &Env, admin: &Address, officer: &Address) {
+        let topics = (Symbol::new(env, "compliance"), symbol_short!("officer"));
+        env.events()
+            .publish(topics, (admin.clone(), officer.clone()));
+    }
+
+    pub fn audit_contract_updated(env: &Env, admin: &Address, audit: &Option<Address>) {
+        let topics = (Symbol::new(env, "compliance"), symbol_short!("audit"));
+        env.events().publish(topics, (admin.clone(), audit.clone()));
+    }
+
+    pub fn travel_rule_threshold_updated(env: &Env, officer: &Address, threshold: Option<i128>) {
+        let topics = (Symbol::new(env, "compliance"), symbol_short!("threshold"));
+        env.events().publish(topics, (officer.clone(), threshold));
+    }
+
+    pub fn compliance_reference(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+        ref_hash: &BytesN<32>,
+    ) {
+        let topics = (
+            Symbol::new(env, "compliance"),
+            symbol_short!("reference"),
+            ref_hash.clone(),
+        );
+        env.events().publish(
+            topics,
+            (from.clone(), to.clone(), amount, env.ledger().timestamp()),
+        );
+    }
+
+    pub fn compliance_action(env: &Env, officer: &Address, action: &Symbol, account: &Address) {
+        let topics = (Symbol::new(env, "compliance"), action.clone());
+        env.events().publish(
+            topics,
+            (officer.clone(), account.clone(), env.ledger().timestamp()),
+        );
+    }
+
+    pub fn burn_scheduled(env: &Env, burn_id: u64, amount: i128, execute_after: u64) {
+        let topics = (symbol_short!("burn"), symbol_short!("scheduled"), burn_id);
+        env.events().publish(topics, (amount, execute_after));
+    }
+
+    pub fn burn_cancelled(env: &Env, burn_id: u64, amount: i128) {
+        let topics = (symbol_short!("burn"), symbol_short!("cancelled"), burn_id);
+        env.events().publish(topics, amount);
+    }
+
+    pub fn burn_executed(env: &Env, burn_id: u64, amount: i128) {
+        let topics = (symbol_short!("burn"), symbol_short!("executed"), burn_id);
+        env.events().publish(topics, amount);
+    }
+
+    pub fn inflation_schedule_updated(
+        env: &Env,
+        admin: &Address,
+        schedule: &Option<InflationSchedule>,
+    ) {
+        let topics = (symbol_short!("inflation"), symbol_short!("schedule"));
+        env.events()
+            .publish(topics, (admin.clone(), schedule.clone()));
+    }
+
+    pub fn inflation_minted(env: &Env, epoch: u64, reward_pool: &Address, amount: i128) {
+        let topics = (symbol_short!("inflation"), symbol_short!("minted"), epoch);
+        env.events().publish(topics, (reward_pool.clone(), amount));
+    }
+
+    pub fn feature_updated(env: &Env, caller: &Address, feature: Feature, enabled: bool) {
+        let topics = (symbol_short!("feature"), symbol_short!("updated"));
+        env.events()
+            .publish(topics, (caller.clone(), feature, enabled));
+    }
+
+    pub fn allowance_low(
+        env: &Env,
+        owner: &Address,
+        spender: &Address,
+        remaining: i128,
+        threshold: i128,
+    ) {
+        let topics = (
+            symbol_short!("allowance"),
+            symbol_short!("low"),
+            owner.clone(),
+        );
+        env.events()
+            .publish(topics, (spender.clone(), remaining, threshold));
+    }
+
     pub fn minter_removed(env: &Env, admin: &Address, minter: &Address) {
         let topics = (symbol_short!("minter"), symbol_short!("removed"));
         env.events().publish(
@@ -171,8 +583,17 @@ impl TokenEvents {
             (admin.clone(), minter.clone(), env.ledger().timestamp()),
         );
     }
+
+    pub fn config_exported(env: &Env, admin: &Address, roles: &RolesView) {
+        let topics = (symbol_short!("config"), symbol_short!("exported"));
+        env.events().publish(
+            topics,
+            (admin.clone(), roles.clone(), env.ledger().timestamp()),
+        );
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_token(
     env: &Env,
     admin: Address,
@@ -181,10 +602,16 @@ pub fn initialize_token(
     decimals: u32,
     mint_cap: Option<i128>,
     burn_cap: Option<i128>,
+    balance_storage: BalanceStorage,
 ) {
     if env.storage().instance().has(&DataKey::Admin) {
         panic_with_error!(env, TokenError::AlreadyInitialized);
     }
+    if let BalanceStorage::Packed(buckets) = balance_storage {
+        if buckets == 0 || buckets > MAX_BALANCE_BUCKETS {
+            panic_with_error!(env, TokenError::InvalidBalanceStorage);
+        }
+    }
 
     // Validate inputs
     if name.is_empty() {
@@ -203,9 +630,15 @@ pub fn initialize_token(
     env.storage().instance().set(&DataKey::TotalMinted, &0i128);
     env.storage().instance().set(&DataKey::TotalBurned, &0i128);
     env.storage().instance().set(&DataKey::Paused, &false);
+    env.storage()
+        .instance()
+        .set(&DataKey::BalanceStorage, &balance_storage);
     env.storage()
         .instance()
         .set(&DataKey::Minters(admin.clone()), &true); // Admin is always a minter
+    env.storage()
+        .instance()
+        .set(&DataKey::MinterList, &vec![env, admin.clone()]);
 
     // Set caps if provided
     if let Some(cap) = mint_cap {
@@ -272,6 +705,9 @@ pub fn add_minter(env: &Env, admin: Address, minter: Address) {
         env.storage()
             .instance()
             .set(&DataKey::Minters(minter.clone()), &true);
+        let mut minters = get_minters(env);
+        minters.push_back(minter.clone());
+        env.storage().instance().set(&DataKey::MinterList, &minters);
         TokenEvents::minter_added(env, &admin, &minter);
     }
 }
@@ -289,19 +725,35 @@ pub fn remove_minter(env: &Env, admin: Address, minter: Address) {
         env.storage()
             .instance()
             .remove(&DataKey::Minters(minter.clone()));
+        let mut minters = get_minters(env);
+        if let Some(index) = minters.first_index_of(&minter) {
+            minters.remove(index);
+            env.storage().instance().set(&DataKey::MinterList, &minters);
+        }
         TokenEvents::minter_removed(env, &admin, &minter);
     }
 }
 
+/// Current minters, in the order they were added. The admin is listed first.
+pub fn get_minters(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::MinterList)
+        .unwrap_or(Vec::new(env))
+}
+
 pub fn mint(env: &Env, minter: Address, to: Address, amount: i128) -> U256 {
     require_minter(env, &minter);
+    mint_tokens(env, &to, amount, &minter)
+}
 
+fn mint_tokens(env: &Env, to: &Address, amount: i128, minter: &Address) -> U256 {
     // Validate inputs
     if amount <= 0 {
         panic_with_error!(env, TokenError::InvalidAmount);
     }
 
-    if to == env.current_contract_address() {
+    if *to == env.current_contract_address() {
         panic_with_error!(env, TokenError::ZeroAddress);
     }
 
@@ -310,6 +762,8 @@ pub fn mint(env: &Env, minter: Address, to: Address, amount: i128) -> U256 {
         panic_with_error!(env, TokenError::Paused);
     }
 
+    enforce_compliance(env, None, Some(to));
+
     // Check mint cap
     let current_supply = get_total_supply(env);
     let new_supply = current_supply
@@ -324,14 +778,12 @@ pub fn mint(env: &Env, minter: Address, to: Address, amount: i128) -> U256 {
     }
 
     // Update balances and supply
-    let current_balance = get_balance(env, &to);
+    let current_balance = get_balance(env, to);
     let new_balance = current_balance
         .checked_add(amount)
         .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(to.clone()), &new_balance);
+    set_balance(env, to, new_balance);
     env.storage()
         .instance()
         .set(&DataKey::TokenSupply, &new_supply);
@@ -355,21 +807,89 @@ pub fn mint(env: &Env, minter: Address, to: Address, amount: i128) -> U256 {
         transaction_id: transaction_id.clone(),
     };
 
-    env.storage().persistent().set(
-        &DataKey::MintHistory(env.ledger().timestamp()),
-        &mint_record,
-    );
+    let position = next_history_position(env, &DataKey::MintHistoryLen);
+    env.storage()
+        .persistent()
+        .set(&DataKey::MintHistory(position), &mint_record);
 
     // Emit events
-    TokenEvents::mint(env, &to, amount, &minter);
+    TokenEvents::mint(env, to, amount, minter);
     TokenEvents::supply_changed(env, new_supply, amount, "mint");
 
     transaction_id
 }
 
+fn history_len(env: &Env, len_key: &DataKey) -> u64 {
+    env.storage().instance().get(len_key).unwrap_or(0)
+}
+
+/// Reserves the next position in a mint or burn history.
+fn next_history_position(env: &Env, len_key: &DataKey) -> u64 {
+    let position = history_len(env, len_key);
+    env.storage().instance().set(len_key, &(position + 1));
+    position
+}
+
+/// Mint records from position `start`, oldest first. Pages hold at most
+/// `pagination::MAX_PAGE_SIZE` records.
+pub fn get_mint_history(env: &Env, start: u64, limit: u32) -> Vec<MintRecord> {
+    let len = history_len(env, &DataKey::MintHistoryLen);
+    read_page(
+        env,
+        page_range(start, limit, len).map(DataKey::MintHistory),
+        HISTORY_TTL_THRESHOLD,
+        HISTORY_TTL_EXTEND_TO,
+    )
+}
+
+/// Burn records from position `start`, oldest first. Pages hold at most
+/// `pagination::MAX_PAGE_SIZE` records.
+pub fn get_burn_history(env: &Env, start: u64, limit: u32) -> Vec<BurnRecord> {
+    let len = history_len(env, &DataKey::BurnHistoryLen);
+    read_page(
+        env,
+        page_range(start, limit, len).map(DataKey::BurnHistory),
+        HISTORY_TTL_THRESHOLD,
+        HISTORY_TTL_EXTEND_TO,
+    )
+}
+
 pub fn burn(env: &Env, from: Address, amount: i128) -> U256 {
     from.require_auth();
 
+    burn_tokens(env, &from, amount, &from)
+}
+
+/// Burns `amount` from `from` on behalf of `spender`, consuming the allowance
+/// `from` granted to `spender`.
+pub fn burn_from(env: &Env, spender: Address, from: Address, amount: i128) -> U256 {
+    spender.require_auth();
+
+    // Validate inputs
+    if amount <= 0 {
+        panic_with_error!(env, TokenError::InvalidAmount);
+    }
+
+    // Check if paused
+    if is_paused(env) {
+        panic_with_error!(env, TokenError::Paused);
+    }
+
+    // Check allowance
+    let allowance = get_allowance(env, &from, &spender);
+    if allowance < amount {
+        panic_with_error!(env, TokenError::InsufficientAllowance);
+    }
+
+    let transaction_id = burn_tokens(env, &from, amount, &spender);
+    let new_allowance = spend_allowance(env, &from, &spender, allowance, amount);
+
+    TokenEvents::approval(env, &from, &spender, new_allowance);
+
+    transaction_id
+}
+
+fn burn_tokens(env: &Env, from: &Address, amount: i128, burner: &Address) -> U256 {
     // Validate inputs
     if amount <= 0 {
         panic_with_error!(env, TokenError::InvalidAmount);
@@ -380,8 +900,10 @@ pub fn burn(env: &Env, from: Address, amount: i128) -> U256 {
         panic_with_error!(env, TokenError::Paused);
     }
 
+    enforce_compliance(env, Some(from), None);
+
     // Check balance
-    let current_balance = get_balance(env, &from);
+    let current_balance = get_balance(env, from);
     if current_balance < amount {
         panic_with_error!(env, TokenError::InsufficientBalance);
     }
@@ -408,9 +930,7 @@ pub fn burn(env: &Env, from: Address, amount: i128) -> U256 {
         .checked_sub(amount)
         .unwrap_or_else(|| panic_with_error!(env, TokenError::Underflow));
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(from.clone()), &new_balance);
+    set_balance(env, from, new_balance);
     env.storage()
         .instance()
         .set(&DataKey::TokenSupply, &new_supply);
@@ -420,13 +940,6 @@ pub fn burn(env: &Env, from: Address, amount: i128) -> U256 {
         .instance()
         .set(&DataKey::TotalBurned, &new_total_burned);
 
-    // Remove balance if zero to save storage
-    if new_balance == 0 {
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Balance(from.clone()));
-    }
-
     // Record burn transaction
     let transaction_id = generate_transaction_id(env);
     let burn_record = BurnRecord {
@@ -434,22 +947,45 @@ pub fn burn(env: &Env, from: Address, amount: i128) -> U256 {
         amount,
         timestamp: env.ledger().timestamp(),
         transaction_id: transaction_id.clone(),
-        burner: from.clone(),
+        burner: burner.clone(),
     };
 
-    env.storage().persistent().set(
-        &DataKey::BurnHistory(env.ledger().timestamp()),
-        &burn_record,
-    );
+    let position = next_history_position(env, &DataKey::BurnHistoryLen);
+    env.storage()
+        .persistent()
+        .set(&DataKey::BurnHistory(position), &burn_record);
 
     // Emit events
-    TokenEvents::burn(env, &from, amount, &from);
+    TokenEvents::burn(env, from, amount, burner);
     TokenEvents::supply_changed(env, new_supply, -amount, "burn");
 
     transaction_id
 }
 
 pub fn transfer(env: &Env, from: Address, to: Address, amount: i128) {
+    transfer_tokens(env, from, to, amount, None);
+}
+
+/// Transfer carrying a reference to the off-chain KYC payload (its hash), as
+/// required by the travel rule for amounts above the configured threshold.
+/// The reference is emitted for regulator-facing indexers.
+pub fn transfer_with_compliance(
+    env: &Env,
+    from: Address,
+    to: Address,
+    amount: i128,
+    ref_hash: BytesN<32>,
+) {
+    transfer_tokens(env, from, to, amount, Some(ref_hash));
+}
+
+fn transfer_tokens(
+    env: &Env,
+    from: Address,
+    to: Address,
+    amount: i128,
+    ref_hash: Option<BytesN<32>>,
+) {
     from.require_auth();
 
     // Validate inputs
@@ -466,6 +1002,10 @@ pub fn transfer(env: &Env, from: Address, to: Address, amount: i128) {
         panic_with_error!(env, TokenError::Paused);
     }
 
+    enforce_compliance(env, Some(&from), Some(&to));
+    enforce_transfer_limit(env, &from, amount);
+    enforce_travel_rule(env, amount, ref_hash.is_some());
+
     // Check balance
     let from_balance = get_balance(env, &from);
     if from_balance < amount {
@@ -481,22 +1021,16 @@ pub fn transfer(env: &Env, from: Address, to: Address, amount: i128) {
         .checked_add(amount)
         .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(from.clone()), &new_from_balance);
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(to.clone()), &new_to_balance);
-
-    // Remove from balance if zero to save storage
-    if new_from_balance == 0 {
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Balance(from.clone()));
-    }
+    set_balance(env, &from, new_from_balance);
+    set_balance(env, &to, new_to_balance);
 
     // Emit event
     TokenEvents::transfer(env, &from, &to, amount);
+    if let Some(ref_hash) = &ref_hash {
+        TokenEvents::compliance_reference(env, &from, &to, amount, ref_hash);
+    }
+
+    notify_transfer_hooks(env, &from, &to, amount);
 }
 
 pub fn approve(env: &Env, owner: Address, spender: Address, amount: i128) {
@@ -526,6 +1060,30 @@ pub fn approve(env: &Env, owner: Address, spender: Address, amount: i128) {
 }
 
 pub fn transfer_from(env: &Env, spender: Address, from: Address, to: Address, amount: i128) {
+    transfer_from_tokens(env, spender, from, to, amount, None);
+}
+
+/// Allowance transfer carrying a travel-rule reference, like
+/// `transfer_with_compliance`.
+pub fn transfer_from_with_compliance(
+    env: &Env,
+    spender: Address,
+    from: Address,
+    to: Address,
+    amount: i128,
+    ref_hash: BytesN<32>,
+) {
+    transfer_from_tokens(env, spender, from, to, amount, Some(ref_hash));
+}
+
+fn transfer_from_tokens(
+    env: &Env,
+    spender: Address,
+    from: Address,
+    to: Address,
+    amount: i128,
+    ref_hash: Option<BytesN<32>>,
+) {
     spender.require_auth();
 
     // Validate inputs
@@ -542,6 +1100,10 @@ pub fn transfer_from(env: &Env, spender: Address, from: Address, to: Address, am
         panic_with_error!(env, TokenError::Paused);
     }
 
+    enforce_compliance(env, Some(&from), Some(&to));
+    enforce_transfer_limit(env, &from, amount);
+    enforce_travel_rule(env, amount, ref_hash.is_some());
+
     // Check allowance
     let allowance = get_allowance(env, &from, &spender);
     if allowance < amount {
@@ -563,39 +1125,921 @@ pub fn transfer_from(env: &Env, spender: Address, from: Address, to: Address, am
         .checked_add(amount)
         .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(from.clone()), &new_from_balance);
-    env.storage()
+    set_balance(env, &from, new_from_balance);
+    set_balance(env, &to, new_to_balance);
+
+    // Update allowance
+    let new_allowance = spend_allowance(env, &from, &spender, allowance, amount);
+
+    // Emit events
+    TokenEvents::transfer(env, &from, &to, amount);
+    TokenEvents::approval(env, &from, &spender, new_allowance);
+    if let Some(ref_hash) = &ref_hash {
+        TokenEvents::compliance_reference(env, &from, &to, amount, ref_hash);
+    }
+
+    notify_transfer_hooks(env, &from, &to, amount);
+}
+
+fn spend_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    allowance: i128,
+    amount: i128,
+) -> i128 {
+    let new_allowance = allowance
+        .checked_sub(amount)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Underflow));
+
+    if new_allowance == 0 {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Allowance(from.clone(), spender.clone()));
+    } else {
+        env.storage().persistent().set(
+            &DataKey::Allowance(from.clone(), spender.clone()),
+            &new_allowance,
+        );
+    }
+
+    if let Some(threshold) = get_allowance_alert(env, from, spender) {
+        if allowance >= threshold && new_allowance < threshold {
+            TokenEvents::allowance_low(env, from, spender, new_allowance, threshold);
+        }
+    }
+
+    new_allowance
+}
+
+/// Warns the owner with an `allowance low` event when a spend leaves the
+/// spender's allowance below `threshold`. `None` removes the warning.
+pub fn set_allowance_alert(env: &Env, owner: Address, spender: Address, threshold: Option<i128>) {
+    owner.require_auth();
+
+    let key = DataKey::AllowanceAlert(owner, spender);
+    match threshold {
+        Some(threshold) if threshold <= 0 => panic_with_error!(env, TokenError::InvalidAmount),
+        Some(threshold) => env.storage().persistent().set(&key, &threshold),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_allowance_alert(env: &Env, owner: &Address, spender: &Address) -> Option<i128> {
+    env.storage()
         .persistent()
-        .set(&DataKey::Balance(to.clone()), &new_to_balance);
+        .get(&DataKey::AllowanceAlert(owner.clone(), spender.clone()))
+}
+
+pub fn register_transfer_hook(env: &Env, admin: Address, hook: Address) {
+    require_admin(env, &admin);
+    require_feature(env, Feature::Hooks);
+
+    let mut hooks = get_transfer_hooks(env);
+    if hooks.contains(&hook) {
+        return;
+    }
+    if hooks.len() >= MAX_TRANSFER_HOOKS {
+        panic_with_error!(env, TokenError::TooManyHooks);
+    }
+
+    hooks.push_back(hook.clone());
+    env.storage()
+        .instance()
+        .set(&DataKey::TransferHooks, &hooks);
+    TokenEvents::hook_registered(env, &admin, &hook);
+}
+
+pub fn unregister_transfer_hook(env: &Env, admin: Address, hook: Address) {
+    require_admin(env, &admin);
+
+    let hooks = get_transfer_hooks(env);
+    if let Some(index) = hooks.first_index_of(&hook) {
+        let mut hooks = hooks;
+        hooks.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::TransferHooks, &hooks);
+        TokenEvents::hook_unregistered(env, &admin, &hook);
+    }
+}
+
+pub fn set_hook_threshold(env: &Env, admin: Address, threshold: i128) {
+    require_admin(env, &admin);
+
+    if threshold < 0 {
+        panic_with_error!(env, TokenError::InvalidAmount);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::HookThreshold, &threshold);
+}
+
+pub fn get_transfer_hooks(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::TransferHooks)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_hook_threshold(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::HookThreshold)
+        .unwrap_or(0)
+}
+
+/// Notifies registered hooks of a transfer at or above the threshold.
+/// Hooks are called with try semantics so a failing integration can never
+/// block a transfer; failures are surfaced as `hook failed` events.
+fn notify_transfer_hooks(env: &Env, from: &Address, to: &Address, amount: i128) {
+    if !is_feature_enabled(env, Feature::Hooks) || amount < get_hook_threshold(env) {
+        return;
+    }
+
+    let token = env.current_contract_address();
+    for hook in get_transfer_hooks(env).iter() {
+        let client = TransferHookClient::new(env, &hook);
+        if client.try_on_transfer(&token, from, to, &amount).is_err() {
+            TokenEvents::hook_failed(env, &hook, from, to, amount);
+        }
+    }
+}
+
+pub fn set_max_transfer_amount(env: &Env, admin: Address, max_amount: Option<i128>) {
+    require_admin(env, &admin);
+    require_feature(env, Feature::TransferLimits);
+
+    match max_amount {
+        Some(max) if max <= 0 => panic_with_error!(env, TokenError::InvalidAmount),
+        Some(max) => env
+            .storage()
+            .instance()
+            .set(&DataKey::MaxTransferAmount, &max),
+        None => env.storage().instance().remove(&DataKey::MaxTransferAmount),
+    }
+    TokenEvents::max_transfer_updated(env, &admin, max_amount);
+}
+
+pub fn get_max_transfer_amount(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::MaxTransferAmount)
+}
+
+pub fn set_transfer_limit_exempt(env: &Env, admin: Address, account: Address, exempt: bool) {
+    require_admin(env, &admin);
+    require_feature(env, Feature::TransferLimits);
+
+    let key = DataKey::TransferLimitExempt(account.clone());
+    if exempt {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    TokenEvents::transfer_limit_exempt(env, &admin, &account, exempt);
+}
+
+pub fn is_transfer_limit_exempt(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TransferLimitExempt(account.clone()))
+        .unwrap_or(false)
+}
+
+/// Circuit breaker against drain attempts from a compromised key: rejects any
+/// single transfer above the configured maximum unless the sender is exempt.
+/// The refusal surfaces as `TransferLimitExceeded`; nothing is published since
+/// the failed call rolls back any events it emitted.
+fn enforce_transfer_limit(env: &Env, from: &Address, amount: i128) {
+    if !is_feature_enabled(env, Feature::TransferLimits) {
+        return;
+    }
+    let Some(max_amount) = get_max_transfer_amount(env) else {
+        return;
+    };
+    if amount <= max_amount || is_transfer_limit_exempt(env, from) {
+        return;
+    }
+
+    panic_with_error!(env, TokenError::TransferLimitExceeded);
+}
+
+/// Appoints the compliance officer. The admin cannot hold the role itself, so
+/// treasury control and compliance control stay with different keys.
+pub fn set_compliance_officer(env: &Env, admin: Address, officer: Address) {
+    require_admin(env, &admin);
+    if officer == admin {
+        panic_with_error!(env, TokenError::RoleConflict);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::ComplianceOfficer, &officer);
+    TokenEvents::compliance_officer_updated(env, &admin, &officer);
+}
+
+pub fn get_compliance_officer(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::ComplianceOfficer)
+}
+
+pub fn require_compliance_officer(env: &Env, caller: &Address) {
+    caller.require_auth();
+    if get_compliance_officer(env) != Some(caller.clone()) {
+        panic_with_error!(env, TokenError::NotComplianceOfficer);
+    }
+}
+
+pub fn set_audit_contract(env: &Env, admin: Address, audit: Option<Address>) {
+    require_admin(env, &admin);
+
+    match &audit {
+        Some(audit) => env.storage().instance().set(&DataKey::AuditContract, audit),
+        None => env.storage().instance().remove(&DataKey::AuditContract),
+    }
+    TokenEvents::audit_contract_updated(env, &admin, &audit);
+}
+
+pub fn get_audit_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::AuditContract)
+}
+
+/// Sets the amount above which transfers must carry a compliance reference,
+/// or removes the requirement when `None`.
+pub fn set_travel_rule_threshold(env: &Env, officer: Address, threshold: Option<i128>) {
+    require_compliance_officer(env, &officer);
+
+    match threshold {
+        Some(threshold) if threshold <= 0 => panic_with_error!(env, TokenError::InvalidAmount),
+        Some(threshold) => env
+            .storage()
+            .instance()
+            .set(&DataKey::TravelRuleThreshold, &threshold),
+        None => env
+            .storage()
+            .instance()
+            .remove(&DataKey::TravelRuleThreshold),
+    }
+    TokenEvents::travel_rule_threshold_updated(env, &officer, threshold);
+}
+
+pub fn get_travel_rule_threshold(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::TravelRuleThreshold)
+}
+
+pub fn freeze_account(env: &Env, officer: Address, account: Address) {
+    set_compliance_flag(env, &officer, DataKey::Frozen(account.clone()), true);
+    log_compliance_action(env, &officer, symbol_short!("frozen"), &account);
+}
+
+pub fn unfreeze_account(env: &Env, officer: Address, account: Address) {
+    set_compliance_flag(env, &officer, DataKey::Frozen(account.clone()), false);
+    log_compliance_action(env, &officer, symbol_short!("unfrozen"), &account);
+}
+
+pub fn add_to_denylist(env: &Env, officer: Address, account: Address) {
+    set_compliance_flag(env, &officer, DataKey::Denylisted(account.clone()), true);
+    log_compliance_action(env, &officer, symbol_short!("denied"), &account);
+}
+
+pub fn remove_from_denylist(env: &Env, officer: Address, account: Address) {
+    set_compliance_flag(env, &officer, DataKey::Denylisted(account.clone()), false);
+    log_compliance_action(env, &officer, symbol_short!("delisted"), &account);
+}
+
+pub fn is_frozen(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Frozen(account.clone()))
+        .unwrap_or(false)
+}
+
+pub fn is_denylisted(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Denylisted(account.clone()))
+        .unwrap_or(false)
+}
+
+fn set_compliance_flag(env: &Env, officer: &Address, key: DataKey, value: bool) {
+    require_compliance_officer(env, officer);
+    require_feature(env, Feature::Freezing);
+    if value {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Publishes the compliance event and, when an audit contract is configured,
+/// records the action there with the affected account as XDR metadata. The
+/// audit call is not wrapped in try semantics: an action that cannot be
+/// logged does not happen.
+fn log_compliance_action(env: &Env, officer: &Address, action: Symbol, account: &Address) {
+    TokenEvents::compliance_action(env, officer, &action, account);
+
+    if let Some(audit) = get_audit_contract(env) {
+        AuditLogClient::new(env, &audit).log_audit(
+            officer,
+            &action,
+            &symbol_short!("success"),
+            &Some(account.clone().to_xdr(env)),
+        );
+    }
+}
+
+/// Frozen accounts cannot send; denylisted accounts can neither send nor receive.
+fn enforce_compliance(env: &Env, from: Option<&Address>, to: Option<&Address>) {
+    if !is_feature_enabled(env, Feature::Freezing) {
+        return;
+    }
+    if let Some(from) = from {
+        if is_denylisted(env, from) {
+            panic_with_error!(env, TokenError::AccountDenylisted);
+        }
+        if is_frozen(env, from) {
+            panic_with_error!(env, TokenError::AccountFrozen);
+        }
+    }
+    if let Some(to) = to {
+        if is_denylisted(env, to) {
+            panic_with_error!(env, TokenError::AccountDenylisted);
+        }
+    }
+}
+
+/// Whether `enforce_compliance` would stop `account` from sending.
+fn is_restricted_sender(env: &Env, account: &Address) -> bool {
+    is_feature_enabled(env, Feature::Freezing)
+        && (is_denylisted(env, account) || is_frozen(env, account))
+}
+
+/// Transfers above the travel-rule threshold must go through
+/// `transfer_with_compliance` or `transfer_from_with_compliance`; plain
+/// transfers and `transfer_from` cannot carry a reference and are rejected.
+fn enforce_travel_rule(env: &Env, amount: i128, has_reference: bool) {
+    if has_reference {
+        return;
+    }
+    if let Some(threshold) = get_travel_rule_threshold(env) {
+        if amount > threshold {
+            panic_with_error!(env, TokenError::ComplianceReferenceRequired);
+        }
+    }
+}
+
+pub fn set_dust_policy(env: &Env, admin: Address, threshold: i128, treasury: Address) {
+    require_admin(env, &admin);
+
+    if threshold <= 0 {
+        panic_with_error!(env, TokenError::InvalidAmount);
+    }
+    if treasury == env.current_contract_address() {
+        panic_with_error!(env, TokenError::InvalidRecipient);
+    }
+
+    let policy = DustPolicy {
+        threshold,
+        treasury: treasury.clone(),
+    };
+    env.storage().instance().set(&DataKey::DustPolicy, &policy);
+    TokenEvents::dust_policy_updated(env, &admin, threshold, &treasury);
+}
+
+pub fn get_dust_policy(env: &Env) -> Option<DustPolicy> {
+    env.storage().instance().get(&DataKey::DustPolicy)
+}
+
+/// Records whether a holder consents to having dust balances swept. Consent
+/// covers the dust threshold in force when it is given; raising the
+/// threshold later does not sweep larger balances without fresh consent.
+pub fn set_dust_sweep_consent(env: &Env, holder: Address, consent: bool) {
+    holder.require_auth();
+
+    let key = DataKey::DustSweepConsent(holder.clone());
+    if consent {
+        let policy = get_dust_policy(env)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::DustPolicyNotSet));
+        env.storage().persistent().set(&key, &policy.threshold);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    TokenEvents::dust_consent(env, &holder, consent);
+}
+
+pub fn has_dust_sweep_consent(env: &Env, holder: &Address) -> bool {
+    get_dust_sweep_consent(env, holder).is_some()
+}
+
+/// Returns the dust threshold a holder consented to, if they opted in.
+pub fn get_dust_sweep_consent(env: &Env, holder: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DustSweepConsent(holder.clone()))
+}
+
+/// Moves balances below the dust threshold to the treasury for consenting
+/// holders, removing their balance entries. A balance is only swept below
+/// the lower of the current threshold and the one the holder consented to.
+/// Accounts that have not opted in, hold nothing, hold at least that
+/// threshold, or are frozen or denylisted are skipped.
+pub fn sweep_dust(env: &Env, admin: Address, accounts: Vec<Address>) -> i128 {
+    require_admin(env, &admin);
+
+    if is_paused(env) {
+        panic_with_error!(env, TokenError::Paused);
+    }
+    if accounts.len() > MAX_DUST_SWEEP_ACCOUNTS {
+        panic_with_error!(env, TokenError::BatchTooLarge);
+    }
+    let policy = get_dust_policy(env)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::DustPolicyNotSet));
+    enforce_compliance(env, None, Some(&policy.treasury));
+
+    let mut total_swept: i128 = 0;
+    for account in accounts.iter() {
+        if account == policy.treasury || is_restricted_sender(env, &account) {
+            continue;
+        }
+        let threshold = match get_dust_sweep_consent(env, &account) {
+            Some(consented) => consented.min(policy.threshold),
+            None => continue,
+        };
+        let balance = get_balance(env, &account);
+        if balance <= 0 || balance >= threshold {
+            continue;
+        }
+
+        set_balance(env, &account, 0);
+        total_swept = total_swept
+            .checked_add(balance)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+        TokenEvents::dust_swept(env, &account, balance, &policy.treasury);
+    }
+
+    if total_swept > 0 {
+        let treasury_balance = get_balance(env, &policy.treasury)
+            .checked_add(total_swept)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+        set_balance(env, &policy.treasury, treasury_balance);
+    }
+
+    total_swept
+}
+
+/// Registers the guardians that can collectively recover `account`. Replacing
+/// the guardian set discards any pending recovery.
+pub fn set_guardians(env: &Env, account: Address, guardians: Vec<Address>, threshold: u32) {
+    account.require_auth();
+
+    if guardians.len() > MAX_GUARDIANS || threshold == 0 || threshold > guardians.len() {
+        panic_with_error!(env, TokenError::InvalidGuardians);
+    }
+    for (i, guardian) in guardians.iter().enumerate() {
+        let is_repeat = guardians.iter().skip(i + 1).any(|other| other == guardian);
+        if guardian == account || is_repeat {
+            panic_with_error!(env, TokenError::InvalidGuardians);
+        }
+    }
+
+    let config = GuardianConfig {
+        guardians: guardians.clone(),
+        threshold,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Guardians(account.clone()), &config);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Recovery(account.clone()));
+    TokenEvents::guardians_updated(env, &account, &guardians, threshold);
+}
+
+pub fn get_guardians(env: &Env, account: &Address) -> Option<GuardianConfig> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Guardians(account.clone()))
+}
+
+/// Open recovery requests of `account`, one per proposed address. Requests
+/// that lapsed before reaching the threshold are left out.
+pub fn get_recovery_requests(env: &Env, account: &Address) -> Vec<RecoveryRequest> {
+    let now = env.ledger().timestamp();
+    let stored: Vec<RecoveryRequest> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Recovery(account.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let mut requests = Vec::new(env);
+    for request in stored.iter() {
+        if request.executable_at.is_some() || now < request.expires_at {
+            requests.push_back(request);
+        }
+    }
+    requests
+}
+
+/// The recovery that has reached the threshold, or else the open request
+/// with the most approvals.
+pub fn get_recovery(env: &Env, account: &Address) -> Option<RecoveryRequest> {
+    let mut leading: Option<RecoveryRequest> = None;
+    for request in get_recovery_requests(env, account).iter() {
+        let leads = match &leading {
+            None => true,
+            Some(current) => {
+                request.executable_at.is_some()
+                    || (current.executable_at.is_none()
+                        && request.approvals.len() > current.approvals.len())
+            }
+        };
+        if leads {
+            leading = Some(request);
+        }
+    }
+    leading
+}
+
+/// Records a guardian's approval to move `account`'s balance to
+/// `new_address`. Approvals are tracked per proposed address and a guardian
+/// backs one address at a time, so a stray request cannot block the others.
+/// The first approval opens a request that lapses after
+/// `RECOVERY_REQUEST_EXPIRY`; once one reaches the threshold the competing
+/// requests are dropped and it becomes executable after `RECOVERY_DELAY`.
+pub fn approve_recovery(env: &Env, guardian: Address, account: Address, new_address: Address) {
+    guardian.require_auth();
+
+    let config = get_guardians(env, &account)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::NotGuardian));
+    if !config.guardians.contains(&guardian) {
+        panic_with_error!(env, TokenError::NotGuardian);
+    }
+    if new_address == account || new_address == env.current_contract_address() {
+        panic_with_error!(env, TokenError::InvalidRecipient);
+    }
+
+    let now = env.ledger().timestamp();
+    let mut request = RecoveryRequest {
+        new_address: new_address.clone(),
+        approvals: Vec::new(env),
+        initiated_at: now,
+        expires_at: now + RECOVERY_REQUEST_EXPIRY,
+        executable_at: None,
+    };
+    let mut others: Vec<RecoveryRequest> = Vec::new(env);
+    for existing in get_recovery_requests(env, &account).iter() {
+        if existing.new_address == new_address {
+            request = existing;
+        } else if existing.executable_at.is_some() {
+            panic_with_error!(env, TokenError::RecoveryMismatch);
+        } else {
+            others.push_back(existing);
+        }
+    }
+    if request.approvals.contains(&guardian) {
+        return;
+    }
+
+    // Backing this address withdraws the guardian's approval elsewhere
+    let mut requests: Vec<RecoveryRequest> = Vec::new(env);
+    for mut other in others.iter() {
+        if let Some(index) = other.approvals.first_index_of(&guardian) {
+            other.approvals.remove(index);
+        }
+        if !other.approvals.is_empty() {
+            requests.push_back(other);
+        }
+    }
+
+    request.approvals.push_back(guardian.clone());
+    TokenEvents::recovery_approved(
+        env,
+        &account,
+        &guardian,
+        &new_address,
+        request.approvals.len(),
+    );
+
+    if request.executable_at.is_none() && request.approvals.len() >= config.threshold {
+        let executable_at = now + RECOVERY_DELAY;
+        request.executable_at = Some(executable_at);
+        TokenEvents::recovery_ready(env, &account, &new_address, executable_at);
+        requests = Vec::new(env);
+    }
+    requests.push_back(request);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Recovery(account), &requests);
+}
+
+/// Lets the original key abort every pending recovery.
+pub fn cancel_recovery(env: &Env, account: Address) {
+    account.require_auth();
+
+    let requests = get_recovery_requests(env, &account);
+    if requests.is_empty() {
+        panic_with_error!(env, TokenError::RecoveryNotFound);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Recovery(account.clone()));
+    for request in requests.iter() {
+        TokenEvents::recovery_cancelled(env, &account, &request.new_address);
+    }
+}
+
+/// Moves the whole balance of `account` to the approved new address once the
+/// waiting period has passed. Anyone may trigger it; returns the amount moved.
+pub fn execute_recovery(env: &Env, account: Address) -> i128 {
+    if is_paused(env) {
+        panic_with_error!(env, TokenError::Paused);
+    }
+
+    let request = get_recovery(env, &account)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::RecoveryNotFound));
+    match request.executable_at {
+        Some(executable_at) if env.ledger().timestamp() >= executable_at => {}
+        _ => panic_with_error!(env, TokenError::RecoveryNotReady),
+    }
+    enforce_compliance(env, Some(&account), Some(&request.new_address));
+
+    let amount = get_balance(env, &account);
+    if amount > 0 {
+        let new_balance = get_balance(env, &request.new_address)
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+        set_balance(env, &account, 0);
+        set_balance(env, &request.new_address, new_balance);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Recovery(account.clone()));
+    TokenEvents::recovery_executed(env, &account, &request.new_address, amount);
+
+    amount
+}
+
+pub fn get_scheduled_burns(env: &Env) -> Vec<ScheduledBurn> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ScheduledBurns)
+        .unwrap_or(Vec::new(env))
+}
 
-    // Remove from balance if zero to save storage
-    if new_from_balance == 0 {
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Balance(from.clone()));
+/// Whether burning `amount` on top of everything burned so far would exceed
+/// the burn cap.
+fn exceeds_burn_cap(env: &Env, amount: i128) -> bool {
+    match get_burn_cap(env) {
+        Some(cap) => get_total_burned(env)
+            .checked_add(amount)
+            .is_none_or(|total| total > cap),
+        None => false,
     }
+}
 
-    // Update allowance
-    let new_allowance = allowance
-        .checked_sub(amount)
-        .unwrap_or_else(|| panic_with_error!(env, TokenError::Underflow));
+/// Announces a burn of `amount` from the treasury (the admin's balance) that
+/// anyone can execute once `execute_after` has passed. The tokens move into
+/// the contract's custody now so the schedule cannot be underfunded later,
+/// and the burn must fit under the burn cap together with the burns already
+/// scheduled.
+pub fn schedule_burn(env: &Env, admin: Address, amount: i128, execute_after: u64) -> u64 {
+    require_admin(env, &admin);
 
-    if new_allowance == 0 {
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Allowance(from.clone(), spender.clone()));
-    } else {
-        env.storage().persistent().set(
-            &DataKey::Allowance(from.clone(), spender.clone()),
-            &new_allowance,
+    if amount <= 0 {
+        panic_with_error!(env, TokenError::InvalidAmount);
+    }
+    if is_paused(env) {
+        panic_with_error!(env, TokenError::Paused);
+    }
+    let mut burns = get_scheduled_burns(env);
+    if execute_after <= env.ledger().timestamp() || burns.len() >= MAX_SCHEDULED_BURNS {
+        panic_with_error!(env, TokenError::InvalidBurnSchedule);
+    }
+    let scheduled = burns
+        .iter()
+        .try_fold(amount, |total, burn| total.checked_add(burn.amount))
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+    if exceeds_burn_cap(env, scheduled) {
+        panic_with_error!(env, TokenError::BurnCapExceeded);
+    }
+
+    let treasury_balance = get_balance(env, &admin);
+    if treasury_balance < amount {
+        panic_with_error!(env, TokenError::InsufficientBalance);
+    }
+    let custody = env.current_contract_address();
+    let custody_balance = get_balance(env, &custody)
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+    set_balance(env, &admin, treasury_balance - amount);
+    set_balance(env, &custody, custody_balance);
+
+    let burn_id = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::LastScheduledBurnId)
+        .unwrap_or(0)
+        + 1;
+    burns.push_back(ScheduledBurn {
+        burn_id,
+        amount,
+        scheduled_at: env.ledger().timestamp(),
+        execute_after,
+    });
+    env.storage()
+        .instance()
+        .set(&DataKey::LastScheduledBurnId, &burn_id);
+    env.storage()
+        .instance()
+        .set(&DataKey::ScheduledBurns, &burns);
+
+    TokenEvents::burn_scheduled(env, burn_id, amount, execute_after);
+    burn_id
+}
+
+/// Cancels a scheduled burn before its execution time, returning the held
+/// tokens to the treasury. A due burn can still be cancelled while burning it
+/// would exceed the burn cap, since it could otherwise never leave custody.
+pub fn cancel_scheduled_burn(env: &Env, admin: Address, burn_id: u64) {
+    require_admin(env, &admin);
+
+    let mut burns = get_scheduled_burns(env);
+    let index = burns
+        .iter()
+        .position(|burn| burn.burn_id == burn_id)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::ScheduledBurnNotFound))
+        as u32;
+    let burn = burns.get(index).unwrap();
+    if env.ledger().timestamp() >= burn.execute_after && !exceeds_burn_cap(env, burn.amount) {
+        panic_with_error!(env, TokenError::BurnAlreadyDue);
+    }
+
+    let custody = env.current_contract_address();
+    let treasury_balance = get_balance(env, &admin)
+        .checked_add(burn.amount)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+    set_balance(env, &custody, get_balance(env, &custody) - burn.amount);
+    set_balance(env, &admin, treasury_balance);
+
+    burns.remove(index);
+    env.storage()
+        .instance()
+        .set(&DataKey::ScheduledBurns, &burns);
+    TokenEvents::burn_cancelled(env, burn_id, burn.amount);
+}
+
+/// Burns every scheduled amount whose execution time has passed. Anyone may
+/// trigger it; returns the total burned. A due burn that would exceed the
+/// burn cap stays scheduled without blocking the others.
+pub fn execute_due_burns(env: &Env) -> i128 {
+    let now = env.ledger().timestamp();
+    let custody = env.current_contract_address();
+
+    let mut pending = Vec::new(env);
+    let mut total_burned: i128 = 0;
+    for burn in get_scheduled_burns(env).iter() {
+        if now < burn.execute_after || exceeds_burn_cap(env, burn.amount) {
+            pending.push_back(burn);
+            continue;
+        }
+        burn_tokens(env, &custody, burn.amount, &custody);
+        total_burned = total_burned
+            .checked_add(burn.amount)
+            .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+        TokenEvents::burn_executed(env, burn.burn_id, burn.amount);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::ScheduledBurns, &pending);
+    total_burned
+}
+
+pub fn get_inflation_schedule(env: &Env) -> Option<InflationSchedule> {
+    env.storage().instance().get(&DataKey::InflationSchedule)
+}
+
+pub fn get_inflation_epochs_minted(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::InflationEpochsMinted)
+        .unwrap_or(0)
+}
+
+/// Replaces the inflation schedule, or removes it when `None`. The first
+/// epoch of a new schedule ends `epoch_length` seconds from now.
+pub fn set_inflation_schedule(
+    env: &Env,
+    admin: Address,
+    rate_bps: u32,
+    epoch_length: u64,
+    reward_pool: Option<Address>,
+) {
+    require_admin(env, &admin);
+
+    let schedule = reward_pool.map(|reward_pool| {
+        if rate_bps == 0
+            || rate_bps > MAX_INFLATION_BPS_PER_EPOCH
+            || epoch_length == 0
+            || reward_pool == env.current_contract_address()
+        {
+            panic_with_error!(env, TokenError::InvalidInflationSchedule);
+        }
+        InflationSchedule {
+            rate_bps,
+            epoch_length,
+            reward_pool,
+            started_at: env.ledger().timestamp(),
+        }
+    });
+
+    match &schedule {
+        Some(schedule) => env
+            .storage()
+            .instance()
+            .set(&DataKey::InflationSchedule, schedule),
+        None => env.storage().instance().remove(&DataKey::InflationSchedule),
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::InflationEpochsMinted, &0u64);
+    TokenEvents::inflation_schedule_updated(env, &admin, &schedule);
+}
+
+/// Mints the next elapsed epoch's inflation, `rate_bps` of the current
+/// supply, to the reward pool. Anyone may trigger it; each epoch is minted
+/// at most once, so a backlog of epochs takes one call per epoch.
+pub fn mint_epoch_inflation(env: &Env) -> i128 {
+    let schedule = get_inflation_schedule(env)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::InflationNotConfigured));
+
+    let epoch = get_inflation_epochs_minted(env) + 1;
+    let elapsed = env.ledger().timestamp() - schedule.started_at;
+    if elapsed / schedule.epoch_length < epoch {
+        panic_with_error!(env, TokenError::InflationEpochNotElapsed);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::InflationEpochsMinted, &epoch);
+
+    let amount = get_total_supply(env)
+        .checked_mul(schedule.rate_bps as i128)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow))
+        / 10_000;
+    if amount > 0 {
+        mint_tokens(
+            env,
+            &schedule.reward_pool,
+            amount,
+            &env.current_contract_address(),
         );
     }
 
-    // Emit events
-    TokenEvents::transfer(env, &from, &to, amount);
-    TokenEvents::approval(env, &from, &spender, new_allowance);
+    TokenEvents::inflation_minted(env, epoch, &schedule.reward_pool, amount);
+    amount
+}
+
+/// Switches an optional behavior on or off. While a feature is off its
+/// configuration calls fail with `FeatureDisabled` and transfers skip its
+/// checks; stored settings are kept and apply again once it is re-enabled.
+///
+/// Freezing lifts every freeze and denylist entry while off, so only the
+/// compliance officer may toggle it; other features are the admin's.
+pub fn set_feature(env: &Env, caller: Address, feature: Feature, enabled: bool) {
+    match feature {
+        Feature::Freezing => require_compliance_officer(env, &caller),
+        _ => require_admin(env, &caller),
+    }
+
+    let mut features = get_enabled_features(env);
+    match (features.first_index_of(feature), enabled) {
+        (None, true) => features.push_back(feature),
+        (Some(index), false) => {
+            features.remove(index);
+        }
+        _ => return,
+    }
+    env.storage().instance().set(&DataKey::Features, &features);
+    TokenEvents::feature_updated(env, &caller, feature, enabled);
+}
+
+/// Features enabled for this deployment. Every feature starts disabled and
+/// must be switched on with `set_feature`.
+pub fn get_enabled_features(env: &Env) -> Vec<Feature> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Features)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn is_feature_enabled(env: &Env, feature: Feature) -> bool {
+    get_enabled_features(env).contains(feature)
+}
+
+fn require_feature(env: &Env, feature: Feature) {
+    if !is_feature_enabled(env, feature) {
+        panic_with_error!(env, TokenError::FeatureDisabled);
+    }
 }
 
 pub fn pause(env: &Env, admin: Address) {
@@ -611,10 +2055,69 @@ pub fn unpause(env: &Env, admin: Address) {
 // Query functions
 
 pub fn get_balance(env: &Env, address: &Address) -> i128 {
+    match get_balance_storage(env) {
+        BalanceStorage::PerAddress => env
+            .storage()
+            .persistent()
+            .get(&DataKey::Balance(address.clone()))
+            .unwrap_or(0),
+        BalanceStorage::Packed(buckets) => {
+            let bucket: Map<Address, i128> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::BalanceBucket(balance_bucket(
+                    env, address, buckets,
+                )))
+                .unwrap_or(Map::new(env));
+            bucket.get(address.clone()).unwrap_or(0)
+        }
+    }
+}
+
+/// Stores a balance, dropping the entry when it reaches zero.
+fn set_balance(env: &Env, address: &Address, balance: i128) {
+    match get_balance_storage(env) {
+        BalanceStorage::PerAddress => {
+            let key = DataKey::Balance(address.clone());
+            if balance == 0 {
+                env.storage().persistent().remove(&key);
+            } else {
+                env.storage().persistent().set(&key, &balance);
+            }
+        }
+        BalanceStorage::Packed(buckets) => {
+            let key = DataKey::BalanceBucket(balance_bucket(env, address, buckets));
+            let mut bucket: Map<Address, i128> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Map::new(env));
+            if balance == 0 {
+                bucket.remove(address.clone());
+            } else {
+                bucket.set(address.clone(), balance);
+            }
+            if bucket.is_empty() {
+                env.storage().persistent().remove(&key);
+            } else {
+                env.storage().persistent().set(&key, &bucket);
+            }
+        }
+    }
+}
+
+pub fn get_balance_storage(env: &Env) -> BalanceStorage {
     env.storage()
-        .persistent()
-        .get(&DataKey::Balance(address.clone()))
-        .unwrap_or(0)
+        .instance()
+        .get(&DataKey::BalanceStorage)
+        .unwrap_or(BalanceStorage::PerAddress)
+}
+
+/// Bucket holding `address`'s packed balance: the first four bytes of the
+/// address hash, modulo the bucket count.
+fn balance_bucket(env: &Env, address: &Address, buckets: u32) -> u32 {
+    let hash = env.crypto().sha256(&address.clone().to_xdr(env)).to_array();
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]) % buckets
 }
 
 pub fn get_total_supply(env: &Env) -> i128 {
@@ -660,6 +2163,29 @@ pub fn is_paused(env: &Env) -> bool {
         .unwrap_or(false)
 }
 
+pub fn get_roles(env: &Env) -> RolesView {
+    RolesView {
+        admin: get_admin(env),
+        minters: get_minters(env),
+        compliance_officer: get_compliance_officer(env),
+        paused: is_paused(env),
+        mint_cap: get_mint_cap(env),
+        burn_cap: get_burn_cap(env),
+        max_transfer_amount: get_max_transfer_amount(env),
+        features: get_enabled_features(env),
+    }
+}
+
+/// Same snapshot as `get_roles`, recorded with a `config_exported` event so
+/// audits can show when the admin exported the configuration.
+pub fn export_roles(env: &Env, admin: Address) -> RolesView {
+    require_admin(env, &admin);
+
+    let roles = get_roles(env);
+    TokenEvents::config_exported(env, &admin, &roles);
+    roles
+}
+
 pub fn get_token_metrics(env: &Env) -> TokenMetrics {
     let total_supply = get_total_supply(env);
     let total_minted = get_total_minted(env);
@@ -704,7 +2230,40 @@ impl TokenContract {
         mint_cap: Option<i128>,
         burn_cap: Option<i128>,
     ) {
-        initialize_token(&env, admin, name, symbol, decimals, mint_cap, burn_cap);
+        initialize_token(
+            &env,
+            admin,
+            name,
+            symbol,
+            decimals,
+            mint_cap,
+            burn_cap,
+            BalanceStorage::PerAddress,
+        );
+    }
+
+    /// Same as `initialize`, with an explicit balance storage layout.
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_with_storage(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        mint_cap: Option<i128>,
+        burn_cap: Option<i128>,
+        balance_storage: BalanceStorage,
+    ) {
+        initialize_token(
+            &env,
+            admin,
+            name,
+            symbol,
+            decimals,
+            mint_cap,
+            burn_cap,
+            balance_storage,
+        );
     }
 
     pub fn get_admin(env: Env) -> Address {
@@ -719,6 +2278,10 @@ impl TokenContract {
         burn(&env, from, amount)
     }
 
+    pub fn burn_from(env: Env, spender: Address, from: Address, amount: i128) -> U256 {
+        burn_from(&env, spender, from, amount)
+    }
+
     pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
         transfer(&env, from, to, amount);
     }
@@ -731,6 +2294,40 @@ impl TokenContract {
         transfer_from(&env, spender, from, to, amount);
     }
 
+    pub fn transfer_with_compliance(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        ref_hash: BytesN<32>,
+    ) {
+        transfer_with_compliance(&env, from, to, amount, ref_hash);
+    }
+
+    pub fn transfer_from_with_compliance(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+        ref_hash: BytesN<32>,
+    ) {
+        transfer_from_with_compliance(&env, spender, from, to, amount, ref_hash);
+    }
+
+    pub fn set_allowance_alert(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        threshold: Option<i128>,
+    ) {
+        set_allowance_alert(&env, owner, spender, threshold);
+    }
+
+    pub fn allowance_alert(env: Env, owner: Address, spender: Address) -> Option<i128> {
+        get_allowance_alert(&env, &owner, &spender)
+    }
+
     pub fn add_minter(env: Env, admin: Address, minter: Address) {
         add_minter(&env, admin, minter);
     }
@@ -739,6 +2336,192 @@ impl TokenContract {
         remove_minter(&env, admin, minter);
     }
 
+    pub fn register_transfer_hook(env: Env, admin: Address, hook: Address) {
+        register_transfer_hook(&env, admin, hook);
+    }
+
+    pub fn unregister_transfer_hook(env: Env, admin: Address, hook: Address) {
+        unregister_transfer_hook(&env, admin, hook);
+    }
+
+    pub fn set_hook_threshold(env: Env, admin: Address, threshold: i128) {
+        set_hook_threshold(&env, admin, threshold);
+    }
+
+    pub fn transfer_hooks(env: Env) -> Vec<Address> {
+        get_transfer_hooks(&env)
+    }
+
+    pub fn hook_threshold(env: Env) -> i128 {
+        get_hook_threshold(&env)
+    }
+
+    pub fn set_max_transfer_amount(env: Env, admin: Address, max_amount: Option<i128>) {
+        set_max_transfer_amount(&env, admin, max_amount);
+    }
+
+    pub fn max_transfer_amount(env: Env) -> Option<i128> {
+        get_max_transfer_amount(&env)
+    }
+
+    pub fn set_transfer_limit_exempt(env: Env, admin: Address, account: Address, exempt: bool) {
+        set_transfer_limit_exempt(&env, admin, account, exempt);
+    }
+
+    pub fn is_transfer_limit_exempt(env: Env, account: Address) -> bool {
+        is_transfer_limit_exempt(&env, &account)
+    }
+
+    pub fn set_dust_policy(env: Env, admin: Address, threshold: i128, treasury: Address) {
+        set_dust_policy(&env, admin, threshold, treasury);
+    }
+
+    pub fn dust_policy(env: Env) -> Option<DustPolicy> {
+        get_dust_policy(&env)
+    }
+
+    pub fn set_dust_sweep_consent(env: Env, holder: Address, consent: bool) {
+        set_dust_sweep_consent(&env, holder, consent);
+    }
+
+    pub fn has_dust_sweep_consent(env: Env, holder: Address) -> bool {
+        has_dust_sweep_consent(&env, &holder)
+    }
+
+    pub fn dust_sweep_consent(env: Env, holder: Address) -> Option<i128> {
+        get_dust_sweep_consent(&env, &holder)
+    }
+
+    pub fn sweep_dust(env: Env, admin: Address, accounts: Vec<Address>) -> i128 {
+        sweep_dust(&env, admin, accounts)
+    }
+
+    pub fn set_guardians(env: Env, account: Address, guardians: Vec<Address>, threshold: u32) {
+        set_guardians(&env, account, guardians, threshold);
+    }
+
+    pub fn guardians(env: Env, account: Address) -> Option<GuardianConfig> {
+        get_guardians(&env, &account)
+    }
+
+    pub fn approve_recovery(env: Env, guardian: Address, account: Address, new_address: Address) {
+        approve_recovery(&env, guardian, account, new_address);
+    }
+
+    pub fn cancel_recovery(env: Env, account: Address) {
+        cancel_recovery(&env, account);
+    }
+
+    pub fn execute_recovery(env: Env, account: Address) -> i128 {
+        execute_recovery(&env, account)
+    }
+
+    pub fn recovery_request(env: Env, account: Address) -> Option<RecoveryRequest> {
+        get_recovery(&env, &account)
+    }
+
+    pub fn recovery_requests(env: Env, account: Address) -> Vec<RecoveryRequest> {
+        get_recovery_requests(&env, &account)
+    }
+
+    pub fn set_compliance_officer(env: Env, admin: Address, officer: Address) {
+        set_compliance_officer(&env, admin, officer);
+    }
+
+    pub fn compliance_officer(env: Env) -> Option<Address> {
+        get_compliance_officer(&env)
+    }
+
+    pub fn set_audit_contract(env: Env, admin: Address, audit: Option<Address>) {
+        set_audit_contract(&env, admin, audit);
+    }
+
+    pub fn audit_contract(env: Env) -> Option<Address> {
+        get_audit_contract(&env)
+    }
+
+    pub fn set_travel_rule_threshold(env: Env, officer: Address, threshold: Option<i128>) {
+        set_travel_rule_threshold(&env, officer, threshold);
+    }
+
+    pub fn travel_rule_threshold(env: Env) -> Option<i128> {
+        get_travel_rule_threshold(&env)
+    }
+
+    pub fn freeze_account(env: Env, officer: Address, account: Address) {
+        freeze_account(&env, officer, account);
+    }
+
+    pub fn unfreeze_account(env: Env, officer: Address, account: Address) {
+        unfreeze_account(&env, officer, account);
+    }
+
+    pub fn add_to_denylist(env: Env, officer: Address, account: Address) {
+        add_to_denylist(&env, officer, account);
+    }
+
+    pub fn remove_from_denylist(env: Env, officer: Address, account: Address) {
+        remove_from_denylist(&env, officer, account);
+    }
+
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        is_frozen(&env, &account)
+    }
+
+    pub fn is_denylisted(env: Env, account: Address) -> bool {
+        is_denylisted(&env, &account)
+    }
+
+    pub fn schedule_burn(env: Env, admin: Address, amount: i128, execute_after: u64) -> u64 {
+        schedule_burn(&env, admin, amount, execute_after)
+    }
+
+    pub fn cancel_scheduled_burn(env: Env, admin: Address, burn_id: u64) {
+        cancel_scheduled_burn(&env, admin, burn_id);
+    }
+
+    pub fn execute_due_burns(env: Env) -> i128 {
+        execute_due_burns(&env)
+    }
+
+    pub fn scheduled_burns(env: Env) -> Vec<ScheduledBurn> {
+        get_scheduled_burns(&env)
+    }
+
+    pub fn set_inflation_schedule(
+        env: Env,
+        admin: Address,
+        rate_bps: u32,
+        epoch_length: u64,
+        reward_pool: Option<Address>,
+    ) {
+        set_inflation_schedule(&env, admin, rate_bps, epoch_length, reward_pool);
+    }
+
+    pub fn inflation_schedule(env: Env) -> Option<InflationSchedule> {
+        get_inflation_schedule(&env)
+    }
+
+    pub fn inflation_epochs_minted(env: Env) -> u64 {
+        get_inflation_epochs_minted(&env)
+    }
+
+    pub fn mint_epoch_inflation(env: Env) -> i128 {
+        mint_epoch_inflation(&env)
+    }
+
+    pub fn set_feature(env: Env, caller: Address, feature: Feature, enabled: bool) {
+        set_feature(&env, caller, feature, enabled);
+    }
+
+    pub fn get_enabled_features(env: Env) -> Vec<Feature> {
+        get_enabled_features(&env)
+    }
+
+    pub fn is_feature_enabled(env: Env, feature: Feature) -> bool {
+        is_feature_enabled(&env, feature)
+    }
+
     pub fn pause(env: Env, admin: Address) {
         pause(&env, admin);
     }
@@ -752,6 +2535,10 @@ impl TokenContract {
         get_balance(&env, &address)
     }
 
+    pub fn balance_storage(env: Env) -> BalanceStorage {
+        get_balance_storage(&env)
+    }
+
     pub fn total_supply(env: Env) -> i128 {
         get_total_supply(&env)
     }
@@ -776,6 +2563,14 @@ impl TokenContract {
         get_total_burned(&env)
     }
 
+    pub fn mint_history(env: Env, start: u64, limit: u32) -> Vec<MintRecord> {
+        get_mint_history(&env, start, limit)
+    }
+
+    pub fn burn_history(env: Env, start: u64, limit: u32) -> Vec<BurnRecord> {
+        get_burn_history(&env, start, limit)
+    }
+
     pub fn is_paused(env: Env) -> bool {
         is_paused(&env)
     }
@@ -784,6 +2579,18 @@ impl TokenContract {
         is_minter(&env, &address)
     }
 
+    pub fn minters(env: Env) -> Vec<Address> {
+        get_minters(&env)
+    }
+
+    pub fn get_roles(env: Env) -> RolesView {
+        get_roles(&env)
+    }
+
+    pub fn export_roles(env: Env, admin: Address) -> RolesView {
+        export_roles(&env, admin)
+    }
+
     pub fn token_metrics(env: Env) -> TokenMetrics {
         get_token_metrics(&env)
     }
@@ -1,6 +1,6 @@
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, Events as _},
+    testutils::{Address as _, Events as _, Ledger as _},
     Address, Env, Vec,
 };
 
@@ -196,7 +196,8 @@ fn test_exempt_wallet_not_throttled() {
     let exempt_wallet = Address::generate(&env);
 
     // Add wallet to exempt list
-    client.add_exempt_address(&admin, &exempt_wallet);
+    let expires_at = env.ledger().timestamp() + 3600;
+    client.add_exempt_address(&admin, &exempt_wallet, &expires_at);
 
     // Should be allowed regardless of frequency
     for i in 0..10 {
@@ -260,6 +261,59 @@ fn test_config_update_unauthorized_fails() {
     client.update_throttle_config(&unauthorized, &new_config);
 }
 
+#[test]
+fn test_config_history_and_scheduled_activation() {
+    let (env, admin, client) = setup_throttle_contract();
+    assert_eq!(client.get_config_history().len(), 0);
+
+    let start = env.ledger().timestamp();
+    client.update_throttle_config(&admin, &create_custom_config(&env, 10, 120, 60, true));
+    let history = client.get_config_history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().admin, admin);
+    assert_eq!(history.get(0).unwrap().changed_at, start);
+
+    let scheduled = create_custom_config(&env, 2, 60, 30, true);
+    assert_eq!(
+        client.try_schedule_config(&admin, &scheduled, &start),
+        Err(Ok(ThrottleError::InvalidEffectiveTime.into()))
+    );
+    client.schedule_config(&admin, &scheduled, &(start + 100));
+    let pending = client.get_pending_config().unwrap();
+    assert_eq!(pending.effective_at, start + 100);
+    assert_eq!(pending.scheduled_at, start);
+
+    // The current limits apply until the effective time
+    let wallet = Address::generate(&env);
+    env.ledger().set_timestamp(start + 99);
+    let result = client.check_transaction_throttle(&wallet);
+    assert_eq!(result.remaining_transactions, 9);
+    assert!(client.get_pending_config().is_some());
+
+    env.ledger().set_timestamp(start + 100);
+    assert_eq!(client.get_throttle_config().max_transactions_per_window, 2);
+    client.check_transaction_throttle(&wallet);
+    assert!(client.get_pending_config().is_none());
+
+    let history = client.get_config_history();
+    assert_eq!(history.len(), 2);
+    let activated = history.get(1).unwrap();
+    assert_eq!(activated.changed_at, start + 100);
+    assert_eq!(activated.config.max_transactions_per_window, 2);
+}
+
+#[test]
+#[should_panic]
+fn test_schedule_config_unauthorized_fails() {
+    let (env, _admin, client) = setup_throttle_contract();
+
+    let unauthorized = Address::generate(&env);
+    let config = create_custom_config(&env, 10, 120, 60, true);
+    let effective_at = env.ledger().timestamp() + 100;
+
+    client.schedule_config(&unauthorized, &config, &effective_at);
+}
+
 #[test]
 fn test_multiple_wallets_independent_throttling() {
     let (env, _admin, client) = setup_throttle_contract();
@@ -335,16 +389,78 @@ fn test_exempt_address_management() {
     let wallet = Address::generate(&env);
 
     // Add to exempt list
-    client.add_exempt_address(&admin, &wallet);
+    let expires_at = env.ledger().timestamp() + 3600;
+    client.add_exempt_address(&admin, &wallet, &expires_at);
 
     let config = client.get_throttle_config();
     assert!(config.exempt_addresses.contains(&wallet));
+    assert_eq!(client.get_exemption_expiry(&wallet), Some(expires_at));
 
     // Remove from exempt list
     client.remove_exempt_address(&admin, &wallet);
 
     let updated_config = client.get_throttle_config();
     assert!(!updated_config.exempt_addresses.contains(&wallet));
+    assert_eq!(client.get_exemption_expiry(&wallet), None);
+}
+
+#[test]
+fn test_exemption_expires_during_check() {
+    let (env, admin, client) = setup_throttle_contract();
+
+    let wallet = Address::generate(&env);
+    let expires_at = env.ledger().timestamp() + 100;
+    client.add_exempt_address(&admin, &wallet, &expires_at);
+
+    let result = client.check_transaction_throttle(&wallet);
+    assert_eq!(result.reason, ThrottleReason::WalletExempt);
+
+    // Once the exemption lapses the wallet is throttled like any other
+    env.ledger().set_timestamp(expires_at);
+    let result = client.check_transaction_throttle(&wallet);
+    assert!(result.allowed);
+    assert_ne!(result.reason, ThrottleReason::WalletExempt);
+
+    let config = client.get_throttle_config();
+    assert!(!config.exempt_addresses.contains(&wallet));
+    assert_eq!(client.get_exemption_expiry(&wallet), None);
+}
+
+#[test]
+fn test_config_update_keeps_exemptions_and_sweeps_lapsed_ones() {
+    let (env, admin, client) = setup_throttle_contract();
+
+    let kept = Address::generate(&env);
+    let lapsed = Address::generate(&env);
+    let start = env.ledger().timestamp();
+    client.add_exempt_address(&admin, &kept, &(start + 1000));
+    client.add_exempt_address(&admin, &lapsed, &(start + 100));
+    env.ledger().set_timestamp(start + 100);
+
+    // A config update cannot grant exemptions or revoke them wholesale
+    let mut new_config = create_custom_config(&env, 10, 120, 60, true);
+    new_config
+        .exempt_addresses
+        .push_back(Address::generate(&env));
+    client.update_throttle_config(&admin, &new_config);
+
+    let config = client.get_throttle_config();
+    assert_eq!(config.max_transactions_per_window, 10);
+    assert_eq!(
+        config.exempt_addresses,
+        Vec::from_array(&env, [kept.clone()])
+    );
+    assert_eq!(client.get_exemption_expiry(&kept), Some(start + 1000));
+    assert_eq!(client.get_exemption_expiry(&lapsed), None);
+}
+
+#[test]
+#[should_panic]
+fn test_exemption_with_past_expiry_fails() {
+    let (env, admin, client) = setup_throttle_contract();
+
+    let wallet = Address::generate(&env);
+    client.add_exempt_address(&admin, &wallet, &env.ledger().timestamp());
 }
 
 #[test]
@@ -355,7 +471,7 @@ fn test_exempt_address_unauthorized_fails() {
     let wallet = Address::generate(&env);
     let unauthorized = Address::generate(&env);
 
-    client.add_exempt_address(&unauthorized, &wallet);
+    client.add_exempt_address(&unauthorized, &wallet, &(env.ledger().timestamp() + 3600));
 }
 
 #[test]
@@ -718,3 +834,109 @@ fn test_edge_case_config_update_with_active_throttles() {
     assert!(allowed_result.allowed);
     assert_eq!(allowed_result.remaining_transactions, 9); // New limit
 }
+
+#[test]
+fn test_unblock_time_projection() {
+    let (env, _admin, client) = setup_throttle_contract();
+
+    let wallet = Address::generate(&env);
+    assert_eq!(client.get_unblock_time(&wallet), None);
+    assert_eq!(client.get_window_reset_time(&wallet), None);
+
+    let start = env.ledger().timestamp();
+    for _ in 0..5 {
+        client.check_transaction_throttle(&wallet);
+    }
+    assert_eq!(client.get_unblock_time(&wallet), None);
+    assert_eq!(client.get_window_reset_time(&wallet), Some(start + 60));
+
+    let blocked_result = client.check_transaction_throttle(&wallet);
+    assert_eq!(
+        client.get_unblock_time(&wallet),
+        blocked_result.throttle_end_time
+    );
+
+    // Views never consume quota or change state
+    let info_before = client.get_wallet_throttle_info(&wallet).unwrap();
+    client.get_unblock_time(&wallet);
+    let info_after = client.get_wallet_throttle_info(&wallet).unwrap();
+    assert_eq!(info_before.transaction_count, info_after.transaction_count);
+
+    env.ledger().set_timestamp(start + 31);
+    assert_eq!(client.get_unblock_time(&wallet), None);
+}
+
+#[test]
+fn test_unblock_time_none_for_exempt_wallet() {
+    let (env, admin, client) = setup_throttle_contract();
+
+    let wallet = Address::generate(&env);
+    for _ in 0..6 {
+        client.check_transaction_throttle(&wallet);
+    }
+    assert!(client.get_unblock_time(&wallet).is_some());
+
+    client.add_exempt_address(&admin, &wallet, &(env.ledger().timestamp() + 3600));
+    assert_eq!(client.get_unblock_time(&wallet), None);
+    assert_eq!(client.get_window_reset_time(&wallet), None);
+}
+
+mod audit_mock {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Bytes, Env, Symbol, Vec};
+
+    #[contract]
+    pub struct RecordingAudit;
+
+    #[contractimpl]
+    impl RecordingAudit {
+        pub fn log_audit(
+            env: Env,
+            _actor: Address,
+            operation: Symbol,
+            _status: Symbol,
+            _metadata: Option<Bytes>,
+        ) {
+            let key = symbol_short!("ops");
+            let mut ops: Vec<Symbol> = env.storage().instance().get(&key).unwrap_or(Vec::new(&env));
+            ops.push_back(operation);
+            env.storage().instance().set(&key, &ops);
+        }
+
+        pub fn operations(env: Env) -> Vec<Symbol> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("ops"))
+                .unwrap_or(Vec::new(&env))
+        }
+    }
+}
+
+#[test]
+fn test_violation_and_lift_recorded_in_audit_trail() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, admin, client) = setup_throttle_contract();
+    let audit_id = env.register(audit_mock::RecordingAudit, ());
+    let audit = audit_mock::RecordingAuditClient::new(&env, &audit_id);
+    client.set_audit_contract(&admin, &audit_id);
+
+    let wallet = Address::generate(&env);
+    for _ in 0..6 {
+        client.check_transaction_throttle(&wallet);
+    }
+    let ops = audit.operations();
+    assert_eq!(ops.len(), 1);
+    assert_eq!(
+        ops.get(0).unwrap(),
+        soroban_sdk::Symbol::new(&env, "throttle_violation")
+    );
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 31);
+    client.check_transaction_throttle(&wallet);
+    let ops = audit.operations();
+    assert_eq!(ops.len(), 2);
+    assert_eq!(
+        ops.get(1).unwrap(),
+        soroban_sdk::Symbol::new(&env, "throttle_lifted")
+    );
+}
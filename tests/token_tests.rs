@@ -2,8 +2,8 @@ use std::panic::AssertUnwindSafe;
 
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, Events as _},
-    Address, Env, String, Symbol, TryFromVal,
+    testutils::{Address as _, Events as _, Ledger as _},
+    Address, BytesN, Env, String, Symbol, TryFromVal,
 };
 
 fn event_topics_contain_symbol(
@@ -20,10 +20,112 @@ fn event_topics_contain_symbol(
 mod token;
 
 use token::{
-    BurnRecord, MintRecord, TokenConfig, TokenContract, TokenContractClient, TokenError,
-    TokenMetrics,
+    BalanceStorage, BurnRecord, Feature, MintRecord, TokenConfig, TokenContract,
+    TokenContractClient, TokenError, TokenMetrics,
 };
 
+mod recording_hook {
+    use super::token::TransferHook;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Hook that counts the notifications it receives.
+    #[contract]
+    pub struct RecordingHook;
+
+    #[contractimpl]
+    impl TransferHook for RecordingHook {
+        fn on_transfer(env: Env, _token: Address, _from: Address, _to: Address, amount: i128) {
+            let calls: u32 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("calls"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("calls"), &(calls + 1));
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &amount);
+        }
+    }
+
+    #[contractimpl]
+    impl RecordingHook {
+        pub fn calls(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("calls"))
+                .unwrap_or(0)
+        }
+    }
+}
+
+mod failing_hook {
+    use super::token::TransferHook;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    /// Hook that always fails.
+    #[contract]
+    pub struct FailingHook;
+
+    #[contractimpl]
+    impl TransferHook for FailingHook {
+        fn on_transfer(_env: Env, _token: Address, _from: Address, _to: Address, _amount: i128) {
+            panic!("hook failure");
+        }
+    }
+}
+
+mod recording_audit {
+    use super::token::AuditLogger;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Bytes, Env, Symbol};
+
+    /// Audit sink that keeps the last operation it was asked to log.
+    #[contract]
+    pub struct RecordingAudit;
+
+    #[contractimpl]
+    impl AuditLogger for RecordingAudit {
+        fn log_audit(
+            env: Env,
+            actor: Address,
+            operation: Symbol,
+            _status: Symbol,
+            _metadata: Option<Bytes>,
+        ) {
+            let entries: u32 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("entries"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("entries"), &(entries + 1));
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &(actor, operation));
+        }
+    }
+
+    #[contractimpl]
+    impl RecordingAudit {
+        pub fn entries(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("entries"))
+                .unwrap_or(0)
+        }
+
+        pub fn last(env: Env) -> Option<(Address, Symbol)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+use failing_hook::FailingHook;
+use recording_audit::{RecordingAudit, RecordingAuditClient};
+use recording_hook::{RecordingHook, RecordingHookClient};
+
 fn setup_token_contract() -> (Env, Address, Address, TokenContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
@@ -443,6 +545,129 @@ fn test_transfer_from_insufficient_allowance_fails() {
     client.transfer_from(&spender, &owner, &recipient, &transfer_amount);
 }
 
+#[test]
+fn test_burn_from_success() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let mint_amount = 1000i128;
+
+    // Mint tokens to owner and approve spender
+    client.mint(&admin, &owner, &mint_amount);
+    client.approve(&owner, &spender, &600i128);
+
+    // Burn using allowance
+    client.burn_from(&spender, &owner, &400i128);
+
+    assert_eq!(client.balance(&owner), 600);
+    assert_eq!(client.allowance(&owner, &spender), 200);
+    assert_eq!(client.total_supply(), 600);
+    assert_eq!(client.total_burned(), 400);
+}
+
+#[test]
+#[should_panic]
+fn test_burn_from_insufficient_allowance_fails() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.mint(&admin, &owner, &1000i128);
+    client.approve(&owner, &spender, &100i128);
+
+    // Try to burn more than allowed
+    client.burn_from(&spender, &owner, &101i128);
+}
+
+#[test]
+#[should_panic]
+fn test_burn_from_cap_exceeded_fails() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    // Burn cap is 500000
+    client.mint(&admin, &owner, &600000i128);
+    client.approve(&owner, &spender, &600000i128);
+
+    client.burn_from(&spender, &owner, &500001i128);
+}
+
+#[test]
+fn test_transfer_hooks_notified_above_threshold() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let hook_id = env.register(RecordingHook, ());
+    let hook = RecordingHookClient::new(&env, &hook_id);
+
+    client.set_feature(&admin, &Feature::Hooks, &true);
+    client.register_transfer_hook(&admin, &hook_id);
+    client.set_hook_threshold(&admin, &500i128);
+    assert_eq!(client.transfer_hooks().len(), 1);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&admin, &user1, &2000i128);
+
+    // Below threshold: no notification
+    client.transfer(&user1, &user2, &100i128);
+    assert_eq!(hook.calls(), 0);
+
+    // At threshold: hook notified
+    client.transfer(&user1, &user2, &500i128);
+    assert_eq!(hook.calls(), 1);
+
+    // transfer_from is observed too
+    let spender = Address::generate(&env);
+    client.approve(&user1, &spender, &1000i128);
+    client.transfer_from(&spender, &user1, &user2, &1000i128);
+    assert_eq!(hook.calls(), 2);
+
+    client.unregister_transfer_hook(&admin, &hook_id);
+    client.transfer(&user2, &user1, &600i128);
+    assert_eq!(hook.calls(), 2);
+}
+
+#[test]
+fn test_failing_transfer_hook_does_not_block_transfer() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let hook_id = env.register(FailingHook, ());
+    client.set_feature(&admin, &Feature::Hooks, &true);
+    client.register_transfer_hook(&admin, &hook_id);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&admin, &user1, &1000i128);
+
+    client.transfer(&user1, &user2, &400i128);
+
+    let hook_failures = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| {
+            event_topics_contain_symbol(&env, &event.1, symbol_short!("hook"))
+                && event_topics_contain_symbol(&env, &event.1, symbol_short!("failed"))
+        })
+        .count();
+    assert_eq!(hook_failures, 1);
+    assert_eq!(client.balance(&user2), 400);
+}
+
+#[test]
+#[should_panic]
+fn test_register_transfer_hook_unauthorized_fails() {
+    let (env, _admin, _token_contract, client) = setup_token_contract();
+
+    let not_admin = Address::generate(&env);
+    let hook_id = env.register(RecordingHook, ());
+    client.register_transfer_hook(&not_admin, &hook_id);
+}
+
 #[test]
 fn test_minter_management() {
     let (env, admin, _token_contract, client) = setup_token_contract();
@@ -791,3 +1016,979 @@ fn test_event_emission_comprehensive() {
         .iter()
         .any(|ev| { event_topics_contain_symbol(&env, &ev.1, symbol_short!("burn")) }));
 }
+
+#[test]
+fn test_sweep_dust_moves_consenting_balances_to_treasury() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let treasury = Address::generate(&env);
+    let consenting = Address::generate(&env);
+    let silent = Address::generate(&env);
+    let large = Address::generate(&env);
+    client.set_dust_policy(&admin, &100i128, &treasury);
+
+    client.mint(&admin, &consenting, &40i128);
+    client.mint(&admin, &silent, &40i128);
+    client.mint(&admin, &large, &500i128);
+    client.set_dust_sweep_consent(&consenting, &true);
+    client.set_dust_sweep_consent(&large, &true);
+    assert!(client.has_dust_sweep_consent(&consenting));
+    assert!(!client.has_dust_sweep_consent(&silent));
+
+    let accounts = soroban_sdk::vec![&env, consenting.clone(), silent.clone(), large.clone()];
+    let swept = client.sweep_dust(&admin, &accounts);
+
+    let sweep_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| {
+            event_topics_contain_symbol(&env, &event.1, symbol_short!("dust"))
+                && event_topics_contain_symbol(&env, &event.1, symbol_short!("swept"))
+        })
+        .count();
+    assert_eq!(sweep_events, 1);
+
+    assert_eq!(swept, 40);
+    assert_eq!(client.balance(&consenting), 0);
+    assert_eq!(client.balance(&silent), 40);
+    assert_eq!(client.balance(&large), 500);
+    assert_eq!(client.balance(&treasury), 40);
+    assert_eq!(client.total_supply(), 580);
+}
+
+#[test]
+fn test_sweep_dust_respects_revoked_consent() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let treasury = Address::generate(&env);
+    let holder = Address::generate(&env);
+    client.set_dust_policy(&admin, &100i128, &treasury);
+    client.mint(&admin, &holder, &10i128);
+    client.set_dust_sweep_consent(&holder, &true);
+    client.set_dust_sweep_consent(&holder, &false);
+
+    let swept = client.sweep_dust(&admin, &soroban_sdk::vec![&env, holder.clone()]);
+    assert_eq!(swept, 0);
+    assert_eq!(client.balance(&holder), 10);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_sweep_dust_without_policy_fails() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let holder = Address::generate(&env);
+    client.sweep_dust(&admin, &soroban_sdk::vec![&env, holder]);
+}
+
+#[test]
+fn test_sweep_dust_uses_consented_threshold() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let treasury = Address::generate(&env);
+    let holder = Address::generate(&env);
+    client.set_dust_policy(&admin, &100i128, &treasury);
+    client.mint(&admin, &holder, &500i128);
+    client.set_dust_sweep_consent(&holder, &true);
+    assert_eq!(client.dust_sweep_consent(&holder), Some(100));
+
+    // Raising the threshold does not widen what the holder agreed to
+    client.set_dust_policy(&admin, &1_000i128, &treasury);
+    let accounts = soroban_sdk::vec![&env, holder.clone()];
+    assert_eq!(client.sweep_dust(&admin, &accounts), 0);
+    assert_eq!(client.balance(&holder), 500);
+
+    // Lowering it below the consented threshold applies immediately
+    client.burn(&holder, &450i128);
+    client.set_dust_policy(&admin, &40i128, &treasury);
+    assert_eq!(client.sweep_dust(&admin, &accounts), 0);
+    client.set_dust_policy(&admin, &60i128, &treasury);
+    assert_eq!(client.sweep_dust(&admin, &accounts), 50);
+    assert_eq!(client.balance(&treasury), 50);
+}
+
+#[test]
+fn test_sweep_dust_skips_frozen_and_denylisted_accounts() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let officer = setup_compliance(&env, &admin, &client);
+
+    let treasury = Address::generate(&env);
+    let frozen = Address::generate(&env);
+    let denied = Address::generate(&env);
+    client.set_dust_policy(&admin, &100i128, &treasury);
+    for account in [&frozen, &denied] {
+        client.mint(&admin, account, &40i128);
+        client.set_dust_sweep_consent(account, &true);
+    }
+    client.freeze_account(&officer, &frozen);
+    client.add_to_denylist(&officer, &denied);
+
+    let accounts = soroban_sdk::vec![&env, frozen.clone(), denied.clone()];
+    assert_eq!(client.sweep_dust(&admin, &accounts), 0);
+    assert_eq!(client.balance(&frozen), 40);
+    assert_eq!(client.balance(&denied), 40);
+
+    // A denylisted treasury cannot receive swept dust
+    client.unfreeze_account(&officer, &frozen);
+    client.add_to_denylist(&officer, &treasury);
+    assert_eq!(
+        client.try_sweep_dust(&admin, &accounts),
+        Err(Ok(TokenError::AccountDenylisted.into()))
+    );
+}
+
+#[test]
+fn test_transfer_within_max_amount_succeeds() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.mint(&admin, &sender, &1_000i128);
+    client.set_feature(&admin, &Feature::TransferLimits, &true);
+    client.set_max_transfer_amount(&admin, &Some(500i128));
+    assert_eq!(client.max_transfer_amount(), Some(500));
+
+    client.transfer(&sender, &recipient, &500i128);
+    assert_eq!(client.balance(&recipient), 500);
+
+    // Clearing the cap lifts the guard entirely
+    client.set_max_transfer_amount(&admin, &None);
+    client.transfer(&sender, &recipient, &500i128);
+    assert_eq!(client.balance(&recipient), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_transfer_above_max_amount_is_blocked() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.mint(&admin, &sender, &1_000i128);
+    client.set_feature(&admin, &Feature::TransferLimits, &true);
+    client.set_max_transfer_amount(&admin, &Some(500i128));
+
+    client.transfer(&sender, &recipient, &501i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_transfer_from_above_max_amount_is_blocked() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.mint(&admin, &owner, &1_000i128);
+    client.approve(&owner, &spender, &1_000i128);
+    client.set_feature(&admin, &Feature::TransferLimits, &true);
+    client.set_max_transfer_amount(&admin, &Some(500i128));
+
+    client.transfer_from(&spender, &owner, &recipient, &501i128);
+}
+
+#[test]
+fn test_exempt_sender_bypasses_max_amount() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let treasury = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.mint(&admin, &treasury, &1_000i128);
+    client.set_feature(&admin, &Feature::TransferLimits, &true);
+    client.set_max_transfer_amount(&admin, &Some(100i128));
+    client.set_transfer_limit_exempt(&admin, &treasury, &true);
+    assert!(client.is_transfer_limit_exempt(&treasury));
+
+    client.transfer(&treasury, &recipient, &1_000i128);
+    assert_eq!(client.balance(&recipient), 1_000);
+    assert!(!client.is_transfer_limit_exempt(&recipient));
+}
+
+fn setup_guardians(
+    env: &Env,
+    client: &TokenContractClient<'static>,
+    account: &Address,
+) -> (Address, Address, Address) {
+    let g1 = Address::generate(env);
+    let g2 = Address::generate(env);
+    let g3 = Address::generate(env);
+    let guardians = soroban_sdk::vec![env, g1.clone(), g2.clone(), g3.clone()];
+    client.set_guardians(account, &guardians, &2);
+    (g1, g2, g3)
+}
+
+#[test]
+fn test_guardian_recovery_moves_balance_after_delay() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let account = Address::generate(&env);
+    let new_address = Address::generate(&env);
+    client.mint(&admin, &account, &700i128);
+    let (g1, g2, _g3) = setup_guardians(&env, &client, &account);
+
+    client.approve_recovery(&g1, &account, &new_address);
+    assert_eq!(
+        client.recovery_request(&account).unwrap().executable_at,
+        None
+    );
+    client.approve_recovery(&g2, &account, &new_address);
+    let request = client.recovery_request(&account).unwrap();
+    assert_eq!(request.approvals.len(), 2);
+    assert_eq!(
+        request.executable_at,
+        Some(env.ledger().timestamp() + token::RECOVERY_DELAY)
+    );
+
+    // Threshold met but still inside the waiting period
+    assert!(client.try_execute_recovery(&account).is_err());
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += token::RECOVERY_DELAY);
+    assert_eq!(client.execute_recovery(&account), 700);
+    assert_eq!(client.balance(&account), 0);
+    assert_eq!(client.balance(&new_address), 700);
+    assert_eq!(client.recovery_request(&account), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_cancelled_recovery_cannot_execute() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let account = Address::generate(&env);
+    let new_address = Address::generate(&env);
+    client.mint(&admin, &account, &700i128);
+    let (g1, g2, _g3) = setup_guardians(&env, &client, &account);
+    client.approve_recovery(&g1, &account, &new_address);
+    client.approve_recovery(&g2, &account, &new_address);
+
+    client.cancel_recovery(&account);
+    env.ledger()
+        .with_mut(|li| li.timestamp += token::RECOVERY_DELAY);
+    client.execute_recovery(&account);
+}
+
+#[test]
+fn test_guardians_must_agree_on_new_address() {
+    let (env, _admin, _token_contract, client) = setup_token_contract();
+
+    let account = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let (g1, g2, g3) = setup_guardians(&env, &client, &account);
+
+    // Competing proposals are tracked side by side
+    client.approve_recovery(&g1, &account, &first);
+    client.approve_recovery(&g2, &account, &second);
+    assert_eq!(client.recovery_requests(&account).len(), 2);
+
+    // The proposal reaching the threshold wins and the other is dropped
+    client.approve_recovery(&g3, &account, &second);
+    let requests = client.recovery_requests(&account);
+    assert_eq!(requests.len(), 1);
+    let request = requests.get(0).unwrap();
+    assert_eq!(request.new_address, second);
+    assert!(request.executable_at.is_some());
+
+    assert_eq!(
+        client.try_approve_recovery(&g1, &account, &first),
+        Err(Ok(TokenError::RecoveryMismatch.into()))
+    );
+}
+
+#[test]
+fn test_guardian_switching_address_moves_approval() {
+    let (env, _admin, _token_contract, client) = setup_token_contract();
+
+    let account = Address::generate(&env);
+    let stray = Address::generate(&env);
+    let new_address = Address::generate(&env);
+    let (g1, g2, _g3) = setup_guardians(&env, &client, &account);
+
+    client.approve_recovery(&g1, &account, &stray);
+    client.approve_recovery(&g1, &account, &new_address);
+    let requests = client.recovery_requests(&account);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests.get(0).unwrap().new_address, new_address);
+
+    client.approve_recovery(&g2, &account, &new_address);
+    assert!(client
+        .recovery_request(&account)
+        .unwrap()
+        .executable_at
+        .is_some());
+}
+
+#[test]
+fn test_unapproved_recovery_request_lapses() {
+    let (env, _admin, _token_contract, client) = setup_token_contract();
+
+    let account = Address::generate(&env);
+    let stale = Address::generate(&env);
+    let new_address = Address::generate(&env);
+    let (g1, g2, _g3) = setup_guardians(&env, &client, &account);
+
+    client.approve_recovery(&g1, &account, &stale);
+    let request = client.recovery_request(&account).unwrap();
+    assert_eq!(
+        request.expires_at,
+        request.initiated_at + token::RECOVERY_REQUEST_EXPIRY
+    );
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += token::RECOVERY_REQUEST_EXPIRY);
+    assert_eq!(client.recovery_request(&account), None);
+    assert_eq!(
+        client.try_cancel_recovery(&account),
+        Err(Ok(TokenError::RecoveryNotFound.into()))
+    );
+
+    // A lapsed request no longer counts towards the threshold
+    client.approve_recovery(&g2, &account, &stale);
+    let request = client.recovery_request(&account).unwrap();
+    assert_eq!(request.approvals.len(), 1);
+    assert_eq!(request.executable_at, None);
+
+    client.approve_recovery(&g1, &account, &new_address);
+    assert_eq!(client.recovery_requests(&account).len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_non_guardian_cannot_approve_recovery() {
+    let (env, _admin, _token_contract, client) = setup_token_contract();
+
+    let account = Address::generate(&env);
+    setup_guardians(&env, &client, &account);
+    let stranger = Address::generate(&env);
+    client.approve_recovery(&stranger, &account, &Address::generate(&env));
+}
+
+fn setup_compliance(env: &Env, admin: &Address, client: &TokenContractClient) -> Address {
+    let officer = Address::generate(env);
+    client.set_compliance_officer(admin, &officer);
+    client.set_feature(&officer, &Feature::Freezing, &true);
+    officer
+}
+
+#[test]
+fn test_frozen_account_cannot_send_but_can_receive() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let officer = setup_compliance(&env, &admin, &client);
+
+    let account = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.mint(&admin, &account, &1_000i128);
+    client.mint(&admin, &other, &1_000i128);
+
+    client.freeze_account(&officer, &account);
+    assert!(client.is_frozen(&account));
+    assert_eq!(
+        client.try_transfer(&account, &other, &100i128),
+        Err(Ok(TokenError::AccountFrozen.into()))
+    );
+
+    // Incoming funds are still accepted
+    client.transfer(&other, &account, &100i128);
+    assert_eq!(client.balance(&account), 1_100);
+
+    client.unfreeze_account(&officer, &account);
+    client.transfer(&account, &other, &100i128);
+    assert_eq!(client.balance(&other), 1_000);
+}
+
+#[test]
+fn test_denylisted_account_cannot_send_or_receive() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let officer = setup_compliance(&env, &admin, &client);
+
+    let account = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.mint(&admin, &other, &1_000i128);
+
+    client.add_to_denylist(&officer, &account);
+    assert!(client.is_denylisted(&account));
+    assert_eq!(
+        client.try_transfer(&other, &account, &100i128),
+        Err(Ok(TokenError::AccountDenylisted.into()))
+    );
+    assert_eq!(
+        client.try_mint(&admin, &account, &100i128),
+        Err(Ok(TokenError::AccountDenylisted.into()))
+    );
+
+    client.remove_from_denylist(&officer, &account);
+    client.transfer(&other, &account, &100i128);
+    assert_eq!(client.balance(&account), 100);
+}
+
+#[test]
+fn test_compliance_actions_are_logged_to_audit_contract() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let officer = setup_compliance(&env, &admin, &client);
+
+    let audit_id = env.register(RecordingAudit, ());
+    let audit = RecordingAuditClient::new(&env, &audit_id);
+    client.set_audit_contract(&admin, &Some(audit_id.clone()));
+    assert_eq!(client.audit_contract(), Some(audit_id));
+
+    let account = Address::generate(&env);
+    client.freeze_account(&officer, &account);
+    client.add_to_denylist(&officer, &account);
+
+    assert_eq!(audit.entries(), 2);
+    assert_eq!(audit.last(), Some((officer, symbol_short!("denied"))));
+}
+
+#[test]
+fn test_large_transfers_require_compliance_reference() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let officer = setup_compliance(&env, &admin, &client);
+
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let spender = Address::generate(&env);
+    client.mint(&admin, &sender, &5_000i128);
+
+    assert_eq!(
+        client.try_set_travel_rule_threshold(&admin, &Some(500i128)),
+        Err(Ok(TokenError::NotComplianceOfficer.into()))
+    );
+    client.set_travel_rule_threshold(&officer, &Some(500i128));
+    assert_eq!(client.travel_rule_threshold(), Some(500));
+
+    // Amounts up to the threshold need no reference
+    client.transfer(&sender, &recipient, &500i128);
+    assert_eq!(
+        client.try_transfer(&sender, &recipient, &501i128),
+        Err(Ok(TokenError::ComplianceReferenceRequired.into()))
+    );
+    client.approve(&sender, &spender, &1_000i128);
+    assert_eq!(
+        client.try_transfer_from(&spender, &sender, &recipient, &600i128),
+        Err(Ok(TokenError::ComplianceReferenceRequired.into()))
+    );
+
+    let ref_hash = BytesN::from_array(&env, &[7u8; 32]);
+    client.transfer_with_compliance(&sender, &recipient, &600i128, &ref_hash);
+    let reference_event = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            event_topics_contain_symbol(&env, topics, symbol_short!("reference"))
+        })
+        .expect("compliance reference event");
+    assert_eq!(
+        BytesN::<32>::try_from_val(&env, &reference_event.1.get(2).unwrap()).unwrap(),
+        ref_hash
+    );
+    assert_eq!(client.balance(&recipient), 1_100);
+
+    // Allowance transfers can carry the reference too
+    client.transfer_from_with_compliance(&spender, &sender, &recipient, &600i128, &ref_hash);
+    assert!(env.events().all().iter().any(|(_, topics, _)| {
+        event_topics_contain_symbol(&env, &topics, symbol_short!("reference"))
+    }));
+    assert_eq!(client.balance(&recipient), 1_700);
+    assert_eq!(client.allowance(&sender, &spender), 400);
+
+    client.set_travel_rule_threshold(&officer, &None);
+    client.transfer(&sender, &recipient, &600i128);
+    assert_eq!(client.balance(&recipient), 2_300);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_admin_cannot_freeze_accounts() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    setup_compliance(&env, &admin, &client);
+
+    client.freeze_account(&admin, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #27)")]
+fn test_admin_cannot_be_compliance_officer() {
+    let (_env, admin, _token_contract, client) = setup_token_contract();
+
+    client.set_compliance_officer(&admin, &admin);
+}
+
+#[test]
+fn test_scheduled_burn_executes_after_announced_time() {
+    let (env, admin, token_contract, client) = setup_token_contract();
+    client.mint(&admin, &admin, &1_000i128);
+
+    let execute_after = env.ledger().timestamp() + 3_600;
+    let burn_id = client.schedule_burn(&admin, &400i128, &execute_after);
+    assert_eq!(client.balance(&admin), 600);
+    assert_eq!(client.balance(&token_contract), 400);
+    assert_eq!(client.scheduled_burns().len(), 1);
+    assert_eq!(client.total_supply(), 1_000);
+
+    // Nothing is due yet
+    assert_eq!(client.execute_due_burns(), 0);
+    assert_eq!(client.scheduled_burns().get(0).unwrap().burn_id, burn_id);
+
+    env.ledger().with_mut(|li| li.timestamp = execute_after);
+    assert_eq!(client.execute_due_burns(), 400);
+    assert_eq!(client.balance(&token_contract), 0);
+    assert_eq!(client.total_supply(), 600);
+    assert_eq!(client.scheduled_burns().len(), 0);
+}
+
+#[test]
+fn test_cancelled_burn_returns_tokens_to_treasury() {
+    let (env, admin, token_contract, client) = setup_token_contract();
+    client.mint(&admin, &admin, &1_000i128);
+
+    let execute_after = env.ledger().timestamp() + 3_600;
+    let burn_id = client.schedule_burn(&admin, &400i128, &execute_after);
+    client.cancel_scheduled_burn(&admin, &burn_id);
+
+    assert_eq!(client.balance(&admin), 1_000);
+    assert_eq!(client.balance(&token_contract), 0);
+    env.ledger().with_mut(|li| li.timestamp = execute_after);
+    assert_eq!(client.execute_due_burns(), 0);
+}
+
+#[test]
+fn test_due_burn_cannot_be_cancelled() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    client.mint(&admin, &admin, &1_000i128);
+
+    let execute_after = env.ledger().timestamp() + 3_600;
+    let burn_id = client.schedule_burn(&admin, &400i128, &execute_after);
+    env.ledger().with_mut(|li| li.timestamp = execute_after);
+
+    assert_eq!(
+        client.try_cancel_scheduled_burn(&admin, &burn_id),
+        Err(Ok(TokenError::BurnAlreadyDue.into()))
+    );
+    assert_eq!(
+        client.try_schedule_burn(&admin, &100i128, &execute_after),
+        Err(Ok(TokenError::InvalidBurnSchedule.into()))
+    );
+}
+
+#[test]
+fn test_scheduled_burns_must_fit_under_burn_cap() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    client.mint(&admin, &admin, &600_000i128);
+
+    let execute_after = env.ledger().timestamp() + 3_600;
+    client.schedule_burn(&admin, &400_000i128, &execute_after);
+    assert_eq!(
+        client.try_schedule_burn(&admin, &100_001i128, &execute_after),
+        Err(Ok(TokenError::BurnCapExceeded.into()))
+    );
+    client.schedule_burn(&admin, &100_000i128, &execute_after);
+}
+
+#[test]
+fn test_due_burn_over_burn_cap_stays_cancellable() {
+    let (env, admin, token_contract, client) = setup_token_contract();
+    let user = Address::generate(&env);
+    client.mint(&admin, &admin, &1_000i128);
+    client.mint(&admin, &user, &500_000i128);
+
+    let execute_after = env.ledger().timestamp() + 3_600;
+    let first = client.schedule_burn(&admin, &400i128, &execute_after);
+    let second = client.schedule_burn(&admin, &100i128, &execute_after);
+    // Regular burns use up the cap before the schedule comes due
+    client.burn(&user, &499_550i128);
+    env.ledger().with_mut(|li| li.timestamp = execute_after);
+
+    // The first burn still fits; the second is left scheduled
+    assert_eq!(client.execute_due_burns(), 400);
+    let burns = client.scheduled_burns();
+    assert_eq!(burns.len(), 1);
+    assert_eq!(burns.get(0).unwrap().burn_id, second);
+    assert_eq!(
+        client.try_cancel_scheduled_burn(&admin, &first),
+        Err(Ok(TokenError::ScheduledBurnNotFound.into()))
+    );
+
+    client.cancel_scheduled_burn(&admin, &second);
+    assert_eq!(client.balance(&admin), 600);
+    assert_eq!(client.balance(&token_contract), 0);
+    assert_eq!(client.scheduled_burns().len(), 0);
+}
+
+#[test]
+fn test_mint_and_burn_history_pages() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let user = Address::generate(&env);
+
+    // Records made in the same ledger are all kept
+    client.mint(&admin, &user, &100i128);
+    client.mint(&admin, &user, &200i128);
+    client.mint(&admin, &admin, &300i128);
+    client.burn(&user, &50i128);
+
+    let page = client.mint_history(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().amount, 100);
+    assert_eq!(page.get(1).unwrap().amount, 200);
+    let rest = client.mint_history(&2, &10);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().to, admin);
+    assert_eq!(client.mint_history(&3, &10).len(), 0);
+
+    let burns = client.burn_history(&0, &10);
+    assert_eq!(burns.len(), 1);
+    assert_eq!(burns.get(0).unwrap().from, user);
+    assert_eq!(burns.get(0).unwrap().amount, 50);
+}
+
+fn setup_token_contract_with_storage(
+    balance_storage: BalanceStorage,
+) -> (Env, Address, Address, TokenContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let name = String::from_str(&env, "Packed Token");
+    let symbol = String::from_str(&env, "PACK");
+    client.initialize_with_storage(
+        &admin,
+        &name,
+        &symbol,
+        &7u32,
+        &None,
+        &None,
+        &balance_storage,
+    );
+
+    (env, admin, contract_id, client)
+}
+
+#[test]
+fn test_packed_balances_behave_like_per_address() {
+    let (env, admin, _token_contract, client) =
+        setup_token_contract_with_storage(BalanceStorage::Packed(4));
+    assert_eq!(client.balance_storage(), BalanceStorage::Packed(4));
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.mint(&admin, &user1, &1_000i128);
+    client.transfer(&user1, &user2, &300i128);
+    client.approve(&user2, &spender, &100i128);
+    client.transfer_from(&spender, &user2, &user1, &100i128);
+    client.burn(&user2, &200i128);
+
+    assert_eq!(client.balance(&user1), 800);
+    assert_eq!(client.balance(&user2), 0);
+    assert_eq!(client.total_supply(), 800);
+
+    client.transfer(&user1, &user2, &800i128);
+    assert_eq!(client.balance(&user1), 0);
+    assert_eq!(client.balance(&user2), 800);
+}
+
+#[test]
+fn test_default_initialization_uses_per_address_balances() {
+    let (_env, _admin, _token_contract, client) = setup_token_contract();
+    assert_eq!(client.balance_storage(), BalanceStorage::PerAddress);
+}
+
+#[test]
+fn test_invalid_bucket_count_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TokenContract, ());
+    let client = TokenContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let name = String::from_str(&env, "Packed Token");
+    let symbol = String::from_str(&env, "PACK");
+
+    assert_eq!(
+        client.try_initialize_with_storage(
+            &admin,
+            &name,
+            &symbol,
+            &7u32,
+            &None,
+            &None,
+            &BalanceStorage::Packed(0),
+        ),
+        Err(Ok(TokenError::InvalidBalanceStorage.into()))
+    );
+}
+
+/// Number of persistent contract data entries in the ledger.
+fn persistent_entry_count(env: &Env) -> usize {
+    use soroban_sdk::xdr::{ContractDataDurability, LedgerKey};
+
+    env.to_snapshot()
+        .ledger
+        .ledger_entries
+        .iter()
+        .filter(|(key, _)| {
+            matches!(key.as_ref(), LedgerKey::ContractData(data)
+                if data.durability == ContractDataDurability::Persistent)
+        })
+        .count()
+}
+
+/// Benchmark: ledger entries and estimated fees of per-address vs packed
+/// balances. Run with `cargo test --test token_tests bench_ -- --nocapture`
+/// to see the numbers.
+#[test]
+fn bench_balance_storage_entries_and_fees() {
+    const HOLDERS: u32 = 64;
+
+    let mut results = std::vec::Vec::new();
+    for storage in [BalanceStorage::PerAddress, BalanceStorage::Packed(8)] {
+        let (env, admin, _token_contract, client) = setup_token_contract_with_storage(storage);
+        let entries_before = persistent_entry_count(&env);
+
+        let mut mint_fee: i64 = 0;
+        for _ in 0..HOLDERS {
+            client.mint(&admin, &Address::generate(&env), &10i128);
+            mint_fee += env.cost_estimate().fee().total;
+        }
+
+        let entries = persistent_entry_count(&env) - entries_before;
+        std::println!(
+            "[{:?}] holders={} ledger_entries={} avg_mint_fee={}",
+            storage,
+            HOLDERS,
+            entries,
+            mint_fee / HOLDERS as i64
+        );
+        results.push(entries);
+    }
+
+    // Per-address keeps one entry per holder; packed is bounded by the buckets
+    assert!(results[0] >= HOLDERS as usize);
+    assert!(results[1] < results[0]);
+}
+
+#[test]
+fn test_all_features_disabled_by_default() {
+    let (_env, _admin, _token_contract, client) = setup_token_contract();
+
+    assert!(client.get_enabled_features().is_empty());
+    assert!(!client.is_feature_enabled(&Feature::Freezing));
+    assert!(!client.is_feature_enabled(&Feature::Hooks));
+    assert!(!client.is_feature_enabled(&Feature::TransferLimits));
+    assert!(!client.is_feature_enabled(&Feature::Sponsorship));
+}
+
+#[test]
+fn test_roles_view_and_config_export() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let minter1 = Address::generate(&env);
+    let minter2 = Address::generate(&env);
+    let officer = Address::generate(&env);
+
+    let roles = client.get_roles();
+    assert_eq!(roles.admin, admin);
+    assert_eq!(roles.minters, soroban_sdk::vec![&env, admin.clone()]);
+    assert_eq!(roles.compliance_officer, None);
+    assert!(!roles.paused);
+    assert_eq!(roles.mint_cap, Some(1000000i128));
+    assert_eq!(roles.burn_cap, Some(500000i128));
+    assert_eq!(roles.max_transfer_amount, None);
+    assert!(roles.features.is_empty());
+
+    client.add_minter(&admin, &minter1);
+    client.add_minter(&admin, &minter2);
+    client.remove_minter(&admin, &minter1);
+    client.set_compliance_officer(&admin, &officer);
+    client.set_feature(&admin, &Feature::TransferLimits, &true);
+    client.set_max_transfer_amount(&admin, &Some(5_000i128));
+    client.set_feature(&admin, &Feature::Hooks, &true);
+    client.set_feature(&admin, &Feature::Hooks, &false);
+    client.pause(&admin);
+
+    let roles = client.export_roles(&admin);
+    let exported = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event_topics_contain_symbol(&env, &event.1, symbol_short!("exported")))
+        .count();
+    assert_eq!(exported, 1);
+
+    assert_eq!(
+        roles.minters,
+        soroban_sdk::vec![&env, admin.clone(), minter2]
+    );
+    assert_eq!(roles.compliance_officer, Some(officer));
+    assert!(roles.paused);
+    assert_eq!(roles.max_transfer_amount, Some(5_000i128));
+    assert_eq!(
+        roles.features,
+        soroban_sdk::vec![&env, Feature::TransferLimits]
+    );
+    assert_eq!(client.get_roles(), roles);
+
+    // Reading the view does not emit an export event
+    client.get_roles();
+    assert!(env.events().all().is_empty());
+
+    assert_eq!(
+        client.try_export_roles(&minter1),
+        Err(Ok(TokenError::Unauthorized.into()))
+    );
+}
+
+#[test]
+fn test_disabled_features_are_skipped_and_rejected() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    let officer = setup_compliance(&env, &admin, &client);
+
+    let account = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.mint(&admin, &account, &1_000i128);
+    client.freeze_account(&officer, &account);
+    client.set_feature(&admin, &Feature::TransferLimits, &true);
+    client.set_max_transfer_amount(&admin, &Some(50i128));
+
+    // Only the compliance officer can lift freezes by switching them off
+    assert_eq!(
+        client.try_set_feature(&admin, &Feature::Freezing, &false),
+        Err(Ok(TokenError::NotComplianceOfficer.into()))
+    );
+    client.set_feature(&officer, &Feature::Freezing, &false);
+    let updates = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event_topics_contain_symbol(&env, &event.1, symbol_short!("feature")))
+        .count();
+    assert_eq!(updates, 1);
+    client.set_feature(&admin, &Feature::TransferLimits, &false);
+    assert!(client.get_enabled_features().is_empty());
+
+    // Freezes and the transfer cap no longer apply, and cannot be changed
+    client.transfer(&account, &other, &100i128);
+    assert_eq!(client.balance(&other), 100);
+    assert_eq!(
+        client.try_unfreeze_account(&officer, &account),
+        Err(Ok(TokenError::FeatureDisabled.into()))
+    );
+    assert_eq!(
+        client.try_set_max_transfer_amount(&admin, &None),
+        Err(Ok(TokenError::FeatureDisabled.into()))
+    );
+
+    // Re-enabling restores the stored settings
+    client.set_feature(&officer, &Feature::Freezing, &true);
+    assert_eq!(
+        client.try_transfer(&account, &other, &10i128),
+        Err(Ok(TokenError::AccountFrozen.into()))
+    );
+}
+
+#[test]
+fn test_disabled_hooks_are_not_notified() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let hook_id = env.register(RecordingHook, ());
+    let hook = RecordingHookClient::new(&env, &hook_id);
+    client.set_feature(&admin, &Feature::Hooks, &true);
+    client.register_transfer_hook(&admin, &hook_id);
+    client.set_feature(&admin, &Feature::Hooks, &false);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    client.mint(&admin, &user1, &1_000i128);
+    client.transfer(&user1, &user2, &100i128);
+    assert_eq!(hook.calls(), 0);
+    assert_eq!(
+        client.try_register_transfer_hook(&admin, &Address::generate(&env)),
+        Err(Ok(TokenError::FeatureDisabled.into()))
+    );
+
+    client.set_feature(&admin, &Feature::Hooks, &true);
+    client.transfer(&user1, &user2, &100i128);
+    assert_eq!(hook.calls(), 1);
+}
+
+#[test]
+fn test_allowance_low_event_when_spend_crosses_threshold() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let merchant = Address::generate(&env);
+    client.mint(&admin, &owner, &1_000i128);
+    client.approve(&owner, &spender, &300i128);
+    client.set_allowance_alert(&owner, &spender, &Some(150i128));
+    assert_eq!(client.allowance_alert(&owner, &spender), Some(150));
+
+    let low_events = |env: &Env| {
+        env.events()
+            .all()
+            .iter()
+            .filter(|event| event_topics_contain_symbol(env, &event.1, symbol_short!("low")))
+            .count()
+    };
+
+    // 300 -> 200 stays above the threshold
+    client.transfer_from(&spender, &owner, &merchant, &100i128);
+    assert_eq!(low_events(&env), 0);
+
+    // 200 -> 100 crosses it
+    client.transfer_from(&spender, &owner, &merchant, &100i128);
+    assert_eq!(low_events(&env), 1);
+
+    // Already below: no repeat warning
+    client.transfer_from(&spender, &owner, &merchant, &50i128);
+    assert_eq!(low_events(&env), 0);
+
+    assert_eq!(
+        client.try_set_allowance_alert(&owner, &spender, &Some(0i128)),
+        Err(Ok(TokenError::InvalidAmount.into()))
+    );
+}
+
+#[test]
+fn test_epoch_inflation_minted_once_per_elapsed_epoch() {
+    let (env, admin, _token_contract, client) = setup_token_contract();
+    client.mint(&admin, &admin, &10_000i128);
+
+    assert_eq!(
+        client.try_mint_epoch_inflation(),
+        Err(Ok(TokenError::InflationNotConfigured.into()))
+    );
+
+    let reward_pool = Address::generate(&env);
+    let epoch_length = 86_400u64;
+    client.set_inflation_schedule(&admin, &100u32, &epoch_length, &Some(reward_pool.clone()));
+
+    // The first epoch has not ended yet
+    assert_eq!(
+        client.try_mint_epoch_inflation(),
+        Err(Ok(TokenError::InflationEpochNotElapsed.into()))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += epoch_length * 2);
+    assert_eq!(client.mint_epoch_inflation(), 100);
+    assert_eq!(client.mint_epoch_inflation(), 101);
+    assert_eq!(client.balance(&reward_pool), 201);
+    assert_eq!(client.total_supply(), 10_201);
+    assert_eq!(client.inflation_epochs_minted(), 2);
+
+    // Both elapsed epochs are minted; the third has not ended
+    assert_eq!(
+        client.try_mint_epoch_inflation(),
+        Err(Ok(TokenError::InflationEpochNotElapsed.into()))
+    );
+
+    assert_eq!(
+        client.try_set_inflation_schedule(&admin, &0u32, &epoch_length, &Some(reward_pool)),
+        Err(Ok(TokenError::InvalidInflationSchedule.into()))
+    );
+}